@@ -1,16 +1,24 @@
-use crossbeam_epoch::{Atomic, Guard, Owned};
 use std::{
     marker::PhantomData,
     sync::{atomic::Ordering, Arc},
 };
 
+use crossbeam_epoch::{Atomic, Guard, Owned};
+
+/// The value of a [`SharedContext`] and the number of times it has been
+/// replaced.
+struct Versioned<T> {
+    version: u64,
+    value: T,
+}
+
 /// Application-wide state management using epoch-based memory reclamation.
 /// Before using it, make sure operations on `T` is read-heavy. [`Context`]
 /// helps reduce the read overhead of Mutex when multiple threads access the
 /// same object that is written rarely (where `T` is more than 90% read and less
 /// than 10% write).
 pub struct SharedContext<T> {
-    ptr: Arc<Atomic<T>>,
+    ptr: Arc<Atomic<Versioned<T>>>,
 }
 
 unsafe impl<T> Send for SharedContext<T> {}
@@ -28,13 +36,13 @@ impl<T> Clone for SharedContext<T> {
 impl<T> From<T> for SharedContext<T> {
     fn from(value: T) -> Self {
         Self {
-            ptr: Arc::new(Atomic::new(value)),
+            ptr: Arc::new(Atomic::new(Versioned { version: 0, value })),
         }
     }
 }
 
 impl<T> SharedContext<T> {
-    fn as_ptr(&self) -> Arc<Atomic<T>> {
+    fn as_ptr(&self) -> Arc<Atomic<Versioned<T>>> {
         self.ptr.clone()
     }
 
@@ -75,9 +83,33 @@ impl<T> SharedContext<T> {
     /// ```
     pub fn store(&self, context: T) {
         let guard = crossbeam_epoch::pin();
-        let previous_context = self.ptr.swap(Owned::new(context), Ordering::SeqCst, &guard);
+        let mut current_context = self.ptr.load(Ordering::SeqCst, &guard);
+        let mut new_context = Owned::new(Versioned {
+            version: 0,
+            value: context,
+        });
+
+        // Retry until the version follows the one being replaced, so that
+        // versions increase in the order the values are stored.
+        loop {
+            new_context.version = unsafe { current_context.deref() }.version + 1;
+
+            match self.ptr.compare_exchange(
+                current_context,
+                new_context,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+                &guard,
+            ) {
+                Ok(_) => break,
+                Err(error) => {
+                    current_context = error.current;
+                    new_context = error.new;
+                }
+            }
+        }
 
-        unsafe { guard.defer_destroy(previous_context) }
+        unsafe { guard.defer_destroy(current_context) }
         guard.flush();
     }
 
@@ -115,46 +147,168 @@ impl<T> SharedContext<T> {
     pub fn update(&self, context: T) -> Result<(), ContextError> {
         let guard = crossbeam_epoch::pin();
         let current_context = self.ptr.load(Ordering::SeqCst, &guard);
+        let version = unsafe { current_context.deref() }.version + 1;
         self.ptr
             .compare_exchange(
                 current_context,
-                Owned::new(context),
+                Owned::new(Versioned {
+                    version,
+                    value: context,
+                }),
                 Ordering::SeqCst,
                 Ordering::SeqCst,
                 &guard,
             )
             .map_err(|_| ContextError::Update)?;
 
+        unsafe { guard.defer_destroy(current_context) }
+        guard.flush();
+
         Ok(())
     }
+
+    /// Same as [`SharedContext::update()`], but fails unless the context is
+    /// still at `version`, e.g. when `context` was checked against the
+    /// [`Context`] loaded at that version. Returns the new version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let context = SharedContext::from(u8::from(1));
+    ///
+    /// let current = context.load();
+    /// let new_count = current.as_ref() + 1;
+    /// let version = context
+    ///     .update_at_version(current.version(), new_count)
+    ///     .unwrap();
+    ///
+    /// // The context moved past `current`.
+    /// assert!(context.update_at_version(current.version(), 0).is_err());
+    /// ```
+    pub fn update_at_version(&self, version: u64, context: T) -> Result<u64, ContextError> {
+        let guard = crossbeam_epoch::pin();
+        let current_context = self.ptr.load(Ordering::SeqCst, &guard);
+        if unsafe { current_context.deref() }.version != version {
+            return Err(ContextError::Update);
+        }
+
+        self.ptr
+            .compare_exchange(
+                current_context,
+                Owned::new(Versioned {
+                    version: version + 1,
+                    value: context,
+                }),
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+                &guard,
+            )
+            .map_err(|_| ContextError::Update)?;
+
+        unsafe { guard.defer_destroy(current_context) }
+        guard.flush();
+
+        Ok(version + 1)
+    }
+
+    /// Replace the current context with `function(current)` and return the
+    /// new version. Unlike [`SharedContext::update()`], a concurrent change
+    /// does not fail but calls `function` again with the newer context, so
+    /// `function` may run more than once and should have no side effects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let context = SharedContext::from(u8::from(1));
+    ///
+    /// let version = context.rcu(|count| count + 1);
+    ///
+    /// let current = context.load();
+    /// println!("{:?}", current.as_ref()); // Prints '2'
+    /// assert_eq!(current.version(), version);
+    /// ```
+    pub fn rcu<F>(&self, mut function: F) -> u64
+    where
+        F: FnMut(&T) -> T,
+    {
+        let guard = crossbeam_epoch::pin();
+        let mut current_context = self.ptr.load(Ordering::SeqCst, &guard);
+
+        loop {
+            let current = unsafe { current_context.deref() };
+            let new_context = Owned::new(Versioned {
+                version: current.version + 1,
+                value: function(&current.value),
+            });
+
+            match self.ptr.compare_exchange(
+                current_context,
+                new_context,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+                &guard,
+            ) {
+                Ok(new_context) => {
+                    let version = unsafe { new_context.deref() }.version;
+
+                    unsafe { guard.defer_destroy(current_context) }
+                    guard.flush();
+
+                    return version;
+                }
+                Err(error) => current_context = error.current,
+            }
+        }
+    }
+
+    /// Number of times the context has been replaced since it was created.
+    /// Compare versions to tell whether the context changed without
+    /// comparing the values.
+    pub fn version(&self) -> u64 {
+        let guard = crossbeam_epoch::pin();
+
+        unsafe { self.ptr.load(Ordering::SeqCst, &guard).deref() }.version
+    }
 }
 
+/// A snapshot of a [`SharedContext`]. The value and the version are read
+/// from the context loaded by [`SharedContext::load()`], whatever has been
+/// stored since.
 pub struct Context<T> {
-    shared_context: SharedContext<T>,
-    guard: Guard,
+    _shared_context: SharedContext<T>,
+    versioned: *const Versioned<T>,
+    // Keeps `versioned` from being reclaimed.
+    _guard: Guard,
     _not_send: PhantomData<NotSend>,
 }
 
 impl<T> AsRef<T> for Context<T> {
     fn as_ref(&self) -> &T {
-        unsafe {
-            self.shared_context
-                .as_ptr()
-                .load(Ordering::SeqCst, &self.guard)
-                .as_ref()
-                .unwrap()
-        }
+        &self.versioned().value
     }
 }
 
 impl<T> Context<T> {
     fn new(context: SharedContext<T>) -> Self {
+        let guard = crossbeam_epoch::pin();
+        let versioned = context.as_ptr().load(Ordering::SeqCst, &guard).as_raw();
+
         Self {
-            shared_context: context,
-            guard: crossbeam_epoch::pin(),
+            _shared_context: context,
+            versioned,
+            _guard: guard,
             _not_send: PhantomData,
         }
     }
+
+    fn versioned(&self) -> &Versioned<T> {
+        unsafe { self.versioned.as_ref().unwrap() }
+    }
+
+    /// See [`SharedContext::version()`].
+    pub fn version(&self) -> u64 {
+        self.versioned().version
+    }
 }
 
 #[allow(unused)]
@@ -162,6 +316,14 @@ struct NotSend(*const ());
 
 pub enum ContextError {
     Update,
+    AlreadyRegistered {
+        type_name: &'static str,
+        name: Option<String>,
+    },
+    NotRegistered {
+        type_name: &'static str,
+        name: Option<String>,
+    },
 }
 
 impl std::fmt::Debug for ContextError {
@@ -170,6 +332,16 @@ impl std::fmt::Debug for ContextError {
             // If you are seeing this error too often, check if there's more than one thread/task
             // updating the context concurrently.
             Self::Update => write!(f, "Context changed while getting updated"),
+            Self::AlreadyRegistered { type_name, name } => {
+                write!(
+                    f,
+                    "Context {} ({:?}) is already registered",
+                    type_name, name
+                )
+            }
+            Self::NotRegistered { type_name, name } => {
+                write!(f, "Context {} ({:?}) is not registered", type_name, name)
+            }
         }
     }
 }
@@ -1,2 +1,4 @@
 mod ebr;
+mod registry;
 pub use ebr::{Context, ContextError, SharedContext};
+pub use registry::ContextRegistry;
@@ -0,0 +1,149 @@
+use std::{
+    any::{self, Any, TypeId},
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{Context, ContextError, SharedContext};
+
+type ContextKey = (TypeId, Option<String>);
+
+type ContextMap = HashMap<ContextKey, Arc<dyn Any + Send + Sync>>;
+
+/// Registry of [`SharedContext`] keyed by type and an optional name, so that
+/// independent pieces of state can be registered once and resolved anywhere
+/// the registry is reachable.
+///
+/// Lookups go through a [`SharedContext`] of the registry itself, so they
+/// take no lock. Registration copies the registry and is meant to happen at
+/// startup.
+///
+/// # Examples
+///
+/// ```
+/// let registry = ContextRegistry::default();
+/// registry.register(ClusterConfig::default()).unwrap();
+/// registry
+///     .register_named("primary", SignerHandle::new())
+///     .unwrap();
+///
+/// let cluster_config = registry.load::<ClusterConfig>().unwrap();
+/// println!("{:?}", cluster_config.as_ref());
+///
+/// let signer = registry.get_named::<SignerHandle>("primary").unwrap();
+/// signer.store(SignerHandle::new());
+/// ```
+#[derive(Clone)]
+pub struct ContextRegistry {
+    contexts: SharedContext<ContextMap>,
+    register_lock: Arc<Mutex<()>>,
+}
+
+impl Default for ContextRegistry {
+    fn default() -> Self {
+        Self {
+            contexts: SharedContext::from(ContextMap::new()),
+            register_lock: Arc::new(Mutex::new(())),
+        }
+    }
+}
+
+impl ContextRegistry {
+    /// Register `value` under its type and return the [`SharedContext`]
+    /// holding it. Fail if a value of the same type is already registered.
+    pub fn register<T>(&self, value: T) -> Result<SharedContext<T>, ContextError>
+    where
+        T: 'static,
+    {
+        self.register_inner(None, value)
+    }
+
+    /// Register `value` under its type and `name`, for when more than one
+    /// value of the same type is needed.
+    pub fn register_named<T>(
+        &self,
+        name: impl Into<String>,
+        value: T,
+    ) -> Result<SharedContext<T>, ContextError>
+    where
+        T: 'static,
+    {
+        self.register_inner(Some(name.into()), value)
+    }
+
+    /// Get the [`SharedContext`] registered under the type `T`.
+    pub fn get<T>(&self) -> Result<SharedContext<T>, ContextError>
+    where
+        T: 'static,
+    {
+        self.get_inner(None)
+    }
+
+    /// Get the [`SharedContext`] registered under the type `T` and `name`.
+    pub fn get_named<T>(&self, name: impl Into<String>) -> Result<SharedContext<T>, ContextError>
+    where
+        T: 'static,
+    {
+        self.get_inner(Some(name.into()))
+    }
+
+    /// Shortcut for `registry.get::<T>()?.load()`.
+    pub fn load<T>(&self) -> Result<Context<T>, ContextError>
+    where
+        T: 'static,
+    {
+        Ok(self.get::<T>()?.load())
+    }
+
+    /// Shortcut for `registry.get_named::<T>(name)?.load()`.
+    pub fn load_named<T>(&self, name: impl Into<String>) -> Result<Context<T>, ContextError>
+    where
+        T: 'static,
+    {
+        Ok(self.get_named::<T>(name)?.load())
+    }
+
+    fn register_inner<T>(
+        &self,
+        name: Option<String>,
+        value: T,
+    ) -> Result<SharedContext<T>, ContextError>
+    where
+        T: 'static,
+    {
+        let _register_lock = self.register_lock.lock().unwrap();
+
+        let key = (TypeId::of::<T>(), name);
+        let mut contexts = self.contexts.load().as_ref().clone();
+        if contexts.contains_key(&key) {
+            return Err(ContextError::AlreadyRegistered {
+                type_name: any::type_name::<T>(),
+                name: key.1,
+            });
+        }
+
+        let shared_context = SharedContext::from(value);
+        contexts.insert(key, Arc::new(shared_context.clone()));
+        self.contexts.store(contexts);
+
+        Ok(shared_context)
+    }
+
+    fn get_inner<T>(&self, name: Option<String>) -> Result<SharedContext<T>, ContextError>
+    where
+        T: 'static,
+    {
+        let key = (TypeId::of::<T>(), name);
+
+        self.contexts
+            .load()
+            .as_ref()
+            .get(&key)
+            .and_then(|shared_context| shared_context.downcast_ref::<SharedContext<T>>())
+            .cloned()
+            .ok_or(ContextError::NotRegistered {
+                type_name: any::type_name::<T>(),
+                name: key.1,
+            })
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use crate::{Id, Payload, RequestObject, ResponseObject, RpcClientError};
+
+/// The responses to a [crate::BatchRequest], returned by
+/// [crate::RpcClient::batch_request] in the order of the requests.
+///
+/// Servers may answer a batch in any order, so responses are matched to
+/// requests by ID. A request without a response is returned as
+/// [RpcClientError::MissingResponse] when iterating. Error responses with a
+/// `null` ID, which servers send for requests whose ID they could not read,
+/// cannot be matched and are kept in [BatchResponse::unmatched].
+#[derive(Debug)]
+pub struct BatchResponse {
+    payloads: Vec<(Id, Option<Payload>)>,
+    unmatched: Vec<Payload>,
+}
+
+impl BatchResponse {
+    pub fn len(&self) -> usize {
+        self.payloads.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.payloads.is_empty()
+    }
+
+    /// The IDs of the requests that got no response.
+    pub fn missing_ids(&self) -> impl Iterator<Item = &Id> {
+        self.payloads
+            .iter()
+            .filter(|(_id, payload)| payload.is_none())
+            .map(|(id, _payload)| id)
+    }
+
+    /// The responses with a `null` ID that no request could be matched to.
+    pub fn unmatched(&self) -> &[Payload] {
+        &self.unmatched
+    }
+}
+
+impl IntoIterator for BatchResponse {
+    type Item = Result<Payload, RpcClientError>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.payloads
+            .into_iter()
+            .map(|(id, payload)| payload.ok_or(RpcClientError::MissingResponse(id)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+/// Index `requests` by ID, failing with [RpcClientError::DuplicateId] if two
+/// of them share one, since their responses could not be told apart.
+pub(crate) fn index(requests: &[RequestObject]) -> Result<HashMap<&Id, usize>, RpcClientError> {
+    let mut indices = HashMap::with_capacity(requests.len());
+    for (index, request) in requests.iter().enumerate() {
+        if indices.insert(&request.id, index).is_some() {
+            return Err(RpcClientError::DuplicateId(request.id.clone()));
+        }
+    }
+
+    Ok(indices)
+}
+
+/// Match `responses` to `requests` by ID. Fail with
+/// [RpcClientError::DuplicateId] if the server answered a request twice and
+/// with [RpcClientError::IdMismatch] if it answered one that was not sent.
+pub(crate) fn match_responses(
+    requests: &[RequestObject],
+    indices: &HashMap<&Id, usize>,
+    responses: Vec<ResponseObject>,
+) -> Result<BatchResponse, RpcClientError> {
+    let mut payloads: Vec<(Id, Option<Payload>)> = requests
+        .iter()
+        .map(|request| (request.id.clone(), None))
+        .collect();
+    let mut unmatched = Vec::new();
+
+    for response in responses {
+        let Some(index) = indices.get(&response.id) else {
+            match response.id {
+                Id::Null => unmatched.push(response.into_payload()),
+                _ => return Err(RpcClientError::IdMismatch),
+            }
+            continue;
+        };
+
+        let (id, payload) = &mut payloads[*index];
+        if payload.is_some() {
+            return Err(RpcClientError::DuplicateId(id.clone()));
+        }
+        *payload = Some(response.into_payload());
+    }
+
+    Ok(BatchResponse {
+        payloads,
+        unmatched,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(id: i64) -> RequestObject {
+        RequestObject::new("method", (), id).unwrap()
+    }
+
+    fn response(id: Id) -> ResponseObject {
+        serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": id,
+            "id": id,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_match_responses() {
+        let requests = vec![request(1), request(2), request(3)];
+        let indices = index(&requests).unwrap();
+
+        // Reordered, with the response to `2` missing.
+        let batch_response = match_responses(
+            &requests,
+            &indices,
+            vec![response(Id::Number(3)), response(Id::Number(1))],
+        )
+        .unwrap();
+        assert_eq!(
+            batch_response.missing_ids().collect::<Vec<_>>(),
+            [&Id::Number(2)]
+        );
+        let results: Vec<Option<i64>> = batch_response
+            .into_iter()
+            .map(|payload| payload.and_then(|payload| payload.parse()).ok())
+            .collect();
+        assert_eq!(results, [Some(1), None, Some(3)]);
+
+        assert!(matches!(
+            match_responses(
+                &requests,
+                &indices,
+                vec![response(Id::Number(1)), response(Id::Number(1))],
+            ),
+            Err(RpcClientError::DuplicateId(Id::Number(1)))
+        ));
+        assert!(matches!(
+            match_responses(&requests, &indices, vec![response(Id::Number(4))]),
+            Err(RpcClientError::IdMismatch)
+        ));
+        assert!(matches!(
+            index(&[request(1), request(1)]),
+            Err(RpcClientError::DuplicateId(Id::Number(1)))
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::value::{to_raw_value, RawValue};
+
+use crate::{parse_header, Id, RequestObject, RpcClient, RpcClientError};
+
+/// A single RPC request built by [RpcClient::call], as an alternative to
+/// the positional arguments of [RpcClient::request].
+///
+/// Unless set, the parameter is `null`, the ID is `0` and the request waits
+/// for as long as the client's request timeout allows.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use radius_sdk::json_rpc::client::RpcClient;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let rpc_client = RpcClient::new().unwrap();
+///
+///     let transaction_count: String = rpc_client
+///         .call("eth_getTransactionCount")
+///         .params(&["0xc6972a7b408b83ceca73da73511df7ce9469608d", "latest"])
+///         .id(1)
+///         .header("Authorization", "Bearer token")
+///         .timeout(Duration::from_secs(2))
+///         .to("http://127.0.0.1:8545")
+///         .send()
+///         .await
+///         .unwrap();
+///
+///     println!("{:?}", transaction_count);
+/// }
+/// ```
+pub struct CallBuilder<'a> {
+    client: &'a RpcClient,
+    method: String,
+    params: Result<Option<Box<RawValue>>, serde_json::Error>,
+    id: Id,
+    timeout: Option<Duration>,
+    rpc_url: Option<String>,
+    headers: Result<HeaderMap, RpcClientError>,
+}
+
+impl<'a> CallBuilder<'a> {
+    pub(crate) fn new(client: &'a RpcClient, method: impl AsRef<str>) -> Self {
+        Self {
+            client,
+            method: method.as_ref().to_owned(),
+            params: Ok(None),
+            id: Id::Number(0),
+            timeout: None,
+            rpc_url: None,
+            headers: Ok(HeaderMap::new()),
+        }
+    }
+
+    /// Set the parameter of the request. A parameter that fails to serialize
+    /// is reported by [CallBuilder::send].
+    pub fn params<P>(mut self, parameter: P) -> Self
+    where
+        P: Serialize,
+    {
+        self.params = to_raw_value(&parameter).map(Some);
+
+        self
+    }
+
+    pub fn id(mut self, id: impl Into<Id>) -> Self {
+        self.id = id.into();
+
+        self
+    }
+
+    /// Fail with [RpcClientError::Timeout] if the response does not arrive
+    /// within `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+
+        self
+    }
+
+    /// Send the header `name: value` with this request only, replacing the
+    /// header of the same name set with [crate::RpcClientBuilder::header].
+    /// An invalid header is reported by [CallBuilder::send].
+    pub fn header(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        if let Ok(headers) = &mut self.headers {
+            match parse_header(name.as_ref(), value.as_ref()) {
+                Ok((name, value)) => {
+                    headers.insert(name, value);
+                }
+                Err(error) => self.headers = Err(error),
+            }
+        }
+
+        self
+    }
+
+    pub fn to(mut self, rpc_url: impl AsRef<str>) -> Self {
+        self.rpc_url = Some(rpc_url.as_ref().to_owned());
+
+        self
+    }
+
+    /// Send the request and wait for the response. Fail with
+    /// [RpcClientError::MissingUrl] if [CallBuilder::to] was not called.
+    pub async fn send<R>(self) -> Result<R, RpcClientError>
+    where
+        R: DeserializeOwned,
+    {
+        let rpc_url = self.rpc_url.ok_or(RpcClientError::MissingUrl)?;
+        let headers = self.headers?;
+        let params = match self.params.map_err(RpcClientError::Serialize)? {
+            Some(params) => params,
+            None => to_raw_value(&()).map_err(RpcClientError::Serialize)?,
+        };
+        let request = RequestObject {
+            jsonrpc: RequestObject::JSON_RPC,
+            method: self.method,
+            params,
+            id: self.id,
+        };
+
+        let headers = (!headers.is_empty()).then_some(&headers);
+        let response = self.client.request_object::<R>(&rpc_url, request, headers);
+        match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, response)
+                .await
+                .unwrap_or(Err(RpcClientError::Timeout)),
+            None => response.await,
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use reqwest::Url;
+
+use crate::RpcClientError;
+
+/// Per-host circuit breaker.
+///
+/// - `Closed`: requests go through and their outcomes are recorded.
+/// - `Open`: requests fail with [RpcClientError::CircuitOpen] without being
+///   sent until the cooldown elapses.
+/// - `HalfOpen`: a single probe request goes through. The circuit closes if it
+///   succeeds and opens again if it fails. Another probe is let through if the
+///   outcome is not recorded within the cooldown, e.g. because
+///   [crate::RpcClient::fetch] dropped the request after another endpoint
+///   answered first.
+pub(crate) struct CircuitBreaker {
+    failure_rate: f64,
+    window: usize,
+    cooldown: Duration,
+    hosts: Mutex<HashMap<String, Host>>,
+}
+
+#[derive(Default)]
+struct Host {
+    state: State,
+    outcomes: VecDeque<bool>,
+}
+
+#[derive(Default)]
+enum State {
+    #[default]
+    Closed,
+    Open(Instant),
+    HalfOpen(Instant),
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_rate: f64, window: usize, cooldown: Duration) -> Self {
+        Self {
+            failure_rate,
+            window: window.max(1),
+            cooldown,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Return the host key of `rpc_url` if a request to it may be sent.
+    pub fn acquire(&self, rpc_url: &str) -> Result<String, RpcClientError> {
+        let host_key = host_key(rpc_url);
+        let mut hosts = self.hosts.lock().unwrap();
+        let host = hosts.entry(host_key.clone()).or_default();
+
+        match host.state {
+            State::Closed => Ok(host_key),
+            State::Open(until) | State::HalfOpen(until) if Instant::now() >= until => {
+                host.state = State::HalfOpen(Instant::now() + self.cooldown);
+
+                Ok(host_key)
+            }
+            State::Open(_) | State::HalfOpen(_) => Err(RpcClientError::CircuitOpen(host_key)),
+        }
+    }
+
+    pub fn record(&self, host_key: &str, is_success: bool) {
+        let mut hosts = self.hosts.lock().unwrap();
+        let host = hosts.entry(host_key.to_owned()).or_default();
+
+        match host.state {
+            State::HalfOpen(_) => {
+                host.outcomes.clear();
+                host.state = match is_success {
+                    true => State::Closed,
+                    false => State::Open(Instant::now() + self.cooldown),
+                };
+            }
+            State::Closed => {
+                if host.outcomes.len() == self.window {
+                    host.outcomes.pop_front();
+                }
+                host.outcomes.push_back(is_success);
+
+                let failures = host.outcomes.iter().filter(|outcome| !**outcome).count();
+                if host.outcomes.len() == self.window
+                    && failures as f64 / self.window as f64 >= self.failure_rate
+                {
+                    host.outcomes.clear();
+                    host.state = State::Open(Instant::now() + self.cooldown);
+                }
+            }
+            // A request acquired before the circuit opened.
+            State::Open(_) => {}
+        }
+    }
+}
+
+pub(crate) fn host_key(rpc_url: &str) -> String {
+    Url::parse(rpc_url)
+        .ok()
+        .and_then(|url| {
+            let host = url.host_str()?.to_owned();
+
+            match url.port_or_known_default() {
+                Some(port) => Some(format!("{}:{}", host, port)),
+                None => Some(host),
+            }
+        })
+        .unwrap_or_else(|| rpc_url.to_owned())
+}
@@ -0,0 +1,79 @@
+use std::{collections::HashMap, io::Write, sync::Mutex};
+
+use flate2::{write::GzEncoder, Compression};
+
+use crate::circuit_breaker::host_key;
+
+/// Gzip compression of request bodies for
+/// [crate::RpcClientBuilder::request_compression]. Endpoints are matched by
+/// host and port.
+///
+/// An endpoint answering a compressed request with `415 Unsupported Media
+/// Type` gets the request again uncompressed, and uncompressed requests from
+/// then on unless compression was enabled for it with
+/// [crate::RpcClientBuilder::endpoint_request_compression].
+pub(crate) struct RequestCompression {
+    threshold: usize,
+    endpoints: Mutex<HashMap<String, Endpoint>>,
+}
+
+#[derive(Clone, Copy)]
+struct Endpoint {
+    is_enabled: bool,
+    is_configured: bool,
+}
+
+impl RequestCompression {
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            endpoints: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_endpoint(&mut self, rpc_url: &str, is_enabled: bool) {
+        self.endpoints.get_mut().unwrap().insert(
+            host_key(rpc_url),
+            Endpoint {
+                is_enabled,
+                is_configured: true,
+            },
+        );
+    }
+
+    /// Return the gzip-compressed `body` if it should be compressed for
+    /// `rpc_url`.
+    pub fn compress(&self, rpc_url: &str, body: &[u8]) -> Option<Vec<u8>> {
+        if body.len() < self.threshold {
+            return None;
+        }
+
+        let is_enabled = self
+            .endpoints
+            .lock()
+            .unwrap()
+            .get(&host_key(rpc_url))
+            .map(|endpoint| endpoint.is_enabled)
+            .unwrap_or(true);
+        if !is_enabled {
+            return None;
+        }
+
+        let mut encoder = GzEncoder::new(Vec::with_capacity(body.len() / 4), Compression::fast());
+        encoder.write_all(body).ok()?;
+        encoder.finish().ok()
+    }
+
+    /// Record that `rpc_url` rejected a compressed request.
+    pub fn reject(&self, rpc_url: &str) {
+        let mut endpoints = self.endpoints.lock().unwrap();
+        let endpoint = endpoints.entry(host_key(rpc_url)).or_insert(Endpoint {
+            is_enabled: true,
+            is_configured: false,
+        });
+
+        if !endpoint.is_configured {
+            endpoint.is_enabled = false;
+        }
+    }
+}
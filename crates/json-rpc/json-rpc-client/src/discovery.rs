@@ -0,0 +1,220 @@
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use hickory_resolver::{
+    config::{NameServerConfigGroup, ResolverConfig, ResolverOpts},
+    error::{ResolveError, ResolveErrorKind},
+    proto::rr::rdata::SRV as SrvRecord,
+    TokioAsyncResolver,
+};
+use tokio::sync::Mutex;
+
+use crate::{validate_rpc_urls, RpcClient, RpcClientError};
+
+/// Where [RpcClient::endpoints] finds the endpoints of a cluster, set with
+/// [crate::RpcClientBuilder::endpoint_discovery].
+///
+/// # Examples
+///
+/// ```rust
+/// use radius_sdk::json_rpc::client::{EndpointDiscovery, RpcClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let rpc_client = RpcClient::builder()
+///         .endpoint_discovery(
+///             EndpointDiscovery::srv("_rpc._tcp.sequencer.example.com").scheme("https"),
+///             30000,
+///         )
+///         .build()
+///         .unwrap();
+///
+///     let rpc_urls = rpc_client.endpoints().await.unwrap();
+///     rpc_client
+///         .multicast(rpc_urls, "send_raw_transaction", &["0x02f8..."], 0)
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct EndpointDiscovery {
+    source: Source,
+    scheme: String,
+    nameserver: Option<SocketAddr>,
+}
+
+#[derive(Clone, Debug)]
+enum Source {
+    Srv(String),
+    Seeder { rpc_url: String, method: String },
+}
+
+impl EndpointDiscovery {
+    /// Resolve the SRV records of `name`, e.g.
+    /// `_rpc._tcp.sequencer.example.com`, into `http://<target>:<port>`
+    /// URLs, ordered by priority and then by weight.
+    pub fn srv(name: impl AsRef<str>) -> Self {
+        Self {
+            source: Source::Srv(name.as_ref().trim_end_matches('.').to_owned()),
+            scheme: "http".to_owned(),
+            nameserver: None,
+        }
+    }
+
+    /// Call `method` without parameters on the seeder at `rpc_url`, which
+    /// returns the URLs as an array of strings.
+    pub fn seeder(rpc_url: impl AsRef<str>, method: impl AsRef<str>) -> Self {
+        Self {
+            source: Source::Seeder {
+                rpc_url: rpc_url.as_ref().to_owned(),
+                method: method.as_ref().to_owned(),
+            },
+            scheme: "http".to_owned(),
+            nameserver: None,
+        }
+    }
+
+    /// Build the URLs of SRV records with `scheme`, e.g. `https`, instead of
+    /// `http`.
+    pub fn scheme(mut self, scheme: impl AsRef<str>) -> Self {
+        self.scheme = scheme.as_ref().to_owned();
+
+        self
+    }
+
+    /// Send SRV queries to `nameserver` instead of the nameservers of the
+    /// system configuration, e.g. `/etc/resolv.conf`.
+    pub fn nameserver(mut self, nameserver: SocketAddr) -> Self {
+        self.nameserver = Some(nameserver);
+
+        self
+    }
+
+    async fn discover(&self, rpc_client: &RpcClient) -> Result<Vec<String>, DiscoveryError> {
+        let rpc_urls = match &self.source {
+            Source::Srv(name) => {
+                let mut records = query_srv(self.nameserver, name).await?;
+                records.sort_by_key(|record| (record.priority(), u16::MAX - record.weight()));
+                records
+                    .into_iter()
+                    .map(|record| {
+                        let target = record.target().to_utf8();
+                        (target.trim_end_matches('.').to_owned(), record.port())
+                    })
+                    // A target of `.` means that the service is not
+                    // available.
+                    .filter(|(target, _port)| !target.is_empty())
+                    .map(|(target, port)| format!("{}://{}:{}", self.scheme, target, port))
+                    .collect()
+            }
+            Source::Seeder { rpc_url, method } => rpc_client
+                .request::<_, Vec<String>>(rpc_url, method, [(); 0], 0)
+                .await
+                .map_err(|error| DiscoveryError::Seeder(Box::new(error)))?,
+        };
+
+        let rpc_urls = validate_rpc_urls(rpc_urls).map_err(|error| match error {
+            RpcClientError::InvalidUrl(rpc_url) => DiscoveryError::InvalidUrl(rpc_url),
+            error => DiscoveryError::Seeder(Box::new(error)),
+        })?;
+        if rpc_urls.is_empty() {
+            return Err(DiscoveryError::NoEndpoints);
+        }
+
+        Ok(rpc_urls)
+    }
+}
+
+/// The endpoints found by [EndpointDiscovery], refreshed once they are older
+/// than `refresh_interval`.
+pub(crate) struct Discovery {
+    endpoint_discovery: EndpointDiscovery,
+    refresh_interval: Duration,
+    endpoints: Mutex<Option<(Instant, Vec<String>)>>,
+}
+
+impl Discovery {
+    pub fn new(endpoint_discovery: EndpointDiscovery, refresh_interval: Duration) -> Self {
+        Self {
+            endpoint_discovery,
+            refresh_interval,
+            endpoints: Mutex::new(None),
+        }
+    }
+
+    pub async fn endpoints(&self, rpc_client: &RpcClient) -> Result<Vec<String>, RpcClientError> {
+        // Holding the lock during the refresh lets concurrent callers wait
+        // for its result instead of querying again.
+        let mut endpoints = self.endpoints.lock().await;
+        if let Some((refreshed_at, rpc_urls)) = endpoints.as_ref() {
+            if refreshed_at.elapsed() < self.refresh_interval {
+                return Ok(rpc_urls.clone());
+            }
+        }
+
+        match self.endpoint_discovery.discover(rpc_client).await {
+            Ok(rpc_urls) => {
+                *endpoints = Some((Instant::now(), rpc_urls.clone()));
+
+                Ok(rpc_urls)
+            }
+            // Keep the last endpoints until the next refresh rather than
+            // failing every request while the source is unavailable.
+            Err(error) => match endpoints.as_mut() {
+                Some((refreshed_at, rpc_urls)) => {
+                    *refreshed_at = Instant::now();
+
+                    Ok(rpc_urls.clone())
+                }
+                None => Err(RpcClientError::Discovery(error)),
+            },
+        }
+    }
+}
+
+async fn query_srv(
+    nameserver: Option<SocketAddr>,
+    name: &str,
+) -> Result<Vec<SrvRecord>, DiscoveryError> {
+    // Queries are sent over UDP with random IDs and retried over TCP when
+    // the response is truncated.
+    let resolver = match nameserver {
+        Some(nameserver) => TokioAsyncResolver::tokio(
+            ResolverConfig::from_parts(
+                None,
+                Vec::new(),
+                NameServerConfigGroup::from_ips_clear(&[nameserver.ip()], nameserver.port(), true),
+            ),
+            ResolverOpts::default(),
+        ),
+        None => TokioAsyncResolver::tokio_from_system_conf().map_err(DiscoveryError::Resolve)?,
+    };
+
+    let srv_lookup = match resolver.srv_lookup(name).await {
+        Ok(srv_lookup) => srv_lookup,
+        Err(error) if matches!(error.kind(), ResolveErrorKind::NoRecordsFound { .. }) => {
+            return Ok(Vec::new());
+        }
+        Err(error) => return Err(DiscoveryError::Resolve(error)),
+    };
+
+    Ok(srv_lookup.iter().cloned().collect())
+}
+
+#[derive(Debug)]
+pub enum DiscoveryError {
+    Resolve(ResolveError),
+    Seeder(Box<RpcClientError>),
+    InvalidUrl(String),
+    NoEndpoints,
+}
+
+impl std::fmt::Display for DiscoveryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for DiscoveryError {}
@@ -0,0 +1,56 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+type DnsEntries = Arc<Mutex<HashMap<String, (Instant, Vec<SocketAddr>)>>>;
+
+/// DNS resolver that keeps the addresses of each host for `ttl` so that
+/// repeated requests to the same endpoints skip the lookup.
+pub(crate) struct DnsCache {
+    ttl: Duration,
+    entries: DnsEntries,
+}
+
+impl DnsCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl Resolve for DnsCache {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_owned();
+
+        if let Some((expires_at, addresses)) = self.entries.lock().unwrap().get(&host) {
+            if Instant::now() < *expires_at {
+                let addresses: Addrs = Box::new(addresses.clone().into_iter());
+
+                return Box::pin(async move { Ok(addresses) });
+            }
+        }
+
+        let ttl = self.ttl;
+        let entries = self.entries.clone();
+        Box::pin(async move {
+            // The port is replaced by the one in the URL.
+            let addresses: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            entries
+                .lock()
+                .unwrap()
+                .insert(host, (Instant::now() + ttl, addresses.clone()));
+
+            let addresses: Addrs = Box::new(addresses.into_iter());
+
+            Ok(addresses)
+        })
+    }
+}
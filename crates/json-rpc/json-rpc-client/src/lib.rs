@@ -1,43 +1,360 @@
 //! Lightweight JSON RPC client for sequencer with the following
 //! functionalities:
+//! - [RpcClient::call]
 //! - [RpcClient::multicast]
+//! - [RpcClient::multicast_batch]
 //! - [RpcClient::fetch]
-use std::{pin::Pin, sync::Arc, time::Duration};
+//! - [RpcClient::fetch_all]
+//!
+//! With the `signed-rpc` feature, [RpcClientBuilder::signer] signs the
+//! serialized body of every request. The signature and the signer address are
+//! sent in the [SIGNATURE_HEADER] and [ADDRESS_HEADER] headers. The signature
+//! also covers the signing time, a random nonce and the `host:port` of the
+//! endpoint, sent in the [TIMESTAMP_HEADER], [NONCE_HEADER] and
+//! [AUDIENCE_HEADER] headers, so that servers can reject replayed requests
+//! and requests captured on another endpoint.
+//!
+//! With [RpcClientBuilder::circuit_breaker], requests to an endpoint that
+//! keeps failing fail immediately with [RpcClientError::CircuitOpen] for a
+//! cooldown period instead of waiting for the timeout.
+//!
+//! [RpcClientBuilder::proxy] and [RpcClientBuilder::endpoint_proxy] route
+//! requests through HTTP (`CONNECT`) or SOCKS5 proxies.
+//!
+//! With [RpcClientBuilder::request_compression], request bodies above a size
+//! threshold are sent gzip-compressed.
+//!
+//! [RpcClientBuilder::header] adds a header to every request, e.g. for the
+//! `Authorization` of a hosted RPC provider, and [CallBuilder::header]
+//! overrides it for a single call.
+//!
+//! Redirects are not followed unless allowed with
+//! [RpcClientBuilder::redirect_limit], since the endpoints are often peers
+//! that are only partly trusted. [RpcClientBuilder::https_only] refuses
+//! plain HTTP endpoints, and [RpcClientBuilder::root_certificates] trusts
+//! only the given certificate authorities.
+//!
+//! With [RpcClientBuilder::endpoint_discovery], [RpcClient::endpoints]
+//! returns the endpoints of a cluster found through DNS SRV records or a
+//! seeder endpoint, refreshed periodically, to pass to
+//! [RpcClient::multicast] and [RpcClient::fetch] instead of a fixed list.
+//!
+//! [RpcClientBuilder::multicast_concurrency] bounds the number of requests in
+//! flight across the functions sending to multiple endpoints.
+//!
+//! Responses are parsed as JSON whatever their `Content-Type`, after undoing
+//! a `gzip` or `deflate` `Content-Encoding`.
+mod batch;
+mod call;
+mod circuit_breaker;
+mod compression;
+mod discovery;
+mod dns_cache;
+mod proxy;
+mod response;
 
-use futures::{
-    future::{join_all, select_ok, Fuse},
-    FutureExt,
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use futures::{future::join_all, stream::FuturesUnordered, StreamExt};
+use reqwest::{
+    header::{self, HeaderMap, HeaderName, HeaderValue},
+    redirect, Certificate, Client, ClientBuilder, RequestBuilder, Response, StatusCode, Url,
 };
-use reqwest::{Client, ClientBuilder};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{
     value::{to_raw_value, RawValue},
     Value,
 };
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+pub use crate::{
+    batch::BatchResponse,
+    call::CallBuilder,
+    discovery::{DiscoveryError, EndpointDiscovery},
+};
+use crate::{
+    circuit_breaker::CircuitBreaker, compression::RequestCompression, discovery::Discovery,
+    dns_cache::DnsCache, proxy::ProxyConfig,
+};
+
+/// The header carrying the hex-encoded signature over the request body.
+#[cfg(feature = "signed-rpc")]
+pub const SIGNATURE_HEADER: &str = "x-radius-signature";
+
+/// The header carrying the hex-encoded address of the signer.
+#[cfg(feature = "signed-rpc")]
+pub const ADDRESS_HEADER: &str = "x-radius-address";
+
+/// The header carrying the signing time in milliseconds since the Unix epoch.
+#[cfg(feature = "signed-rpc")]
+pub const TIMESTAMP_HEADER: &str = "x-radius-timestamp";
+
+/// The header carrying the hex-encoded random nonce of a signed request.
+#[cfg(feature = "signed-rpc")]
+pub const NONCE_HEADER: &str = "x-radius-nonce";
+
+/// The header carrying the `host:port` a signed request is sent to.
+#[cfg(feature = "signed-rpc")]
+pub const AUDIENCE_HEADER: &str = "x-radius-audience";
 
 #[derive(Default)]
-pub struct RpcClientBuilder(ClientBuilder);
+pub struct RpcClientBuilder {
+    client_builder: ClientBuilder,
+    circuit_breaker: Option<CircuitBreaker>,
+    proxy: ProxyConfig,
+    request_compression: Option<RequestCompression>,
+    multicast_concurrency: Option<usize>,
+    default_headers: HeaderMap,
+    redirect_limit: usize,
+    discovery: Option<Discovery>,
+    #[cfg(feature = "signed-rpc")]
+    signer: Option<signature::PrivateKeySigner>,
+}
 
 impl RpcClientBuilder {
     /// Set the connection timeout in milliseconds.
-    pub fn connection_timeout(self, timeout: u64) -> Self {
+    pub fn connection_timeout(mut self, timeout: u64) -> Self {
         let timeout = Duration::from_millis(timeout);
-        let builder = self.0.connect_timeout(timeout);
+        self.client_builder = self.client_builder.connect_timeout(timeout);
 
-        Self(builder)
+        self
     }
 
     /// Set the request timeout in milliseconds.
-    pub fn request_timeout(self, timeout: u64) -> Self {
+    pub fn request_timeout(mut self, timeout: u64) -> Self {
         let timeout = Duration::from_millis(timeout);
-        let builder = self.0.read_timeout(timeout);
+        self.client_builder = self.client_builder.read_timeout(timeout);
+
+        self
+    }
+
+    /// Stop sending requests to a host for `cooldown` milliseconds once
+    /// `failure_rate` (between 0.0 and 1.0) of its last `window` requests
+    /// failed. After the cooldown, a single probe request decides whether the
+    /// host is reachable again.
+    ///
+    /// A request fails if it cannot be sent or its response cannot be parsed.
+    /// Error responses returned by the endpoint do not count as failures.
+    pub fn circuit_breaker(mut self, failure_rate: f64, window: usize, cooldown: u64) -> Self {
+        let cooldown = Duration::from_millis(cooldown);
+        self.circuit_breaker = Some(CircuitBreaker::new(failure_rate, window, cooldown));
+
+        self
+    }
+
+    /// Find the endpoints returned by [RpcClient::endpoints] with
+    /// `endpoint_discovery`, again once they are older than
+    /// `refresh_interval` milliseconds. See [EndpointDiscovery] for an
+    /// example.
+    pub fn endpoint_discovery(
+        mut self,
+        endpoint_discovery: EndpointDiscovery,
+        refresh_interval: u64,
+    ) -> Self {
+        let refresh_interval = Duration::from_millis(refresh_interval);
+        self.discovery = Some(Discovery::new(endpoint_discovery, refresh_interval));
+
+        self
+    }
+
+    /// Cache the resolved addresses of each host for `ttl` milliseconds
+    /// instead of looking them up on every new connection.
+    pub fn dns_cache(mut self, ttl: u64) -> Self {
+        let dns_cache = DnsCache::new(Duration::from_millis(ttl));
+        self.client_builder = self.client_builder.dns_resolver(Arc::new(dns_cache));
+
+        self
+    }
+
+    /// Send every request through the proxy at `proxy_url`, such as
+    /// `http://proxy.example.com:3128` for an HTTP proxy (tunneling `https`
+    /// endpoints with `CONNECT`) or `socks5h://127.0.0.1:9050` for a SOCKS5
+    /// proxy that also resolves host names, as Tor does. Setting any proxy
+    /// disables the proxies configured in the environment (`HTTP_PROXY`,
+    /// `HTTPS_PROXY`, ...).
+    pub fn proxy(mut self, proxy_url: impl AsRef<str>) -> Result<Self, RpcClientError> {
+        self.proxy.set_default(proxy_url.as_ref())?;
+
+        Ok(self)
+    }
+
+    /// Send requests to the host and port of `rpc_url` through the proxy at
+    /// `proxy_url` instead of the one set with [RpcClientBuilder::proxy].
+    /// `None` connects to the endpoint directly.
+    pub fn endpoint_proxy(
+        mut self,
+        rpc_url: impl AsRef<str>,
+        proxy_url: Option<&str>,
+    ) -> Result<Self, RpcClientError> {
+        self.proxy.set_endpoint(rpc_url.as_ref(), proxy_url)?;
+
+        Ok(self)
+    }
+
+    /// Send request bodies of at least `threshold` bytes gzip-compressed with
+    /// `Content-Encoding: gzip`, e.g. for large encrypted transactions
+    /// multicast to every member of a cluster.
+    ///
+    /// An endpoint that answers a compressed request with `415 Unsupported
+    /// Media Type` gets the request again uncompressed, and every later
+    /// request uncompressed. The server of `json-rpc-server` accepts gzip,
+    /// deflate, br and zstd bodies.
+    pub fn request_compression(mut self, threshold: usize) -> Self {
+        self.request_compression = Some(RequestCompression::new(threshold));
+
+        self
+    }
 
-        Self(builder)
+    /// Always (`true`) or never (`false`) compress the requests to the host
+    /// and port of `rpc_url` once they reach the threshold set with
+    /// [RpcClientBuilder::request_compression], instead of negotiating. A
+    /// compressed request answered with `415 Unsupported Media Type` is still
+    /// sent again uncompressed. Does nothing unless
+    /// [RpcClientBuilder::request_compression] was called before.
+    pub fn endpoint_request_compression(
+        mut self,
+        rpc_url: impl AsRef<str>,
+        is_enabled: bool,
+    ) -> Self {
+        if let Some(request_compression) = &mut self.request_compression {
+            request_compression.set_endpoint(rpc_url.as_ref(), is_enabled);
+        }
+
+        self
+    }
+
+    /// Send at most `limit` requests at a time across
+    /// [RpcClient::multicast], [RpcClient::multicast_batch],
+    /// [RpcClient::fetch] and [RpcClient::fetch_all], including concurrent
+    /// calls on the same client, so that multicasting to hundreds of
+    /// endpoints does not open hundreds of sockets at once. The requests to
+    /// the remaining endpoints are sent as earlier ones complete, and each
+    /// function still returns once every endpoint is done. The `timeout` of
+    /// [RpcClient::fetch_all] starts once the request is sent.
+    pub fn multicast_concurrency(mut self, limit: usize) -> Self {
+        self.multicast_concurrency = Some(limit);
+
+        self
+    }
+
+    /// Follow up to `limit` redirects. By default no redirect is followed,
+    /// and a redirect response fails with [RpcClientError::Redirect], so
+    /// that a peer cannot silently send requests somewhere else.
+    ///
+    /// Only `307 Temporary Redirect` and `308 Permanent Redirect` send the
+    /// request body again. The other redirects turn the `POST` into a `GET`
+    /// without a body, which JSON-RPC endpoints rarely answer. `Authorization`
+    /// and the other credential headers are dropped when redirected to
+    /// another host.
+    pub fn redirect_limit(mut self, limit: usize) -> Self {
+        self.redirect_limit = limit;
+
+        self
+    }
+
+    /// Refuse to send requests, or follow redirects, to endpoints that are
+    /// not `https`. Such requests fail with [RpcClientError::Request].
+    pub fn https_only(mut self) -> Self {
+        self.client_builder = self.client_builder.https_only(true);
+
+        self
+    }
+
+    /// Trust only the certificate authorities in `pem_bundle`, one or more
+    /// PEM-encoded certificates, instead of the system ones, e.g. the
+    /// private CA issuing the certificates of the sequencers of a cluster.
+    /// Calling it again adds to the trusted authorities.
+    pub fn root_certificates(mut self, pem_bundle: &[u8]) -> Result<Self, RpcClientError> {
+        let certificates = Certificate::from_pem_bundle(pem_bundle)
+            .map_err(|error| RpcClientError::InvalidCertificate(error.to_string()))?;
+        if certificates.is_empty() {
+            return Err(RpcClientError::InvalidCertificate(
+                "no certificate in the bundle".to_owned(),
+            ));
+        }
+
+        for certificate in certificates {
+            self.client_builder = self.client_builder.add_root_certificate(certificate);
+        }
+        self.client_builder = self.client_builder.tls_built_in_root_certs(false);
+
+        Ok(self)
     }
 
-    pub fn build(self) -> Result<RpcClient, RpcClientError> {
+    /// Send the header `name: value` with every request, e.g.
+    /// `Authorization` for a hosted RPC provider or `X-Radius-Cluster-Id`
+    /// for a gateway. Setting the same header again replaces it, and
+    /// [CallBuilder::header] overrides it for a single call. `Authorization`
+    /// values are left out of `Debug` output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sdk::json_rpc::client::RpcClient;
+    ///
+    /// let rpc_client = RpcClient::builder()
+    ///     .header("Authorization", "Bearer token")
+    ///     .unwrap()
+    ///     .header("X-Radius-Cluster-Id", "radius")
+    ///     .unwrap()
+    ///     .user_agent("sequencer/0.1.0")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn header(
+        mut self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Result<Self, RpcClientError> {
+        let (name, value) = parse_header(name.as_ref(), value.as_ref())?;
+        self.default_headers.insert(name, value);
+
+        Ok(self)
+    }
+
+    /// Set the `User-Agent` header of every request. Sends none by default.
+    pub fn user_agent(mut self, user_agent: impl AsRef<str>) -> Self {
+        self.client_builder = self.client_builder.user_agent(user_agent.as_ref());
+
+        self
+    }
+
+    /// Sign every request with `signer`.
+    #[cfg(feature = "signed-rpc")]
+    pub fn signer(mut self, signer: signature::PrivateKeySigner) -> Self {
+        self.signer = Some(signer);
+
+        self
+    }
+
+    pub fn build(mut self) -> Result<RpcClient, RpcClientError> {
+        if !self.proxy.is_empty() {
+            self.client_builder = self.client_builder.proxy(self.proxy.into_proxy());
+        }
+        if !self.default_headers.is_empty() {
+            self.client_builder = self.client_builder.default_headers(self.default_headers);
+        }
+        self.client_builder = self.client_builder.redirect(match self.redirect_limit {
+            0 => redirect::Policy::none(),
+            limit => redirect::Policy::limited(limit),
+        });
+
         let rpc_client = RpcClient {
-            inner: self.0.build().map_err(RpcClientError::Initialize)?,
+            inner: self
+                .client_builder
+                .build()
+                .map_err(RpcClientError::Initialize)?,
+            circuit_breaker: self.circuit_breaker,
+            request_compression: self.request_compression,
+            multicast_permits: self
+                .multicast_concurrency
+                .map(|limit| Semaphore::new(limit.max(1))),
+            discovery: self.discovery,
+            #[cfg(feature = "signed-rpc")]
+            signer: self.signer,
         };
 
         Ok(rpc_client)
@@ -46,6 +363,12 @@ impl RpcClientBuilder {
 
 pub struct RpcClient {
     inner: Client,
+    circuit_breaker: Option<CircuitBreaker>,
+    request_compression: Option<RequestCompression>,
+    multicast_permits: Option<Semaphore>,
+    discovery: Option<Discovery>,
+    #[cfg(feature = "signed-rpc")]
+    signer: Option<signature::PrivateKeySigner>,
 }
 
 impl RpcClient {
@@ -56,38 +379,185 @@ impl RpcClient {
     pub fn new() -> Result<Self, RpcClientError> {
         let rpc_client = Self {
             inner: ClientBuilder::default()
+                .redirect(redirect::Policy::none())
                 .build()
                 .map_err(RpcClientError::Initialize)?,
+            circuit_breaker: None,
+            request_compression: None,
+            multicast_permits: None,
+            discovery: None,
+            #[cfg(feature = "signed-rpc")]
+            signer: None,
         };
 
         Ok(rpc_client)
     }
 
+    /// Build the request and return whether its body is compressed, which it
+    /// never is unless `is_compression_allowed`. `headers` replace the
+    /// default headers of the same name.
+    fn post<P>(
+        &self,
+        url: impl AsRef<str>,
+        payload: &P,
+        headers: Option<&HeaderMap>,
+        is_compression_allowed: bool,
+    ) -> Result<(RequestBuilder, bool), RpcClientError>
+    where
+        P: Serialize,
+    {
+        let request_builder = self
+            .inner
+            .post(url.as_ref())
+            .header(header::CONTENT_TYPE, "application/json");
+        let request_builder = match headers {
+            Some(headers) => request_builder.headers(headers.clone()),
+            None => request_builder,
+        };
+        let body = serde_json::to_vec(payload).map_err(RpcClientError::Serialize)?;
+
+        #[cfg(feature = "signed-rpc")]
+        let request_builder = match &self.signer {
+            Some(signer) => {
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                let nonce = format!("{:032x}", rand::random::<u128>());
+                let audience = audience(url.as_ref())?;
+
+                // `<timestamp>\n<nonce>\n<audience>\n<body>`, as verified by
+                // `SignatureVerificationLayer` of `json-rpc-server`.
+                let mut message = format!("{}\n{}\n{}\n", timestamp, nonce, audience).into_bytes();
+                message.extend_from_slice(&body);
+                let signature = signer
+                    .sign_message(&message)
+                    .map_err(RpcClientError::Sign)?;
+
+                request_builder
+                    .header(SIGNATURE_HEADER, signature.as_hex_string())
+                    .header(ADDRESS_HEADER, signer.address().as_hex_string())
+                    .header(TIMESTAMP_HEADER, timestamp)
+                    .header(NONCE_HEADER, nonce)
+                    .header(AUDIENCE_HEADER, audience)
+            }
+            None => request_builder,
+        };
+
+        // The signature covers the uncompressed body.
+        let compressed_body = self
+            .request_compression
+            .as_ref()
+            .filter(|_| is_compression_allowed)
+            .and_then(|request_compression| request_compression.compress(url.as_ref(), &body));
+        match compressed_body {
+            Some(compressed_body) => Ok((
+                request_builder
+                    .header(header::CONTENT_ENCODING, "gzip")
+                    .body(compressed_body),
+                true,
+            )),
+            None => Ok((request_builder.body(body), false)),
+        }
+    }
+
+    /// Send the request, again uncompressed if the endpoint rejects the
+    /// compressed body.
+    async fn send<P>(
+        &self,
+        url: &str,
+        payload: &P,
+        headers: Option<&HeaderMap>,
+    ) -> Result<Response, RpcClientError>
+    where
+        P: Serialize,
+    {
+        let (request_builder, is_compressed) = self.post(url, payload, headers, true)?;
+        let response = request_builder
+            .send()
+            .await
+            .map_err(RpcClientError::Request)?;
+
+        match (&self.request_compression, response.status()) {
+            (Some(request_compression), StatusCode::UNSUPPORTED_MEDIA_TYPE) if is_compressed => {
+                request_compression.reject(url);
+
+                // Uncompressed even if compression is enabled for the
+                // endpoint with `endpoint_request_compression()`.
+                let (request_builder, _) = self.post(url, payload, headers, false)?;
+                request_builder
+                    .send()
+                    .await
+                    .map_err(RpcClientError::Request)
+            }
+            _others => Ok(response),
+        }
+        .and_then(|response| match response.status().is_redirection() {
+            true => Err(RpcClientError::Redirect(
+                response
+                    .headers()
+                    .get(header::LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .unwrap_or_default()
+                    .to_owned(),
+            )),
+            false => Ok(response),
+        })
+    }
+
     async fn request_inner<P, R>(
         &self,
         url: impl AsRef<str>,
         payload: P,
+        headers: Option<&HeaderMap>,
     ) -> Result<R, RpcClientError>
     where
         P: Serialize,
         R: DeserializeOwned,
     {
-        self.inner
-            .post(url.as_ref())
-            .json(&payload)
-            .send()
-            .await
-            .map_err(RpcClientError::Request)?
-            .json::<R>()
-            .await
-            .map_err(RpcClientError::ParseResponse)
+        let host_key = self.acquire(url.as_ref())?;
+
+        let response = async {
+            let response = self.send(url.as_ref(), &payload, headers).await?;
+
+            response::parse::<R>(response).await
+        }
+        .await;
+        self.record(host_key, response.is_ok());
+
+        response
     }
 
     async fn fire_and_forget<P>(&self, url: impl AsRef<str>, payload: P)
     where
         P: Serialize,
     {
-        let _ = self.inner.post(url.as_ref()).json(&payload).send().await;
+        if let Ok(host_key) = self.acquire(url.as_ref()) {
+            let response = self.send(url.as_ref(), &payload, None).await;
+            self.record(host_key, response.is_ok());
+        }
+    }
+
+    fn acquire(&self, url: &str) -> Result<Option<String>, RpcClientError> {
+        self.circuit_breaker
+            .as_ref()
+            .map(|circuit_breaker| circuit_breaker.acquire(url))
+            .transpose()
+    }
+
+    /// Wait until fewer than [RpcClientBuilder::multicast_concurrency]
+    /// requests are in flight. The permit is released when dropped.
+    async fn multicast_permit(&self) -> Option<SemaphorePermit<'_>> {
+        match &self.multicast_permits {
+            Some(multicast_permits) => multicast_permits.acquire().await.ok(),
+            None => None,
+        }
+    }
+
+    fn record(&self, host_key: Option<String>, is_success: bool) {
+        if let (Some(circuit_breaker), Some(host_key)) = (&self.circuit_breaker, host_key) {
+            circuit_breaker.record(&host_key, is_success);
+        }
     }
 
     /// Send an RPC request and wait for the response.
@@ -135,7 +605,20 @@ impl RpcClient {
     {
         let request =
             RequestObject::new(method, &parameter, id).map_err(RpcClientError::Serialize)?;
-        let response: ResponseObject = self.request_inner(rpc_url, &request).await?;
+
+        self.request_object(rpc_url, request, None).await
+    }
+
+    async fn request_object<R>(
+        &self,
+        rpc_url: impl AsRef<str>,
+        request: RequestObject,
+        headers: Option<&HeaderMap>,
+    ) -> Result<R, RpcClientError>
+    where
+        R: DeserializeOwned,
+    {
+        let response: ResponseObject = self.request_inner(rpc_url, &request, headers).await?;
 
         if response.id != request.id {
             return Err(RpcClientError::IdMismatch);
@@ -144,8 +627,19 @@ impl RpcClient {
         response.into_payload().parse::<R>()
     }
 
-    /// Send a batch of several requests at the same time and get the response
-    /// as a vector of RPC response object [Payload].
+    /// Start building a request to `method`, to be sent with
+    /// [CallBuilder::send] once the endpoint is set with [CallBuilder::to].
+    /// See [CallBuilder] for an example.
+    pub fn call(&self, method: impl AsRef<str>) -> CallBuilder<'_> {
+        CallBuilder::new(self, method)
+    }
+
+    /// Send a batch of several requests at the same time and get the
+    /// responses as a [BatchResponse], matched to the requests by ID and in
+    /// the order of the requests.
+    ///
+    /// Fail with [RpcClientError::DuplicateId] without sending anything if
+    /// two requests share an ID, and if the server answers a request twice.
     ///
     /// # Examples
     ///
@@ -200,7 +694,7 @@ impl RpcClient {
     ///         .await
     ///         .unwrap();
     ///     for (index, response) in batch_response.into_iter().enumerate() {
-    ///         match response.parse::<String>() {
+    ///         match response.and_then(|payload| payload.parse::<String>()) {
     ///             Ok(nonce) => println!("Nonce for Address {}: {:?}", index, nonce),
     ///             Err(error) => println!("Error: {}", error),
     ///         }
@@ -211,28 +705,33 @@ impl RpcClient {
         &self,
         rpc_url: impl AsRef<str>,
         batch_request: &BatchRequest,
-    ) -> Result<Vec<Payload>, RpcClientError> {
+    ) -> Result<BatchResponse, RpcClientError> {
+        let indices = batch::index(&batch_request.0)?;
         let response_objects: Vec<ResponseObject> =
-            self.request_inner(rpc_url, &batch_request).await?;
-
-        let payloads: Vec<Payload> = batch_request
-            .iter()
-            .zip(response_objects.into_iter())
-            .map(|(request, response)| {
-                if request.id == response.id {
-                    Ok(response.into_payload())
-                } else {
-                    Err(RpcClientError::IdMismatch)
-                }
-            })
-            .collect::<Result<Vec<Payload>, RpcClientError>>()?;
+            self.request_inner(rpc_url, &batch_request, None).await?;
 
-        Ok(payloads)
+        batch::match_responses(&batch_request.0, &indices, response_objects)
+    }
+
+    /// Return the endpoints found with [RpcClientBuilder::endpoint_discovery],
+    /// refreshing them first if they are older than its refresh interval.
+    /// If a refresh fails, the last endpoints found are returned until the
+    /// next one.
+    ///
+    /// Return [RpcClientError::MissingDiscovery] if no discovery is set.
+    pub async fn endpoints(&self) -> Result<Vec<String>, RpcClientError> {
+        match &self.discovery {
+            Some(discovery) => discovery.endpoints(self).await,
+            None => Err(RpcClientError::MissingDiscovery),
+        }
     }
 
     /// Send RPC requests to multiple endpoints. Once transactions are sent,
     /// the function short-circuits without waiting for responses.
     ///
+    /// Return [RpcClientError::InvalidUrl] without sending any request if one
+    /// of `rpc_urls` is not a valid HTTP(S) URL.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -275,13 +774,21 @@ impl RpcClient {
     where
         P: Serialize,
     {
+        let rpc_urls = validate_rpc_urls(rpc_urls)?;
         let request: Arc<RequestObject> = RequestObject::new(method, parameter, id)
             .map_err(RpcClientError::Serialize)?
             .into();
 
         let tasks: Vec<_> = rpc_urls
             .into_iter()
-            .map(|rpc_url| self.fire_and_forget(rpc_url, request.clone()))
+            .map(|rpc_url| {
+                let request = request.clone();
+
+                async move {
+                    let _permit = self.multicast_permit().await;
+                    self.fire_and_forget(rpc_url, request).await;
+                }
+            })
             .collect();
 
         join_all(tasks).await;
@@ -289,8 +796,71 @@ impl RpcClient {
         Ok(())
     }
 
+    /// Send the whole batch as a single request to each endpoint
+    /// concurrently and return the responses of every endpoint, in the same
+    /// order as `rpc_urls`. Sending N requests to M endpoints takes M HTTP
+    /// requests instead of N×M.
+    ///
+    /// Return [RpcClientError::InvalidUrl] without sending any request if one
+    /// of `rpc_urls` is not a valid HTTP(S) URL.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sdk::json_rpc::client::{BatchRequest, RpcClient};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let rpc_urls = vec!["http://127.0.0.1:8545", "http://127.0.0.1:8546"];
+    ///
+    ///     let mut batch_request = BatchRequest::new();
+    ///     batch_request
+    ///         .push("eth_sendRawTransaction", &["0x02f8..."], 0)
+    ///         .unwrap();
+    ///     batch_request
+    ///         .push("eth_sendRawTransaction", &["0x02f9..."], 1)
+    ///         .unwrap();
+    ///
+    ///     let rpc_client = RpcClient::new().unwrap();
+    ///
+    ///     let batch_responses = rpc_client
+    ///         .multicast_batch(rpc_urls, &batch_request)
+    ///         .await
+    ///         .unwrap();
+    ///     for batch_response in batch_responses {
+    ///         match batch_response.response {
+    ///             Ok(payloads) => println!("{}: {:?}", batch_response.rpc_url, payloads),
+    ///             Err(error) => println!("{}: {}", batch_response.rpc_url, error),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub async fn multicast_batch(
+        &self,
+        rpc_urls: Vec<impl AsRef<str>>,
+        batch_request: &BatchRequest,
+    ) -> Result<Vec<EndpointBatchResponse>, RpcClientError> {
+        let rpc_urls = validate_rpc_urls(rpc_urls)?;
+
+        let tasks: Vec<_> = rpc_urls
+            .into_iter()
+            .map(|rpc_url| async move {
+                let _permit = self.multicast_permit().await;
+                let response = self.batch_request(&rpc_url, batch_request).await;
+
+                EndpointBatchResponse { rpc_url, response }
+            })
+            .collect();
+
+        Ok(join_all(tasks).await)
+    }
+
     /// Send RPC requests to multiple endpoints and return the first successful
-    /// response or an error if none of the responses succeeds.
+    /// response. If none of the responses succeeds, return
+    /// [RpcClientError::Fetch] with the error of each endpoint.
+    ///
+    /// Return [RpcClientError::InvalidUrl] without sending any request if one
+    /// of `rpc_url_list` is not a valid HTTP(S) URL.
     ///
     /// # Examples
     ///
@@ -337,29 +907,142 @@ impl RpcClient {
         P: Clone + Serialize,
         R: DeserializeOwned,
     {
-        let method = method.as_ref().to_owned();
+        let rpc_url_list = validate_rpc_urls(rpc_url_list)?;
+        let method = method.as_ref();
         let request: Arc<P> = parameter.clone().into();
         let id: Id = id.into();
 
-        let fused_futures: Vec<Pin<Box<Fuse<_>>>> = rpc_url_list
+        let mut responses: FuturesUnordered<_> = rpc_url_list
             .into_iter()
             .map(|rpc_url| {
-                Box::pin(
-                    self.request::<Arc<P>, R>(rpc_url, method.clone(), request.clone(), id.clone())
-                        .fuse(),
-                )
+                let request = request.clone();
+                let id = id.clone();
+
+                async move {
+                    let _permit = self.multicast_permit().await;
+                    let response = self
+                        .request::<Arc<P>, R>(&rpc_url, method, request, id)
+                        .await;
+
+                    (rpc_url, response)
+                }
             })
             .collect();
 
-        let (response, _): (R, Vec<_>) = select_ok(fused_futures)
-            .await
-            .map_err(|error| RpcClientError::Fetch(error.into()))?;
+        let mut endpoint_errors = Vec::new();
+        while let Some((rpc_url, response)) = responses.next().await {
+            match response {
+                Ok(response) => return Ok(response),
+                Err(error) => endpoint_errors.push(EndpointError { rpc_url, error }),
+            }
+        }
+
+        Err(RpcClientError::Fetch(endpoint_errors))
+    }
+
+    /// Send RPC requests to multiple endpoints and wait for every response
+    /// for up to `timeout` milliseconds. Return the response of each
+    /// endpoint with the time it took, in the same order as `rpc_urls`, e.g.
+    /// to monitor the health of a cluster or to detect endpoints that
+    /// disagree. Endpoints that do not respond in time fail with
+    /// [RpcClientError::Timeout].
+    ///
+    /// Return [RpcClientError::InvalidUrl] without sending any request if one
+    /// of `rpc_urls` is not a valid HTTP(S) URL.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sdk::json_rpc::client::RpcClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let rpc_urls = vec!["http://127.0.0.1:8545", "http://127.0.0.1:8546"];
+    ///
+    ///     let rpc_client = RpcClient::new().unwrap();
+    ///
+    ///     let responses = rpc_client
+    ///         .fetch_all::<_, String>(rpc_urls, "eth_blockNumber", &(), 0, 1000)
+    ///         .await
+    ///         .unwrap();
+    ///     for response in responses {
+    ///         match response.response {
+    ///             Ok(block_number) => println!(
+    ///                 "{}: {} in {:?}",
+    ///                 response.rpc_url, block_number, response.latency
+    ///             ),
+    ///             Err(error) => println!("{}: {}", response.rpc_url, error),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub async fn fetch_all<P, R>(
+        &self,
+        rpc_urls: Vec<impl AsRef<str>>,
+        method: impl AsRef<str>,
+        parameter: &P,
+        id: impl Into<Id>,
+        timeout: u64,
+    ) -> Result<Vec<EndpointResponse<R>>, RpcClientError>
+    where
+        P: Serialize,
+        R: DeserializeOwned,
+    {
+        let rpc_urls = validate_rpc_urls(rpc_urls)?;
+        let request =
+            RequestObject::new(method, parameter, id).map_err(RpcClientError::Serialize)?;
+        let timeout = Duration::from_millis(timeout);
 
-        Ok(response)
+        let tasks: Vec<_> = rpc_urls
+            .into_iter()
+            .map(|rpc_url| {
+                let request = &request;
+
+                async move {
+                    let _permit = self.multicast_permit().await;
+                    let start = Instant::now();
+                    let response = tokio::time::timeout(timeout, async {
+                        let response: ResponseObject =
+                            self.request_inner(&rpc_url, request, None).await?;
+                        if response.id != request.id {
+                            return Err(RpcClientError::IdMismatch);
+                        }
+
+                        response.into_payload().parse::<R>()
+                    })
+                    .await
+                    .unwrap_or(Err(RpcClientError::Timeout));
+
+                    EndpointResponse {
+                        rpc_url,
+                        response,
+                        latency: start.elapsed(),
+                    }
+                }
+            })
+            .collect();
+
+        Ok(join_all(tasks).await)
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+fn validate_rpc_urls(rpc_urls: Vec<impl AsRef<str>>) -> Result<Vec<String>, RpcClientError> {
+    rpc_urls
+        .into_iter()
+        .map(|rpc_url| {
+            let rpc_url = rpc_url.as_ref();
+            let is_valid = Url::parse(rpc_url)
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+
+            match is_valid {
+                true => Ok(rpc_url.to_owned()),
+                false => Err(RpcClientError::InvalidUrl(rpc_url.to_owned())),
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum Id {
     String(String),
@@ -443,7 +1126,7 @@ pub enum Payload {
     Error {
         code: i32,
         message: String,
-        data: Option<Id>,
+        data: Option<Value>,
     },
 }
 
@@ -454,14 +1137,72 @@ impl Payload {
                 serde_json::from_value::<T>(value).map_err(RpcClientError::Deserialize)
             }
             Self::Error {
-                code: _,
+                code,
                 message,
-                data: _,
-            } => Err(RpcClientError::Response(message)),
+                data,
+            } => Err(RpcClientError::Response(ResponseError {
+                code: code.into(),
+                message,
+                data,
+            })),
         }
     }
 }
 
+/// The error codes defined by the JSON-RPC 2.0 specification. Codes outside
+/// of the predefined ones, including the `-32000` to `-32099` range reserved
+/// for implementation-defined server errors, are kept as
+/// [JsonRpcErrorCode::ServerError].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonRpcErrorCode {
+    ParseError,
+    InvalidRequest,
+    MethodNotFound,
+    InvalidParams,
+    InternalError,
+    ServerError(i32),
+}
+
+impl JsonRpcErrorCode {
+    pub fn code(&self) -> i32 {
+        match self {
+            Self::ParseError => -32700,
+            Self::InvalidRequest => -32600,
+            Self::MethodNotFound => -32601,
+            Self::InvalidParams => -32602,
+            Self::InternalError => -32603,
+            Self::ServerError(code) => *code,
+        }
+    }
+}
+
+impl From<i32> for JsonRpcErrorCode {
+    fn from(value: i32) -> Self {
+        match value {
+            -32700 => Self::ParseError,
+            -32600 => Self::InvalidRequest,
+            -32601 => Self::MethodNotFound,
+            -32602 => Self::InvalidParams,
+            -32603 => Self::InternalError,
+            others => Self::ServerError(others),
+        }
+    }
+}
+
+impl From<JsonRpcErrorCode> for i32 {
+    fn from(value: JsonRpcErrorCode) -> Self {
+        value.code()
+    }
+}
+
+/// The error object of a response, returned as [RpcClientError::Response].
+#[derive(Clone, Debug)]
+pub struct ResponseError {
+    pub code: JsonRpcErrorCode,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
 #[derive(Debug, Default, Serialize)]
 pub struct BatchRequest(Vec<RequestObject>);
 
@@ -485,22 +1226,68 @@ impl BatchRequest {
 
         Ok(())
     }
+}
 
-    fn iter(&self) -> std::slice::Iter<RequestObject> {
-        self.0.iter()
-    }
+/// The error returned by one of the endpoints of [RpcClient::fetch].
+#[derive(Debug)]
+pub struct EndpointError {
+    pub rpc_url: String,
+    pub error: RpcClientError,
+}
+
+/// The response of one of the endpoints of [RpcClient::fetch_all] and the
+/// time from sending the request to decoding the response.
+#[derive(Debug)]
+pub struct EndpointResponse<R> {
+    pub rpc_url: String,
+    pub response: Result<R, RpcClientError>,
+    pub latency: Duration,
+}
+
+/// The response of one of the endpoints of [RpcClient::multicast_batch].
+#[derive(Debug)]
+pub struct EndpointBatchResponse {
+    pub rpc_url: String,
+    pub response: Result<BatchResponse, RpcClientError>,
 }
 
 #[derive(Debug)]
 pub enum RpcClientError {
     Initialize(reqwest::Error),
     Request(reqwest::Error),
-    ParseResponse(reqwest::Error),
-    Response(String),
+    ReadResponse(reqwest::Error),
+    DecompressResponse(std::io::Error),
+    UnsupportedContentEncoding(String),
+    /// The body, cut to its first 256 bytes, could not be parsed as a
+    /// JSON-RPC response.
+    ParseResponse {
+        error: serde_json::Error,
+        body: String,
+    },
+    Response(ResponseError),
     IdMismatch,
+    /// Two requests of a batch share the ID, or the server answered the
+    /// request with the ID twice.
+    DuplicateId(Id),
+    /// The server did not answer the request of a batch with the ID.
+    MissingResponse(Id),
     Serialize(serde_json::Error),
     Deserialize(serde_json::Error),
-    Fetch(Box<dyn std::error::Error>),
+    InvalidUrl(String),
+    MissingUrl,
+    Fetch(Vec<EndpointError>),
+    CircuitOpen(String),
+    InvalidProxy(String),
+    InvalidHeader(String),
+    InvalidCertificate(String),
+    /// The endpoint redirected the request to the location, see
+    /// [RpcClientBuilder::redirect_limit].
+    Redirect(String),
+    MissingDiscovery,
+    Discovery(DiscoveryError),
+    Timeout,
+    #[cfg(feature = "signed-rpc")]
+    Sign(signature::SignatureError),
 }
 
 unsafe impl Send for RpcClientError {}
@@ -512,3 +1299,28 @@ impl std::fmt::Display for RpcClientError {
 }
 
 impl std::error::Error for RpcClientError {}
+
+/// Parse a header set with [RpcClientBuilder::header] or
+/// [CallBuilder::header], marking credentials as sensitive.
+fn parse_header(name: &str, value: &str) -> Result<(HeaderName, HeaderValue), RpcClientError> {
+    let header_name = HeaderName::from_bytes(name.as_bytes())
+        .map_err(|_| RpcClientError::InvalidHeader(name.to_owned()))?;
+    let mut header_value =
+        HeaderValue::from_str(value).map_err(|_| RpcClientError::InvalidHeader(name.to_owned()))?;
+    if header_name == header::AUTHORIZATION || header_name == header::PROXY_AUTHORIZATION {
+        header_value.set_sensitive(true);
+    }
+
+    Ok((header_name, header_value))
+}
+
+/// The `host:port` of `rpc_url`, with the default port of the scheme if the
+/// URL has none.
+#[cfg(feature = "signed-rpc")]
+fn audience(rpc_url: &str) -> Result<String, RpcClientError> {
+    let url = Url::parse(rpc_url).map_err(|_| RpcClientError::InvalidUrl(rpc_url.to_owned()))?;
+    match (url.host_str(), url.port_or_known_default()) {
+        (Some(host), Some(port)) => Ok(format!("{}:{}", host, port)),
+        _others => Err(RpcClientError::InvalidUrl(rpc_url.to_owned())),
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use reqwest::{Proxy, Url};
+
+use crate::{circuit_breaker::host_key, RpcClientError};
+
+/// Proxy selection for [crate::RpcClientBuilder::proxy] and
+/// [crate::RpcClientBuilder::endpoint_proxy]. Endpoints are matched by host
+/// and port, so every path of an endpoint uses the same proxy.
+#[derive(Default)]
+pub(crate) struct ProxyConfig {
+    default: Option<Url>,
+    // `None` connects to the endpoint directly.
+    endpoints: HashMap<String, Option<Url>>,
+}
+
+impl ProxyConfig {
+    pub fn set_default(&mut self, proxy_url: &str) -> Result<(), RpcClientError> {
+        self.default = Some(parse(proxy_url)?);
+
+        Ok(())
+    }
+
+    pub fn set_endpoint(
+        &mut self,
+        rpc_url: &str,
+        proxy_url: Option<&str>,
+    ) -> Result<(), RpcClientError> {
+        let proxy_url = proxy_url.map(parse).transpose()?;
+        self.endpoints.insert(host_key(rpc_url), proxy_url);
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.endpoints.is_empty()
+    }
+
+    pub fn into_proxy(self) -> Proxy {
+        Proxy::custom(
+            move |url| match self.endpoints.get(&host_key(url.as_str())) {
+                Some(proxy_url) => proxy_url.clone(),
+                None => self.default.clone(),
+            },
+        )
+    }
+}
+
+fn parse(proxy_url: &str) -> Result<Url, RpcClientError> {
+    let url =
+        Url::parse(proxy_url).map_err(|_| RpcClientError::InvalidProxy(proxy_url.to_owned()))?;
+
+    match url.scheme() {
+        "http" | "https" | "socks5" | "socks5h" => Ok(url),
+        _ => Err(RpcClientError::InvalidProxy(proxy_url.to_owned())),
+    }
+}
@@ -0,0 +1,80 @@
+use std::io::Read;
+
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use reqwest::{header, Response};
+use serde::de::DeserializeOwned;
+
+use crate::RpcClientError;
+
+/// The longest part of an unparsable body kept in
+/// [RpcClientError::ParseResponse].
+const BODY_SNIPPET_LENGTH: usize = 256;
+
+/// Decode the body of `response` as JSON whatever its `Content-Type`, since
+/// some peers answer with `text/plain` or no content type at all. Bodies
+/// compressed with `gzip` or `deflate` are decompressed as declared by
+/// `Content-Encoding`.
+pub(crate) async fn parse<R>(response: Response) -> Result<R, RpcClientError>
+where
+    R: DeserializeOwned,
+{
+    let content_encoding = response
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .and_then(|content_encoding| content_encoding.to_str().ok())
+        .map(str::to_owned);
+    let body = response
+        .bytes()
+        .await
+        .map_err(RpcClientError::ReadResponse)?;
+
+    let body = match content_encoding {
+        Some(content_encoding) => decode(&content_encoding, body.to_vec())?,
+        None => body.to_vec(),
+    };
+    let json = body.strip_prefix(b"\xef\xbb\xbf").unwrap_or(&body);
+
+    serde_json::from_slice(json).map_err(|error| RpcClientError::ParseResponse {
+        error,
+        body: snippet(json),
+    })
+}
+
+/// Undo the encodings listed in `content_encoding`, which are applied in
+/// order.
+fn decode(content_encoding: &str, mut body: Vec<u8>) -> Result<Vec<u8>, RpcClientError> {
+    for encoding in content_encoding.rsplit(',').map(str::trim) {
+        body = match encoding.to_ascii_lowercase().as_str() {
+            "" | "identity" => body,
+            "gzip" | "x-gzip" => read_all(GzDecoder::new(body.as_slice()))?,
+            // `deflate` is meant to be zlib-wrapped, but some servers send a
+            // raw deflate stream.
+            "deflate" => read_all(ZlibDecoder::new(body.as_slice()))
+                .or_else(|_| read_all(DeflateDecoder::new(body.as_slice())))?,
+            _ => {
+                return Err(RpcClientError::UnsupportedContentEncoding(
+                    encoding.to_owned(),
+                ))
+            }
+        };
+    }
+
+    Ok(body)
+}
+
+fn read_all(mut decoder: impl Read) -> Result<Vec<u8>, RpcClientError> {
+    let mut body = Vec::new();
+    decoder
+        .read_to_end(&mut body)
+        .map_err(RpcClientError::DecompressResponse)?;
+
+    Ok(body)
+}
+
+fn snippet(body: &[u8]) -> String {
+    let snippet = String::from_utf8_lossy(&body[..body.len().min(BODY_SNIPPET_LENGTH)]);
+    match body.len() > BODY_SNIPPET_LENGTH {
+        true => format!("{}...", snippet),
+        false => snippet.into_owned(),
+    }
+}
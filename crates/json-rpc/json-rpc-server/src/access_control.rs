@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+
+use signature::Address;
+
+/// The callers allowed to invoke a method registered with
+/// [`crate::RpcServer::register_authenticated_rpc_method()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MethodPolicy {
+    /// Any caller, signed or not.
+    Public,
+    /// Signed callers that are cluster members or admins.
+    ClusterMember,
+    /// Signed callers that are admins.
+    Admin,
+}
+
+/// Decide which addresses belong to the cluster or administer the node.
+///
+/// The provider is queried on every call to a non-public method, so
+/// implementations backed by a contract should cache the member list.
+pub trait AclProvider: Send + Sync + 'static {
+    fn is_cluster_member(&self, address: &Address) -> bool;
+
+    fn is_admin(&self, address: &Address) -> bool;
+}
+
+/// [`AclProvider`] backed by fixed address lists.
+#[derive(Clone, Debug, Default)]
+pub struct StaticAcl {
+    cluster_members: HashSet<Address>,
+    admins: HashSet<Address>,
+}
+
+impl StaticAcl {
+    pub fn new(
+        cluster_members: impl IntoIterator<Item = Address>,
+        admins: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        Self {
+            cluster_members: cluster_members.into_iter().collect(),
+            admins: admins.into_iter().collect(),
+        }
+    }
+}
+
+impl AclProvider for StaticAcl {
+    fn is_cluster_member(&self, address: &Address) -> bool {
+        self.cluster_members.contains(address)
+    }
+
+    fn is_admin(&self, address: &Address) -> bool {
+        self.admins.contains(address)
+    }
+}
+
+impl MethodPolicy {
+    pub(crate) fn check(
+        &self,
+        acl_provider: Option<&dyn AclProvider>,
+        address: Option<&Address>,
+    ) -> Result<(), AccessError> {
+        if *self == Self::Public {
+            return Ok(());
+        }
+
+        let address = address.ok_or(AccessError::Unauthenticated)?;
+        let is_allowed = acl_provider.is_some_and(|acl_provider| match self {
+            Self::Public => true,
+            Self::ClusterMember => {
+                acl_provider.is_cluster_member(address) || acl_provider.is_admin(address)
+            }
+            Self::Admin => acl_provider.is_admin(address),
+        });
+
+        match is_allowed {
+            true => Ok(()),
+            false => Err(AccessError::Forbidden(address.clone())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AccessError {
+    Unauthenticated,
+    Forbidden(Address),
+}
+
+impl std::fmt::Display for AccessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for AccessError {}
@@ -0,0 +1,196 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use futures::future::{ready, Either, Ready};
+use jsonrpsee::{
+    server::{middleware::rpc::RpcServiceT, MethodResponse},
+    types::{ErrorObject, Request},
+};
+
+/// The error code of calls to a method disabled with
+/// [`AdminHandle::disable_method()`].
+pub const METHOD_DISABLED_CODE: i32 = -32010;
+
+/// The error code of calls to write methods while the server drains, see
+/// [`AdminHandle::set_draining()`].
+pub const DRAINING_CODE: i32 = -32011;
+
+/// Runtime controls of a running [`crate::RpcServer`], returned by
+/// [`crate::RpcServer::admin_handle()`], e.g. to take a sequencer out of
+/// rotation for maintenance without restarting the process.
+///
+/// The handle is cheap to clone and applies to every server started from
+/// the same [`crate::RpcServer`], including every listener.
+///
+/// # Examples
+///
+/// ```rust
+/// use json_rpc_server::RpcServer;
+///
+/// let server = RpcServer::new(()).read_methods(["get_block"]);
+/// let admin_handle = server.admin_handle();
+///
+/// // Reject new writes, keep serving `get_block`.
+/// admin_handle.set_draining(true);
+/// admin_handle.disable_method("get_block");
+/// assert!(!admin_handle.is_method_enabled("get_block"));
+/// ```
+#[derive(Clone, Default)]
+pub struct AdminHandle {
+    inner: Arc<AdminState>,
+}
+
+#[derive(Default)]
+struct AdminState {
+    disabled_methods: RwLock<HashSet<String>>,
+    read_methods: RwLock<HashSet<String>>,
+    is_draining: AtomicBool,
+    connection_count: AtomicUsize,
+}
+
+impl AdminHandle {
+    /// Reject calls to `method` with [`METHOD_DISABLED_CODE`] until it is
+    /// enabled again. Subscriptions that are already open are not closed.
+    pub fn disable_method(&self, method: impl AsRef<str>) {
+        self.inner
+            .disabled_methods
+            .write()
+            .unwrap()
+            .insert(method.as_ref().to_owned());
+    }
+
+    pub fn enable_method(&self, method: impl AsRef<str>) {
+        self.inner
+            .disabled_methods
+            .write()
+            .unwrap()
+            .remove(method.as_ref());
+    }
+
+    pub fn is_method_enabled(&self, method: &str) -> bool {
+        !self.inner.disabled_methods.read().unwrap().contains(method)
+    }
+
+    pub fn disabled_methods(&self) -> Vec<String> {
+        self.inner
+            .disabled_methods
+            .read()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// In drain mode, only the read methods are served and every other
+    /// call is rejected with [`DRAINING_CODE`]. Read methods are the ones
+    /// passed to [`crate::RpcServer::read_methods()`], cached methods,
+    /// subscriptions and the method registry.
+    pub fn set_draining(&self, is_draining: bool) {
+        self.inner.is_draining.store(is_draining, Ordering::Relaxed);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.inner.is_draining.load(Ordering::Relaxed)
+    }
+
+    /// The number of open WebSocket connections and HTTP requests in flight,
+    /// e.g. to wait until a draining server is idle.
+    pub fn connection_count(&self) -> usize {
+        self.inner.connection_count.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn insert_read_method(&self, method: impl AsRef<str>) {
+        self.inner
+            .read_methods
+            .write()
+            .unwrap()
+            .insert(method.as_ref().to_owned());
+    }
+
+    fn check(&self, method: &str) -> Result<(), ErrorObject<'static>> {
+        if !self.is_method_enabled(method) {
+            return Err(ErrorObject::owned::<()>(
+                METHOD_DISABLED_CODE,
+                format!("Method `{}` is disabled.", method),
+                None,
+            ));
+        }
+
+        if self.is_draining() && !self.inner.read_methods.read().unwrap().contains(method) {
+            return Err(ErrorObject::owned::<()>(
+                DRAINING_CODE,
+                "The server is draining and only serves read methods.",
+                None,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// RPC middleware that applies the [`AdminHandle`] to every call, including
+/// the calls inside a batch and subscriptions. jsonrpsee builds the service
+/// once per WebSocket connection and once per HTTP request, which is what
+/// [`AdminHandle::connection_count()`] counts.
+#[derive(Clone)]
+pub(crate) struct AdminLayer(pub AdminHandle);
+
+impl<S> tower::Layer<S> for AdminLayer {
+    type Service = Admin<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Admin {
+            inner,
+            connection: Connection::open(self.0.clone()),
+        }
+    }
+}
+
+pub(crate) struct Admin<S> {
+    inner: S,
+    connection: Connection,
+}
+
+impl<'a, S> RpcServiceT<'a> for Admin<S>
+where
+    S: RpcServiceT<'a>,
+{
+    type Future = Either<S::Future, Ready<MethodResponse>>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        match self.connection.admin_handle.check(request.method_name()) {
+            Ok(()) => Either::Left(self.inner.call(request)),
+            Err(error) => Either::Right(ready(MethodResponse::error(request.id, error))),
+        }
+    }
+}
+
+/// Counts an open connection until dropped.
+struct Connection {
+    admin_handle: AdminHandle,
+}
+
+impl Connection {
+    fn open(admin_handle: AdminHandle) -> Self {
+        admin_handle
+            .inner
+            .connection_count
+            .fetch_add(1, Ordering::Relaxed);
+
+        Self { admin_handle }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.admin_handle
+            .inner
+            .connection_count
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+/// Responses of the methods registered with
+/// [`crate::RpcServer::register_cached_rpc_method()`], keyed by method and
+/// parameter.
+///
+/// The cache is a handle: keep a clone in the server context so that the
+/// handlers of write methods can invalidate the responses they make stale.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use json_rpc_server::{ResponseCache, RpcError, RpcParameter, RpcServer};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Clone)]
+/// struct AppContext {
+///     cache: ResponseCache,
+/// }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct GetBlock {
+///     height: u64,
+/// }
+///
+/// impl RpcParameter<AppContext> for GetBlock {
+///     type Response = String;
+///
+///     fn method() -> &'static str {
+///         "get_block"
+///     }
+///
+///     async fn handler(self, _context: AppContext) -> Result<Self::Response, RpcError> {
+///         Ok(format!("block {}", self.height))
+///     }
+/// }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct PutBlock {
+///     height: u64,
+/// }
+///
+/// impl RpcParameter<AppContext> for PutBlock {
+///     type Response = ();
+///
+///     fn method() -> &'static str {
+///         "put_block"
+///     }
+///
+///     async fn handler(self, context: AppContext) -> Result<Self::Response, RpcError> {
+///         context.cache.invalidate_entry(
+///             "get_block",
+///             &GetBlock {
+///                 height: self.height,
+///             },
+///         );
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let cache = ResponseCache::new();
+/// let server = RpcServer::new(AppContext {
+///     cache: cache.clone(),
+/// })
+/// .register_cached_rpc_method::<GetBlock>(&cache, Duration::from_secs(2), 1024)
+/// .unwrap()
+/// .register_rpc_method::<PutBlock>()
+/// .unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct ResponseCache {
+    methods: Arc<Mutex<HashMap<&'static str, MethodCache>>>,
+}
+
+struct MethodCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: HashMap<String, Entry>,
+}
+
+struct Entry {
+    inserted_at: Instant,
+    response: Box<dyn Any + Send>,
+}
+
+impl ResponseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop every cached response of `method`.
+    pub fn invalidate(&self, method: &str) {
+        if let Some(method_cache) = self.methods.lock().unwrap().get_mut(method) {
+            method_cache.entries.clear();
+        }
+    }
+
+    /// Drop the cached response of `method` for `parameter`.
+    pub fn invalidate_entry<P: Serialize>(&self, method: &str, parameter: &P) {
+        let Some(key) = cache_key(parameter) else {
+            return;
+        };
+
+        if let Some(method_cache) = self.methods.lock().unwrap().get_mut(method) {
+            method_cache.entries.remove(&key);
+        }
+    }
+
+    /// Drop every cached response.
+    pub fn invalidate_all(&self) {
+        for method_cache in self.methods.lock().unwrap().values_mut() {
+            method_cache.entries.clear();
+        }
+    }
+
+    pub(crate) fn configure(&self, method: &'static str, ttl: Duration, max_entries: usize) {
+        self.methods.lock().unwrap().insert(
+            method,
+            MethodCache {
+                ttl,
+                max_entries,
+                entries: HashMap::new(),
+            },
+        );
+    }
+
+    pub(crate) fn get<R>(&self, method: &str, key: &str) -> Option<R>
+    where
+        R: Clone + 'static,
+    {
+        let mut methods = self.methods.lock().unwrap();
+        let method_cache = methods.get_mut(method)?;
+
+        let entry = method_cache.entries.get(key)?;
+        if entry.inserted_at.elapsed() >= method_cache.ttl {
+            method_cache.entries.remove(key);
+
+            return None;
+        }
+
+        entry.response.downcast_ref::<R>().cloned()
+    }
+
+    pub(crate) fn insert<R>(&self, method: &str, key: String, response: R)
+    where
+        R: Send + 'static,
+    {
+        let mut methods = self.methods.lock().unwrap();
+        let Some(method_cache) = methods.get_mut(method) else {
+            return;
+        };
+        if method_cache.max_entries == 0 {
+            return;
+        }
+
+        if method_cache.entries.len() >= method_cache.max_entries
+            && !method_cache.entries.contains_key(&key)
+        {
+            let ttl = method_cache.ttl;
+            method_cache
+                .entries
+                .retain(|_key, entry| entry.inserted_at.elapsed() < ttl);
+
+            // All entries share the TTL, so the oldest one expires first.
+            if method_cache.entries.len() >= method_cache.max_entries {
+                if let Some(oldest_key) = method_cache
+                    .entries
+                    .iter()
+                    .min_by_key(|(_key, entry)| entry.inserted_at)
+                    .map(|(key, _entry)| key.clone())
+                {
+                    method_cache.entries.remove(&oldest_key);
+                }
+            }
+        }
+
+        method_cache.entries.insert(
+            key,
+            Entry {
+                inserted_at: Instant::now(),
+                response: Box::new(response),
+            },
+        );
+    }
+}
+
+/// The key of the response to `parameter`. The parameter is serialized
+/// again after parsing, so that requests differing only in whitespace or
+/// field order share an entry.
+pub(crate) fn cache_key<P: Serialize>(parameter: &P) -> Option<String> {
+    serde_json::to_string(parameter).ok()
+}
@@ -0,0 +1,76 @@
+use context::{ContextError, SharedContext};
+use serde::{Deserialize, Serialize};
+
+/// Parameters of a method registered with
+/// [`crate::RpcServer::register_config_method()`], e.g.
+/// `{"config": {"max_requests": 200}, "expected_version": 3}`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ConfigUpdate<T> {
+    pub config: T,
+    /// Reject the update with [`ConfigError::VersionMismatch`] unless the
+    /// config is still at this [`SharedContext::version()`], so that an
+    /// operator does not overwrite a change made since they read it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_version: Option<u64>,
+}
+
+/// Replace the value of `config` with `update.config` once `validate`
+/// accepted it, and return the new version.
+pub(crate) fn update<T, F>(
+    config: &SharedContext<T>,
+    update: ConfigUpdate<T>,
+    validate: &F,
+) -> Result<u64, crate::RpcError>
+where
+    F: Fn(&T, &T) -> Result<(), crate::RpcError>,
+{
+    // The version is checked, the value validated and the update made
+    // against the same snapshot.
+    let current = config.load();
+    let version = current.version();
+    if let Some(expected_version) = update.expected_version {
+        if version != expected_version {
+            return Err(ConfigError::VersionMismatch {
+                expected_version,
+                version,
+            }
+            .into());
+        }
+    }
+    validate(current.as_ref(), &update.config)?;
+
+    // Fails if the config changed since it was loaded, so the update never
+    // replaces a value it was not validated against.
+    config
+        .update_at_version(version, update.config)
+        .map_err(|error| ConfigError::Update(error).into())
+}
+
+pub enum ConfigError {
+    VersionMismatch { expected_version: u64, version: u64 },
+    Update(ContextError),
+}
+
+impl std::fmt::Debug for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VersionMismatch {
+                expected_version,
+                version,
+            } => write!(
+                f,
+                "Expected config version {}, found {}",
+                expected_version, version
+            ),
+            Self::Update(error) => write!(f, "{:?}", error),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -0,0 +1,76 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use http::{header, HeaderValue};
+use jsonrpsee::server::{HttpRequest, HttpResponse};
+use tower::{Layer, Service};
+
+/// Deprecated method names called within one HTTP request, collected by the
+/// alias handlers and turned into `Warning` headers by [`DeprecationLayer`].
+#[derive(Clone, Default)]
+pub(crate) struct DeprecationWarnings(Arc<Mutex<Vec<String>>>);
+
+impl DeprecationWarnings {
+    pub fn push(&self, alias: &str, method: &str) {
+        self.0.lock().unwrap().push(format!(
+            "299 - \"Method `{}` is deprecated, use `{}` instead.\"",
+            alias, method
+        ));
+    }
+}
+
+/// Layer that adds a `Warning` header to the response for every deprecated
+/// alias registered with [`crate::RpcServer::register_alias()`] that the
+/// request called, including calls inside a batch.
+#[derive(Clone)]
+pub(crate) struct DeprecationLayer;
+
+impl<S> Layer<S> for DeprecationLayer {
+    type Service = Deprecation<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Deprecation { inner }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Deprecation<S> {
+    inner: S,
+}
+
+impl<S, B> Service<HttpRequest<B>> for Deprecation<S>
+where
+    S: Service<HttpRequest<B>, Response = HttpResponse>,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: HttpRequest<B>) -> Self::Future {
+        let warnings = DeprecationWarnings::default();
+        request.extensions_mut().insert(warnings.clone());
+
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let mut response = future.await?;
+
+            for warning in warnings.0.lock().unwrap().drain(..) {
+                if let Ok(warning) = HeaderValue::from_str(&warning) {
+                    response.headers_mut().append(header::WARNING, warning);
+                }
+            }
+
+            Ok(response)
+        })
+    }
+}
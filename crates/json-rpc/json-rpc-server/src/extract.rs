@@ -0,0 +1,94 @@
+/// A part of the server context `C` that handlers registered with
+/// [`crate::RpcServer::register_rpc_method_with_context()`] receive instead
+/// of a clone of the whole context.
+///
+/// # Examples
+///
+/// ```rust
+/// use json_rpc_server::{Ctx, FromContext, RpcError, RpcParameter, RpcServer};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Clone)]
+/// struct AppContext {
+///     chain_id: u64,
+///     // Heavyweight state that `GetChainId` does not need.
+///     blocks: Vec<Vec<u8>>,
+/// }
+///
+/// #[derive(Clone)]
+/// struct ChainId {
+///     id: u64,
+/// }
+///
+/// impl FromContext<AppContext> for ChainId {
+///     fn from_context(context: &AppContext) -> Self {
+///         Self {
+///             id: context.chain_id,
+///         }
+///     }
+/// }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct GetChainId {}
+///
+/// impl RpcParameter<Ctx<ChainId>> for GetChainId {
+///     type Response = u64;
+///
+///     fn method() -> &'static str {
+///         "get_chain_id"
+///     }
+///
+///     async fn handler(self, chain_id: Ctx<ChainId>) -> Result<Self::Response, RpcError> {
+///         Ok(chain_id.id)
+///     }
+/// }
+///
+/// let context = AppContext {
+///     chain_id: 1,
+///     blocks: Vec::new(),
+/// };
+/// let server = RpcServer::new(context)
+///     .register_rpc_method_with_context::<GetChainId, Ctx<ChainId>>()
+///     .unwrap();
+/// ```
+pub trait FromContext<C>: Sized {
+    fn from_context(context: &C) -> Self;
+}
+
+/// Extracts `T` from the server context with [`FromContext`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ctx<T>(pub T);
+
+impl<C, T> FromContext<C> for Ctx<T>
+where
+    T: FromContext<C>,
+{
+    fn from_context(context: &C) -> Self {
+        Self(T::from_context(context))
+    }
+}
+
+impl<T> std::ops::Deref for Ctx<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+macro_rules! impl_from_context_for_tuple {
+    ($($part:ident),+) => {
+        impl<C, $($part),+> FromContext<C> for ($($part,)+)
+        where
+            $($part: FromContext<C>),+
+        {
+            fn from_context(context: &C) -> Self {
+                ($($part::from_context(context),)+)
+            }
+        }
+    };
+}
+
+impl_from_context_for_tuple!(A, B);
+impl_from_context_for_tuple!(A, B, D);
+impl_from_context_for_tuple!(A, B, D, E);
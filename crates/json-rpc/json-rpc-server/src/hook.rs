@@ -0,0 +1,125 @@
+use std::{
+    future::Future,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use jsonrpsee::types::Params;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::RpcError;
+
+/// Interceptor called around every method call, registered with
+/// [`crate::RpcServer::hook()`], for cross-cutting concerns such as audit
+/// logging, rewriting parameters or tracking latency budgets.
+///
+/// Hooks are called in registration order with the name the method was
+/// registered under, also for calls through an alias. Subscriptions are not
+/// intercepted.
+pub trait RpcHook<C>: Send + Sync + 'static {
+    /// Called before the parameters are parsed. `parameter` may be modified
+    /// in place, and returning an error rejects the call without calling
+    /// the handler or the remaining hooks.
+    fn before(&self, method: &str, parameter: &mut Value, context: &C) -> Result<(), RpcError> {
+        let _ = (method, parameter, context);
+
+        Ok(())
+    }
+
+    /// Called with the serialized response or the error once the call
+    /// completed, including calls rejected by [`RpcHook::before()`].
+    fn after(&self, method: &str, result: Result<&Value, &RpcError>, latency: Duration) {
+        let _ = (method, result, latency);
+    }
+}
+
+pub(crate) type SharedHooks<C> = Arc<RwLock<Vec<Arc<dyn RpcHook<C>>>>>;
+
+/// Parse `parameter` and call `handler`, passing through the registered
+/// hooks.
+pub(crate) async fn call<C, P, R, F, Fut>(
+    hooks: &SharedHooks<C>,
+    method: &str,
+    parameter: Params<'static>,
+    context: &C,
+    handler: F,
+) -> Result<R, RpcError>
+where
+    C: 'static,
+    P: DeserializeOwned,
+    R: Serialize,
+    F: FnOnce(P) -> Fut,
+    Fut: Future<Output = Result<R, RpcError>>,
+{
+    let validate = None::<fn(&Value) -> Result<(), RpcError>>;
+
+    call_validated(hooks, method, parameter, context, validate, handler).await
+}
+
+/// Same as [`call()`], checking the parameter with `validate` after the
+/// hooks rewrote it and before it is parsed.
+pub(crate) async fn call_validated<C, P, R, V, F, Fut>(
+    hooks: &SharedHooks<C>,
+    method: &str,
+    parameter: Params<'static>,
+    context: &C,
+    validate: Option<V>,
+    handler: F,
+) -> Result<R, RpcError>
+where
+    C: 'static,
+    P: DeserializeOwned,
+    R: Serialize,
+    V: FnOnce(&Value) -> Result<(), RpcError>,
+    F: FnOnce(P) -> Fut,
+    Fut: Future<Output = Result<R, RpcError>>,
+{
+    let hooks = hooks.read().unwrap().clone();
+    if hooks.is_empty() {
+        let parameter = match validate {
+            Some(validate) => {
+                let parameter = parameter.parse::<Value>()?;
+                validate(&parameter)?;
+
+                serde_json::from_value::<P>(parameter)?
+            }
+            None => parameter.parse::<P>()?,
+        };
+
+        return handler(parameter).await;
+    }
+
+    let start = Instant::now();
+    let result = async {
+        let mut parameter = parameter.parse::<Value>()?;
+        for hook in hooks.iter() {
+            hook.before(method, &mut parameter, context)?;
+        }
+        if let Some(validate) = validate {
+            validate(&parameter)?;
+        }
+        let parameter = serde_json::from_value::<P>(parameter)?;
+
+        handler(parameter).await
+    }
+    .await;
+    let latency = start.elapsed();
+
+    // A response that does not serialize fails the call, so the hooks see
+    // the error rather than the response.
+    let result = result.and_then(|response| {
+        let value = serde_json::to_value(&response)?;
+
+        Ok((response, value))
+    });
+    for hook in hooks.iter() {
+        hook.after(
+            method,
+            result.as_ref().map(|(_response, value)| value),
+            latency,
+        );
+    }
+
+    result.map(|(response, _value)| response)
+}
@@ -0,0 +1,91 @@
+use std::{net::IpAddr, str::FromStr};
+
+use ipnet::IpNet;
+
+use crate::ParseError;
+
+/// CIDR ranges that the TCP servers of an [`crate::RpcServer`] accept
+/// connections from, set with [`crate::RpcServer::ip_filter()`] or
+/// [`crate::Listener::ip_filter()`].
+///
+/// A connection is accepted if its peer address is in none of the denied
+/// ranges and, if any range is allowed, in one of the allowed ones. Rejected
+/// connections are closed right after they are accepted, before any request
+/// is read. IPv4 addresses mapped to IPv6 are matched as IPv4.
+///
+/// # Examples
+///
+/// ```rust
+/// use json_rpc_server::IpFilter;
+///
+/// let ip_filter = IpFilter::new()
+///     .allow(["10.0.0.0/8", "192.168.1.0/24"])
+///     .unwrap()
+///     .deny(["10.0.0.1"])
+///     .unwrap();
+///
+/// assert!(ip_filter.is_allowed("10.1.2.3".parse().unwrap()));
+/// assert!(!ip_filter.is_allowed("10.0.0.1".parse().unwrap()));
+/// assert!(!ip_filter.is_allowed("172.16.0.1".parse().unwrap()));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IpFilter {
+    allowed: Vec<IpNet>,
+    denied: Vec<IpNet>,
+}
+
+impl IpFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept connections from `ranges` only, e.g. `10.0.0.0/8` or a single
+    /// address such as `10.0.0.1`. Can be called more than once to add
+    /// ranges.
+    pub fn allow<I, S>(mut self, ranges: I) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.allowed.extend(parse_ranges(ranges)?);
+
+        Ok(self)
+    }
+
+    /// Reject connections from `ranges`, even if they are allowed by
+    /// [`IpFilter::allow()`].
+    pub fn deny<I, S>(mut self, ranges: I) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.denied.extend(parse_ranges(ranges)?);
+
+        Ok(self)
+    }
+
+    pub fn is_allowed(&self, address: IpAddr) -> bool {
+        let address = address.to_canonical();
+        if self.denied.iter().any(|range| range.contains(&address)) {
+            return false;
+        }
+
+        self.allowed.is_empty() || self.allowed.iter().any(|range| range.contains(&address))
+    }
+}
+
+fn parse_ranges<I, S>(ranges: I) -> Result<Vec<IpNet>, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    ranges
+        .into_iter()
+        .map(|range| {
+            let range = range.as_ref();
+            IpNet::from_str(range)
+                .or_else(|_| IpAddr::from_str(range).map(IpNet::from))
+                .map_err(|_| ParseError::InvalidCidr(range.to_owned()))
+        })
+        .collect()
+}
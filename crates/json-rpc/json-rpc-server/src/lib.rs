@@ -1,15 +1,100 @@
-use std::{str::FromStr, sync::Arc};
+#[cfg(feature = "signed-rpc")]
+mod access_control;
+mod admin;
+mod cache;
+#[cfg(feature = "shared-context")]
+mod config;
+mod deprecation;
+mod extract;
+mod hook;
+mod ip_filter;
+mod listener;
+mod pagination;
+mod registry;
+#[cfg(feature = "signed-rpc")]
+mod replay_protection;
+#[cfg(feature = "schema-validation")]
+mod schema_validation;
+#[cfg(feature = "signed-rpc")]
+mod session_token;
+#[cfg(feature = "signed-rpc")]
+mod signature_verification;
+mod subscription;
+mod trace;
+#[cfg(unix)]
+mod unix_socket;
 
-use http::{header, method::Method, Extensions};
+#[cfg(feature = "signed-rpc")]
+use std::sync::RwLock;
+use std::{any, collections::HashSet, net::SocketAddr, str::FromStr, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+#[cfg(feature = "shared-context")]
+use context::SharedContext;
+use futures::stream::BoxStream;
+use http::{
+    header::{self, HeaderName, HeaderValue},
+    method::Method,
+    Extensions,
+};
 pub use jsonrpsee::server::ServerHandle;
 use jsonrpsee::{
-    server::{middleware::http::ProxyGetRequestLayer, RpcModule, Server},
+    server::{
+        middleware::{http::ProxyGetRequestLayer, rpc::RpcServiceBuilder},
+        HttpBody, HttpResponse, RpcModule, Server, SubscriptionCloseResponse,
+    },
     types::{ErrorCode, ErrorObject, Params},
 };
+#[cfg(feature = "schema-validation")]
+pub use schemars;
 use serde::{de::DeserializeOwned, Serialize};
-use tower_http::cors::{Any, CorsLayer};
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tower::{
+    layer::util::{Identity, Stack},
+    util::{Either, MapResponseLayer},
+    BoxError, ServiceBuilder,
+};
+use tower_http::{
+    body::UnsyncBoxBody,
+    cors::{AllowOrigin, CorsLayer},
+    decompression::RequestDecompressionLayer,
+};
 use url::Url;
 
+#[cfg(feature = "shared-context")]
+pub use crate::config::{ConfigError, ConfigUpdate};
+#[cfg(feature = "schema-validation")]
+use crate::schema_validation::{InvalidParams, ParamsSchema};
+#[cfg(unix)]
+pub use crate::unix_socket::UnixSocket;
+#[cfg(feature = "signed-rpc")]
+pub use crate::{
+    access_control::{AccessError, AclProvider, MethodPolicy, StaticAcl},
+    replay_protection::ReplayProtection,
+    signature_verification::{
+        SignatureVerification, SignatureVerificationLayer, ADDRESS_HEADER, AUDIENCE_HEADER,
+        NONCE_HEADER, SESSION_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+    },
+};
+use crate::{
+    admin::AdminLayer,
+    deprecation::{DeprecationLayer, DeprecationWarnings},
+    hook::SharedHooks,
+    pagination::{Cursors, InvalidPageToken, NextPage},
+    trace::{TraceLayer, WithPeerAddress},
+};
+pub use crate::{
+    admin::{AdminHandle, DRAINING_CODE, METHOD_DISABLED_CODE},
+    cache::ResponseCache,
+    extract::{Ctx, FromContext},
+    hook::RpcHook,
+    ip_filter::IpFilter,
+    listener::Listener,
+    pagination::Page,
+    registry::{MethodEntry, MethodKind, MethodRegistry},
+    subscription::SubscriptionPolicy,
+};
+
 #[trait_variant::make(RpcParameter: Send)]
 pub trait LocalRpcParameter<C>: DeserializeOwned + Serialize
 where
@@ -22,11 +107,146 @@ where
     async fn handler(self, context: C) -> Result<Self::Response, RpcError>;
 }
 
+/// Parameter of a subscription registered with
+/// [`RpcServer::register_subscription()`]. Every item of the stream returned
+/// by the handler is sent to the client as a
+/// [`RpcSubscription::notification_method()`] notification.
+#[trait_variant::make(RpcSubscription: Send)]
+pub trait LocalRpcSubscription<C>: DeserializeOwned + Serialize
+where
+    C: Clone + Send + Sync + 'static,
+{
+    type Item: Send + 'static + Serialize;
+
+    fn subscribe_method() -> &'static str;
+
+    fn notification_method() -> &'static str;
+
+    fn unsubscribe_method() -> &'static str;
+
+    async fn handler(self, context: C) -> Result<BoxStream<'static, Self::Item>, RpcError>;
+}
+
+/// Parameter of a method registered with
+/// [`RpcServer::register_paginated_method()`]. The stream returned by the
+/// handler is read a [`Page`] at a time, so that a large result is never
+/// held in memory as a whole. See [`Page`] for an example.
+#[trait_variant::make(RpcPaginatedParameter: Send)]
+pub trait LocalRpcPaginatedParameter<C>: DeserializeOwned + Serialize
+where
+    C: Clone + Send + Sync + 'static,
+{
+    type Item: Clone + Send + 'static + Serialize;
+
+    fn method() -> &'static str;
+
+    fn next_page_method() -> &'static str;
+
+    async fn handler(self, context: C) -> Result<BoxStream<'static, Self::Item>, RpcError>;
+}
+
+/// [`RpcParameter`] for methods that are guarded by a [`MethodPolicy`]. The
+/// handler receives the address verified by [`SignatureVerificationLayer`],
+/// which is `None` only for unsigned calls to [`MethodPolicy::Public`]
+/// methods.
+#[cfg(feature = "signed-rpc")]
+#[trait_variant::make(AuthenticatedRpcParameter: Send)]
+pub trait LocalAuthenticatedRpcParameter<C>: DeserializeOwned + Serialize
+where
+    C: Clone + Send + Sync + 'static,
+{
+    type Response: Clone + Send + 'static + DeserializeOwned + Serialize;
+
+    fn method() -> &'static str;
+
+    fn policy() -> MethodPolicy;
+
+    async fn handler(
+        self,
+        context: C,
+        address: Option<signature::Address>,
+    ) -> Result<Self::Response, RpcError>;
+}
+
+#[cfg(feature = "signed-rpc")]
+type SharedAclProvider = Arc<RwLock<Option<Arc<dyn AclProvider>>>>;
+
+/// Turns the responses of [`RequestDecompressionLayer`] back into the
+/// responses expected by the layers around it.
+type DecompressionResponse = fn(HttpResponse<UnsyncBoxBody<Bytes, BoxError>>) -> HttpResponse;
+
+type DecompressionMiddleware = Stack<
+    RequestDecompressionLayer,
+    Stack<
+        MapResponseLayer<DecompressionResponse>,
+        Stack<Either<DeprecationLayer, Identity>, Stack<CorsLayer, Identity>>,
+    >,
+>;
+
+#[cfg(not(feature = "signed-rpc"))]
+type HttpMiddleware = Stack<ProxyGetRequestLayer, DecompressionMiddleware>;
+
+#[cfg(feature = "signed-rpc")]
+type HttpMiddleware = Stack<
+    ProxyGetRequestLayer,
+    Stack<Either<SignatureVerificationLayer, Identity>, DecompressionMiddleware>,
+>;
+
+/// Every call is handled within an `rpc_call` [`tracing`] span recording
+/// `method`, `request_id`, `peer_address` and, for requests verified by
+/// `RpcServer::verify_signature()`, `signer`, so that logs of a call can
+/// be correlated with the client by its JSON-RPC id.
 pub struct RpcServer<C>
 where
     C: Clone + Send + Sync + 'static,
 {
     rpc_module: RpcModule<C>,
+    admin_handle: AdminHandle,
+    cors: Cors,
+    deprecation: Option<DeprecationLayer>,
+    hooks: SharedHooks<C>,
+    ip_filter: Option<IpFilter>,
+    listeners: Vec<Listener>,
+    method_registry: MethodRegistry,
+    method_registry_method: Option<&'static str>,
+    #[cfg(feature = "signed-rpc")]
+    signature_verification: Option<SignatureVerificationLayer>,
+    #[cfg(feature = "signed-rpc")]
+    acl_provider: SharedAclProvider,
+}
+
+struct Cors {
+    allowed_origins: Option<Vec<HeaderValue>>,
+    allowed_headers: Vec<HeaderName>,
+    allowed_methods: Vec<Method>,
+    allow_credentials: bool,
+}
+
+impl Default for Cors {
+    fn default() -> Self {
+        Self {
+            allowed_origins: None,
+            allowed_headers: vec![header::CONTENT_TYPE],
+            allowed_methods: vec![Method::GET, Method::POST],
+            allow_credentials: false,
+        }
+    }
+}
+
+impl Cors {
+    fn layer(&self) -> Result<CorsLayer, RpcServerError> {
+        let allowed_origins = match &self.allowed_origins {
+            Some(allowed_origins) => AllowOrigin::list(allowed_origins.clone()),
+            None if self.allow_credentials => return Err(RpcServerError::CorsCredentials),
+            None => AllowOrigin::any(),
+        };
+
+        Ok(CorsLayer::new()
+            .allow_methods(self.allowed_methods.clone())
+            .allow_origin(allowed_origins)
+            .allow_headers(self.allowed_headers.clone())
+            .allow_credentials(self.allow_credentials))
+    }
 }
 
 impl<C> RpcServer<C>
@@ -36,68 +256,1024 @@ where
     pub fn new(context: C) -> Self {
         Self {
             rpc_module: RpcModule::new(context),
+            admin_handle: AdminHandle::default(),
+            cors: Cors::default(),
+            deprecation: None,
+            hooks: SharedHooks::default(),
+            ip_filter: None,
+            listeners: Vec::new(),
+            method_registry: MethodRegistry::default(),
+            method_registry_method: None,
+            #[cfg(feature = "signed-rpc")]
+            signature_verification: None,
+            #[cfg(feature = "signed-rpc")]
+            acl_provider: SharedAclProvider::default(),
+        }
+    }
+
+    /// Reject requests that are not signed as described in
+    /// [`SignatureVerificationLayer`].
+    #[cfg(feature = "signed-rpc")]
+    pub fn verify_signature(mut self, layer: SignatureVerificationLayer) -> Self {
+        self.signature_verification = Some(layer);
+
+        self
+    }
+
+    /// Set the [`AclProvider`] checked against the [`MethodPolicy`] of
+    /// methods registered with
+    /// [`RpcServer::register_authenticated_rpc_method()`]. Without a
+    /// provider, only [`MethodPolicy::Public`] methods can be called.
+    #[cfg(feature = "signed-rpc")]
+    pub fn acl_provider(self, acl_provider: impl AclProvider) -> Self {
+        *self.acl_provider.write().unwrap() = Some(Arc::new(acl_provider));
+
+        self
+    }
+
+    /// The [`AdminHandle`] of the servers started from this instance, to
+    /// disable methods or drain them at runtime.
+    pub fn admin_handle(&self) -> AdminHandle {
+        self.admin_handle.clone()
+    }
+
+    /// Keep serving `methods` while draining, see
+    /// [`AdminHandle::set_draining()`]. Methods registered with
+    /// [`RpcServer::register_cached_rpc_method()`] are read methods already.
+    pub fn read_methods<I, S>(self, methods: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for method in methods {
+            self.admin_handle.insert_read_method(method);
         }
+
+        self
+    }
+
+    /// Add `hook` to the hooks called around every method call, after the
+    /// ones added before it.
+    pub fn hook(self, hook: impl RpcHook<C>) -> Self {
+        self.hooks.write().unwrap().push(Arc::new(hook));
+
+        self
+    }
+
+    /// Restrict CORS to the given origins (e.g. `https://example.com`).
+    /// Every origin is allowed by default.
+    pub fn allowed_origins<I, S>(mut self, origins: I) -> Result<Self, RpcServerError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let allowed_origins = origins
+            .into_iter()
+            .map(|origin| HeaderValue::from_str(origin.as_ref()))
+            .collect::<Result<Vec<HeaderValue>, _>>()
+            .map_err(ParseError::InvalidOrigin)?;
+        self.cors.allowed_origins = Some(allowed_origins);
+
+        Ok(self)
+    }
+
+    /// Set the request headers allowed by CORS. Defaults to `Content-Type`.
+    pub fn allowed_headers<I, S>(mut self, headers: I) -> Result<Self, RpcServerError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.cors.allowed_headers = headers
+            .into_iter()
+            .map(|header| HeaderName::from_str(header.as_ref()))
+            .collect::<Result<Vec<HeaderName>, _>>()
+            .map_err(ParseError::InvalidHeader)?;
+
+        Ok(self)
+    }
+
+    /// Set the HTTP methods allowed by CORS. Defaults to `GET` and `POST`.
+    pub fn allowed_methods<I, S>(mut self, methods: I) -> Result<Self, RpcServerError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.cors.allowed_methods = methods
+            .into_iter()
+            .map(|method| Method::from_str(method.as_ref()))
+            .collect::<Result<Vec<Method>, _>>()
+            .map_err(ParseError::InvalidMethod)?;
+
+        Ok(self)
+    }
+
+    /// Allow credentials (cookies, authorization headers) in cross-origin
+    /// requests. Requires [`RpcServer::allowed_origins()`] to be set because
+    /// browsers reject credentials with a wildcard origin.
+    pub fn allow_credentials(mut self, allow_credentials: bool) -> Self {
+        self.cors.allow_credentials = allow_credentials;
+
+        self
+    }
+
+    /// Only accept TCP connections from the ranges allowed by `ip_filter`,
+    /// e.g. the subnets of the peer sequencers for cluster-internal methods.
+    /// Listeners with their own [`Listener::ip_filter()`] use that one
+    /// instead. Connections to [`RpcServer::init_unix_socket()`] are not
+    /// filtered.
+    pub fn ip_filter(mut self, ip_filter: IpFilter) -> Self {
+        self.ip_filter = Some(ip_filter);
+
+        self
     }
 
     async fn handler<P>(
         parameter: Params<'static>,
         context: Arc<C>,
         _extensions: Extensions,
+        hooks: SharedHooks<C>,
     ) -> Result<P::Response, RpcError>
     where
         P: RpcParameter<C> + 'static,
     {
-        let parameter = parameter.parse::<P>()?;
-
-        P::handler(parameter, (*context).clone()).await
+        hook::call(&hooks, P::method(), parameter, &context, |parameter: P| {
+            P::handler(parameter, (*context).clone())
+        })
+        .await
     }
 
     pub fn register_rpc_method<P>(mut self) -> Result<Self, RpcServerError>
     where
         P: RpcParameter<C> + 'static,
     {
+        self.method_registry
+            .insert(P::method(), MethodKind::Method, any::type_name::<P>())?;
+
+        let hooks = self.hooks.clone();
+        self.rpc_module
+            .register_async_method(P::method(), move |parameter, context, extensions| {
+                Self::handler::<P>(parameter, context, extensions, hooks.clone())
+            })
+            .map_err(RpcServerError::RegisterMethod)?;
+
+        Ok(self)
+    }
+
+    async fn cached_handler<P>(
+        parameter: Params<'static>,
+        context: Arc<C>,
+        _extensions: Extensions,
+        hooks: SharedHooks<C>,
+        cache: ResponseCache,
+    ) -> Result<P::Response, RpcError>
+    where
+        P: RpcParameter<C> + 'static,
+    {
+        let cache = &cache;
+        let context = &context;
+        hook::call(
+            &hooks,
+            P::method(),
+            parameter,
+            context,
+            |parameter: P| async move {
+                let key = cache::cache_key(&parameter);
+                if let Some(response) = key
+                    .as_ref()
+                    .and_then(|key| cache.get::<P::Response>(P::method(), key))
+                {
+                    return Ok(response);
+                }
+
+                let response = P::handler(parameter, (**context).clone()).await?;
+                if let Some(key) = key {
+                    cache.insert(P::method(), key, response.clone());
+                }
+
+                Ok(response)
+            },
+        )
+        .await
+    }
+
+    /// Register `P` with its responses cached in `cache` for `ttl`, keyed by
+    /// parameter, e.g. for read methods that are called far more often than
+    /// their data changes. Once `max_entries` responses are cached, the
+    /// oldest one is dropped to make room. Errors are not cached.
+    ///
+    /// Handlers that change the data call [`ResponseCache::invalidate()`] or
+    /// [`ResponseCache::invalidate_entry()`] on a clone of `cache` kept in
+    /// the context. See [`ResponseCache`] for an example.
+    pub fn register_cached_rpc_method<P>(
+        mut self,
+        cache: &ResponseCache,
+        ttl: Duration,
+        max_entries: usize,
+    ) -> Result<Self, RpcServerError>
+    where
+        P: RpcParameter<C> + 'static,
+    {
+        self.method_registry
+            .insert(P::method(), MethodKind::Method, any::type_name::<P>())?;
+        cache.configure(P::method(), ttl, max_entries);
+        self.admin_handle.insert_read_method(P::method());
+
+        let hooks = self.hooks.clone();
+        let cache = cache.clone();
+        self.rpc_module
+            .register_async_method(P::method(), move |parameter, context, extensions| {
+                Self::cached_handler::<P>(
+                    parameter,
+                    context,
+                    extensions,
+                    hooks.clone(),
+                    cache.clone(),
+                )
+            })
+            .map_err(RpcServerError::RegisterMethod)?;
+
+        Ok(self)
+    }
+
+    async fn extract_handler<P, X>(
+        parameter: Params<'static>,
+        context: Arc<C>,
+        _extensions: Extensions,
+        hooks: SharedHooks<C>,
+    ) -> Result<P::Response, RpcError>
+    where
+        P: RpcParameter<X> + 'static,
+        X: FromContext<C> + Clone + Send + Sync + 'static,
+    {
+        hook::call(&hooks, P::method(), parameter, &context, |parameter: P| {
+            P::handler(parameter, X::from_context(&context))
+        })
+        .await
+    }
+
+    /// Register `P`, whose handler receives `X` extracted from the server
+    /// context with [`FromContext`] instead of a clone of the whole context,
+    /// e.g. `Ctx<KvStore>` or `(Ctx<KvStore>, Ctx<ClusterState>)`.
+    pub fn register_rpc_method_with_context<P, X>(mut self) -> Result<Self, RpcServerError>
+    where
+        P: RpcParameter<X> + 'static,
+        X: FromContext<C> + Clone + Send + Sync + 'static,
+    {
+        self.method_registry
+            .insert(P::method(), MethodKind::Method, any::type_name::<P>())?;
+
+        let hooks = self.hooks.clone();
+        self.rpc_module
+            .register_async_method(P::method(), move |parameter, context, extensions| {
+                Self::extract_handler::<P, X>(parameter, context, extensions, hooks.clone())
+            })
+            .map_err(RpcServerError::RegisterMethod)?;
+
+        Ok(self)
+    }
+
+    #[cfg(feature = "schema-validation")]
+    async fn validated_handler<P>(
+        parameter: Params<'static>,
+        context: Arc<C>,
+        _extensions: Extensions,
+        hooks: SharedHooks<C>,
+        schema: ParamsSchema,
+    ) -> Result<P::Response, RpcError>
+    where
+        P: RpcParameter<C> + 'static,
+    {
+        hook::call_validated(
+            &hooks,
+            P::method(),
+            parameter,
+            &context,
+            Some(|parameter: &serde_json::Value| schema.validate(parameter)),
+            |parameter: P| P::handler(parameter, (*context).clone()),
+        )
+        .await
+    }
+
+    /// Register `P` with its parameters checked against the JSON schema
+    /// generated from `P` before they are parsed. Invalid parameters are
+    /// rejected with an `InvalidParams` error naming the offending field,
+    /// e.g. ``Invalid params at `/block/height`: "ten" is not of type
+    /// "integer"``, with every violation in the error data as `path` and
+    /// `message` pairs, instead of the first serde error.
+    ///
+    /// The schema is checked after [`RpcHook::before()`], against the
+    /// parameters as sent, so positional parameters only match a schema
+    /// generated from a tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use json_rpc_server::{schemars::JsonSchema, RpcError, RpcParameter, RpcServer};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Deserialize, Serialize, JsonSchema)]
+    /// #[schemars(crate = "json_rpc_server::schemars")]
+    /// struct GetBlock {
+    ///     height: u64,
+    /// }
+    ///
+    /// impl RpcParameter<()> for GetBlock {
+    ///     type Response = String;
+    ///
+    ///     fn method() -> &'static str {
+    ///         "get_block"
+    ///     }
+    ///
+    ///     async fn handler(self, _context: ()) -> Result<Self::Response, RpcError> {
+    ///         Ok(format!("block {}", self.height))
+    ///     }
+    /// }
+    ///
+    /// let server = RpcServer::new(())
+    ///     .register_validated_rpc_method::<GetBlock>()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "schema-validation")]
+    pub fn register_validated_rpc_method<P>(mut self) -> Result<Self, RpcServerError>
+    where
+        P: RpcParameter<C> + schemars::JsonSchema + 'static,
+    {
+        self.method_registry
+            .insert(P::method(), MethodKind::Method, any::type_name::<P>())?;
+        let schema = ParamsSchema::new::<P>()?;
+
+        let hooks = self.hooks.clone();
         self.rpc_module
-            .register_async_method(P::method(), Self::handler::<P>)
+            .register_async_method(P::method(), move |parameter, context, extensions| {
+                Self::validated_handler::<P>(
+                    parameter,
+                    context,
+                    extensions,
+                    hooks.clone(),
+                    schema.clone(),
+                )
+            })
             .map_err(RpcServerError::RegisterMethod)?;
 
         Ok(self)
     }
 
-    pub async fn init(self, rpc_url: impl AsRef<str>) -> Result<ServerHandle, RpcServerError> {
-        let rpc_url = match Url::from_str(rpc_url.as_ref()) {
-            Ok(url) => format!(
-                "{}:{}",
-                url.host_str().ok_or(ParseError::InvalidHost)?,
-                url.port().ok_or(ParseError::InvalidPort)?,
-            ),
-            Err(error) => {
-                if error == url::ParseError::RelativeUrlWithoutBase {
-                    rpc_url.as_ref().to_owned()
-                } else {
-                    return Err(ParseError::InvalidRpcUrl(error).into());
+    /// Serve `method` to replace the value of `config` at runtime, e.g. rate
+    /// limits or allowed clusters kept in the server context, without a
+    /// restart. The method takes a [`ConfigUpdate`] and returns the new
+    /// [`SharedContext::version()`].
+    ///
+    /// `validate` is called with the current and the new value, and
+    /// returning an error rejects the update. The update is applied only if
+    /// the config did not change while it was validated, and fails with
+    /// [`ConfigError::Update`] otherwise. Handlers see the new value on
+    /// their next [`SharedContext::load()`].
+    ///
+    /// The method changes the server behavior, so serve it on an admin
+    /// listener only, see [`Listener::rpc_methods()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io;
+    ///
+    /// use context::SharedContext;
+    /// use json_rpc_server::RpcServer;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Clone, Deserialize, Serialize)]
+    /// struct RateLimit {
+    ///     max_requests: u32,
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// struct AppContext {
+    ///     rate_limit: SharedContext<RateLimit>,
+    /// }
+    ///
+    /// let context = AppContext {
+    ///     rate_limit: SharedContext::from(RateLimit { max_requests: 100 }),
+    /// };
+    /// let server = RpcServer::new(context.clone())
+    ///     .register_config_method(
+    ///         "admin_set_rate_limit",
+    ///         context.rate_limit,
+    ///         |_current: &RateLimit, new: &RateLimit| match new.max_requests {
+    ///             0 => Err(io::Error::other("max_requests must be positive").into()),
+    ///             _ => Ok(()),
+    ///         },
+    ///     )
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "shared-context")]
+    pub fn register_config_method<T, F>(
+        mut self,
+        method: &'static str,
+        config: SharedContext<T>,
+        validate: F,
+    ) -> Result<Self, RpcServerError>
+    where
+        T: DeserializeOwned + Serialize + Send + 'static,
+        F: Fn(&T, &T) -> Result<(), RpcError> + Send + Sync + 'static,
+    {
+        self.method_registry
+            .insert(method, MethodKind::Config, any::type_name::<T>())?;
+
+        let hooks = self.hooks.clone();
+        let validate = Arc::new(validate);
+        self.rpc_module
+            .register_async_method(method, move |parameter, context, _extensions| {
+                let hooks = hooks.clone();
+                let config = config.clone();
+                let validate = validate.clone();
+
+                async move {
+                    hook::call(
+                        &hooks,
+                        method,
+                        parameter,
+                        &context,
+                        |update: ConfigUpdate<T>| async move {
+                            config::update(&config, update, &*validate)
+                        },
+                    )
+                    .await
                 }
+            })
+            .map_err(RpcServerError::RegisterMethod)?;
+
+        Ok(self)
+    }
+
+    async fn alias_handler<P>(
+        parameter: Params<'static>,
+        context: Arc<C>,
+        extensions: Extensions,
+        hooks: SharedHooks<C>,
+        alias: &'static str,
+    ) -> Result<P::Response, RpcError>
+    where
+        P: RpcParameter<C> + 'static,
+    {
+        if let Some(warnings) = extensions.get::<DeprecationWarnings>() {
+            warnings.push(alias, P::method());
+        }
+
+        Self::handler::<P>(parameter, context, extensions, hooks).await
+    }
+
+    /// Route calls to the deprecated method name `alias` to the handler of
+    /// `P`, so that a method can be renamed without breaking clients that
+    /// still use the old name. Responses to calls made through `alias` carry
+    /// a `Warning` header naming [`RpcParameter::method()`] as the
+    /// replacement.
+    pub fn register_alias<P>(mut self, alias: &'static str) -> Result<Self, RpcServerError>
+    where
+        P: RpcParameter<C> + 'static,
+    {
+        self.method_registry
+            .insert(alias, MethodKind::Alias, any::type_name::<P>())?;
+
+        let hooks = self.hooks.clone();
+        self.rpc_module
+            .register_async_method(alias, move |parameter, context, extensions| {
+                Self::alias_handler::<P>(parameter, context, extensions, hooks.clone(), alias)
+            })
+            .map_err(RpcServerError::RegisterMethod)?;
+        self.deprecation = Some(DeprecationLayer);
+
+        Ok(self)
+    }
+
+    async fn paginated_handler<P>(
+        parameter: Params<'static>,
+        context: Arc<C>,
+        _extensions: Extensions,
+        hooks: SharedHooks<C>,
+        cursors: Cursors<P::Item>,
+    ) -> Result<Page<P::Item>, RpcError>
+    where
+        P: RpcPaginatedParameter<C> + 'static,
+    {
+        let cursors = &cursors;
+        let context = &context;
+        hook::call(
+            &hooks,
+            P::method(),
+            parameter,
+            context,
+            |parameter: P| async move {
+                let stream = P::handler(parameter, (**context).clone()).await?;
+
+                Ok(cursors.first_page(stream).await)
+            },
+        )
+        .await
+    }
+
+    async fn next_page_handler<P>(
+        parameter: Params<'static>,
+        context: Arc<C>,
+        _extensions: Extensions,
+        hooks: SharedHooks<C>,
+        cursors: Cursors<P::Item>,
+    ) -> Result<Page<P::Item>, RpcError>
+    where
+        P: RpcPaginatedParameter<C> + 'static,
+    {
+        let cursors = &cursors;
+        hook::call(
+            &hooks,
+            P::next_page_method(),
+            parameter,
+            &context,
+            |parameter: NextPage| async move {
+                Ok(cursors.next_page(&parameter.next_page_token).await?)
+            },
+        )
+        .await
+    }
+
+    /// Register `P`, whose result is returned `page_size` items at a time,
+    /// e.g. the transactions of a block too large to serialize at once.
+    /// Each [`Page`] carries a token for
+    /// [`RpcPaginatedParameter::next_page_method()`], which returns the next
+    /// one. Items are only read from the stream when their page is
+    /// requested, and a stream whose next page is not requested within
+    /// `ttl` is dropped. At most 1024 streams are kept per method, beyond
+    /// which the one closest to expiry is dropped.
+    pub fn register_paginated_method<P>(
+        mut self,
+        page_size: usize,
+        ttl: Duration,
+    ) -> Result<Self, RpcServerError>
+    where
+        P: RpcPaginatedParameter<C> + 'static,
+    {
+        self.method_registry
+            .insert(P::method(), MethodKind::Method, any::type_name::<P>())?;
+        self.method_registry.insert(
+            P::next_page_method(),
+            MethodKind::Method,
+            any::type_name::<P>(),
+        )?;
+
+        let cursors = Cursors::<P::Item>::new(page_size, ttl);
+
+        let hooks = self.hooks.clone();
+        let first_page_cursors = cursors.clone();
+        self.rpc_module
+            .register_async_method(P::method(), move |parameter, context, extensions| {
+                Self::paginated_handler::<P>(
+                    parameter,
+                    context,
+                    extensions,
+                    hooks.clone(),
+                    first_page_cursors.clone(),
+                )
+            })
+            .map_err(RpcServerError::RegisterMethod)?;
+
+        let hooks = self.hooks.clone();
+        self.rpc_module
+            .register_async_method(
+                P::next_page_method(),
+                move |parameter, context, extensions| {
+                    Self::next_page_handler::<P>(
+                        parameter,
+                        context,
+                        extensions,
+                        hooks.clone(),
+                        cursors.clone(),
+                    )
+                },
+            )
+            .map_err(RpcServerError::RegisterMethod)?;
+
+        Ok(self)
+    }
+
+    /// Register the subscription `S`. Items that the client has not received
+    /// yet are buffered up to `buffer_size` per subscription, after which
+    /// `policy` decides between dropping items, closing the subscription and
+    /// pausing the stream, so that a slow client cannot make the buffer grow
+    /// without bound.
+    pub fn register_subscription<S>(
+        mut self,
+        buffer_size: usize,
+        policy: SubscriptionPolicy,
+    ) -> Result<Self, RpcServerError>
+    where
+        S: RpcSubscription<C> + 'static,
+    {
+        self.method_registry.insert(
+            S::subscribe_method(),
+            MethodKind::Subscribe,
+            any::type_name::<S>(),
+        )?;
+        self.method_registry.insert(
+            S::unsubscribe_method(),
+            MethodKind::Unsubscribe,
+            any::type_name::<S>(),
+        )?;
+        self.admin_handle.insert_read_method(S::subscribe_method());
+        self.admin_handle
+            .insert_read_method(S::unsubscribe_method());
+
+        self.rpc_module
+            .register_subscription(
+                S::subscribe_method(),
+                S::notification_method(),
+                S::unsubscribe_method(),
+                move |parameter, pending, context, _extensions| async move {
+                    let stream = match parameter.parse::<S>() {
+                        Ok(parameter) => S::handler(parameter, (*context).clone()).await,
+                        Err(error) => Err(error.into()),
+                    };
+                    let stream = match stream {
+                        Ok(stream) => stream,
+                        Err(error) => {
+                            pending.reject(ErrorObject::from(error)).await;
+                            return SubscriptionCloseResponse::None;
+                        }
+                    };
+
+                    match pending.accept().await {
+                        Ok(sink) => subscription::forward(sink, stream, buffer_size, policy).await,
+                        Err(_) => SubscriptionCloseResponse::None,
+                    }
+                },
+            )
+            .map_err(RpcServerError::RegisterMethod)?;
+
+        Ok(self)
+    }
+
+    #[cfg(feature = "signed-rpc")]
+    async fn authenticated_handler<P>(
+        parameter: Params<'static>,
+        context: Arc<C>,
+        extensions: Extensions,
+        acl_provider: SharedAclProvider,
+        hooks: SharedHooks<C>,
+    ) -> Result<P::Response, RpcError>
+    where
+        P: AuthenticatedRpcParameter<C> + 'static,
+    {
+        let address = extensions.get::<signature::Address>().cloned();
+        let acl_provider = acl_provider.read().unwrap().clone();
+        P::policy().check(acl_provider.as_deref(), address.as_ref())?;
+
+        hook::call(&hooks, P::method(), parameter, &context, |parameter: P| {
+            P::handler(parameter, (*context).clone(), address)
+        })
+        .await
+    }
+
+    /// Register a method guarded by [`AuthenticatedRpcParameter::policy()`].
+    /// Requests must pass through [`RpcServer::verify_signature()`] for the
+    /// caller address to be known.
+    #[cfg(feature = "signed-rpc")]
+    pub fn register_authenticated_rpc_method<P>(mut self) -> Result<Self, RpcServerError>
+    where
+        P: AuthenticatedRpcParameter<C> + 'static,
+    {
+        self.method_registry.insert(
+            P::method(),
+            MethodKind::Authenticated,
+            any::type_name::<P>(),
+        )?;
+
+        let acl_provider = self.acl_provider.clone();
+        let hooks = self.hooks.clone();
+        self.rpc_module
+            .register_async_method(P::method(), move |parameter, context, extensions| {
+                Self::authenticated_handler::<P>(
+                    parameter,
+                    context,
+                    extensions,
+                    acl_provider.clone(),
+                    hooks.clone(),
+                )
+            })
+            .map_err(RpcServerError::RegisterMethod)?;
+
+        Ok(self)
+    }
+
+    /// Same as [`RpcServer::register_alias()`] for methods registered with
+    /// [`RpcServer::register_authenticated_rpc_method()`]. Calls through
+    /// `alias` are checked against the same [`MethodPolicy`].
+    #[cfg(feature = "signed-rpc")]
+    pub fn register_authenticated_alias<P>(
+        mut self,
+        alias: &'static str,
+    ) -> Result<Self, RpcServerError>
+    where
+        P: AuthenticatedRpcParameter<C> + 'static,
+    {
+        self.method_registry
+            .insert(alias, MethodKind::Alias, any::type_name::<P>())?;
+
+        let acl_provider = self.acl_provider.clone();
+        let hooks = self.hooks.clone();
+        self.rpc_module
+            .register_async_method(alias, move |parameter, context, extensions| {
+                if let Some(warnings) = extensions.get::<DeprecationWarnings>() {
+                    warnings.push(alias, P::method());
+                }
+
+                Self::authenticated_handler::<P>(
+                    parameter,
+                    context,
+                    extensions,
+                    acl_provider.clone(),
+                    hooks.clone(),
+                )
+            })
+            .map_err(RpcServerError::RegisterMethod)?;
+        self.deprecation = Some(DeprecationLayer);
+
+        Ok(self)
+    }
+
+    /// Every method registered so far.
+    pub fn method_registry(&self) -> &MethodRegistry {
+        &self.method_registry
+    }
+
+    /// Serve the [`MethodRegistry`] as the RPC method `method`, e.g. for an
+    /// admin interface. The method is added on init so that it lists every
+    /// method registered before, and can be restricted to an admin listener
+    /// with [`Listener::rpc_methods()`].
+    pub fn method_registry_method(mut self, method: &'static str) -> Result<Self, RpcServerError> {
+        self.method_registry.insert(
+            method,
+            MethodKind::Registry,
+            any::type_name::<MethodRegistry>(),
+        )?;
+        self.admin_handle.insert_read_method(method);
+        self.method_registry_method = Some(method);
+
+        Ok(self)
+    }
+
+    fn register_method_registry_method(&mut self) -> Result<(), RpcServerError> {
+        if let Some(method) = self.method_registry_method.take() {
+            let method_entries: Vec<MethodEntry> = self.method_registry.iter().cloned().collect();
+            self.rpc_module
+                .register_method(method, move |_parameter, _context, _extensions| {
+                    method_entries.clone()
+                })
+                .map_err(RpcServerError::RegisterMethod)?;
+        }
+
+        Ok(())
+    }
+
+    /// Add an address to listen on with [`RpcServer::init_listeners()`].
+    /// Each listener can serve a different subset of the registered methods,
+    /// e.g. admin methods on `127.0.0.1` only and the public API on
+    /// `0.0.0.0`.
+    pub fn listener(mut self, listener: Listener) -> Self {
+        self.listeners.push(listener);
+
+        self
+    }
+
+    pub async fn init(mut self, rpc_url: impl AsRef<str>) -> Result<ServerHandle, RpcServerError> {
+        let rpc_url = parse_rpc_url(rpc_url.as_ref())?;
+        self.register_method_registry_method()?;
+
+        self.start(self.rpc_module.clone(), rpc_url, self.ip_filter.clone())
+            .await
+    }
+
+    /// Start a server on every address of the listeners added with
+    /// [`RpcServer::listener()`]. Dropping a handle stops its server.
+    pub async fn init_listeners(mut self) -> Result<Vec<ServerHandle>, RpcServerError> {
+        if self.listeners.is_empty() {
+            return Err(RpcServerError::NoListener);
+        }
+        self.register_method_registry_method()?;
+
+        let mut server_handles = Vec::new();
+        for listener in &self.listeners {
+            let rpc_module = self.listener_rpc_module(listener)?;
+            let rpc_url = parse_rpc_url(&listener.rpc_url)?;
+            // Resolvers may return an address more than once, and not
+            // necessarily in a row. Keep the first of each, in order.
+            let mut resolved = HashSet::new();
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host(&rpc_url)
+                .await
+                .map_err(RpcServerError::Initialize)?
+                .filter(|address| resolved.insert(*address))
+                .collect();
+            let ip_filter = listener
+                .ip_filter
+                .clone()
+                .or_else(|| self.ip_filter.clone());
+
+            for address in addresses {
+                let server_handle = self
+                    .start(rpc_module.clone(), address, ip_filter.clone())
+                    .await?;
+                server_handles.push(server_handle);
             }
-        };
+        }
+
+        Ok(server_handles)
+    }
 
-        let cors = CorsLayer::new()
-            .allow_methods([Method::GET, Method::POST])
-            .allow_origin(Any)
-            .allow_headers([header::CONTENT_TYPE]);
+    fn listener_rpc_module(&self, listener: &Listener) -> Result<RpcModule<C>, RpcServerError> {
+        let mut rpc_module = self.rpc_module.clone();
+
+        if let Some(rpc_methods) = &listener.rpc_methods {
+            if let Some(method) = rpc_methods
+                .iter()
+                .find(|method| !self.rpc_module.method_names().any(|name| name == *method))
+            {
+                return Err(RpcServerError::UnknownMethod(method.clone()));
+            }
+
+            let excluded_methods: Vec<&'static str> = self
+                .rpc_module
+                .method_names()
+                .filter(|name| !rpc_methods.contains(*name))
+                .collect();
+            for method in excluded_methods {
+                rpc_module.remove_method(method);
+            }
+        }
+
+        Ok(rpc_module)
+    }
+
+    /// Start a server on the Unix domain socket `unix_socket` instead of a
+    /// TCP address, so that the methods are only reachable from the same
+    /// host. Dropping the handle stops the server and removes the socket
+    /// file.
+    #[cfg(unix)]
+    pub async fn init_unix_socket(
+        mut self,
+        unix_socket: UnixSocket,
+    ) -> Result<ServerHandle, RpcServerError> {
+        self.register_method_registry_method()?;
+
+        let listener = unix_socket.bind().map_err(RpcServerError::Initialize)?;
+        let service_builder = Server::builder()
+            .set_http_middleware(self.http_middleware()?)
+            .set_rpc_middleware(self.rpc_middleware())
+            .to_service_builder();
+        let rpc_module = self.rpc_module.clone();
+        let (stop_handle, server_handle) = jsonrpsee::server::stop_channel();
+
+        tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _address)) => stream,
+                        Err(error) => {
+                            handle_accept_error(error).await;
+                            continue;
+                        }
+                    },
+                    _ = stop_handle.clone().shutdown() => break,
+                };
+
+                let service = service_builder
+                    .clone()
+                    .build(rpc_module.clone(), stop_handle.clone());
+                tokio::spawn(jsonrpsee::server::serve_with_graceful_shutdown(
+                    stream,
+                    service,
+                    stop_handle.clone().shutdown(),
+                ));
+            }
+
+            let _ = std::fs::remove_file(&unix_socket.path);
+        });
+
+        Ok(server_handle)
+    }
+
+    fn http_middleware(&self) -> Result<ServiceBuilder<HttpMiddleware>, RpcServerError> {
+        let cors = self.cors.layer()?;
         let health_check =
             ProxyGetRequestLayer::new("/health", "health").map_err(RpcServerError::Middleware)?;
-        let middleware = tower::ServiceBuilder::new().layer(cors).layer(health_check);
+        // Requests are decompressed ahead of the signature verification, as
+        // signatures cover the uncompressed body, and bodies in an encoding
+        // other than gzip, deflate, br or zstd are rejected with `415
+        // Unsupported Media Type`.
+        let decompression_response: DecompressionResponse = |response| response.map(HttpBody::new);
+        let middleware = ServiceBuilder::new()
+            .layer(cors)
+            .option_layer(self.deprecation.clone())
+            .map_response(decompression_response)
+            .layer(RequestDecompressionLayer::new());
+        #[cfg(not(feature = "signed-rpc"))]
+        let middleware = middleware.layer(health_check);
+        #[cfg(feature = "signed-rpc")]
+        let middleware = middleware
+            .option_layer(self.signature_verification.clone())
+            .layer(health_check);
+
+        Ok(middleware)
+    }
+
+    /// Every call is traced, see [`TraceLayer`], including the calls
+    /// rejected by the [`AdminHandle`].
+    fn rpc_middleware(&self) -> RpcServiceBuilder<Stack<AdminLayer, Stack<TraceLayer, Identity>>> {
+        RpcServiceBuilder::new()
+            .layer(TraceLayer)
+            .layer(AdminLayer(self.admin_handle.clone()))
+    }
 
-        let server = Server::builder()
-            .set_http_middleware(middleware)
-            .build(rpc_url)
+    /// Connections are accepted here instead of by [`Server::start()`], which
+    /// does not pass the peer address on to the middleware, and dropped if
+    /// their peer address is rejected by `ip_filter`.
+    async fn start(
+        &self,
+        rpc_module: RpcModule<C>,
+        address: impl ToSocketAddrs,
+        ip_filter: Option<IpFilter>,
+    ) -> Result<ServerHandle, RpcServerError> {
+        let listener = TcpListener::bind(address)
             .await
             .map_err(RpcServerError::Initialize)?;
-        let server_handle = server.start(self.rpc_module);
+        let service_builder = Server::builder()
+            .set_http_middleware(self.http_middleware()?)
+            .set_rpc_middleware(self.rpc_middleware())
+            .to_service_builder();
+        let (stop_handle, server_handle) = jsonrpsee::server::stop_channel();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, peer_address) = tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok(accepted) => accepted,
+                        Err(error) => {
+                            handle_accept_error(error).await;
+                            continue;
+                        }
+                    },
+                    _ = stop_handle.clone().shutdown() => break,
+                };
+                if let Some(ip_filter) = &ip_filter {
+                    if !ip_filter.is_allowed(peer_address.ip()) {
+                        tracing::debug!(%peer_address, "connection rejected by the IP filter");
+                        continue;
+                    }
+                }
+                let _ = stream.set_nodelay(true);
+
+                let service = WithPeerAddress {
+                    inner: service_builder
+                        .clone()
+                        .build(rpc_module.clone(), stop_handle.clone()),
+                    peer_address,
+                };
+                tokio::spawn(jsonrpsee::server::serve_with_graceful_shutdown(
+                    stream,
+                    service,
+                    stop_handle.clone().shutdown(),
+                ));
+            }
+        });
 
         Ok(server_handle)
     }
 }
 
+/// Skip a connection that failed before it was accepted. Other errors, such
+/// as running out of file descriptors, fail every `accept()` until they are
+/// resolved, so wait before retrying instead of spinning.
+async fn handle_accept_error(error: std::io::Error) {
+    use std::io::ErrorKind;
+
+    if matches!(
+        error.kind(),
+        ErrorKind::ConnectionAborted | ErrorKind::ConnectionRefused | ErrorKind::ConnectionReset
+    ) {
+        return;
+    }
+
+    tracing::error!(%error, "failed to accept a connection, retrying in 1 second");
+    tokio::time::sleep(Duration::from_secs(1)).await;
+}
+
+fn parse_rpc_url(rpc_url: &str) -> Result<String, ParseError> {
+    match Url::from_str(rpc_url) {
+        Ok(url) => Ok(format!(
+            "{}:{}",
+            url.host_str().ok_or(ParseError::InvalidHost)?,
+            url.port().ok_or(ParseError::InvalidPort)?,
+        )),
+        Err(url::ParseError::RelativeUrlWithoutBase) => Ok(rpc_url.to_owned()),
+        Err(error) => Err(ParseError::InvalidRpcUrl(error)),
+    }
+}
+
 #[derive(Debug)]
 pub struct RpcError(Box<dyn std::error::Error + Send + 'static>);
 
@@ -117,6 +1293,14 @@ impl From<RpcError> for String {
 
 impl From<RpcError> for ErrorObject<'static> {
     fn from(value: RpcError) -> Self {
+        #[cfg(feature = "schema-validation")]
+        if let Some(invalid_params) = value.0.downcast_ref::<InvalidParams>() {
+            return invalid_params.into();
+        }
+        if let Some(invalid_page_token) = value.0.downcast_ref::<InvalidPageToken>() {
+            return invalid_page_token.into();
+        }
+
         ErrorObject::owned::<i32>(ErrorCode::InternalError.code(), value, None)
     }
 }
@@ -136,6 +1320,16 @@ pub enum RpcServerError {
     Parse(ParseError),
     RegisterMethod(jsonrpsee::server::RegisterMethodError),
     Initialize(std::io::Error),
+    CorsCredentials,
+    NoListener,
+    #[cfg(feature = "schema-validation")]
+    ParamsSchema(jsonschema::ValidationError<'static>),
+    UnknownMethod(String),
+    MethodCollision {
+        method: &'static str,
+        type_name: &'static str,
+        registered_type_name: &'static str,
+    },
 }
 
 impl std::fmt::Display for RpcServerError {
@@ -157,4 +1351,105 @@ pub enum ParseError {
     InvalidHost,
     InvalidPort,
     InvalidRpcUrl(url::ParseError),
+    InvalidOrigin(http::header::InvalidHeaderValue),
+    InvalidHeader(http::header::InvalidHeaderName),
+    InvalidMethod(http::method::InvalidMethod),
+    InvalidCidr(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use json_rpc_client::RpcClient;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    struct Echo {
+        message: String,
+    }
+
+    impl RpcParameter<()> for Echo {
+        type Response = String;
+
+        fn method() -> &'static str {
+            "echo"
+        }
+
+        async fn handler(self, _context: ()) -> Result<Self::Response, RpcError> {
+            Ok(self.message)
+        }
+    }
+
+    fn rpc_url() -> String {
+        let address = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        format!("http://{}", address)
+    }
+
+    async fn echo(rpc_client: &RpcClient, rpc_url: &str) {
+        let message = "transaction ".repeat(1000);
+        let response: String = rpc_client
+            .request(
+                rpc_url,
+                "echo",
+                Echo {
+                    message: message.clone(),
+                },
+                1,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response, message);
+    }
+
+    #[tokio::test]
+    async fn test_request_compression() {
+        let rpc_url = rpc_url();
+        let _server_handle = RpcServer::new(())
+            .register_rpc_method::<Echo>()
+            .unwrap()
+            .init(&rpc_url)
+            .await
+            .unwrap();
+
+        let rpc_client = RpcClient::builder()
+            .request_compression(0)
+            .endpoint_request_compression(&rpc_url, true)
+            .build()
+            .unwrap();
+        echo(&rpc_client, &rpc_url).await;
+    }
+
+    #[cfg(feature = "signed-rpc")]
+    #[tokio::test]
+    async fn test_signed_request_compression() {
+        use signature::{ChainType, PrivateKeySigner};
+
+        let rpc_url = rpc_url();
+        let _server_handle = RpcServer::new(())
+            .verify_signature(SignatureVerificationLayer::new(ChainType::Ethereum))
+            .register_rpc_method::<Echo>()
+            .unwrap()
+            .init(&rpc_url)
+            .await
+            .unwrap();
+
+        let signer = PrivateKeySigner::from_str(
+            ChainType::Ethereum,
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let rpc_client = RpcClient::builder()
+            .signer(signer)
+            .request_compression(0)
+            .endpoint_request_compression(&rpc_url, true)
+            .build()
+            .unwrap();
+        echo(&rpc_client, &rpc_url).await;
+    }
 }
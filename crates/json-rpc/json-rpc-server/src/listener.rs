@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use crate::IpFilter;
+
+/// Address that an [`crate::RpcServer`] listens on, registered with
+/// [`crate::RpcServer::listener()`].
+///
+/// A host that resolves to several addresses, such as `localhost` to
+/// `127.0.0.1` and `::1`, is bound on each of them.
+#[derive(Clone, Debug)]
+pub struct Listener {
+    pub(crate) rpc_url: String,
+    pub(crate) rpc_methods: Option<HashSet<String>>,
+    pub(crate) ip_filter: Option<IpFilter>,
+}
+
+impl Listener {
+    pub fn new(rpc_url: impl AsRef<str>) -> Self {
+        Self {
+            rpc_url: rpc_url.as_ref().to_owned(),
+            rpc_methods: None,
+            ip_filter: None,
+        }
+    }
+
+    /// Only serve the given RPC methods on this listener. Every registered
+    /// method is served if not set. Aliases and subscription methods are
+    /// listed by their own names.
+    pub fn rpc_methods<I, S>(mut self, methods: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.rpc_methods = Some(
+            methods
+                .into_iter()
+                .map(|method| method.as_ref().to_owned())
+                .collect(),
+        );
+
+        self
+    }
+
+    /// Only accept connections on this listener from the ranges allowed by
+    /// `ip_filter`, instead of the ones of [`crate::RpcServer::ip_filter()`],
+    /// e.g. to serve cluster-internal methods to the peer sequencer subnets
+    /// only.
+    pub fn ip_filter(mut self, ip_filter: IpFilter) -> Self {
+        self.ip_filter = Some(ip_filter);
+
+        self
+    }
+}
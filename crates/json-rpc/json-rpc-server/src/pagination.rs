@@ -0,0 +1,276 @@
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex, Once, Weak},
+    time::{Duration, Instant},
+};
+
+use futures::{
+    stream::{BoxStream, Peekable},
+    StreamExt,
+};
+use jsonrpsee::types::{ErrorCode, ErrorObject};
+use serde::{Deserialize, Serialize};
+
+/// The response of a method registered with
+/// [`crate::RpcServer::register_paginated_method()`] and of its
+/// [`crate::RpcPaginatedParameter::next_page_method()`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use futures::{stream, StreamExt};
+/// use json_rpc_server::{RpcError, RpcPaginatedParameter, RpcServer};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct GetBlockTransactions {
+///     height: u64,
+/// }
+///
+/// impl RpcPaginatedParameter<()> for GetBlockTransactions {
+///     type Item = String;
+///
+///     fn method() -> &'static str {
+///         "get_block_transactions"
+///     }
+///
+///     fn next_page_method() -> &'static str {
+///         "get_block_transactions_next_page"
+///     }
+///
+///     async fn handler(
+///         self,
+///         _context: (),
+///     ) -> Result<stream::BoxStream<'static, Self::Item>, RpcError> {
+///         let height = self.height;
+///
+///         Ok(stream::iter(0..10_000)
+///             .map(move |index| format!("transaction {} of block {}", index, height))
+///             .boxed())
+///     }
+/// }
+///
+/// let server = RpcServer::new(())
+///     .register_paginated_method::<GetBlockTransactions>(500, Duration::from_secs(30))
+///     .unwrap();
+/// ```
+///
+/// `get_block_transactions` returns `{"items": [...], "next_page_token":
+/// "..."}` with the first 500 transactions, and
+/// `get_block_transactions_next_page` called with `{"next_page_token": "..."}`
+/// returns the next 500 and a new token, until `next_page_token` is `null`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// `None` on the last page. A token is valid for a single call.
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct NextPage {
+    pub next_page_token: String,
+}
+
+/// The most streams kept per method, after which the one closest to expiry
+/// is dropped for a new one, so that clients not reading their pages cannot
+/// grow the memory of the server without bound.
+pub(crate) const MAX_CURSORS: usize = 1024;
+
+type Cursor<T> = Peekable<BoxStream<'static, T>>;
+
+type Streams<T> = HashMap<String, (Instant, Cursor<T>)>;
+
+/// The streams of the pages that were not requested yet, keyed by token.
+///
+/// Expired streams are dropped on every insert and by a task sweeping them
+/// every `ttl`, started with the first stream, so that a method which is no
+/// longer called does not keep its last streams alive.
+pub(crate) struct Cursors<T> {
+    page_size: usize,
+    ttl: Duration,
+    streams: Arc<Mutex<Streams<T>>>,
+    sweep: Arc<Once>,
+}
+
+impl<T> Clone for Cursors<T> {
+    fn clone(&self) -> Self {
+        Self {
+            page_size: self.page_size,
+            ttl: self.ttl,
+            streams: self.streams.clone(),
+            sweep: self.sweep.clone(),
+        }
+    }
+}
+
+impl<T> Cursors<T>
+where
+    T: Send + 'static,
+{
+    pub fn new(page_size: usize, ttl: Duration) -> Self {
+        Self {
+            page_size: page_size.max(1),
+            ttl,
+            streams: Arc::new(Mutex::new(HashMap::new())),
+            sweep: Arc::new(Once::new()),
+        }
+    }
+
+    pub async fn first_page(&self, stream: BoxStream<'static, T>) -> Page<T> {
+        self.page(stream.peekable()).await
+    }
+
+    pub async fn next_page(&self, token: &str) -> Result<Page<T>, InvalidPageToken> {
+        let stream = self.take(token).ok_or(InvalidPageToken)?;
+
+        Ok(self.page(stream).await)
+    }
+
+    async fn page(&self, mut stream: Cursor<T>) -> Page<T> {
+        let mut items = Vec::new();
+        while items.len() < self.page_size {
+            match stream.next().await {
+                Some(item) => items.push(item),
+                None => break,
+            }
+        }
+
+        // Peek so that the last page does not hand out a token for an empty
+        // one.
+        let has_next_page = Pin::new(&mut stream).peek().await.is_some();
+        let next_page_token = has_next_page.then(|| self.insert(stream));
+
+        Page {
+            items,
+            next_page_token,
+        }
+    }
+
+    fn insert(&self, stream: Cursor<T>) -> String {
+        self.sweep.call_once(|| self.spawn_sweep());
+
+        let token = format!("{:032x}", rand::random::<u128>());
+        let now = Instant::now();
+
+        let mut streams = self.streams.lock().unwrap();
+        streams.retain(|_, (expires_at, _)| *expires_at > now);
+        if streams.len() >= MAX_CURSORS {
+            let oldest = streams
+                .iter()
+                .min_by_key(|(_, (expires_at, _))| *expires_at)
+                .map(|(token, _)| token.clone());
+            if let Some(oldest) = oldest {
+                streams.remove(&oldest);
+            }
+        }
+        streams.insert(token.clone(), (now + self.ttl, stream));
+
+        token
+    }
+
+    /// Drop the expired streams every `ttl` until the method is dropped
+    /// along with the server.
+    fn spawn_sweep(&self) {
+        let streams: Weak<Mutex<Streams<T>>> = Arc::downgrade(&self.streams);
+        let ttl = self.ttl.max(Duration::from_secs(1));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(ttl);
+            loop {
+                interval.tick().await;
+
+                let Some(streams) = streams.upgrade() else {
+                    break;
+                };
+                let now = Instant::now();
+                streams
+                    .lock()
+                    .unwrap()
+                    .retain(|_, (expires_at, _)| *expires_at > now);
+            }
+        });
+    }
+
+    fn take(&self, token: &str) -> Option<Cursor<T>> {
+        let (expires_at, stream) = self.streams.lock().unwrap().remove(token)?;
+
+        (expires_at > Instant::now()).then_some(stream)
+    }
+}
+
+/// Returned to the client as an `InvalidParams` error when the token is
+/// unknown, was already used or has expired.
+#[derive(Debug)]
+pub(crate) struct InvalidPageToken;
+
+impl std::fmt::Display for InvalidPageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid params: unknown or expired page token")
+    }
+}
+
+impl std::error::Error for InvalidPageToken {}
+
+impl From<&InvalidPageToken> for ErrorObject<'static> {
+    fn from(value: &InvalidPageToken) -> Self {
+        ErrorObject::owned::<()>(ErrorCode::InvalidParams.code(), value.to_string(), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+
+    fn items(count: u64) -> BoxStream<'static, u64> {
+        stream::iter(0..count).boxed()
+    }
+
+    #[tokio::test]
+    async fn test_cursors() {
+        let cursors = Cursors::new(2, Duration::from_secs(60));
+
+        let page = cursors.first_page(items(5)).await;
+        assert_eq!(page.items, [0, 1]);
+        let token = page.next_page_token.unwrap();
+
+        let page = cursors.next_page(&token).await.unwrap();
+        assert_eq!(page.items, [2, 3]);
+        // A token is valid for a single call.
+        assert!(cursors.next_page(&token).await.is_err());
+
+        let page = cursors
+            .next_page(&page.next_page_token.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(page.items, [4]);
+        assert!(page.next_page_token.is_none());
+
+        assert!(cursors.next_page("unknown").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cursors_expiry() {
+        let cursors = Cursors::new(1, Duration::ZERO);
+
+        let token = cursors.first_page(items(2)).await.next_page_token.unwrap();
+        assert!(cursors.next_page(&token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cursors_limit() {
+        let cursors = Cursors::new(1, Duration::from_secs(60));
+
+        let first_token = cursors.first_page(items(2)).await.next_page_token.unwrap();
+        for _ in 0..MAX_CURSORS {
+            cursors.first_page(items(2)).await;
+        }
+
+        assert_eq!(cursors.streams.lock().unwrap().len(), MAX_CURSORS);
+        assert!(cursors.next_page(&first_token).await.is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use serde::Serialize;
+
+use crate::RpcServerError;
+
+/// How a method of a [`MethodRegistry`] was registered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MethodKind {
+    Method,
+    /// A deprecated name of another method.
+    Alias,
+    Subscribe,
+    Unsubscribe,
+    /// A method guarded by a `MethodPolicy`.
+    Authenticated,
+    /// The method serving the registry itself, see
+    /// [`crate::RpcServer::method_registry_method()`].
+    Registry,
+    /// A method replacing a shared config, see
+    /// `RpcServer::register_config_method()`.
+    Config,
+}
+
+/// A method registered with [`crate::RpcServer`] and the type that
+/// registered it.
+#[derive(Clone, Debug, Serialize)]
+pub struct MethodEntry {
+    pub method: &'static str,
+    pub kind: MethodKind,
+    pub type_name: &'static str,
+}
+
+/// Every method registered with [`crate::RpcServer`], in registration order.
+/// Registering a method name twice fails with
+/// [`RpcServerError::MethodCollision`] naming both types, which tells where
+/// the collision comes from when a server is composed from several crates.
+///
+/// [`std::fmt::Display`] prints the registry as a table, e.g. to log it at
+/// startup.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct MethodRegistry(Vec<MethodEntry>);
+
+impl MethodRegistry {
+    pub(crate) fn insert(
+        &mut self,
+        method: &'static str,
+        kind: MethodKind,
+        type_name: &'static str,
+    ) -> Result<(), RpcServerError> {
+        if let Some(entry) = self.get(method) {
+            return Err(RpcServerError::MethodCollision {
+                method,
+                type_name,
+                registered_type_name: entry.type_name,
+            });
+        }
+
+        self.0.push(MethodEntry {
+            method,
+            kind,
+            type_name,
+        });
+
+        Ok(())
+    }
+
+    pub fn get(&self, method: &str) -> Option<&MethodEntry> {
+        self.0.iter().find(|entry| entry.method == method)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MethodEntry> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::fmt::Display for MethodRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let method_width = self
+            .0
+            .iter()
+            .map(|entry| entry.method.len())
+            .chain(["METHOD".len()])
+            .max()
+            .unwrap_or_default();
+
+        write!(f, "{:<method_width$}  {:<13}  TYPE", "METHOD", "KIND")?;
+        for entry in &self.0 {
+            let kind = format!("{:?}", entry.kind).to_lowercase();
+            write!(
+                f,
+                "\n{:<method_width$}  {:<13}  {}",
+                entry.method, kind, entry.type_name
+            )?;
+        }
+
+        Ok(())
+    }
+}
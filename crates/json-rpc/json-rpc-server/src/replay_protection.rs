@@ -0,0 +1,99 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Replay checks of [`crate::SignatureVerificationLayer::replay_protection()`].
+///
+/// A signed request carries its signing time in [`crate::TIMESTAMP_HEADER`],
+/// a random nonce in [`crate::NONCE_HEADER`] and the endpoint it was sent to
+/// in [`crate::AUDIENCE_HEADER`], all covered by the signature. A request is
+/// accepted if its timestamp is within `window` of the server clock, its
+/// nonce was not seen within the window, and its audience is one of
+/// [`ReplayProtection::audiences()`] if set. Binding the audience keeps a
+/// request captured on one sequencer node from being accepted by another.
+///
+/// Nonces are remembered in memory until their timestamp falls out of the
+/// window, after which the request is rejected by its timestamp anyway.
+pub struct ReplayProtection {
+    window: Duration,
+    audiences: Option<HashSet<String>>,
+    nonces: Mutex<HashMap<String, u64>>,
+}
+
+impl ReplayProtection {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            audiences: None,
+            nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Only accept requests addressed to one of `audiences`, the `host:port`
+    /// of this node in the URLs used by the clients, with the default port
+    /// of the scheme if the URL has none, e.g. `sequencer-1.example.com:443`.
+    pub fn audiences<I, S>(mut self, audiences: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.audiences = Some(
+            audiences
+                .into_iter()
+                .map(|audience| audience.as_ref().to_owned())
+                .collect(),
+        );
+
+        self
+    }
+
+    /// Whether `audience` is one of [`ReplayProtection::audiences()`], or any
+    /// audience if they are not set.
+    pub(crate) fn is_allowed_audience(&self, audience: &str) -> bool {
+        self.audiences
+            .as_ref()
+            .map(|audiences| audiences.contains(audience))
+            .unwrap_or(true)
+    }
+
+    /// Check the envelope and remember `nonce`, so call this only once the
+    /// signature is verified, or an attacker could burn nonces.
+    pub(crate) fn check(&self, timestamp: u64, nonce: &str, audience: &str) -> bool {
+        if !self.is_allowed_audience(audience) {
+            return false;
+        }
+
+        let now = unix_millis();
+        let window = self.window.as_millis() as u64;
+        if timestamp.abs_diff(now) > window {
+            return false;
+        }
+
+        let mut nonces = self.nonces.lock().unwrap();
+        nonces.retain(|_nonce, expiry| *expiry > now);
+        if nonces.contains_key(nonce) {
+            return false;
+        }
+        nonces.insert(nonce.to_owned(), timestamp + window);
+
+        true
+    }
+}
+
+/// The message signed by the client for a request with replay protection:
+/// `<timestamp>\n<nonce>\n<audience>\n` followed by the body.
+pub(crate) fn envelope(timestamp: u64, nonce: &str, audience: &str, body: &[u8]) -> Vec<u8> {
+    let mut message = format!("{}\n{}\n{}\n", timestamp, nonce, audience).into_bytes();
+    message.extend_from_slice(body);
+
+    message
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use jsonrpsee::types::{ErrorCode, ErrorObject};
+use jsonschema::Validator;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{RpcError, RpcServerError};
+
+/// The JSON schema generated from the parameter of a method registered with
+/// [`crate::RpcServer::register_validated_rpc_method()`], compiled once at
+/// registration.
+#[derive(Clone)]
+pub(crate) struct ParamsSchema(Arc<Validator>);
+
+impl ParamsSchema {
+    pub fn new<P>() -> Result<Self, RpcServerError>
+    where
+        P: JsonSchema,
+    {
+        let schema = schemars::schema_for!(P);
+        let validator =
+            jsonschema::validator_for(schema.as_value()).map_err(RpcServerError::ParamsSchema)?;
+
+        Ok(Self(Arc::new(validator)))
+    }
+
+    /// Check `parameter` against the schema, collecting every violation
+    /// rather than stopping at the first one.
+    pub fn validate(&self, parameter: &Value) -> Result<(), RpcError> {
+        let errors: Vec<InvalidParam> = self
+            .0
+            .iter_errors(parameter)
+            .map(|error| InvalidParam {
+                path: match error.instance_path.as_str() {
+                    "" => "/".to_owned(),
+                    path => path.to_owned(),
+                },
+                message: error.to_string(),
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidParams(errors).into())
+        }
+    }
+}
+
+/// A violation of the parameter schema, with the JSON pointer of the
+/// offending field, e.g. `/block/height`.
+#[derive(Debug, Serialize)]
+pub(crate) struct InvalidParam {
+    path: String,
+    message: String,
+}
+
+/// Returned to the client as an `InvalidParams` error naming the first
+/// offending field, with every violation in the error data.
+#[derive(Debug)]
+pub(crate) struct InvalidParams(Vec<InvalidParam>);
+
+impl std::fmt::Display for InvalidParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid params")?;
+        if let Some(error) = self.0.first() {
+            write!(f, " at `{}`: {}", error.path, error.message)?;
+        }
+        if self.0.len() > 1 {
+            write!(f, " (and {} more)", self.0.len() - 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for InvalidParams {}
+
+impl From<&InvalidParams> for ErrorObject<'static> {
+    fn from(value: &InvalidParams) -> Self {
+        ErrorObject::owned(
+            ErrorCode::InvalidParams.code(),
+            value.to_string(),
+            Some(&value.0),
+        )
+    }
+}
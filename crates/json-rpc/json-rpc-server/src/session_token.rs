@@ -0,0 +1,122 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use signature::Address;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Issues and verifies the tokens of
+/// [`crate::SignatureVerificationLayer::session_tokens()`].
+///
+/// A token is `<address>.<audience>.<expiry>.<tag>`, with the hex-encoded
+/// address, the hex-encoded audience of the signed request it was issued
+/// for (empty if the request had none), the expiry in seconds since the
+/// Unix epoch and the hex-encoded HMAC-SHA256 of the rest under the server
+/// secret. Verifying a token costs one HMAC instead of a public key recovery.
+pub(crate) struct SessionTokens {
+    secret: Vec<u8>,
+    ttl: Duration,
+}
+
+/// The claims of a verified token.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct SessionToken {
+    pub address: Address,
+    pub audience: Option<String>,
+}
+
+impl SessionTokens {
+    pub fn new(secret: Vec<u8>, ttl: Duration) -> Self {
+        Self { secret, ttl }
+    }
+
+    pub fn issue(&self, address: &Address, audience: Option<&str>) -> String {
+        let expiry = (SystemTime::now() + self.ttl)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let claims = format!(
+            "{}.{}.{}",
+            const_hex::encode(address),
+            const_hex::encode(audience.unwrap_or_default()),
+            expiry
+        );
+        let tag = self.mac(&claims).finalize().into_bytes();
+
+        format!("{}.{}", claims, const_hex::encode(tag))
+    }
+
+    /// Return the claims of the token, or `None` if the token is forged or
+    /// expired.
+    pub fn verify(&self, token: &str) -> Option<SessionToken> {
+        let (claims, tag) = token.rsplit_once('.')?;
+        self.mac(claims)
+            .verify_slice(&const_hex::decode(tag).ok()?)
+            .ok()?;
+
+        let mut claims = claims.split('.');
+        let (address, audience, expiry) = (claims.next()?, claims.next()?, claims.next()?);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+        if expiry.parse::<u64>().ok()? <= now {
+            return None;
+        }
+
+        let audience = String::from_utf8(const_hex::decode(audience).ok()?).ok()?;
+
+        Some(SessionToken {
+            address: Address::from(const_hex::decode(address).ok()?),
+            audience: (!audience.is_empty()).then_some(audience),
+        })
+    }
+
+    fn mac(&self, claims: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(claims.as_bytes());
+
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_tokens() {
+        let session_tokens = SessionTokens::new(b"secret".to_vec(), Duration::from_secs(60));
+        let address = Address::from(vec![0xab; 20]);
+
+        let token = session_tokens.issue(&address, Some("sequencer-1:443"));
+        assert_eq!(
+            session_tokens.verify(&token),
+            Some(SessionToken {
+                address: address.clone(),
+                audience: Some("sequencer-1:443".to_owned()),
+            })
+        );
+        let token = session_tokens.issue(&address, None);
+        assert_eq!(session_tokens.verify(&token).unwrap().audience, None);
+
+        // Signed under another secret.
+        let other_session_tokens = SessionTokens::new(b"other".to_vec(), Duration::from_secs(60));
+        assert_eq!(other_session_tokens.verify(&token), None);
+
+        // Claims changed after signing.
+        let (claims, tag) = token.rsplit_once('.').unwrap();
+        let (_, expiry) = claims.rsplit_once('.').unwrap();
+        let forged_token = format!(
+            "{}.{}.{}.{}",
+            const_hex::encode(vec![0xcd; 20]),
+            "",
+            expiry,
+            tag
+        );
+        assert_eq!(session_tokens.verify(&forged_token), None);
+
+        let expired_session_tokens = SessionTokens::new(b"secret".to_vec(), Duration::ZERO);
+        let token = expired_session_tokens.issue(&address, None);
+        assert_eq!(expired_session_tokens.verify(&token), None);
+    }
+}
@@ -0,0 +1,475 @@
+use std::{
+    collections::HashSet,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use http::{header::HeaderMap, Method, StatusCode};
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use signature::{Address, ChainType, Signature};
+use tower::{BoxError, Layer, Service};
+
+use crate::{
+    replay_protection::{self, ReplayProtection},
+    session_token::SessionTokens,
+};
+
+/// The header carrying the hex-encoded signature over the request body.
+pub const SIGNATURE_HEADER: &str = "x-radius-signature";
+
+/// The header carrying the hex-encoded address of the signer.
+pub const ADDRESS_HEADER: &str = "x-radius-address";
+
+/// The header carrying the signing time in milliseconds since the Unix
+/// epoch, see [`SignatureVerificationLayer::replay_protection()`].
+pub const TIMESTAMP_HEADER: &str = "x-radius-timestamp";
+
+/// The header carrying the hex-encoded random nonce of a signed request.
+pub const NONCE_HEADER: &str = "x-radius-nonce";
+
+/// The header carrying the `host:port` the client sent a signed request to.
+pub const AUDIENCE_HEADER: &str = "x-radius-audience";
+
+/// The header carrying the session token, see
+/// [`SignatureVerificationLayer::session_tokens()`].
+pub const SESSION_HEADER: &str = "x-radius-session";
+
+/// The largest request body read before verifying its signature, the
+/// `max_request_body_size` that [`crate::RpcServer`] keeps from jsonrpsee.
+/// Larger requests are rejected with `413 Payload Too Large` before they are
+/// buffered.
+pub(crate) const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// Layer that rejects `POST` requests whose body is not signed by the address
+/// in [`ADDRESS_HEADER`]. The signature is expected in [`SIGNATURE_HEADER`]
+/// as produced by `RpcClientBuilder::signer()` of `json-rpc-client`.
+///
+/// When the request carries [`TIMESTAMP_HEADER`], [`NONCE_HEADER`] and
+/// [`AUDIENCE_HEADER`], the signature covers them along with the body, as
+/// described in [`crate::ReplayProtection`]. Requests signed over the body
+/// only are accepted unless [`SignatureVerificationLayer::replay_protection()`]
+/// is set.
+///
+/// The verified [`Address`] is inserted into the request extensions and
+/// passed to [`crate::AuthenticatedRpcParameter::handler()`].
+#[derive(Clone)]
+pub struct SignatureVerificationLayer {
+    chain_type: ChainType,
+    allowed_addresses: Option<Arc<HashSet<Address>>>,
+    allow_unsigned: bool,
+    session_tokens: Option<Arc<SessionTokens>>,
+    replay_protection: Option<Arc<ReplayProtection>>,
+}
+
+impl SignatureVerificationLayer {
+    pub fn new(chain_type: ChainType) -> Self {
+        Self {
+            chain_type,
+            allowed_addresses: None,
+            allow_unsigned: false,
+            session_tokens: None,
+            replay_protection: None,
+        }
+    }
+
+    /// Only accept requests signed by one of `addresses`. Any valid signer is
+    /// accepted by default.
+    pub fn allowed_addresses(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.allowed_addresses = Some(Arc::new(addresses.into_iter().collect()));
+
+        self
+    }
+
+    /// Pass requests without signature headers through without an
+    /// [`Address`] instead of rejecting them, so that
+    /// [`crate::MethodPolicy::Public`] methods stay reachable. Requests with
+    /// an invalid signature are still rejected.
+    pub fn allow_unsigned(mut self, allow_unsigned: bool) -> Self {
+        self.allow_unsigned = allow_unsigned;
+
+        self
+    }
+
+    /// Issue session tokens to verified signers, so that high-frequency
+    /// clients skip the signature verification of subsequent requests.
+    ///
+    /// The response to a request with a valid signature carries a token in
+    /// [`SESSION_HEADER`], valid for `ttl`. A request sending the token back
+    /// in [`SESSION_HEADER`] is accepted as coming from the signer without
+    /// signature headers. Requests with an expired or forged token are
+    /// verified by their signature headers instead, if any.
+    ///
+    /// Tokens are authenticated with HMAC-SHA256 under `secret`, so servers
+    /// sharing the secret accept each other's tokens and changing it revokes
+    /// every token. A token is bound to the [`AUDIENCE_HEADER`] of the signed
+    /// request it was issued for, and is rejected by servers whose
+    /// [`ReplayProtection::audiences()`] do not include it.
+    ///
+    /// A token is not bound to the requests it authenticates: until it
+    /// expires, anyone holding it can call any method as the signer on the
+    /// servers accepting its audience. Keep `ttl` short and serve the
+    /// methods over TLS so that tokens cannot be captured.
+    pub fn session_tokens(mut self, secret: impl AsRef<[u8]>, ttl: Duration) -> Self {
+        self.session_tokens = Some(Arc::new(SessionTokens::new(secret.as_ref().to_vec(), ttl)));
+
+        self
+    }
+
+    /// Reject signed requests without a fresh timestamp and an unused nonce,
+    /// so that a captured request cannot be sent again, see
+    /// [`ReplayProtection`]. Requests authenticated by a session token are
+    /// not affected.
+    pub fn replay_protection(mut self, replay_protection: ReplayProtection) -> Self {
+        self.replay_protection = Some(Arc::new(replay_protection));
+
+        self
+    }
+
+    fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Verification {
+        if let Some(address) = self.verify_session_token(headers) {
+            return Verification::Session(address);
+        }
+
+        let is_unsigned =
+            !headers.contains_key(SIGNATURE_HEADER) && !headers.contains_key(ADDRESS_HEADER);
+        if is_unsigned && self.allow_unsigned {
+            return Verification::Unsigned;
+        }
+
+        match self.verify_signature(headers, body) {
+            Some(address) => Verification::Signed(address),
+            None => Verification::Invalid,
+        }
+    }
+
+    fn verify_session_token(&self, headers: &HeaderMap) -> Option<Address> {
+        let session_tokens = self.session_tokens.as_ref()?;
+        let token = headers.get(SESSION_HEADER)?.to_str().ok()?;
+        let session_token = session_tokens.verify(token)?;
+
+        if let Some(replay_protection) = &self.replay_protection {
+            if !replay_protection.is_allowed_audience(session_token.audience.as_deref()?) {
+                return None;
+            }
+        }
+
+        // Tokens outlive changes of the allowed addresses.
+        self.is_allowed(&session_token.address)
+            .then_some(session_token.address)
+    }
+
+    fn is_allowed(&self, address: &Address) -> bool {
+        self.allowed_addresses
+            .as_ref()
+            .map(|allowed_addresses| allowed_addresses.contains(address))
+            .unwrap_or(true)
+    }
+
+    fn verify_signature(&self, headers: &HeaderMap, body: &[u8]) -> Option<Address> {
+        let signature = headers.get(SIGNATURE_HEADER)?.to_str().ok()?;
+        let signature = Signature::from(const_hex::decode(signature).ok()?);
+
+        let address = headers.get(ADDRESS_HEADER)?.to_str().ok()?;
+        let address = Address::from_str(self.chain_type, address).ok()?;
+
+        if !self.is_allowed(&address) {
+            return None;
+        }
+
+        match Envelope::from_headers(headers) {
+            Some(envelope) => {
+                let message = replay_protection::envelope(
+                    envelope.timestamp,
+                    envelope.nonce,
+                    envelope.audience,
+                    body,
+                );
+                signature
+                    .verify_message_strict(self.chain_type, &message, &address)
+                    .ok()?;
+
+                if let Some(replay_protection) = &self.replay_protection {
+                    replay_protection
+                        .check(envelope.timestamp, envelope.nonce, envelope.audience)
+                        .then_some(())?;
+                }
+            }
+            None if self.replay_protection.is_some() => return None,
+            None => {
+                signature
+                    .verify_message_strict(self.chain_type, &body, &address)
+                    .ok()?;
+            }
+        }
+
+        Some(address)
+    }
+}
+
+struct Envelope<'a> {
+    timestamp: u64,
+    nonce: &'a str,
+    audience: &'a str,
+}
+
+impl<'a> Envelope<'a> {
+    fn from_headers(headers: &'a HeaderMap) -> Option<Self> {
+        Some(Self {
+            timestamp: headers.get(TIMESTAMP_HEADER)?.to_str().ok()?.parse().ok()?,
+            nonce: headers.get(NONCE_HEADER)?.to_str().ok()?,
+            audience: headers.get(AUDIENCE_HEADER)?.to_str().ok()?,
+        })
+    }
+}
+
+enum Verification {
+    Signed(Address),
+    Session(Address),
+    Unsigned,
+    Invalid,
+}
+
+impl<S> Layer<S> for SignatureVerificationLayer {
+    type Service = SignatureVerification<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SignatureVerification {
+            inner,
+            verifier: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SignatureVerification<S> {
+    inner: S,
+    verifier: SignatureVerificationLayer,
+}
+
+impl<S, B> Service<HttpRequest<B>> for SignatureVerification<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse> + Clone + Send + 'static,
+    S::Error: Into<BoxError> + 'static,
+    S::Future: Send + 'static,
+    B: http_body::Body<Data = Bytes> + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = HttpResponse;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: HttpRequest<B>) -> Self::Future {
+        // `GET` requests (e.g. `/health`) carry no body to sign.
+        if request.method() != Method::POST {
+            let future = self.inner.call(request.map(HttpBody::new));
+
+            return Box::pin(async move { future.await.map_err(Into::into) });
+        }
+
+        // Take the service that was driven to readiness and leave a clone.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let verifier = self.verifier.clone();
+
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+            let body = match Limited::new(body, MAX_BODY_SIZE).collect().await {
+                Ok(body) => body.to_bytes(),
+                Err(error) if error.is::<LengthLimitError>() => return Ok(payload_too_large()),
+                Err(error) => return Err(error),
+            };
+
+            let mut session_token = None;
+            match verifier.verify(&parts.headers, &body) {
+                Verification::Signed(address) => {
+                    // The audience is only covered by the signature with
+                    // replay protection.
+                    let audience = verifier
+                        .replay_protection
+                        .as_ref()
+                        .and_then(|_| parts.headers.get(AUDIENCE_HEADER))
+                        .and_then(|audience| audience.to_str().ok());
+                    session_token = verifier
+                        .session_tokens
+                        .as_ref()
+                        .map(|session_tokens| session_tokens.issue(&address, audience));
+                    parts.extensions.insert(address);
+                }
+                Verification::Session(address) => {
+                    parts.extensions.insert(address);
+                }
+                Verification::Unsigned => {}
+                Verification::Invalid => return Ok(unauthorized()),
+            }
+
+            let request = HttpRequest::from_parts(parts, HttpBody::from(body.to_vec()));
+            let mut response = inner.call(request).await.map_err(Into::into)?;
+
+            if let Some(session_token) = session_token.and_then(|token| token.try_into().ok()) {
+                response.headers_mut().insert(SESSION_HEADER, session_token);
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::builder()
+        .status(StatusCode::UNAUTHORIZED)
+        .header(http::header::CONTENT_TYPE, "text/plain")
+        .body(HttpBody::from("Missing or invalid request signature.\n"))
+        .expect("Unable to build the response")
+}
+
+fn payload_too_large() -> HttpResponse {
+    HttpResponse::builder()
+        .status(StatusCode::PAYLOAD_TOO_LARGE)
+        .header(http::header::CONTENT_TYPE, "text/plain")
+        .body(HttpBody::from("Request body too large.\n"))
+        .expect("Unable to build the response")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http_body_util::Full;
+    use signature::PrivateKeySigner;
+
+    use super::*;
+
+    const SIGNING_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    /// Call `layer` in front of a service answering `200 OK` to requests
+    /// with a verified [`Address`] and `204 No Content` to the others.
+    async fn call(
+        layer: &SignatureVerificationLayer,
+        request: HttpRequest<Full<Bytes>>,
+    ) -> StatusCode {
+        let mut service = layer.layer(tower::service_fn(|request: HttpRequest| async move {
+            let status = match request.extensions().get::<Address>() {
+                Some(_address) => StatusCode::OK,
+                None => StatusCode::NO_CONTENT,
+            };
+
+            Ok::<_, Infallible>(
+                HttpResponse::builder()
+                    .status(status)
+                    .body(HttpBody::empty())
+                    .unwrap(),
+            )
+        }));
+
+        service.call(request).await.unwrap().status()
+    }
+
+    fn request(
+        body: impl Into<Bytes>,
+        signer: Option<&PrivateKeySigner>,
+    ) -> HttpRequest<Full<Bytes>> {
+        let body = body.into();
+        let mut request = HttpRequest::builder().method(Method::POST).uri("/");
+        if let Some(signer) = signer {
+            let signature = signer.sign_message(&*body).unwrap();
+            request = request
+                .header(
+                    SIGNATURE_HEADER,
+                    const_hex::encode_prefixed(signature.as_bytes()),
+                )
+                .header(ADDRESS_HEADER, const_hex::encode_prefixed(signer.address()));
+        }
+
+        request.body(Full::new(body)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_signature_verification() {
+        let signer = PrivateKeySigner::from_str(ChainType::Ethereum, SIGNING_KEY).unwrap();
+        let layer = SignatureVerificationLayer::new(ChainType::Ethereum);
+
+        assert_eq!(
+            call(&layer, request("{}", Some(&signer))).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(&layer, request("{}", None)).await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        // Signed over another body.
+        let mut tampered_request = request("{}", Some(&signer));
+        *tampered_request.body_mut() = Full::new(Bytes::from("[]"));
+        assert_eq!(
+            call(&layer, tampered_request).await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        assert_eq!(
+            call(&layer.clone().allow_unsigned(true), request("{}", None)).await,
+            StatusCode::NO_CONTENT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_body_size_limit() {
+        let layer = SignatureVerificationLayer::new(ChainType::Ethereum).allow_unsigned(true);
+
+        assert_eq!(
+            call(&layer, request(vec![b' '; MAX_BODY_SIZE], None)).await,
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(
+            call(&layer, request(vec![b' '; MAX_BODY_SIZE + 1], None)).await,
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_token_audience() {
+        let signer = PrivateKeySigner::from_str(ChainType::Ethereum, SIGNING_KEY).unwrap();
+        let layer = SignatureVerificationLayer::new(ChainType::Ethereum)
+            .session_tokens("secret", Duration::from_secs(60))
+            .replay_protection(
+                ReplayProtection::new(Duration::from_secs(60)).audiences(["node-1:443"]),
+            );
+
+        let session_request = |secret: &str, audience: Option<&str>| {
+            let token = SessionTokens::new(secret.as_bytes().to_vec(), Duration::from_secs(60))
+                .issue(signer.address(), audience);
+            let mut request = request("{}", None);
+            request
+                .headers_mut()
+                .insert(SESSION_HEADER, token.try_into().unwrap());
+
+            request
+        };
+
+        assert_eq!(
+            call(&layer, session_request("secret", Some("node-1:443"))).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(&layer, session_request("secret", Some("node-2:443"))).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call(&layer, session_request("secret", None)).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call(&layer, session_request("other", Some("node-1:443"))).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+use futures::{stream::BoxStream, StreamExt};
+use jsonrpsee::server::{SubscriptionCloseResponse, SubscriptionMessage, SubscriptionSink};
+use serde::Serialize;
+
+/// What to do with a new item when the buffer of a subscription is full
+/// because the client reads slower than items are produced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubscriptionPolicy {
+    /// Drop the oldest buffered item to make room for the new one.
+    DropOldest,
+    /// Close the subscription with an error notification.
+    CloseSubscription,
+    /// Stop polling the stream until the client catches up, which slows the
+    /// producer down to the pace of the client.
+    Block,
+}
+
+/// Forward the items of `stream` to `sink` through a buffer of at most
+/// `buffer_size` items, applying `policy` when it is full.
+pub(crate) async fn forward<T>(
+    sink: SubscriptionSink,
+    mut stream: BoxStream<'static, T>,
+    buffer_size: usize,
+    policy: SubscriptionPolicy,
+) -> SubscriptionCloseResponse
+where
+    T: Serialize,
+{
+    let buffer_size = buffer_size.max(1);
+    let mut buffer = VecDeque::<SubscriptionMessage>::with_capacity(buffer_size);
+    let mut is_stream_done = false;
+
+    loop {
+        if is_stream_done && buffer.is_empty() {
+            return SubscriptionCloseResponse::None;
+        }
+
+        let is_receiving =
+            !is_stream_done && (buffer.len() < buffer_size || policy != SubscriptionPolicy::Block);
+        let front = buffer.front().cloned();
+
+        tokio::select! {
+            // `SubscriptionSink::send()` is cancel-safe: the message stays at
+            // the front of the buffer until it is sent.
+            result = async { sink.send(front.unwrap()).await }, if front.is_some() => {
+                if result.is_err() {
+                    return SubscriptionCloseResponse::None;
+                }
+
+                buffer.pop_front();
+            }
+            item = stream.next(), if is_receiving => {
+                let Some(item) = item else {
+                    is_stream_done = true;
+                    continue;
+                };
+
+                let message = match SubscriptionMessage::from_json(&item) {
+                    Ok(message) => message,
+                    Err(error) => {
+                        return SubscriptionCloseResponse::NotifErr(error.to_string().into());
+                    }
+                };
+
+                if buffer.len() == buffer_size {
+                    match policy {
+                        SubscriptionPolicy::DropOldest => {
+                            buffer.pop_front();
+                        }
+                        SubscriptionPolicy::CloseSubscription => {
+                            return SubscriptionCloseResponse::NotifErr(
+                                format!("Subscription buffer of {} items is full.", buffer_size)
+                                    .into(),
+                            );
+                        }
+                        SubscriptionPolicy::Block => {}
+                    }
+                }
+                buffer.push_back(message);
+            }
+            _ = sink.closed() => return SubscriptionCloseResponse::None,
+        }
+    }
+}
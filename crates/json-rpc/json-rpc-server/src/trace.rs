@@ -0,0 +1,93 @@
+use std::net::SocketAddr;
+
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request};
+use tracing::{field, instrument::Instrumented, Instrument};
+
+/// The address of the client of an HTTP request or WebSocket connection,
+/// inserted into the request extensions by the TCP servers.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PeerAddress(pub SocketAddr);
+
+/// RPC middleware that runs every call, including the calls inside a batch,
+/// in an `rpc_call` span with the fields:
+///
+/// - `method`
+/// - `request_id`, the JSON-RPC id chosen by the client, to correlate the logs
+///   of both sides
+/// - `peer_address`, unless served on a Unix domain socket
+/// - `signer`, the address verified by [`crate::SignatureVerificationLayer`] if
+///   the request was signed
+#[derive(Clone)]
+pub(crate) struct TraceLayer;
+
+impl<S> tower::Layer<S> for TraceLayer {
+    type Service = Trace<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Trace { inner }
+    }
+}
+
+pub(crate) struct Trace<S> {
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for Trace<S>
+where
+    S: RpcServiceT<'a>,
+{
+    type Future = Instrumented<S::Future>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let span = tracing::info_span!(
+            "rpc_call",
+            method = request.method_name(),
+            request_id = %request.id,
+            peer_address = field::Empty,
+            signer = field::Empty,
+        );
+        if let Some(PeerAddress(peer_address)) = request.extensions().get() {
+            span.record("peer_address", field::display(peer_address));
+        }
+        #[cfg(feature = "signed-rpc")]
+        if let Some(address) = request.extensions().get::<signature::Address>() {
+            span.record("signer", const_hex::encode_prefixed(address));
+        }
+
+        self.inner.call(request).instrument(span)
+    }
+}
+
+/// Inserts the [`PeerAddress`] of a TCP connection into every HTTP request
+/// served on it.
+#[derive(Clone)]
+pub(crate) struct WithPeerAddress<S> {
+    pub inner: S,
+    pub peer_address: SocketAddr,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for WithPeerAddress<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+
+    type Error = S::Error;
+
+    type Future = S::Future;
+
+    fn poll_ready(
+        &mut self,
+        context: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(context)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        request
+            .extensions_mut()
+            .insert(PeerAddress(self.peer_address));
+
+        self.inner.call(request)
+    }
+}
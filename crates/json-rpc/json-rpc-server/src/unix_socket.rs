@@ -0,0 +1,53 @@
+use std::{
+    fs::Permissions,
+    os::unix::fs::{FileTypeExt, PermissionsExt},
+    path::{Path, PathBuf},
+};
+
+use tokio::net::UnixListener;
+
+/// Unix domain socket that [`crate::RpcServer::init_unix_socket()`] listens
+/// on, e.g. for admin methods that should only be reachable from the same
+/// host.
+///
+/// The socket file is created with the permissions set by
+/// [`UnixSocket::mode()`], which default to `0o600` so that only the owner of
+/// the server process can connect. A socket file left over by a previous run
+/// is replaced, and the file is removed when the server stops.
+#[derive(Clone, Debug)]
+pub struct UnixSocket {
+    pub(crate) path: PathBuf,
+    mode: u32,
+}
+
+impl UnixSocket {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_owned(),
+            mode: 0o600,
+        }
+    }
+
+    /// Set the permission bits of the socket file, e.g. `0o660` to let the
+    /// group of the server process connect as well.
+    pub fn mode(mut self, mode: u32) -> Self {
+        self.mode = mode;
+
+        self
+    }
+
+    pub(crate) fn bind(&self) -> std::io::Result<UnixListener> {
+        // Only remove sockets, so that a mistyped path does not delete a
+        // regular file.
+        if let Ok(metadata) = std::fs::symlink_metadata(&self.path) {
+            if metadata.file_type().is_socket() {
+                std::fs::remove_file(&self.path)?;
+            }
+        }
+
+        let listener = UnixListener::bind(&self.path)?;
+        std::fs::set_permissions(&self.path, Permissions::from_mode(self.mode))?;
+
+        Ok(listener)
+    }
+}
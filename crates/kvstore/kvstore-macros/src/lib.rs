@@ -3,6 +3,39 @@ mod model;
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput, Error};
 
+/// Generate the functions storing the struct or enum under
+/// `#[kvstore(key(..))]`, prefixed by the type name.
+///
+/// The functions are inherent associated functions of the type, so it cannot
+/// define its own with the same names:
+///
+/// - `kind = model`, the default: `put`, `get`, `get_or`, `get_mut`,
+///   `get_mut_or`, `apply`, `delete`, `exists`, `count`, `put_in`, `get_in`,
+///   `get_or_in` and `delete_in`.
+/// - `kind = counter`: `increment`, `decrement`, `get` and `delete`.
+/// - `kind = list`: `push`, `pop`, `range` and `len`.
+///
+/// Every kind also defines the `ID` constant. `count` takes every key field
+/// but the last one and counts the models whose leading key fields are
+/// equal to them, e.g. `count(1)` does not count models under `10`.
+///
+/// Enums are stored through the same codec as structs, so a versioned model
+/// can be an enum over its versions:
+///
+/// ```rust,ignore
+/// #[derive(Clone, Debug, Deserialize, Serialize, Model)]
+/// #[kvstore(key(height: u64))]
+/// pub enum Block {
+///     V1(BlockV1),
+///     V2(BlockV2),
+/// }
+/// ```
+///
+/// Generic types are supported. The generated functions require the type to
+/// implement `Debug`, `Serialize` and `Deserialize` for the instantiation in
+/// use, so type parameters need no extra bounds beyond the ones serde needs.
+/// All of their instantiations share the same prefix, so `Model<A>` and
+/// `Model<B>` stored under the same key overwrite each other.
 #[proc_macro_derive(Model, attributes(kvstore))]
 pub fn derive_model(input: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(input as DeriveInput);
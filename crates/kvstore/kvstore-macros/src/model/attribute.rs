@@ -10,12 +10,14 @@ use syn::{
 pub struct KvStoreAttribute {
     path_attribute: PathAttribute,
     key_attribute: Option<KeyAttribute>,
+    kind_attribute: Option<KindAttribute>,
 }
 
 impl KvStoreAttribute {
     pub fn from_ast(ast: &DeriveInput) -> Result<Self> {
         let mut path_attribute: Option<PathAttribute> = None;
         let mut key_attribute: Option<KeyAttribute> = None;
+        let mut kind_attribute: Option<KindAttribute> = None;
 
         for attribute in ast.attrs.iter() {
             if attribute.path().is_ident("kvstore") {
@@ -42,6 +44,15 @@ impl KvStoreAttribute {
                                 }
                                 key_attribute = Some(key);
                             }
+                            AttributeType::Kind(kind) => {
+                                if kind_attribute.is_some() {
+                                    return Err(Error::new_spanned(
+                                        meta_list,
+                                        "Attribute kind already exists.",
+                                    ));
+                                }
+                                kind_attribute = Some(kind);
+                            }
                         }
                     }
                     others => return Err(Error::new_spanned(others, "Expect kvstore(token)")),
@@ -58,6 +69,7 @@ impl KvStoreAttribute {
         Ok(Self {
             path_attribute: path_attribute.unwrap(),
             key_attribute,
+            kind_attribute,
         })
     }
 
@@ -68,12 +80,20 @@ impl KvStoreAttribute {
     pub fn key_attribute(&self) -> Option<&KeyAttribute> {
         self.key_attribute.as_ref()
     }
+
+    pub fn kind(&self) -> Kind {
+        self.kind_attribute
+            .as_ref()
+            .map(|kind_attribute| kind_attribute.kind)
+            .unwrap_or(Kind::Model)
+    }
 }
 
 #[derive(Debug)]
 pub enum AttributeType {
     Path(PathAttribute),
     Key(KeyAttribute),
+    Kind(KindAttribute),
 }
 
 impl Parse for AttributeType {
@@ -93,7 +113,14 @@ impl Parse for AttributeType {
 
                 Ok(Self::Key(key_attribute))
             }
-            _others => Err(Error::new_spanned(ident, "Must be 'path' or 'key'")),
+            "kind" => {
+                let _punctuation: Token![=] = input.parse()?;
+                let tokens: TokenStream = input.parse()?;
+                let kind_attribute = syn::parse2::<KindAttribute>(tokens)?;
+
+                Ok(Self::Kind(kind_attribute))
+            }
+            _others => Err(Error::new_spanned(ident, "Must be 'path', 'key' or 'kind'")),
         }
     }
 }
@@ -120,6 +147,40 @@ impl PathAttribute {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// The struct is stored as a whole under its key.
+    Model,
+    /// A `u64` counter is stored under the key of the struct.
+    Counter,
+    /// The struct is an element of a list stored under its key.
+    List,
+}
+
+#[derive(Debug)]
+pub struct KindAttribute {
+    kind: Kind,
+}
+
+impl Parse for KindAttribute {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        let kind = match ident.to_string().as_str() {
+            "model" => Kind::Model,
+            "counter" => Kind::Counter,
+            "list" => Kind::List,
+            _others => {
+                return Err(Error::new_spanned(
+                    ident,
+                    "Must be 'model', 'counter' or 'list'",
+                ))
+            }
+        };
+
+        Ok(Self { kind })
+    }
+}
+
 #[derive(Debug)]
 pub struct KeyAttribute {
     key_list: Punctuated<Key, Token![,]>,
@@ -127,11 +188,30 @@ pub struct KeyAttribute {
 
 impl Parse for KeyAttribute {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
-        let (_delimiter, _span, buffer) = input.parse_any_delimiter()?;
+        let (_delimiter, span, buffer) = input.parse_any_delimiter()?;
+        let key_list: Punctuated<Key, Token![,]> = Punctuated::parse_terminated(&buffer)?;
 
-        Ok(Self {
-            key_list: Punctuated::parse_terminated(&buffer)?,
-        })
+        if key_list.is_empty() {
+            return Err(Error::new(
+                span.join(),
+                "Expect at least one key, e.g. key(name: String)",
+            ));
+        }
+
+        for (index, key) in key_list.iter().enumerate() {
+            if key_list
+                .iter()
+                .take(index)
+                .any(|previous| previous.name == key.name)
+            {
+                return Err(Error::new_spanned(
+                    &key.name,
+                    format!("Key `{}` is declared more than once.", key.name),
+                ));
+            }
+        }
+
+        Ok(Self { key_list })
     }
 }
 
@@ -150,6 +230,23 @@ impl KeyAttribute {
             #(#key_ident #key_punctuation #key_reference #key_type,)*
         }
     }
+
+    /// The keys but the last one, which every key of a model with the same
+    /// leading fields starts with.
+    pub fn prefix(&self) -> impl Iterator<Item = &Key> {
+        self.key_list.iter().take(self.key_list.len() - 1)
+    }
+
+    pub fn as_prefix_function_parameters(&self) -> TokenStream {
+        let key_ident = self.prefix().map(|key| &key.name);
+        let key_punctuation = self.prefix().map(|key| &key.punctuation);
+        let key_reference = self.prefix().map(|key| &key.reference);
+        let key_type = self.prefix().map(|key| &key.key_type);
+
+        quote! {
+            #(#key_ident #key_punctuation #key_reference #key_type,)*
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -1,8 +1,8 @@
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::Ident;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, Ident, Type};
 
-use crate::model::attribute::KvStoreAttribute;
+use crate::model::attribute::{Kind, KvStoreAttribute};
 
 pub fn const_id(type_name: &Ident) -> TokenStream {
     quote! {
@@ -10,6 +10,93 @@ pub fn const_id(type_name: &Ident) -> TokenStream {
     }
 }
 
+/// Register the ID and key layout of the model for `KvStore::models()`. The
+/// registration does not mention the generic parameters, so a generic model
+/// is listed once.
+pub fn submit_model_info(type_name: &Ident, kvstore_attribute: &KvStoreAttribute) -> TokenStream {
+    let path = kvstore_attribute.path();
+    let kind = match kvstore_attribute.kind() {
+        Kind::Model => quote!(Model),
+        Kind::Counter => quote!(Counter),
+        Kind::List => quote!(List),
+    };
+    let keys = kvstore_attribute
+        .key_attribute()
+        .into_iter()
+        .flat_map(|key_attribute| {
+            key_attribute.iter().map(|key| {
+                let name = key.name.to_string();
+                let key_type = type_to_string(&key.key_type);
+
+                quote! {
+                    #path::ModelKey {
+                        name: #name,
+                        type_name: #key_type,
+                    }
+                }
+            })
+        });
+
+    quote! {
+        #path::__private::inventory::submit! {
+            #path::ModelInfo {
+                id: stringify!(#type_name),
+                type_name: concat!(module_path!(), "::", stringify!(#type_name)),
+                kind: #path::ModelKind::#kind,
+                key: &[#(#keys,)*],
+            }
+        }
+    }
+}
+
+/// Print `key_type` the way it is written, e.g. `Vec<u8>` rather than the
+/// `Vec < u8 >` of the token stream.
+fn type_to_string(key_type: &Type) -> String {
+    key_type
+        .to_token_stream()
+        .to_string()
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace(" ;", ";")
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace("& ", "&")
+        .replace("[ ", "[")
+        .replace(" ]", "]")
+        .replace("( ", "(")
+        .replace(" )", ")")
+}
+
+/// Check that every key field implements the traits required by the store,
+/// reporting the error at the type of the offending key field instead of
+/// inside the generated functions.
+pub fn fn_assert_key_fields(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let path = kvstore_attribute.path();
+        let assertions = key_attribute.iter().map(|key| {
+            let function = format_ident!("key_field_{}", key.name);
+            let key_type = &key.key_type;
+
+            quote_spanned! {key_type.span()=>
+                fn #function<T: ?Sized + #path::__private::KeyField>() {}
+                #function::<#key_type>();
+            }
+        });
+
+        Some(quote! {
+            #[doc(hidden)]
+            #[allow(dead_code, non_snake_case)]
+            fn __assert_key_fields() {
+                #(#assertions)*
+            }
+        })
+    } else {
+        None
+    }
+}
+
 pub fn fn_put(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
     if let Some(key_attribute) = kvstore_attribute.key_attribute() {
         let parameters = key_attribute.as_function_parameters();
@@ -74,6 +161,7 @@ pub fn fn_get_mut(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
         let path = kvstore_attribute.path();
 
         Some(quote! {
+            #[track_caller]
             pub fn get_mut(#parameters) -> std::result::Result<#path::Lock<'static, Self>, #path::KvStoreError> {
                 let key = &(Self::ID, #(#key_names,)*);
 
@@ -92,6 +180,7 @@ pub fn fn_get_mut_or(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream
         let path = kvstore_attribute.path();
 
         Some(quote! {
+            #[track_caller]
             pub fn get_mut_or<F>(#parameters function: F) -> std::result::Result<#path::Lock<'static, Self>, #path::KvStoreError>
             where
                 F: FnOnce() -> Self,
@@ -113,6 +202,7 @@ pub fn fn_apply(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
         let path = kvstore_attribute.path();
 
         Some(quote! {
+            #[track_caller]
             pub fn apply<F>(#parameters operation: F) -> std::result::Result<(), #path::KvStoreError>
             where
                 F: FnOnce(&mut Self),
@@ -144,3 +234,243 @@ pub fn fn_delete(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
         None
     }
 }
+
+pub fn fn_exists(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn exists(#parameters) -> std::result::Result<bool, #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                #path::kvstore()?.contains_key(key)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+/// `count()` takes every key but the last one, e.g. `count(rollup_id)` for
+/// `key(rollup_id: String, height: u64)`, and counts the models stored under
+/// them.
+pub fn fn_count(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_prefix_function_parameters();
+        let key_names = key_attribute.prefix().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn count(#parameters) -> std::result::Result<u64, #path::KvStoreError> {
+                let prefix = &(Self::ID, #(#key_names,)*);
+
+                #path::kvstore()?.count_prefix(prefix)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_put_in(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn put_in(&self, session: &#path::Session<'_>, #parameters) -> std::result::Result<(), #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                session.put(key, self)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_get_in(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn get_in(session: &#path::Session<'_>, #parameters) -> std::result::Result<Self, #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                session.get(key)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_get_or_in(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn get_or_in<F>(session: &#path::Session<'_>, #parameters function: F) -> std::result::Result<Self, #path::KvStoreError>
+            where
+                F: FnOnce() -> Self,
+            {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                session.get_or(key, function)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_delete_in(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn delete_in(session: &#path::Session<'_>, #parameters) -> std::result::Result<(), #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                session.delete(key)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_increment(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn increment(#parameters) -> std::result::Result<u64, #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                #path::kvstore()?.increment(key, 1)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_decrement(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn decrement(#parameters) -> std::result::Result<u64, #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                #path::kvstore()?.decrement(key, 1)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_get_count(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn get(#parameters) -> std::result::Result<u64, #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                #path::kvstore()?.get_or_default(key)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_push(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn push(&self, #parameters) -> std::result::Result<u64, #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                #path::kvstore()?.list_push(key, self)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_pop(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn pop(#parameters) -> std::result::Result<Self, #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                #path::kvstore()?.list_pop(key)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_range(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn range(#parameters start: u64, end: u64) -> std::result::Result<Vec<Self>, #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                #path::kvstore()?.list_range(key, start, end)
+            }
+        })
+    } else {
+        None
+    }
+}
+
+pub fn fn_len(kvstore_attribute: &KvStoreAttribute) -> Option<TokenStream> {
+    if let Some(key_attribute) = kvstore_attribute.key_attribute() {
+        let parameters = key_attribute.as_function_parameters();
+        let key_names = key_attribute.iter().map(|key| &key.name);
+        let path = kvstore_attribute.path();
+
+        Some(quote! {
+            pub fn len(#parameters) -> std::result::Result<u64, #path::KvStoreError> {
+                let key = &(Self::ID, #(#key_names,)*);
+
+                #path::kvstore()?.list_len(key)
+            }
+        })
+    } else {
+        None
+    }
+}
@@ -4,32 +4,132 @@ mod impl_block;
 use attribute::*;
 use impl_block::*;
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{DeriveInput, Result};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{parse_quote, Data, DeriveInput, Error, Result, WhereClause};
 
 pub fn expand_derive_model(input: &mut DeriveInput) -> Result<TokenStream> {
     let ident = &input.ident;
+    // Structs and enums are stored through their serde implementation, which
+    // unions do not have.
+    if let Data::Union(data_union) = &input.data {
+        return Err(Error::new_spanned(
+            data_union.union_token,
+            "Model cannot be derived for unions, use a struct or an enum.",
+        ));
+    }
+
     let kvstore_attribute = KvStoreAttribute::from_ast(input)?;
+    if kvstore_attribute.key_attribute().is_none() {
+        return Err(Error::new_spanned(
+            ident,
+            "Missing key attribute, e.g. #[kvstore(key(name: String))]",
+        ));
+    }
+
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let mut where_clause: WhereClause =
+        where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    // Counters store a `u64` under the key, every other kind stores `Self`.
+    if kvstore_attribute.kind() != Kind::Counter {
+        // Report unsatisfied bounds at the struct name rather than at the path
+        // attribute.
+        let path: TokenStream = kvstore_attribute
+            .path()
+            .to_token_stream()
+            .into_iter()
+            .map(|mut token| {
+                token.set_span(ident.span());
+                token
+            })
+            .collect();
+        let predicate = quote_spanned! {ident.span()=>
+            Self: #path::__private::ModelValue
+        };
+        where_clause.predicates.push(syn::parse2(predicate)?);
+    }
 
     let id = const_id(ident);
-    let put = fn_put(&kvstore_attribute);
-    let get = fn_get(&kvstore_attribute);
-    let get_or = fn_get_or(&kvstore_attribute);
-    let get_mut = fn_get_mut(&kvstore_attribute);
-    let get_mut_or = fn_get_mut_or(&kvstore_attribute);
-    let apply = fn_apply(&kvstore_attribute);
-    let delete = fn_delete(&kvstore_attribute);
+    let model_info = submit_model_info(ident, &kvstore_attribute);
+    let assert_key_fields = fn_assert_key_fields(&kvstore_attribute);
+    let functions = match kvstore_attribute.kind() {
+        Kind::Model => model_functions(&kvstore_attribute),
+        Kind::Counter => counter_functions(&kvstore_attribute),
+        Kind::List => list_functions(&kvstore_attribute),
+    };
+
+    let path = kvstore_attribute.path();
 
     Ok(quote! {
-        impl #ident {
+        impl #impl_generics #ident #type_generics #where_clause {
+            #id
+            #assert_key_fields
+            #functions
+        }
+
+        impl #impl_generics #path::Model for #ident #type_generics #where_clause {
             #id
-            #put
-            #get
-            #get_or
-            #get_mut
-            #get_mut_or
-            #apply
-            #delete
         }
+
+        #model_info
     })
 }
+
+fn model_functions(kvstore_attribute: &KvStoreAttribute) -> TokenStream {
+    let put = fn_put(kvstore_attribute);
+    let get = fn_get(kvstore_attribute);
+    let get_or = fn_get_or(kvstore_attribute);
+    let get_mut = fn_get_mut(kvstore_attribute);
+    let get_mut_or = fn_get_mut_or(kvstore_attribute);
+    let apply = fn_apply(kvstore_attribute);
+    let delete = fn_delete(kvstore_attribute);
+    let exists = fn_exists(kvstore_attribute);
+    let count = fn_count(kvstore_attribute);
+    let put_in = fn_put_in(kvstore_attribute);
+    let get_in = fn_get_in(kvstore_attribute);
+    let get_or_in = fn_get_or_in(kvstore_attribute);
+    let delete_in = fn_delete_in(kvstore_attribute);
+
+    quote! {
+        #put
+        #get
+        #get_or
+        #get_mut
+        #get_mut_or
+        #apply
+        #delete
+        #exists
+        #count
+        #put_in
+        #get_in
+        #get_or_in
+        #delete_in
+    }
+}
+
+fn counter_functions(kvstore_attribute: &KvStoreAttribute) -> TokenStream {
+    let increment = fn_increment(kvstore_attribute);
+    let decrement = fn_decrement(kvstore_attribute);
+    let get = fn_get_count(kvstore_attribute);
+    let delete = fn_delete(kvstore_attribute);
+
+    quote! {
+        #increment
+        #decrement
+        #get
+        #delete
+    }
+}
+
+fn list_functions(kvstore_attribute: &KvStoreAttribute) -> TokenStream {
+    let push = fn_push(kvstore_attribute);
+    let pop = fn_pop(kvstore_attribute);
+    let range = fn_range(kvstore_attribute);
+    let len = fn_len(kvstore_attribute);
+
+    quote! {
+        #push
+        #pop
+        #range
+        #len
+    }
+}
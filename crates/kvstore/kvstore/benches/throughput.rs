@@ -0,0 +1,276 @@
+//! Throughput of the hot paths of [`KvStore`] on disk, single-threaded and
+//! under concurrent access.
+//!
+//! Run with `cargo bench -p kvstore` for the default JSON encoding and with
+//! `cargo bench -p kvstore --no-default-features --features bytes` for
+//! bincode. Criterion compares each run with the previous one on the same
+//! machine, so run the baseline before the change.
+
+use std::{
+    hint::black_box,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use kvstore::{Direction, KvStore, Lock, OrderedKey};
+use serde::{Deserialize, Serialize};
+
+/// Number of keys written before the read benchmarks.
+const KEYS: u64 = 10_000;
+/// Number of keys read per iteration of the range benchmark.
+const RANGE_LIMIT: usize = 100;
+const THREADS: [u64; 3] = [2, 4, 8];
+
+/// Roughly the size and shape of a raw transaction stored by a sequencer.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct Transaction {
+    rollup_id: String,
+    order: u64,
+    hash: [u8; 32],
+    payload: Vec<u8>,
+}
+
+impl Transaction {
+    fn new(order: u64) -> Self {
+        Self {
+            rollup_id: "rollup_id".to_owned(),
+            order,
+            hash: [order as u8; 32],
+            payload: vec![0xab; 256],
+        }
+    }
+}
+
+fn key(order: u64) -> (&'static str, &'static str, OrderedKey) {
+    ("transaction", "rollup_id", OrderedKey::new().u64(order))
+}
+
+/// A database in a fresh directory, removed on drop.
+struct Database {
+    kvstore: Option<KvStore>,
+    path: PathBuf,
+}
+
+impl Database {
+    fn open() -> Self {
+        static COUNT: AtomicU64 = AtomicU64::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "kvstore-bench-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let kvstore = KvStore::open(&path).unwrap();
+
+        Self {
+            kvstore: Some(kvstore),
+            path,
+        }
+    }
+
+    fn filled() -> Self {
+        let database = Self::open();
+        for order in 0..KEYS {
+            database
+                .kvstore()
+                .put(&key(order), &Transaction::new(order))
+                .unwrap();
+        }
+
+        database
+    }
+
+    fn kvstore(&self) -> &KvStore {
+        self.kvstore.as_ref().unwrap()
+    }
+}
+
+impl Drop for Database {
+    fn drop(&mut self) {
+        drop(self.kvstore.take());
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Run `operation(thread_index, iteration)` `iterations` times split across
+/// `threads` threads and return the wall time.
+fn run_concurrently<F>(threads: u64, iterations: u64, operation: F) -> Duration
+where
+    F: Fn(u64, u64) + Sync,
+{
+    let per_thread = iterations.div_ceil(threads);
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for thread_index in 0..threads {
+            let operation = &operation;
+            scope.spawn(move || {
+                for iteration in 0..per_thread {
+                    operation(thread_index, iteration);
+                }
+            });
+        }
+    });
+
+    start.elapsed()
+}
+
+fn put(c: &mut Criterion) {
+    let mut group = c.benchmark_group("put");
+    group.throughput(Throughput::Elements(1));
+
+    let database = Database::open();
+    let mut order = 0;
+    group.bench_function("single", |b| {
+        b.iter_batched(
+            || {
+                order += 1;
+                (key(order), Transaction::new(order))
+            },
+            |(key, transaction)| database.kvstore().put(&key, &transaction).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    for threads in THREADS {
+        let database = Database::open();
+        let next_order = AtomicU64::new(0);
+        group.bench_function(format!("threads/{}", threads), |b| {
+            b.iter_custom(|iterations| {
+                run_concurrently(threads, iterations, |_, _| {
+                    let order = next_order.fetch_add(1, Ordering::Relaxed);
+                    database
+                        .kvstore()
+                        .put(&key(order), &Transaction::new(order))
+                        .unwrap();
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get");
+    group.throughput(Throughput::Elements(1));
+
+    let database = Database::filled();
+    let mut order = 0;
+    group.bench_function("single", |b| {
+        b.iter(|| {
+            order = (order + 1) % KEYS;
+            let transaction: Transaction = database.kvstore().get(&key(order)).unwrap();
+            black_box(transaction)
+        })
+    });
+
+    for threads in THREADS {
+        group.bench_function(format!("threads/{}", threads), |b| {
+            b.iter_custom(|iterations| {
+                run_concurrently(threads, iterations, |thread_index, iteration| {
+                    let order = (thread_index * 7_919 + iteration) % KEYS;
+                    let transaction: Transaction = database.kvstore().get(&key(order)).unwrap();
+                    black_box(transaction);
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn apply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("apply");
+    group.throughput(Throughput::Elements(1));
+
+    let database = Database::filled();
+    let mut order = 0;
+    group.bench_function("single", |b| {
+        b.iter(|| {
+            order = (order + 1) % KEYS;
+            database
+                .kvstore()
+                .apply(&key(order), |transaction: &mut Lock<Transaction>| {
+                    transaction.order += 1;
+                })
+                .unwrap()
+        })
+    });
+
+    // Every thread updates its own keys.
+    for threads in THREADS {
+        group.bench_function(format!("threads/{}", threads), |b| {
+            b.iter_custom(|iterations| {
+                run_concurrently(threads, iterations, |thread_index, iteration| {
+                    let order =
+                        (thread_index * (KEYS / threads) + iteration % (KEYS / threads)) % KEYS;
+                    database
+                        .kvstore()
+                        .apply(&key(order), |transaction: &mut Lock<Transaction>| {
+                            transaction.order += 1;
+                        })
+                        .unwrap();
+                })
+            })
+        });
+    }
+
+    // Every thread updates the same key, e.g. the latest order of a rollup.
+    for threads in THREADS {
+        group.bench_function(format!("contended/{}", threads), |b| {
+            b.iter_custom(|iterations| {
+                run_concurrently(threads, iterations, |_, _| {
+                    database
+                        .kvstore()
+                        .apply(&key(0), |transaction: &mut Lock<Transaction>| {
+                            transaction.order += 1;
+                        })
+                        .unwrap();
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn iter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iter");
+    group.throughput(Throughput::Elements(RANGE_LIMIT as u64));
+
+    let database = Database::filled();
+    for direction in [Direction::Forward, Direction::Reverse] {
+        group.bench_function(format!("{:?}", direction).to_lowercase(), |b| {
+            b.iter(|| {
+                let transactions: Vec<Transaction> = database
+                    .kvstore()
+                    .get_range(&key(0), &key(u64::MAX), RANGE_LIMIT, direction)
+                    .unwrap();
+                black_box(transactions)
+            })
+        });
+    }
+
+    for threads in THREADS {
+        group.bench_function(format!("threads/{}", threads), |b| {
+            b.iter_custom(|iterations| {
+                run_concurrently(threads, iterations, |thread_index, _| {
+                    let start = thread_index * RANGE_LIMIT as u64 % KEYS;
+                    let transactions: Vec<Transaction> = database
+                        .kvstore()
+                        .get_range(&key(start), &key(u64::MAX), RANGE_LIMIT, Direction::Forward)
+                        .unwrap();
+                    black_box(transactions);
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, put, get, apply, iter);
+criterion_main!(benches);
@@ -0,0 +1,146 @@
+use std::{fmt::Debug, future::Future};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{CachedKvStore, CachedKvStoreError, KvStore, KvStoreError};
+
+/// The operations [`KvStore`] and [`CachedKvStore`] have in common, named
+/// after those of a sled `Tree`, so that a crate can be written against the
+/// trait, run on [`KvStore`] and tested on [`CachedKvStore`].
+///
+/// Values must be both serializable, for [`KvStore`], and `Clone`, for
+/// [`CachedKvStore`]. A value read as another type than it was inserted as
+/// fails with an error from either store.
+///
+/// # Examples
+///
+/// ```rust
+/// #[derive(Clone, Debug, Deserialize, Serialize)]
+/// pub struct Rollup {
+///     pub block_height: u64,
+/// }
+///
+/// async fn advance<B: KeyValueBackend>(backend: &B, rollup_id: &str) -> Result<u64, B::Error> {
+///     let mut rollup = backend
+///         .get(&("rollup", rollup_id))
+///         .await?
+///         .unwrap_or(Rollup { block_height: 0 });
+///     rollup.block_height += 1;
+///     backend.insert(&("rollup", rollup_id), &rollup).await?;
+///
+///     Ok(rollup.block_height)
+/// }
+///
+/// // In production.
+/// let database = KvStore::open("database").unwrap();
+/// advance(&database, "rollup_id").await.unwrap();
+///
+/// // In tests.
+/// let database = CachedKvStore::default();
+/// assert_eq!(advance(&database, "rollup_id").await.unwrap(), 1);
+/// ```
+pub trait KeyValueBackend: Clone + Send + Sync + 'static {
+    type Error: std::error::Error + Send + 'static;
+
+    /// Store `value` at `key`, replacing the value stored there.
+    fn insert<K, V>(
+        &self,
+        key: &K,
+        value: &V,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send
+    where
+        K: Debug + Serialize + Sync,
+        V: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static;
+
+    /// Return the value stored at `key`, or `None` if there is none.
+    fn get<K, V>(&self, key: &K) -> impl Future<Output = Result<Option<V>, Self::Error>> + Send
+    where
+        K: Debug + Serialize + Sync,
+        V: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static;
+
+    /// Remove the value stored at `key`, if any.
+    fn remove<K>(&self, key: &K) -> impl Future<Output = Result<(), Self::Error>> + Send
+    where
+        K: Debug + Serialize + Sync;
+
+    fn contains_key<K>(&self, key: &K) -> impl Future<Output = Result<bool, Self::Error>> + Send
+    where
+        K: Debug + Serialize + Sync;
+}
+
+impl KeyValueBackend for KvStore {
+    type Error = KvStoreError;
+
+    async fn insert<K, V>(&self, key: &K, value: &V) -> Result<(), Self::Error>
+    where
+        K: Debug + Serialize + Sync,
+        V: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static,
+    {
+        self.put(key, value)
+    }
+
+    async fn get<K, V>(&self, key: &K) -> Result<Option<V>, Self::Error>
+    where
+        K: Debug + Serialize + Sync,
+        V: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static,
+    {
+        match KvStore::get(self, key) {
+            Ok(value) => Ok(Some(value)),
+            Err(KvStoreError::NoneType) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn remove<K>(&self, key: &K) -> Result<(), Self::Error>
+    where
+        K: Debug + Serialize + Sync,
+    {
+        self.delete(key)
+    }
+
+    async fn contains_key<K>(&self, key: &K) -> Result<bool, Self::Error>
+    where
+        K: Debug + Serialize + Sync,
+    {
+        KvStore::contains_key(self, key)
+    }
+}
+
+impl KeyValueBackend for CachedKvStore {
+    type Error = CachedKvStoreError;
+
+    async fn insert<K, V>(&self, key: &K, value: &V) -> Result<(), Self::Error>
+    where
+        K: Debug + Serialize + Sync,
+        V: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static,
+    {
+        self.put(key, value.clone()).await
+    }
+
+    async fn get<K, V>(&self, key: &K) -> Result<Option<V>, Self::Error>
+    where
+        K: Debug + Serialize + Sync,
+        V: Clone + Debug + DeserializeOwned + Serialize + Send + Sync + 'static,
+    {
+        match CachedKvStore::get(self, key).await {
+            Ok(value) => Ok(Some(value)),
+            Err(CachedKvStoreError::KeyError(_)) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn remove<K>(&self, key: &K) -> Result<(), Self::Error>
+    where
+        K: Debug + Serialize + Sync,
+    {
+        // The value type only bounds the signature of `delete()`.
+        self.delete::<K, ()>(key).await
+    }
+
+    async fn contains_key<K>(&self, key: &K) -> Result<bool, Self::Error>
+    where
+        K: Debug + Serialize + Sync,
+    {
+        CachedKvStore::contains_key(self, key).await
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    io::{BufReader, BufWriter, Read, Write},
+    sync::Mutex,
+};
+
+use rocksdb::{Transaction, TransactionDB, WriteBatchIterator, WriteBatchWithTransaction};
+
+use crate::KvStoreError;
+
+pub type CommitSinkError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A key written or deleted by a [`Commit`]. Keys and values are encoded as
+/// stored in the database, so that [`crate::KvStore::apply_commit()`] can
+/// write them back without knowing their types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommitRecord {
+    Put { key: Vec<u8>, value: Vec<u8> },
+    Delete { key: Vec<u8> },
+}
+
+impl CommitRecord {
+    pub fn key(&self) -> &[u8] {
+        match self {
+            Self::Put { key, .. } => key,
+            Self::Delete { key } => key,
+        }
+    }
+}
+
+/// The records of a committed write, in the order they were written.
+///
+/// `sequence` starts at 1 when the database is opened and increases by one
+/// with every commit, so a gap tells that a commit was missed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Commit {
+    pub sequence: u64,
+    pub records: Vec<CommitRecord>,
+}
+
+/// Receives every commit of a [`crate::KvStore`] built with
+/// [`crate::KvStoreBuilder::commit_hook()`], in commit order.
+///
+/// `send()` is called while holding the lock that orders commits, so it
+/// should hand the commit off rather than do slow work itself.
+pub trait CommitSink: Send + Sync + 'static {
+    fn send(&self, commit: Commit) -> Result<(), CommitSinkError>;
+}
+
+impl CommitSink for std::sync::mpsc::Sender<Commit> {
+    fn send(&self, commit: Commit) -> Result<(), CommitSinkError> {
+        std::sync::mpsc::Sender::send(self, commit).map_err(|error| error.into())
+    }
+}
+
+impl CommitSink for tokio::sync::mpsc::UnboundedSender<Commit> {
+    fn send(&self, commit: Commit) -> Result<(), CommitSinkError> {
+        tokio::sync::mpsc::UnboundedSender::send(self, commit).map_err(|error| error.into())
+    }
+}
+
+const PUT: u8 = 0;
+const DELETE: u8 = 1;
+
+/// Appends commits to a writer, e.g. a file shipped to a standby node and
+/// read back with [`CommitLogReader`]. Each commit is flushed before the
+/// write that made it returns.
+///
+/// A commit is written as `sequence (u64 BE) | record count (u32 BE)`
+/// followed by its records, each `op (u8) | key length (u32 BE) | key` plus
+/// `value length (u32 BE) | value` for puts.
+pub struct CommitLogWriter<W: Write> {
+    writer: Mutex<BufWriter<W>>,
+}
+
+impl<W: Write> CommitLogWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(BufWriter::new(writer)),
+        }
+    }
+
+    fn write(writer: &mut impl Write, commit: &Commit) -> std::io::Result<()> {
+        writer.write_all(&commit.sequence.to_be_bytes())?;
+        write_length(writer, commit.records.len())?;
+
+        for record in &commit.records {
+            match record {
+                CommitRecord::Put { key, value } => {
+                    writer.write_all(&[PUT])?;
+                    write_field(writer, key)?;
+                    write_field(writer, value)?;
+                }
+                CommitRecord::Delete { key } => {
+                    writer.write_all(&[DELETE])?;
+                    write_field(writer, key)?;
+                }
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+impl<W: Write + Send + 'static> CommitSink for CommitLogWriter<W> {
+    fn send(&self, commit: Commit) -> Result<(), CommitSinkError> {
+        let mut writer = self.writer.lock().unwrap();
+
+        Self::write(&mut *writer, &commit).map_err(|error| error.into())
+    }
+}
+
+fn write_length(writer: &mut impl Write, length: usize) -> std::io::Result<()> {
+    let length = u32::try_from(length)
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "record too large"))?;
+
+    writer.write_all(&length.to_be_bytes())
+}
+
+fn write_field(writer: &mut impl Write, field: &[u8]) -> std::io::Result<()> {
+    write_length(writer, field.len())?;
+
+    writer.write_all(field)
+}
+
+/// Reads the commits written by [`CommitLogWriter`].
+pub struct CommitLogReader<R: Read> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read> CommitLogReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+        }
+    }
+
+    /// Return the next commit, or `None` at the end of the log.
+    pub fn read(&mut self) -> Result<Option<Commit>, KvStoreError> {
+        let mut sequence_bytes = [0; 8];
+        let mut read = 0;
+        while read < sequence_bytes.len() {
+            match self.reader.read(&mut sequence_bytes[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(KvStoreError::TruncatedCommitLog),
+                Ok(length) => read += length,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(KvStoreError::ReadCommitLog(error)),
+            }
+        }
+
+        let record_count = self.read_length()?;
+        let mut records = Vec::with_capacity(record_count.min(1024));
+        for _ in 0..record_count {
+            let mut op = [0; 1];
+            self.read_exact(&mut op)?;
+
+            let record = match op[0] {
+                PUT => CommitRecord::Put {
+                    key: self.read_field()?,
+                    value: self.read_field()?,
+                },
+                DELETE => CommitRecord::Delete {
+                    key: self.read_field()?,
+                },
+                others => return Err(KvStoreError::InvalidCommitOp(others)),
+            };
+            records.push(record);
+        }
+
+        Ok(Some(Commit {
+            sequence: u64::from_be_bytes(sequence_bytes),
+            records,
+        }))
+    }
+
+    fn read_length(&mut self) -> Result<usize, KvStoreError> {
+        let mut length_bytes = [0; 4];
+        self.read_exact(&mut length_bytes)?;
+
+        Ok(u32::from_be_bytes(length_bytes) as usize)
+    }
+
+    fn read_field(&mut self) -> Result<Vec<u8>, KvStoreError> {
+        let mut field = vec![0; self.read_length()?];
+        self.read_exact(&mut field)?;
+
+        Ok(field)
+    }
+
+    fn read_exact(&mut self, buffer: &mut [u8]) -> Result<(), KvStoreError> {
+        self.reader.read_exact(buffer).map_err(|error| {
+            if error.kind() == std::io::ErrorKind::UnexpectedEof {
+                KvStoreError::TruncatedCommitLog
+            } else {
+                KvStoreError::ReadCommitLog(error)
+            }
+        })
+    }
+}
+
+/// Orders the commits of a [`crate::KvStore`] and passes them to its sink.
+pub(crate) struct CommitHook {
+    sink: Box<dyn CommitSink>,
+    sequence: Mutex<u64>,
+}
+
+impl CommitHook {
+    pub fn new(sink: impl CommitSink) -> Self {
+        Self {
+            sink: Box::new(sink),
+            sequence: Mutex::new(0),
+        }
+    }
+
+    pub fn commit(
+        &self,
+        transaction: Transaction<'_, TransactionDB>,
+        map_error: fn(rocksdb::Error) -> KvStoreError,
+    ) -> Result<(), KvStoreError> {
+        let records = Records::collect(&strip_noop(transaction.get_writebatch()));
+
+        let mut sequence = self.sequence.lock().unwrap();
+        transaction.commit().map_err(map_error)?;
+
+        self.send(&mut sequence, records)
+    }
+
+    pub fn write(
+        &self,
+        database: &TransactionDB,
+        batch: WriteBatchWithTransaction<true>,
+        map_error: fn(rocksdb::Error) -> KvStoreError,
+    ) -> Result<(), KvStoreError> {
+        let records = Records::collect(&batch);
+
+        let mut sequence = self.sequence.lock().unwrap();
+        database.write(batch).map_err(map_error)?;
+
+        self.send(&mut sequence, records)
+    }
+
+    fn send(&self, sequence: &mut u64, records: Vec<CommitRecord>) -> Result<(), KvStoreError> {
+        // Transactions that only read commit nothing worth replicating.
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        *sequence += 1;
+        self.sink
+            .send(Commit {
+                sequence: *sequence,
+                records,
+            })
+            .map_err(KvStoreError::CommitSink)
+    }
+}
+
+/// Length of the sequence number and record count heading a write batch.
+const WRITE_BATCH_HEADER_LENGTH: usize = 12;
+/// `kTypeNoop` of RocksDB.
+const WRITE_BATCH_NOOP: u8 = 0xD;
+
+/// Drop the no-op marker that transactions put in front of their records.
+/// `WriteBatch::iterate()` stops at it, since the C API defines no handler
+/// for it.
+fn strip_noop(batch: WriteBatchWithTransaction<true>) -> WriteBatchWithTransaction<true> {
+    let data = batch.data();
+    match data.get(WRITE_BATCH_HEADER_LENGTH) {
+        Some(&WRITE_BATCH_NOOP) => {
+            let mut stripped = data[..WRITE_BATCH_HEADER_LENGTH].to_vec();
+            stripped.extend_from_slice(&data[WRITE_BATCH_HEADER_LENGTH + 1..]);
+
+            WriteBatchWithTransaction::from_data(&stripped)
+        }
+        _ => batch,
+    }
+}
+
+struct Records(Vec<CommitRecord>);
+
+impl Records {
+    fn collect(batch: &WriteBatchWithTransaction<true>) -> Vec<CommitRecord> {
+        let mut records = Self(Vec::with_capacity(batch.len()));
+        batch.iterate(&mut records);
+
+        records.0
+    }
+}
+
+impl WriteBatchIterator for Records {
+    fn put(&mut self, key: Box<[u8]>, value: Box<[u8]>) {
+        self.0.push(CommitRecord::Put {
+            key: key.into(),
+            value: value.into(),
+        });
+    }
+
+    fn delete(&mut self, key: Box<[u8]>) {
+        self.0.push(CommitRecord::Delete { key: key.into() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+
+    use rocksdb::{Env, Options, TransactionDBOptions};
+
+    use super::*;
+    use crate::{Compression, KvStore, KvStoreBuilder, Lock};
+
+    fn export(database: &KvStore) -> Vec<u8> {
+        let mut export = Vec::new();
+        database
+            .export::<_, (String, u64), u64>(&("block",), &mut export, Compression::None)
+            .unwrap();
+
+        export
+    }
+
+    #[test]
+    fn test_apply_commit() {
+        let (sender, receiver) = channel();
+        let database = KvStoreBuilder::default()
+            .commit_hook(sender)
+            .build_in_memory()
+            .unwrap();
+
+        for height in 0..5u64 {
+            database.put(&("block", height), &height).unwrap();
+        }
+        database.delete(&("block", 1u64)).unwrap();
+        database
+            .apply(&("block", 2u64), |value: &mut Lock<u64>| **value += 10)
+            .unwrap();
+        let session = database.session();
+        session.put(&("block", 5u64), &5u64).unwrap();
+        session.delete(&("block", 3u64)).unwrap();
+        session.commit().unwrap();
+        database
+            .delete_range(&("block",), &("block".to_owned(), 1u64))
+            .unwrap();
+
+        let standby = KvStore::new_in_memory().unwrap();
+        for (index, commit) in receiver.try_iter().enumerate() {
+            assert_eq!(commit.sequence, index as u64 + 1);
+            standby.apply_commit(&commit).unwrap();
+        }
+
+        assert_eq!(export(&standby), export(&database));
+        assert_eq!(standby.get::<_, u64>(&("block", 2u64)).unwrap(), 12);
+        for height in [0u64, 1, 3] {
+            assert!(standby
+                .get::<_, u64>(&("block", height))
+                .unwrap_err()
+                .is_none_type());
+        }
+    }
+
+    /// Guards the write batch layout `strip_noop()` relies on: the records of
+    /// a transaction are only read back if the no-op marker is where it is
+    /// expected.
+    #[test]
+    fn test_strip_noop_transaction() {
+        let env = Env::mem_env().unwrap();
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.set_env(&env);
+        let database = TransactionDB::open(
+            &options,
+            &TransactionDBOptions::default(),
+            std::env::temp_dir().join("kvstore-commit-hook"),
+        )
+        .unwrap();
+
+        let transaction = database.transaction();
+        transaction.put(b"key", b"value").unwrap();
+        transaction.delete(b"other").unwrap();
+
+        assert_eq!(
+            Records::collect(&strip_noop(transaction.get_writebatch())),
+            [
+                CommitRecord::Put {
+                    key: b"key".to_vec(),
+                    value: b"value".to_vec(),
+                },
+                CommitRecord::Delete {
+                    key: b"other".to_vec(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_noop_unchanged() {
+        let mut batch = WriteBatchWithTransaction::<true>::default();
+        batch.put(b"key", b"value");
+        batch.delete(b"other");
+        let data = batch.data().to_vec();
+
+        assert_eq!(strip_noop(batch).data(), data);
+    }
+}
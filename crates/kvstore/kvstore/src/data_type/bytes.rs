@@ -20,6 +20,38 @@ where
     })
 }
 
+/// Serialize a key prefix so that it is a byte prefix of every key sharing
+/// the same leading fields, e.g. `("block",)` for `("block", height)`.
+pub fn serialize_prefix<T>(data: &T) -> Result<Vec<u8>, DataTypeError>
+where
+    T: Debug + Serialize,
+{
+    serialize(data)
+}
+
+/// Serialize `key` with `field` appended as its last field, e.g.
+/// `("list", rollup_id, index)` for `("list", rollup_id)`. Tuples are
+/// serialized without framing, so this is the same as `(key, field)`.
+pub fn serialize_with_field<K, T>(key: &K, field: &T) -> Result<Vec<u8>, DataTypeError>
+where
+    K: Debug + Serialize,
+    T: Debug + Serialize,
+{
+    let mut data_vec = serialize(key)?;
+    data_vec.extend(serialize(field)?);
+
+    Ok(data_vec)
+}
+
+/// Return the length of the leading length-prefixed model ID of a serialized
+/// key, or `None` if the key is too short to hold one.
+pub fn model_id_prefix_length(key: &[u8]) -> Option<usize> {
+    let length_bytes: [u8; 8] = key.get(0..8)?.try_into().ok()?;
+    let length = 8 + usize::try_from(u64::from_le_bytes(length_bytes)).ok()?;
+
+    (key.len() >= length).then_some(length)
+}
+
 #[derive(Debug)]
 pub enum DataTypeError {
     Deserialize {
@@ -20,6 +20,64 @@ where
     })
 }
 
+/// Serialize a key prefix so that it is a byte prefix of every key sharing
+/// the same leading fields, e.g. `("block",)` for `("block", height)`.
+///
+/// The prefix ends on the separator after its last field, so that
+/// `("block", 1)` is not a prefix of `("block", 10, ..)`.
+pub fn serialize_prefix<T>(data: &T) -> Result<Vec<u8>, DataTypeError>
+where
+    T: Debug + Serialize,
+{
+    let mut data_vec = serialize(data)?;
+    if data_vec.last() == Some(&b']') {
+        data_vec.pop();
+        data_vec.push(b',');
+    }
+
+    Ok(data_vec)
+}
+
+/// Serialize `key` with `field` appended as its last field, e.g.
+/// `("list", rollup_id, index)` for `("list", rollup_id)`. A key that is not
+/// a tuple is serialized as `(key, field)`.
+pub fn serialize_with_field<K, T>(key: &K, field: &T) -> Result<Vec<u8>, DataTypeError>
+where
+    K: Debug + Serialize,
+    T: Debug + Serialize,
+{
+    let mut data_vec = serialize(key)?;
+    if data_vec.last() != Some(&b']') {
+        return serialize(&(key, field));
+    }
+
+    data_vec.pop();
+    if data_vec.last() != Some(&b'[') {
+        data_vec.push(b',');
+    }
+    data_vec.extend(serialize(field)?);
+    data_vec.push(b']');
+
+    Ok(data_vec)
+}
+
+/// Return the length of the leading `["<model ID>"` of a serialized key, or
+/// `None` if the key does not start with a string field.
+pub fn model_id_prefix_length(key: &[u8]) -> Option<usize> {
+    let field = key.strip_prefix(b"[\"")?;
+
+    let mut is_escaped = false;
+    for (index, byte) in field.iter().enumerate() {
+        match (is_escaped, byte) {
+            (false, b'\\') => is_escaped = true,
+            (false, b'"') => return Some(index + 3),
+            _ => is_escaped = false,
+        }
+    }
+
+    None
+}
+
 #[derive(Debug)]
 pub enum DataTypeError {
     Deserialize {
@@ -4,12 +4,105 @@ mod bytes;
 mod json;
 
 #[cfg(feature = "bytes")]
-pub use bytes::{deserialize, serialize, DataTypeError};
+pub use bytes::{
+    deserialize, model_id_prefix_length, serialize, serialize_prefix, serialize_with_field,
+    DataTypeError,
+};
 #[cfg(any(feature = "default", feature = "json"))]
-pub use json::{deserialize, serialize, DataTypeError};
+pub use json::{
+    deserialize, model_id_prefix_length, serialize, serialize_prefix, serialize_with_field,
+    DataTypeError,
+};
 
 mod prelude {
     pub use std::{any, fmt::Debug};
 
     pub use serde::{de::DeserializeOwned, ser::Serialize};
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::{serialize, serialize_prefix, serialize_with_field};
+    use crate::OrderedKey;
+
+    #[derive(Debug, Serialize)]
+    struct Transaction {
+        rollup_id: String,
+        order: u64,
+        hash: [u8; 32],
+        payload: Vec<u8>,
+    }
+
+    /// Expected size in bytes of `value` in the JSON and the bincode
+    /// encodings.
+    fn assert_size<T>(value: &T, json: usize, bytes: usize)
+    where
+        T: std::fmt::Debug + Serialize,
+    {
+        let value_vec = serialize(value).unwrap();
+
+        #[cfg(feature = "bytes")]
+        let _ = json;
+        #[cfg(feature = "bytes")]
+        assert_eq!(value_vec.len(), bytes, "{:?}", value);
+
+        #[cfg(not(feature = "bytes"))]
+        let _ = bytes;
+        #[cfg(not(feature = "bytes"))]
+        assert_eq!(value_vec.len(), json, "{:?}", value);
+    }
+
+    /// The sizes of typical sequencer keys and values, so that a change of
+    /// encoding that grows every stored value shows up in review.
+    #[test]
+    fn test_serialized_size() {
+        assert_size(&0u64, 1, 8);
+        assert_size(&u64::MAX, 20, 8);
+        assert_size(
+            &(
+                "transaction".to_owned(),
+                "rollup_id".to_owned(),
+                OrderedKey::new().u64(1_000),
+            ),
+            46,
+            44,
+        );
+        assert_size(
+            &Transaction {
+                rollup_id: "rollup_id".to_owned(),
+                order: 1_000,
+                hash: [0xab; 32],
+                payload: vec![0xab; 256],
+            },
+            1211,
+            321,
+        );
+        assert_size(&vec![[0xabu8; 32]; 16], 2081, 520);
+        assert_size(&None::<String>, 4, 1);
+    }
+
+    #[test]
+    fn test_serialize_prefix() {
+        let prefix_vec = serialize_prefix(&("block", 1u64)).unwrap();
+
+        assert!(serialize(&("block", 1u64, 0u64))
+            .unwrap()
+            .starts_with(&prefix_vec));
+        assert!(!serialize(&("block", 10u64, 0u64))
+            .unwrap()
+            .starts_with(&prefix_vec));
+        assert!(!serialize(&("blocks", 1u64, 0u64))
+            .unwrap()
+            .starts_with(&serialize_prefix(&("block",)).unwrap()));
+    }
+
+    #[test]
+    fn test_serialize_with_field() {
+        assert_eq!(
+            serialize_with_field(&("list", "rollup_id"), &3u64).unwrap(),
+            serialize(&("list", "rollup_id", 3u64)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::io::{BufReader, BufWriter, Read, Write};
+
+use flate2::{read::GzDecoder, write::GzEncoder};
+use serde::{de::DeserializeOwned, ser::Serialize};
+
+use crate::KvStoreError;
+
+/// Leading bytes of every export.
+const MAGIC: &[u8; 8] = b"KVSTORE\0";
+/// Bumped on every incompatible change to the layout below.
+const VERSION: u8 = 1;
+
+/// Compression of the records of an export, see [`crate::KvStore::export()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+}
+
+impl Compression {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Gzip => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, KvStoreError> {
+        match byte {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Gzip),
+            others => Err(KvStoreError::UnsupportedCompression(others)),
+        }
+    }
+}
+
+/// Writes the header of an export followed by records of the form
+/// `key length (u32 BE) | key | value length (u32 BE) | value`, with keys and
+/// values encoded as JSON whatever the encoding of the database.
+pub(crate) enum ExportWriter<W: Write> {
+    Plain(BufWriter<W>),
+    Gzip(GzEncoder<BufWriter<W>>),
+}
+
+impl<W: Write> ExportWriter<W> {
+    pub fn new(writer: W, compression: Compression) -> Result<Self, KvStoreError> {
+        let mut writer = BufWriter::new(writer);
+        writer.write_all(MAGIC).map_err(KvStoreError::Export)?;
+        writer
+            .write_all(&[VERSION, compression.to_byte()])
+            .map_err(KvStoreError::Export)?;
+
+        Ok(match compression {
+            Compression::None => Self::Plain(writer),
+            Compression::Gzip => Self::Gzip(GzEncoder::new(writer, flate2::Compression::default())),
+        })
+    }
+
+    pub fn write<K, V>(&mut self, key: &K, value: &V) -> Result<(), KvStoreError>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        let key_vec = serde_json::to_vec(key).map_err(KvStoreError::EncodeRecord)?;
+        let value_vec = serde_json::to_vec(value).map_err(KvStoreError::EncodeRecord)?;
+
+        self.write_field(&key_vec)?;
+        self.write_field(&value_vec)
+    }
+
+    fn write_field(&mut self, field: &[u8]) -> Result<(), KvStoreError> {
+        let length = u32::try_from(field.len()).map_err(|_| KvStoreError::RecordTooLarge)?;
+
+        let writer: &mut dyn Write = match self {
+            Self::Plain(writer) => writer,
+            Self::Gzip(writer) => writer,
+        };
+        writer
+            .write_all(&length.to_be_bytes())
+            .map_err(KvStoreError::Export)?;
+        writer.write_all(field).map_err(KvStoreError::Export)
+    }
+
+    /// Write the trailer of the compression, if any, and flush.
+    pub fn finish(self) -> Result<(), KvStoreError> {
+        let mut writer = match self {
+            Self::Plain(writer) => writer,
+            Self::Gzip(writer) => writer.finish().map_err(KvStoreError::Export)?,
+        };
+
+        writer.flush().map_err(KvStoreError::Export)
+    }
+}
+
+/// Reads an export written by [`ExportWriter`].
+pub(crate) struct ExportReader<'r> {
+    reader: Box<dyn Read + 'r>,
+}
+
+impl<'r> ExportReader<'r> {
+    pub fn new(reader: impl Read + 'r) -> Result<Self, KvStoreError> {
+        let mut reader = BufReader::new(reader);
+
+        let mut header = [0; MAGIC.len() + 2];
+        reader
+            .read_exact(&mut header)
+            .map_err(KvStoreError::Import)?;
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(KvStoreError::InvalidExportHeader);
+        }
+        if header[MAGIC.len()] != VERSION {
+            return Err(KvStoreError::UnsupportedExportVersion(header[MAGIC.len()]));
+        }
+
+        let reader: Box<dyn Read + 'r> = match Compression::from_byte(header[MAGIC.len() + 1])? {
+            Compression::None => Box::new(reader),
+            Compression::Gzip => Box::new(GzDecoder::new(reader)),
+        };
+
+        Ok(Self { reader })
+    }
+
+    /// Return the next record, or `None` at the end of the export.
+    pub fn read<K, V>(&mut self) -> Result<Option<(K, V)>, KvStoreError>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let Some(key_vec) = self.read_field(true)? else {
+            return Ok(None);
+        };
+        let Some(value_vec) = self.read_field(false)? else {
+            return Err(KvStoreError::TruncatedExport);
+        };
+
+        let key = serde_json::from_slice(&key_vec).map_err(KvStoreError::DecodeRecord)?;
+        let value = serde_json::from_slice(&value_vec).map_err(KvStoreError::DecodeRecord)?;
+
+        Ok(Some((key, value)))
+    }
+
+    fn read_field(&mut self, is_end_allowed: bool) -> Result<Option<Vec<u8>>, KvStoreError> {
+        let mut length_bytes = [0; 4];
+        let mut read = 0;
+        while read < length_bytes.len() {
+            match self.reader.read(&mut length_bytes[read..]) {
+                Ok(0) if read == 0 && is_end_allowed => return Ok(None),
+                Ok(0) => return Err(KvStoreError::TruncatedExport),
+                Ok(length) => read += length,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(KvStoreError::Import(error)),
+            }
+        }
+
+        // The buffer grows with the bytes actually read rather than being
+        // allocated from the untrusted length, which may be up to 4 GiB.
+        let length = u32::from_be_bytes(length_bytes) as u64;
+        let mut field = Vec::new();
+        (&mut self.reader)
+            .take(length)
+            .read_to_end(&mut field)
+            .map_err(KvStoreError::Import)?;
+        if field.len() as u64 != length {
+            return Err(KvStoreError::TruncatedExport);
+        }
+
+        Ok(Some(field))
+    }
+}
@@ -2,19 +2,47 @@ use std::{
     any::{type_name, Any},
     collections::HashMap,
     fmt::Debug,
-    sync::Arc,
+    sync::{Arc, Weak},
+    time::Duration,
 };
 
 use serde::Serialize;
-use tokio::sync::{Mutex, MutexGuard, OwnedMutexGuard};
+use tokio::{
+    sync::{Mutex, MutexGuard, OwnedMutexGuard},
+    task::JoinHandle,
+    time::Instant,
+};
 
 use crate::data_type::serialize;
 
 type Key = Vec<u8>;
 type ValueAny = Box<dyn Any + Send + Sync>;
 
+struct Entry {
+    value: ValueAny,
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn new<V>(value: V, expires_at: Option<Instant>) -> Self
+    where
+        V: Clone + Any + Send + 'static,
+    {
+        Self {
+            value: Box::new(Arc::new(Mutex::new(value))),
+            expires_at,
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at <= now)
+            .unwrap_or(false)
+    }
+}
+
 fn downcast<V>(
-    database: MutexGuard<'_, HashMap<Key, ValueAny>>,
+    database: MutexGuard<'_, HashMap<Key, Entry>>,
     key_vec: Vec<u8>,
 ) -> Result<Arc<Mutex<V>>, CachedKvStoreError>
 where
@@ -22,7 +50,10 @@ where
 {
     let value = database
         .get(&key_vec)
+        // Expired entries are missing even before the sweeper removes them.
+        .filter(|entry| !entry.is_expired(Instant::now()))
         .ok_or(CachedKvStoreError::KeyError(type_name::<V>()))?
+        .value
         .downcast_ref::<Arc<Mutex<V>>>()
         .ok_or(CachedKvStoreError::Downcast(type_name::<V>()))?
         .clone();
@@ -31,7 +62,7 @@ where
 }
 
 pub struct CachedKvStore {
-    inner: Arc<Mutex<HashMap<Key, ValueAny>>>,
+    inner: Arc<Mutex<HashMap<Key, Entry>>>,
 }
 
 unsafe impl Send for CachedKvStore {}
@@ -61,10 +92,9 @@ impl CachedKvStore {
         V: Clone + Any + Send + 'static,
     {
         let key_vec = serialize(key)?;
-        let value_any: ValueAny = Box::new(Arc::new(Mutex::new(value)));
 
         let mut database = self.inner.blocking_lock();
-        database.insert(key_vec, value_any);
+        database.insert(key_vec, Entry::new(value, None));
 
         Ok(())
     }
@@ -75,10 +105,55 @@ impl CachedKvStore {
         V: Clone + Any + Send + 'static,
     {
         let key_vec = serialize(key)?;
-        let value_any: ValueAny = Box::new(Arc::new(Mutex::new(value)));
 
         let mut database = self.inner.lock().await;
-        database.insert(key_vec, value_any);
+        database.insert(key_vec, Entry::new(value, None));
+
+        Ok(())
+    }
+
+    /// Same as [`CachedKvStore::blocking_put()`] except that the value
+    /// expires after `ttl`. See [`CachedKvStore::put_with_expiry()`].
+    pub fn blocking_put_with_expiry<K, V>(
+        &self,
+        key: &K,
+        value: V,
+        ttl: Duration,
+    ) -> Result<(), CachedKvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Clone + Any + Send + 'static,
+    {
+        let key_vec = serialize(key)?;
+        let expires_at = Instant::now() + ttl;
+
+        let mut database = self.inner.blocking_lock();
+        database.insert(key_vec, Entry::new(value, Some(expires_at)));
+
+        Ok(())
+    }
+
+    /// Put a value that expires after `ttl`, such as a session token of an
+    /// RPC caller. An expired value is no longer returned by `get()` and
+    /// `get_mut()`, and it is removed from memory by the next
+    /// [`CachedKvStore::remove_expired()`], which
+    /// [`CachedKvStore::spawn_sweeper()`] calls periodically. Putting the key
+    /// again replaces the expiry.
+    pub async fn put_with_expiry<K, V>(
+        &self,
+        key: &K,
+        value: V,
+        ttl: Duration,
+    ) -> Result<(), CachedKvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Clone + Any + Send + 'static,
+    {
+        let key_vec = serialize(key)?;
+        let expires_at = Instant::now() + ttl;
+
+        let mut database = self.inner.lock().await;
+        database.insert(key_vec, Entry::new(value, Some(expires_at)));
 
         Ok(())
     }
@@ -139,6 +214,21 @@ impl CachedKvStore {
         Ok(Value::lock(value).await)
     }
 
+    /// Return whether an unexpired value of any type is stored at `key`.
+    pub async fn contains_key<K>(&self, key: &K) -> Result<bool, CachedKvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        let key_vec = serialize(key)?;
+
+        let database = self.inner.lock().await;
+        let is_stored = database
+            .get(&key_vec)
+            .is_some_and(|entry| !entry.is_expired(Instant::now()));
+
+        Ok(is_stored)
+    }
+
     pub fn blocking_delete<K, V>(&self, key: &K) -> Result<(), CachedKvStoreError>
     where
         K: Debug + Serialize,
@@ -164,6 +254,43 @@ impl CachedKvStore {
 
         Ok(())
     }
+
+    /// Remove every expired value and return how many were removed.
+    pub async fn remove_expired(&self) -> usize {
+        let now = Instant::now();
+
+        let mut database = self.inner.lock().await;
+        let length = database.len();
+        database.retain(|_, entry| !entry.is_expired(now));
+
+        length - database.len()
+    }
+
+    /// Spawn a task on the current Tokio runtime that calls
+    /// [`CachedKvStore::remove_expired()`] every `interval`. The task stops
+    /// once every clone of the store has been dropped, or when the returned
+    /// handle is aborted.
+    pub fn spawn_sweeper(&self, interval: Duration) -> JoinHandle<()> {
+        let inner = Arc::downgrade(&self.inner);
+
+        tokio::spawn(Self::sweep(inner, interval))
+    }
+
+    async fn sweep(inner: Weak<Mutex<HashMap<Key, Entry>>>, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            match inner.upgrade() {
+                Some(inner) => {
+                    CachedKvStore { inner }.remove_expired().await;
+                }
+                None => break,
+            }
+        }
+    }
 }
 
 /// An owned mutex equivalent to [`crate::Lock`] except that [`Value<V>`] does
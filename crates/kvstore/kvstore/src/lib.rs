@@ -1,7 +1,61 @@
+mod backend;
+mod commit_hook;
 mod data_type;
+mod export;
 mod in_memory;
+mod lock_tracker;
+mod namespace;
 mod on_disk;
+mod options;
+mod range;
+mod schema;
+mod tiering;
+mod type_tag;
 
+pub use backend::KeyValueBackend;
+pub use commit_hook::{
+    Commit, CommitLogReader, CommitLogWriter, CommitRecord, CommitSink, CommitSinkError,
+};
+pub use export::Compression;
 pub use in_memory::{CachedKvStore, CachedKvStoreError, Value};
 pub use kvstore_macros::*;
-pub use on_disk::{kvstore, KvStore, KvStoreBuilder, KvStoreError, Lock};
+pub use lock_tracker::HeldLock;
+pub use on_disk::{
+    kvstore, KvStore, KvStoreBuilder, KvStoreError, Lock, Namespace, PrefixStats, Session,
+};
+pub use options::KvStoreOptions;
+pub use range::{Direction, OrderedKey};
+pub use schema::{ModelInfo, ModelKey, ModelKind};
+pub use tiering::ColdTier;
+pub use type_tag::TypeFingerprint;
+
+/// Implemented by `#[derive(Model)]`. Every key of a model starts with its
+/// `ID`, see [`KvStore::prefix_stats()`].
+pub trait Model {
+    const ID: &'static str;
+}
+
+/// Items used by the code generated by `#[derive(Model)]`.
+#[doc(hidden)]
+pub mod __private {
+    use std::fmt::Debug;
+
+    pub use inventory;
+    pub use serde::{de::DeserializeOwned, Serialize};
+
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` cannot be used as a key field",
+        label = "key fields must implement `Debug` and `Serialize`"
+    )]
+    pub trait KeyField: Debug + Serialize {}
+
+    impl<T> KeyField for T where T: ?Sized + Debug + Serialize {}
+
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` cannot be stored by `#[derive(Model)]`",
+        label = "models must implement `Debug`, `Serialize` and `Deserialize`"
+    )]
+    pub trait ModelValue: Debug + DeserializeOwned + Serialize {}
+
+    impl<T> ModelValue for T where T: Debug + DeserializeOwned + Serialize {}
+}
@@ -0,0 +1,85 @@
+use std::{
+    collections::HashMap,
+    panic::Location,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// A [`crate::Lock`] that has not been updated or dropped yet, see
+/// [`crate::KvStore::held_locks()`].
+#[derive(Clone, Debug)]
+pub struct HeldLock {
+    /// The `Debug` representation of the locked key.
+    pub key: String,
+    /// Where the lock was taken, e.g. the call to
+    /// [`crate::KvStore::get_mut()`].
+    pub location: &'static Location<'static>,
+    pub held_for: Duration,
+}
+
+struct Entry {
+    key: String,
+    location: &'static Location<'static>,
+    acquired_at: Instant,
+}
+
+/// The locks currently held on a database, enabled with
+/// [`crate::KvStoreBuilder::track_locks()`].
+#[derive(Clone, Default)]
+pub(crate) struct LockTracker {
+    entries: Arc<Mutex<HashMap<u64, Entry>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl LockTracker {
+    pub fn track(&self, key: String, location: &'static Location<'static>) -> TrackedLock {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.entries.lock().unwrap().insert(
+            id,
+            Entry {
+                key,
+                location,
+                acquired_at: Instant::now(),
+            },
+        );
+
+        TrackedLock {
+            tracker: self.clone(),
+            id,
+        }
+    }
+
+    /// The locks held for at least `min_held_for`, the longest held first.
+    pub fn held_locks(&self, min_held_for: Duration) -> Vec<HeldLock> {
+        let mut held_locks: Vec<HeldLock> = self
+            .entries
+            .lock()
+            .unwrap()
+            .values()
+            .map(|entry| HeldLock {
+                key: entry.key.clone(),
+                location: entry.location,
+                held_for: entry.acquired_at.elapsed(),
+            })
+            .filter(|held_lock| held_lock.held_for >= min_held_for)
+            .collect();
+        held_locks.sort_by(|a, b| b.held_for.cmp(&a.held_for));
+
+        held_locks
+    }
+}
+
+/// Removes the lock from its [`LockTracker`] when dropped.
+pub(crate) struct TrackedLock {
+    tracker: LockTracker,
+    id: u64,
+}
+
+impl Drop for TrackedLock {
+    fn drop(&mut self) {
+        self.tracker.entries.lock().unwrap().remove(&self.id);
+    }
+}
@@ -0,0 +1,97 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, RwLock},
+};
+
+use rocksdb::{compaction_filter::Decision, IteratorMode, Options, ReadOptions, TransactionDB};
+
+use crate::KvStoreError;
+
+/// Leads every key of a [`crate::Namespace`], followed by the generation
+/// big-endian and the serialized key. JSON keys never start with `0xFF`,
+/// and bincode keys only start with the whole prefix by accident.
+const NAMESPACE_PREFIX: &[u8] = b"\xffkvstore.namespace\x00";
+
+/// Leads the marker key of a dropped generation, followed by the generation
+/// big-endian.
+const DROPPED_PREFIX: &[u8] = b"\xffkvstore.dropped_namespace\x00";
+
+pub(crate) fn namespaced_key(generation: u64, key_vec: &[u8]) -> Vec<u8> {
+    let mut namespaced_key = Vec::with_capacity(NAMESPACE_PREFIX.len() + 8 + key_vec.len());
+    namespaced_key.extend_from_slice(NAMESPACE_PREFIX);
+    namespaced_key.extend_from_slice(&generation.to_be_bytes());
+    namespaced_key.extend_from_slice(key_vec);
+
+    namespaced_key
+}
+
+pub(crate) fn dropped_marker_key(generation: u64) -> Vec<u8> {
+    let mut marker_key = DROPPED_PREFIX.to_vec();
+    marker_key.extend_from_slice(&generation.to_be_bytes());
+
+    marker_key
+}
+
+fn generation(key: &[u8], prefix: &[u8]) -> Option<u64> {
+    let generation_bytes = key.strip_prefix(prefix)?.get(..8)?;
+
+    Some(u64::from_be_bytes(generation_bytes.try_into().ok()?))
+}
+
+/// The generations passed to [`crate::KvStore::drop_namespace()`], shared
+/// with the compaction filter that removes their keys.
+#[derive(Clone, Default)]
+pub(crate) struct DroppedNamespaces(Arc<RwLock<BTreeSet<u64>>>);
+
+impl DroppedNamespaces {
+    pub fn contains(&self, generation: u64) -> bool {
+        self.0.read().unwrap().contains(&generation)
+    }
+
+    pub fn insert(&self, generation: u64) {
+        self.0.write().unwrap().insert(generation);
+    }
+
+    /// Record the generation of `key` if it is a dropped marker, e.g. one
+    /// written by [`crate::KvStore::apply_commit()`].
+    pub fn insert_marker(&self, key: &[u8]) {
+        if let Some(generation) = generation(key, DROPPED_PREFIX) {
+            self.insert(generation);
+        }
+    }
+
+    /// Remove the keys of dropped generations whenever RocksDB compacts the
+    /// files holding them.
+    pub fn set_compaction_filter(&self, database_options: &mut Options) {
+        let dropped_namespaces = self.clone();
+        database_options.set_compaction_filter(
+            "kvstore.dropped_namespace",
+            move |_level: u32, key: &[u8], _value: &[u8]| match generation(key, NAMESPACE_PREFIX) {
+                Some(generation) if dropped_namespaces.contains(generation) => Decision::Remove,
+                _ => Decision::Keep,
+            },
+        );
+    }
+
+    /// Load the markers of the generations dropped before the database was
+    /// opened.
+    pub fn load(&self, database: &TransactionDB) -> Result<(), KvStoreError> {
+        // The markers have no model ID for the prefix extractor.
+        let mut read_options = ReadOptions::default();
+        read_options.set_total_order_seek(true);
+
+        for item in database.iterator_opt(
+            IteratorMode::From(DROPPED_PREFIX, rocksdb::Direction::Forward),
+            read_options,
+        ) {
+            let (key, _value) = item.map_err(KvStoreError::Iterator)?;
+            if !key.starts_with(DROPPED_PREFIX) {
+                break;
+            }
+
+            self.insert_marker(&key);
+        }
+
+        Ok(())
+    }
+}
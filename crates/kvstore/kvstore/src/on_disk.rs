@@ -1,14 +1,34 @@
 use std::{
+    cell::RefCell,
     fmt::Debug,
+    io::{Read, Write},
     mem::MaybeUninit,
+    panic::Location,
     path::Path,
     sync::{Arc, Once},
+    time::Duration,
 };
 
-use rocksdb::{Options, Transaction, TransactionDB, TransactionDBOptions};
+use rocksdb::{
+    Direction, Env, ErrorKind, IteratorMode, Options, ReadOptions, Transaction, TransactionDB,
+    TransactionDBOptions, TransactionOptions, WriteBatch, WriteBatchWithTransaction, WriteOptions,
+    DB,
+};
 use serde::{de::DeserializeOwned, ser::Serialize};
-
-use crate::data_type::{deserialize, serialize};
+use tokio::task::JoinHandle;
+
+use crate::{
+    commit_hook::{Commit, CommitHook, CommitRecord, CommitSink},
+    data_type::{
+        deserialize, model_id_prefix_length, serialize, serialize_prefix, serialize_with_field,
+    },
+    export::{Compression, ExportReader, ExportWriter},
+    lock_tracker::{HeldLock, LockTracker, TrackedLock},
+    namespace::{dropped_marker_key, namespaced_key, DroppedNamespaces},
+    tiering::{ColdDatabase, ColdTier},
+    type_tag::{TypeFingerprint, ValueCodec},
+    KvStoreOptions,
+};
 
 static mut KVSTORE: MaybeUninit<KvStore> = MaybeUninit::uninit();
 static INIT: Once = Once::new();
@@ -24,6 +44,11 @@ pub fn kvstore() -> Result<&'static KvStore, KvStoreError> {
 pub struct KvStoreBuilder {
     database_options: Options,
     transaction_database_options: TransactionDBOptions,
+    commit_hook: Option<CommitHook>,
+    codec: ValueCodec,
+    lock_ttl: Option<Duration>,
+    lock_tracker: Option<LockTracker>,
+    cold_tier: Option<ColdTier>,
 }
 
 impl Default for KvStoreBuilder {
@@ -34,6 +59,11 @@ impl Default for KvStoreBuilder {
         Self {
             database_options,
             transaction_database_options: TransactionDBOptions::default(),
+            commit_hook: None,
+            codec: ValueCodec::default(),
+            lock_ttl: None,
+            lock_tracker: None,
+            cold_tier: None,
         }
     }
 }
@@ -46,6 +76,14 @@ impl KvStoreBuilder {
         self
     }
 
+    /// Apply the block cache, bloom filter, prefix extractor, memtable,
+    /// write-ahead log and background job settings of `options`.
+    pub fn options(mut self, options: KvStoreOptions) -> Self {
+        options.apply(&mut self.database_options);
+
+        self
+    }
+
     /// https://docs.rs/rocksdb/0.22.0/rocksdb/struct.TransactionDBOptions.html#method.set_default_lock_timeout
     pub fn set_default_lock_timeout(mut self, default_lock_timeout: i64) -> Self {
         self.transaction_database_options
@@ -78,22 +116,206 @@ impl KvStoreBuilder {
         self
     }
 
+    /// Pass every commit to `sink` in commit order, e.g. to replicate the
+    /// database to a standby node that applies them with
+    /// [`KvStore::apply_commit()`].
+    ///
+    /// Commits are serialized to keep the order of the sink, and the sink is
+    /// called after the commit is durable. If the sink fails, the write
+    /// returns [`KvStoreError::CommitSink`] although it was committed, and
+    /// the standby has to be resynchronized, e.g. with [`KvStore::export()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::{Commit, KvStore, KvStoreBuilder};
+    ///
+    /// let (sender, receiver) = std::sync::mpsc::channel::<Commit>();
+    /// let database = KvStoreBuilder::default()
+    ///     .commit_hook(sender)
+    ///     .build_in_memory()
+    ///     .unwrap();
+    /// database.put(&"key", &"value").unwrap();
+    ///
+    /// let standby = KvStore::new_in_memory().unwrap();
+    /// standby.apply_commit(&receiver.recv().unwrap()).unwrap();
+    /// assert_eq!(standby.get::<_, String>(&"key").unwrap(), "value");
+    /// ```
+    pub fn commit_hook(mut self, sink: impl CommitSink) -> Self {
+        self.commit_hook = Some(CommitHook::new(sink));
+
+        self
+    }
+
+    /// Store every value with the [`TypeFingerprint`] of its type, so that
+    /// reading it as another type fails with [`KvStoreError::TypeMismatch`]
+    /// instead of silently decoding whatever fields happen to match, e.g.
+    /// when a key is reused by another model.
+    ///
+    /// The fingerprint adds the length of the type name and 12 bytes to
+    /// every value. Values written without strict mode fail to read with it,
+    /// and the other way round, so the database has to be exported and
+    /// imported to switch.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::{KvStoreBuilder, KvStoreError};
+    ///
+    /// let database = KvStoreBuilder::default()
+    ///     .strict_types(true)
+    ///     .build_in_memory()
+    ///     .unwrap();
+    /// database.put(&"height", &10u64).unwrap();
+    ///
+    /// assert!(matches!(
+    ///     database.get::<_, String>(&"height"),
+    ///     Err(KvStoreError::TypeMismatch { .. })
+    /// ));
+    /// ```
+    pub fn strict_types(mut self, strict_types: bool) -> Self {
+        self.codec.strict_types = strict_types;
+
+        self
+    }
+
+    /// Expire a [`Lock`] held for longer than `lock_ttl`, e.g. because it
+    /// was kept across an `await` or leaked. Once it has expired, the next
+    /// writer of the key takes it over instead of failing, and
+    /// [`Lock::update()`] on the expired lock discards its changes and
+    /// fails with [`KvStoreError::LockExpired`].
+    ///
+    /// Writers still wait for a held lock for at most the lock timeout,
+    /// 1 second by default, see [`KvStoreBuilder::set_txn_lock_timeout()`].
+    /// A writer that starts waiting more than the lock timeout before the
+    /// lock expires times out as without a TTL, so the TTL only frees keys
+    /// for writers that come after it.
+    ///
+    /// Without a TTL, a lock blocks other writers of its key until it is
+    /// updated or dropped. Locks of databases built with
+    /// [`KvStoreBuilder::build_in_memory()`] never expire.
+    pub fn lock_ttl(mut self, lock_ttl: Duration) -> Self {
+        self.lock_ttl = Some(lock_ttl);
+
+        self
+    }
+
+    /// Record the key and call site of every [`Lock`] until it is updated or
+    /// dropped, to find long-lived locks with [`KvStore::held_locks()`]. Adds
+    /// formatting the key and a mutex to every lock.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use radius_sequencer_sdk::kvstore::KvStoreBuilder;
+    ///
+    /// let database = KvStoreBuilder::default()
+    ///     .track_locks(true)
+    ///     .build_in_memory()
+    ///     .unwrap();
+    /// database.put(&"height", &10u64).unwrap();
+    ///
+    /// let height = database.get_mut::<_, u64>(&"height").unwrap();
+    /// for held_lock in database.held_locks(Duration::ZERO) {
+    ///     println!(
+    ///         "{} locked at {} for {:?}",
+    ///         held_lock.key, held_lock.location, held_lock.held_for
+    ///     );
+    /// }
+    /// ```
+    pub fn track_locks(mut self, track_locks: bool) -> Self {
+        self.lock_tracker = track_locks.then(LockTracker::default);
+
+        self
+    }
+
+    /// Keep the keys under the prefixes of `cold_tier` in a second database,
+    /// e.g. finalized blocks on slower storage, to bound the size of this
+    /// one. [`KvStore::move_to_cold_tier()`] moves them, usually from the
+    /// task of [`KvStore::spawn_tiering()`].
+    ///
+    /// `get()`, `get_mut()` and their variants, `apply()` and the reads of a
+    /// [`Session`] look up a key in the cold tier if it is not in this
+    /// database, and `delete()` and `delete_range()` delete it from both.
+    /// Range reads, counters, lists, exports, [`KvStore::prefix_stats()`] and
+    /// [`KvStore::count_prefix()`] only see this database, so the prefixes
+    /// should hold values that are no longer written or scanned once they
+    /// are moved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::{ColdTier, KvStoreBuilder};
+    ///
+    /// let database = KvStoreBuilder::default()
+    ///     .cold_tier(ColdTier::new("/mnt/hdd/database", &[("block",)]).unwrap())
+    ///     .build("database")
+    ///     .unwrap();
+    /// database.put(&("block", 1u64), &"0x01").unwrap();
+    ///
+    /// database.move_to_cold_tier().unwrap();
+    /// assert_eq!(database.get::<_, String>(&("block", 1u64)).unwrap(), "0x01");
+    /// ```
+    pub fn cold_tier(mut self, cold_tier: ColdTier) -> Self {
+        self.cold_tier = Some(cold_tier);
+
+        self
+    }
+
     pub fn build(self, path: impl AsRef<Path>) -> Result<KvStore, KvStoreError> {
+        self.open(path, None)
+    }
+
+    fn open(mut self, path: impl AsRef<Path>, env: Option<&Env>) -> Result<KvStore, KvStoreError> {
+        let dropped_namespaces = DroppedNamespaces::default();
+        dropped_namespaces.set_compaction_filter(&mut self.database_options);
+
         let transaction_database = TransactionDB::open(
             &self.database_options,
             &self.transaction_database_options,
             path,
         )
         .map_err(KvStoreError::Open)?;
+        dropped_namespaces.load(&transaction_database)?;
+        let cold_tier = self
+            .cold_tier
+            .map(|cold_tier| cold_tier.open(env))
+            .transpose()?;
 
         Ok(KvStore {
             database: Arc::new(transaction_database),
+            commit_hook: self.commit_hook.map(Arc::new),
+            dropped_namespaces,
+            codec: self.codec,
+            lock_ttl: self.lock_ttl,
+            lock_tracker: self.lock_tracker,
+            cold_tier: cold_tier.map(Arc::new),
         })
     }
+
+    /// Build a database that lives in memory and is discarded when the last
+    /// [`KvStore`] handle is dropped. Every call returns a separate database,
+    /// and the [`ColdTier`] is kept in memory as well.
+    pub fn build_in_memory(mut self) -> Result<KvStore, KvStoreError> {
+        let env = Env::mem_env().map_err(KvStoreError::Open)?;
+        self.database_options.set_env(&env);
+
+        // `TransactionDB::open()` creates the directory on disk even though
+        // the database files are kept in `env`.
+        self.open(std::env::temp_dir().join("kvstore-in-memory"), Some(&env))
+    }
 }
 
 pub struct KvStore {
     database: Arc<TransactionDB>,
+    commit_hook: Option<Arc<CommitHook>>,
+    dropped_namespaces: DroppedNamespaces,
+    codec: ValueCodec,
+    lock_ttl: Option<Duration>,
+    lock_tracker: Option<LockTracker>,
+    cold_tier: Option<Arc<ColdDatabase>>,
 }
 
 unsafe impl Send for KvStore {}
@@ -104,6 +326,12 @@ impl Clone for KvStore {
     fn clone(&self) -> Self {
         Self {
             database: self.database.clone(),
+            commit_hook: self.commit_hook.clone(),
+            dropped_namespaces: self.dropped_namespaces.clone(),
+            codec: self.codec,
+            lock_ttl: self.lock_ttl,
+            lock_tracker: self.lock_tracker.clone(),
+            cold_tier: self.cold_tier.clone(),
         }
     }
 }
@@ -116,6 +344,23 @@ impl KvStore {
         builder.build(path)
     }
 
+    /// Open an in-memory database with default options, e.g. for tests that
+    /// run in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::KvStore;
+    ///
+    /// let database = KvStore::new_in_memory().unwrap();
+    /// database.put(&"key", &"value").unwrap();
+    /// ```
+    pub fn new_in_memory() -> Result<Self, KvStoreError> {
+        let builder = KvStoreBuilder::default();
+
+        builder.build_in_memory()
+    }
+
     #[allow(static_mut_refs)]
     pub fn init(self) {
         unsafe {
@@ -131,14 +376,14 @@ impl KvStore {
         V: Debug + DeserializeOwned + Serialize,
     {
         let key_vec = serialize(key)?;
-        let value_vec = serialize(value)?;
+        let value_vec = self.codec.serialize(value)?;
 
         let transaction = self.database.transaction();
 
         transaction
             .put(key_vec, value_vec)
             .map_err(KvStoreError::Put)?;
-        transaction.commit().map_err(KvStoreError::CommitPut)?;
+        self.commit(transaction, KvStoreError::CommitPut)?;
 
         Ok(())
     }
@@ -152,12 +397,16 @@ impl KvStore {
 
         let value_slice = self
             .database
-            .get_pinned(key_vec)
-            .map_err(KvStoreError::Get)?
-            .ok_or(KvStoreError::NoneType)?;
-        let value: V = deserialize(value_slice)?;
+            .get_pinned(&key_vec)
+            .map_err(KvStoreError::Get)?;
 
-        Ok(value)
+        match value_slice {
+            Some(value_slice) => self.codec.deserialize(value_slice),
+            None => match self.get_cold(&key_vec)? {
+                Some(value_vec) => self.codec.deserialize(value_vec),
+                None => Err(KvStoreError::NoneType),
+            },
+        }
     }
 
     pub fn get_or<K, V, F>(&self, key: &K, function: F) -> Result<V, KvStoreError>
@@ -170,12 +419,15 @@ impl KvStore {
 
         let value_slice = self
             .database
-            .get_pinned(key_vec)
+            .get_pinned(&key_vec)
             .map_err(KvStoreError::Get)?;
 
         match value_slice {
-            Some(value_slice) => deserialize(value_slice).map_err(|error| error.into()),
-            None => Ok(function()),
+            Some(value_slice) => self.codec.deserialize(value_slice),
+            None => match self.get_cold(&key_vec)? {
+                Some(value_vec) => self.codec.deserialize(value_vec),
+                None => Ok(function()),
+            },
         }
     }
 
@@ -189,71 +441,80 @@ impl KvStore {
 
         let value_slice = self
             .database
-            .get_pinned(key_vec)
+            .get_pinned(&key_vec)
             .map_err(KvStoreError::Get)?;
 
         match value_slice {
-            Some(value_slice) => deserialize(value_slice).map_err(|error| error.into()),
-            None => Ok(V::default()),
+            Some(value_slice) => self.codec.deserialize(value_slice),
+            None => match self.get_cold(&key_vec)? {
+                Some(value_vec) => self.codec.deserialize(value_vec),
+                None => Ok(V::default()),
+            },
         }
     }
 
+    #[track_caller]
     pub fn get_mut<K, V>(&self, key: &K) -> Result<Lock<V>, KvStoreError>
     where
         K: Debug + Serialize,
         V: Debug + DeserializeOwned + Serialize,
     {
+        let location = Location::caller();
         let key_vec = serialize(key)?;
 
-        let transaction = self.database.transaction();
+        let transaction = self.lock_transaction();
 
         let value_vec = transaction
             .get_for_update(&key_vec, true)
-            .map_err(KvStoreError::GetMut)?
+            .map_err(KvStoreError::GetMut)?;
+        let value_vec = self
+            .or_cold(&key_vec, value_vec)?
             .ok_or(KvStoreError::NoneType)?;
-        let value: V = deserialize(value_vec)?;
-        let locked_value = Lock::new(Some(transaction), key_vec, value);
+        let value: V = self.codec.deserialize(value_vec)?;
+        let locked_value = self.lock(transaction, key, key_vec, value, location);
 
         Ok(locked_value)
     }
 
+    #[track_caller]
     pub fn get_mut_or<K, V, F>(&self, key: &K, function: F) -> Result<Lock<V>, KvStoreError>
     where
         K: Debug + Serialize,
         V: Debug + DeserializeOwned + Serialize,
         F: FnOnce() -> V,
     {
+        let location = Location::caller();
         let key_vec = serialize(key)?;
 
-        let transaction = self.database.transaction();
+        let transaction = self.lock_transaction();
 
         let value_vec = transaction
             .get_for_update(&key_vec, true)
             .map_err(KvStoreError::GetMut)?;
-        match value_vec {
+        match self.or_cold(&key_vec, value_vec)? {
             Some(value_vec) => {
-                let value: V = deserialize(value_vec)?;
-                let locked_value = Lock::new(Some(transaction), key_vec, value);
+                let value: V = self.codec.deserialize(value_vec)?;
+                let locked_value = self.lock(transaction, key, key_vec, value, location);
 
                 Ok(locked_value)
             }
             None => {
                 let value = function();
-                let value_vec = serialize(&value)?;
+                let value_vec = self.codec.serialize(&value)?;
 
                 transaction
                     .put(&key_vec, value_vec)
                     .map_err(KvStoreError::Put)?;
 
                 // After the `commit()`, other threads may access [FnOnce() -> V].
-                transaction.commit().map_err(KvStoreError::CommitPut)?;
+                self.commit(transaction, KvStoreError::CommitPut)?;
 
-                let transaction = self.database.transaction();
+                let transaction = self.lock_transaction();
 
                 transaction
                     .get_for_update(&key_vec, true)
                     .map_err(KvStoreError::GetMut)?;
-                let locked_value = Lock::new(Some(transaction), key_vec, value);
+                let locked_value = self.lock(transaction, key, key_vec, value, location);
 
                 Ok(locked_value)
             }
@@ -265,42 +526,44 @@ impl KvStore {
     /// returning value might not necessarily be [`V::default()`] because
     /// internally, the operation putting [`V::default()`] and getting
     /// [`Lock<V>`] are different transactions.
+    #[track_caller]
     pub fn get_mut_or_default<K, V>(&self, key: &K) -> Result<Lock<V>, KvStoreError>
     where
         K: Debug + Serialize,
         V: Debug + Default + DeserializeOwned + Serialize,
     {
+        let location = Location::caller();
         let key_vec = serialize(key)?;
 
-        let transaction = self.database.transaction();
+        let transaction = self.lock_transaction();
 
         let value_vec = transaction
             .get_for_update(&key_vec, true)
             .map_err(KvStoreError::GetMut)?;
-        match value_vec {
+        match self.or_cold(&key_vec, value_vec)? {
             Some(value_vec) => {
-                let value: V = deserialize(value_vec)?;
-                let locked_value = Lock::new(Some(transaction), key_vec, value);
+                let value: V = self.codec.deserialize(value_vec)?;
+                let locked_value = self.lock(transaction, key, key_vec, value, location);
 
                 Ok(locked_value)
             }
             None => {
                 let value = V::default();
-                let value_vec = serialize(&value)?;
+                let value_vec = self.codec.serialize(&value)?;
 
                 transaction
                     .put(&key_vec, value_vec)
                     .map_err(KvStoreError::Put)?;
 
                 // After the `commit()`, other threads may access [`V::default`].
-                transaction.commit().map_err(KvStoreError::CommitPut)?;
+                self.commit(transaction, KvStoreError::CommitPut)?;
 
-                let transaction = self.database.transaction();
+                let transaction = self.lock_transaction();
 
                 transaction
                     .get_for_update(&key_vec, true)
                     .map_err(KvStoreError::GetMut)?;
-                let locked_value = Lock::new(Some(transaction), key_vec, value);
+                let locked_value = self.lock(transaction, key, key_vec, value, location);
 
                 Ok(locked_value)
             }
@@ -333,138 +596,1465 @@ impl KvStore {
     /// let user: User = database.get(&"user").unwrap();
     /// println!("{:?}", user);
     /// ```
+    #[track_caller]
     pub fn apply<K, V, F>(&self, key: &K, operation: F) -> Result<(), KvStoreError>
     where
         K: Debug + Serialize,
         V: Debug + DeserializeOwned + Serialize,
         F: FnOnce(&mut Lock<V>),
     {
+        let location = Location::caller();
         let key_vec = serialize(key)?;
 
-        let transaction = self.database.transaction();
+        let transaction = self.lock_transaction();
 
         let value_vec = transaction
             .get_for_update(&key_vec, true)
-            .map_err(KvStoreError::GetMut)?
+            .map_err(KvStoreError::GetMut)?;
+        let value_vec = self
+            .or_cold(&key_vec, value_vec)?
             .ok_or(KvStoreError::NoneType)?;
-        let value: V = deserialize(value_vec)?;
+        let value: V = self.codec.deserialize(value_vec)?;
 
-        let mut locked_value = Lock::new(Some(transaction), key_vec, value);
+        let mut locked_value = self.lock(transaction, key, key_vec, value, location);
         operation(&mut locked_value);
         locked_value.update()?;
 
         Ok(())
     }
 
-    pub fn delete<K>(&self, key: &K) -> Result<(), KvStoreError>
+    /// Add `amount` to the counter at `key` and return the new count. A
+    /// missing counter starts from 0.
+    ///
+    /// The counter is read and written within one transaction, so concurrent
+    /// increments never hand out the same count. Use
+    /// [`Session::increment()`] to also write other keys atomically with it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::KvStore;
+    ///
+    /// let database = KvStore::open("database").unwrap();
+    ///
+    /// let order = database.increment(&("order", "rollup_id"), 1).unwrap();
+    /// println!("{}", order);
+    /// ```
+    pub fn increment<K>(&self, key: &K, amount: u64) -> Result<u64, KvStoreError>
     where
         K: Debug + Serialize,
     {
-        let key_vec = serialize(key)?;
+        self.update_counter(key, |count| {
+            count
+                .checked_add(amount)
+                .ok_or(KvStoreError::CounterOverflow)
+        })
+    }
+
+    /// Subtract `amount` from the counter at `key` and return the new count.
+    /// A missing counter starts from 0.
+    pub fn decrement<K>(&self, key: &K, amount: u64) -> Result<u64, KvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        self.update_counter(key, |count| {
+            count
+                .checked_sub(amount)
+                .ok_or(KvStoreError::CounterUnderflow)
+        })
+    }
 
+    fn update_counter<K, F>(&self, key: &K, operation: F) -> Result<u64, KvStoreError>
+    where
+        K: Debug + Serialize,
+        F: FnOnce(u64) -> Result<u64, KvStoreError>,
+    {
         let transaction = self.database.transaction();
 
-        transaction.delete(key_vec).map_err(KvStoreError::Delete)?;
-        transaction.commit().map_err(KvStoreError::CommitDelete)?;
+        let count = update_counter(&transaction, self.codec, key, operation)?;
+        self.commit(transaction, KvStoreError::CommitPut)?;
 
-        Ok(())
+        Ok(count)
     }
-}
 
-pub struct Lock<'db, V>
-where
-    V: Debug + Serialize + DeserializeOwned,
-{
-    transaction: Option<Transaction<'db, TransactionDB>>,
-    key_vec: Vec<u8>,
-    value: V,
-}
+    /// Append `value` to the back of the list at `key` and return the length
+    /// of the list.
+    ///
+    /// The list stores its bounds at `key` and each element at `key` with
+    /// its index appended, e.g. `("list", rollup_id, index)` for
+    /// `("list", rollup_id)`, so elements are never moved by
+    /// [`KvStore::list_pop()`] and share the prefix of `key`.
+    pub fn list_push<K, V>(&self, key: &K, value: &V) -> Result<u64, KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let key_vec = serialize(key)?;
 
-impl<V> std::ops::Deref for Lock<'_, V>
-where
-    V: Debug + Serialize + DeserializeOwned,
-{
-    type Target = V;
+        let transaction = self.database.transaction();
 
-    fn deref(&self) -> &Self::Target {
-        &self.value
-    }
-}
+        let (head, tail) = self.list_bounds(&transaction, &key_vec, true)?;
+        let element_key_vec = serialize_with_field(key, &tail)?;
+        let value_vec = self.codec.serialize(value)?;
 
-impl<V> std::ops::DerefMut for Lock<'_, V>
-where
-    V: Debug + Serialize + DeserializeOwned,
-{
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.value
+        transaction
+            .put(element_key_vec, value_vec)
+            .map_err(KvStoreError::Put)?;
+        transaction
+            .put(&key_vec, self.codec.serialize(&(head, tail + 1))?)
+            .map_err(KvStoreError::Put)?;
+        self.commit(transaction, KvStoreError::CommitPut)?;
+
+        Ok(tail + 1 - head)
     }
-}
 
-impl<'db, V> Lock<'db, V>
-where
-    V: Debug + Serialize + DeserializeOwned,
-{
-    pub fn new(
-        transaction: Option<Transaction<'db, TransactionDB>>,
-        key_vec: Vec<u8>,
-        value: V,
-    ) -> Self {
-        Self {
-            transaction,
-            key_vec,
-            value,
+    /// Remove and return the front of the list at `key`. Return
+    /// [`KvStoreError::NoneType`] if the list is empty.
+    pub fn list_pop<K, V>(&self, key: &K) -> Result<V, KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let key_vec = serialize(key)?;
+
+        let transaction = self.database.transaction();
+
+        let (head, tail) = self.list_bounds(&transaction, &key_vec, true)?;
+        if head == tail {
+            return Err(KvStoreError::NoneType);
+        }
+
+        let element_key_vec = serialize_with_field(key, &head)?;
+        let value_vec = transaction
+            .get(&element_key_vec)
+            .map_err(KvStoreError::Get)?
+            .ok_or(KvStoreError::NoneType)?;
+        let value: V = self.codec.deserialize(value_vec)?;
+
+        transaction
+            .delete(element_key_vec)
+            .map_err(KvStoreError::Delete)?;
+        match head + 1 == tail {
+            true => transaction.delete(&key_vec).map_err(KvStoreError::Delete)?,
+            false => transaction
+                .put(&key_vec, self.codec.serialize(&(head + 1, tail))?)
+                .map_err(KvStoreError::Put)?,
         }
+        self.commit(transaction, KvStoreError::CommitDelete)?;
+
+        Ok(value)
     }
 
-    pub fn update(mut self) -> Result<(), KvStoreError> {
-        if let Some(transaction) = self.transaction.take() {
-            let value_vec = serialize(&self.value)?;
+    /// Return the elements at positions `[start, end)` of the list at `key`,
+    /// where position 0 is the front. `end` is clamped to the list length.
+    pub fn list_range<K, V>(&self, key: &K, start: u64, end: u64) -> Result<Vec<V>, KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let key_vec = serialize(key)?;
 
-            transaction
-                .put(&self.key_vec, value_vec)
-                .map_err(KvStoreError::Update)?;
-            transaction.commit().map_err(KvStoreError::CommitUpdate)?;
+        // Hold a shared lock on the bounds so that the elements are not popped
+        // while reading.
+        let transaction = self.database.transaction();
+
+        let (head, tail) = self.list_bounds(&transaction, &key_vec, false)?;
+        let start = head.saturating_add(start).min(tail);
+        let end = head.saturating_add(end).min(tail);
+
+        let mut values = Vec::with_capacity(end.saturating_sub(start) as usize);
+        for index in start..end {
+            let element_key_vec = serialize_with_field(key, &index)?;
+            let value_vec = transaction
+                .get(element_key_vec)
+                .map_err(KvStoreError::Get)?
+                .ok_or(KvStoreError::NoneType)?;
+            values.push(self.codec.deserialize(value_vec)?);
         }
 
-        Ok(())
+        Ok(values)
     }
-}
 
-#[derive(Debug)]
-pub enum KvStoreError {
-    Open(rocksdb::Error),
-    DataType(crate::data_type::DataTypeError),
-    Get(rocksdb::Error),
-    GetMut(rocksdb::Error),
-    Put(rocksdb::Error),
-    CommitPut(rocksdb::Error),
-    Delete(rocksdb::Error),
-    CommitDelete(rocksdb::Error),
-    Update(rocksdb::Error),
-    CommitUpdate(rocksdb::Error),
-    NoneType,
-    Initialize,
-}
+    /// Return the length of the list at `key`.
+    pub fn list_len<K>(&self, key: &K) -> Result<u64, KvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        let key_vec = serialize(key)?;
 
-impl std::fmt::Display for KvStoreError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        let transaction = self.database.transaction();
+
+        let (head, tail) = self.list_bounds(&transaction, &key_vec, false)?;
+
+        Ok(tail - head)
     }
-}
 
-impl std::error::Error for KvStoreError {}
+    fn list_bounds(
+        &self,
+        transaction: &Transaction<TransactionDB>,
+        key_vec: &[u8],
+        exclusive: bool,
+    ) -> Result<(u64, u64), KvStoreError> {
+        let value_vec = transaction
+            .get_for_update(key_vec, exclusive)
+            .map_err(KvStoreError::GetMut)?;
 
-impl From<crate::data_type::DataTypeError> for KvStoreError {
-    fn from(value: crate::data_type::DataTypeError) -> Self {
-        Self::DataType(value)
+        match value_vec {
+            Some(value_vec) => self.codec.deserialize(value_vec),
+            None => Ok((0, 0)),
+        }
     }
-}
 
-impl KvStoreError {
-    pub fn is_none_type(&self) -> bool {
-        match self {
-            Self::NoneType => true,
-            _others => false,
+    /// Start a [`Session`] that reads and writes multiple keys within a
+    /// single transaction.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::KvStore;
+    ///
+    /// let database = KvStore::open("database").unwrap();
+    ///
+    /// let session = database.session();
+    /// let height: u64 = session.get_or(&"latest_height", || 0).unwrap();
+    /// session.put(&("block", height + 1), &"block").unwrap();
+    /// session.put(&"latest_height", &(height + 1)).unwrap();
+    /// session.commit().unwrap();
+    /// ```
+    pub fn session(&self) -> Session {
+        Session {
+            transaction: self.database.transaction(),
+            commit_hook: self.commit_hook.as_deref(),
+            codec: self.codec,
+            cold_tier: self.cold_tier.as_deref(),
+            cold_deletes: RefCell::default(),
+        }
+    }
+
+    /// Return whether a value is stored at `key`, in either tier, without
+    /// deserializing it.
+    pub fn contains_key<K>(&self, key: &K) -> Result<bool, KvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        let key_vec = serialize(key)?;
+
+        let value_slice = self
+            .database
+            .get_pinned(&key_vec)
+            .map_err(KvStoreError::Get)?;
+
+        match value_slice {
+            Some(_) => Ok(true),
+            None => Ok(self.get_cold(&key_vec)?.is_some()),
+        }
+    }
+
+    pub fn delete<K>(&self, key: &K) -> Result<(), KvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        let key_vec = serialize(key)?;
+
+        let transaction = self.database.transaction();
+
+        transaction.delete(&key_vec).map_err(KvStoreError::Delete)?;
+        // The transaction locks the key, so it cannot be moved to the cold
+        // tier before it is deleted from both.
+        if let Some(cold_tier) = &self.cold_tier {
+            cold_tier.delete([&key_vec])?;
+        }
+        self.commit(transaction, KvStoreError::CommitDelete)?;
+
+        Ok(())
+    }
+
+    /// Return the total size in bytes of the keys and values stored under
+    /// each prefix, in the same order as `prefixes`.
+    ///
+    /// A prefix is the leading fields of a key as a tuple, e.g. `("block",)`
+    /// for keys of the form `("block", height)`.
+    ///
+    /// The `rocksdb` crate does not expose `GetApproximateSizes()` for a
+    /// [`TransactionDB`], so, as for [`KvStore::prefix_stats()`], the keys
+    /// under each prefix are scanned without copying them. The sizes are not
+    /// taken from a snapshot and may be off by concurrent writes.
+    pub fn approximate_sizes<P>(&self, prefixes: &[P]) -> Result<Vec<u64>, KvStoreError>
+    where
+        P: Debug + Serialize,
+    {
+        let mut sizes = Vec::with_capacity(prefixes.len());
+        for prefix in prefixes {
+            let prefix_vec = serialize_prefix(prefix)?;
+
+            let mut size = 0;
+            let mut iterator = self
+                .database
+                .raw_iterator_opt(prefix_read_options(&prefix_vec));
+            iterator.seek(&prefix_vec);
+            while let (Some(key), Some(value)) = (iterator.key(), iterator.value()) {
+                if !key.starts_with(&prefix_vec) {
+                    break;
+                }
+
+                size += (key.len() + value.len()) as u64;
+                iterator.next();
+            }
+            iterator.status().map_err(KvStoreError::Iterator)?;
+            sizes.push(size);
+        }
+
+        Ok(sizes)
+    }
+
+    /// Return the number of keys and the bytes stored for the model `M`, e.g.
+    /// to find which models dominate storage before pruning.
+    ///
+    /// [`TransactionDB`] does not expose RocksDB properties or approximate
+    /// sizes, so the keys of the model are scanned without copying them. The
+    /// counts are not taken from a snapshot and may be off by concurrent
+    /// writes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::{KvStore, Model};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Deserialize, Serialize, Model)]
+    /// #[kvstore(key(height: u64))]
+    /// pub struct Block {
+    ///     hash: String,
+    /// }
+    ///
+    /// let database = KvStore::new_in_memory().unwrap();
+    /// database
+    ///     .put(
+    ///         &(Block::ID, 1),
+    ///         &Block {
+    ///             hash: "0x01".into(),
+    ///         },
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let stats = database.prefix_stats::<Block>().unwrap();
+    /// assert_eq!(stats.keys, 1);
+    /// ```
+    pub fn prefix_stats<M>(&self) -> Result<PrefixStats, KvStoreError>
+    where
+        M: crate::Model,
+    {
+        let prefix_vec = serialize_prefix(&(M::ID,))?;
+
+        let mut stats = PrefixStats::default();
+        let mut iterator = self
+            .database
+            .raw_iterator_opt(prefix_read_options(&prefix_vec));
+        iterator.seek(&prefix_vec);
+        while let (Some(key), Some(value)) = (iterator.key(), iterator.value()) {
+            if !key.starts_with(&prefix_vec) {
+                break;
+            }
+
+            stats.keys += 1;
+            stats.key_bytes += key.len() as u64;
+            stats.value_bytes += value.len() as u64;
+            iterator.next();
+        }
+        iterator.status().map_err(KvStoreError::Iterator)?;
+
+        Ok(stats)
+    }
+
+    /// Return every model derived with `#[derive(Model)]` in the binary with
+    /// its ID and key layout, e.g. for debug tooling listing what a node
+    /// stores. Pass the IDs to [`KvStore::prefix_stats()`] to see how much
+    /// each model takes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::{KvStore, Model, ModelKind};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Deserialize, Serialize, Model)]
+    /// #[kvstore(key(rollup_id: String, height: u64))]
+    /// pub struct Block {
+    ///     hash: String,
+    /// }
+    ///
+    /// let database = KvStore::new_in_memory().unwrap();
+    /// let block = database
+    ///     .models()
+    ///     .into_iter()
+    ///     .find(|model| model.id == Block::ID)
+    ///     .unwrap();
+    /// assert_eq!(block.kind, ModelKind::Model);
+    /// assert_eq!(block.key[1].name, "height");
+    /// assert_eq!(block.key[1].type_name, "u64");
+    /// ```
+    pub fn models(&self) -> Vec<crate::ModelInfo> {
+        crate::schema::models()
+    }
+
+    /// Return the number of keys under `prefix` without deserializing their
+    /// values, e.g. `("block", rollup_id)` for keys of the form
+    /// `("block", rollup_id, height)`.
+    pub fn count_prefix<P>(&self, prefix: &P) -> Result<u64, KvStoreError>
+    where
+        P: Debug + Serialize,
+    {
+        let prefix_vec = serialize_prefix(prefix)?;
+
+        let mut count = 0;
+        let mut iterator = self
+            .database
+            .raw_iterator_opt(prefix_read_options(&prefix_vec));
+        iterator.seek(&prefix_vec);
+        while let Some(key) = iterator.key() {
+            if !key.starts_with(&prefix_vec) {
+                break;
+            }
+
+            count += 1;
+            iterator.next();
+        }
+        iterator.status().map_err(KvStoreError::Iterator)?;
+
+        Ok(count)
+    }
+
+    /// Return the values of up to `limit` keys in `[start, end)`, in the
+    /// order of `direction`. Keys are compared as serialized bytes, so the
+    /// fields that vary within the range should be [`crate::OrderedKey`]s.
+    /// See [`crate::OrderedKey`] for an example.
+    pub fn get_range<K, V>(
+        &self,
+        start: &K,
+        end: &K,
+        limit: usize,
+        direction: crate::Direction,
+    ) -> Result<Vec<V>, KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let start_vec = serialize(start)?;
+        let end_vec = serialize(end)?;
+
+        let mut read_options = prefix_read_options(&start_vec);
+        let model_id = |key_vec: &[u8]| {
+            model_id_prefix_length(key_vec).map(|length| key_vec[..length].to_vec())
+        };
+        if model_id(&start_vec) != model_id(&end_vec) {
+            read_options.set_total_order_seek(true);
+        }
+        read_options.set_iterate_lower_bound(start_vec);
+        read_options.set_iterate_upper_bound(end_vec);
+
+        let mode = match direction {
+            crate::Direction::Forward => IteratorMode::Start,
+            crate::Direction::Reverse => IteratorMode::End,
+        };
+
+        let mut values = Vec::new();
+        for item in self.database.iterator_opt(mode, read_options).take(limit) {
+            let (_key, value) = item.map_err(KvStoreError::Iterator)?;
+            values.push(self.codec.deserialize(value)?);
+        }
+
+        Ok(values)
+    }
+
+    /// Delete every key under `prefix` that is less than `older_than_key` and
+    /// return the number of deleted keys. Every key under `prefix` must
+    /// decode as `K`.
+    ///
+    /// Keys are compared as `K` rather than as serialized bytes, which order
+    /// integers by their little-endian bytes or their decimal digits, so
+    /// every key under `prefix` is read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::KvStore;
+    ///
+    /// let database = KvStore::new_in_memory().unwrap();
+    /// for height in 0..20u64 {
+    ///     database.put(&("block", height), &height).unwrap();
+    /// }
+    ///
+    /// let deleted = database
+    ///     .delete_range(&("block",), &("block".to_owned(), 5u64))
+    ///     .unwrap();
+    /// assert_eq!(deleted, 5);
+    /// assert!(database.get::<_, u64>(&("block", 10u64)).is_ok());
+    /// ```
+    pub fn delete_range<P, K>(&self, prefix: &P, older_than_key: &K) -> Result<usize, KvStoreError>
+    where
+        P: Debug + Serialize,
+        K: Debug + DeserializeOwned + Serialize + Ord,
+    {
+        let prefix_vec = serialize_prefix(prefix)?;
+
+        let mut batch = WriteBatchWithTransaction::<true>::default();
+        for item in self.database.iterator_opt(
+            IteratorMode::From(&prefix_vec, Direction::Forward),
+            prefix_read_options(&prefix_vec),
+        ) {
+            let (key, _value) = item.map_err(KvStoreError::Iterator)?;
+            if !key.starts_with(&prefix_vec) {
+                break;
+            }
+
+            if deserialize::<K>(&key)? < *older_than_key {
+                batch.delete(key);
+            }
+        }
+
+        let mut deleted = batch.len();
+        self.write(batch, KvStoreError::DeleteRange)?;
+
+        if let Some(cold_tier) = &self.cold_tier {
+            let mut key_vecs = Vec::new();
+            for key_vec in cold_tier.keys(&prefix_vec)? {
+                if deserialize::<K>(&key_vec)? < *older_than_key {
+                    key_vecs.push(key_vec);
+                }
+            }
+            deleted += key_vecs.len();
+            cold_tier.delete(key_vecs)?;
+        }
+
+        Ok(deleted)
+    }
+
+    /// Return the keys of `generation`, e.g. the temporary data of a block
+    /// or an epoch, which [`KvStore::drop_namespace()`] deletes at once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::KvStore;
+    ///
+    /// let database = KvStore::new_in_memory().unwrap();
+    /// let block = database.namespace(10);
+    /// block.put(&("transaction", 0u64), &"0x01").unwrap();
+    ///
+    /// database.drop_namespace(10).unwrap();
+    /// assert!(block
+    ///     .get::<_, String>(&("transaction", 0u64))
+    ///     .unwrap_err()
+    ///     .is_none_type());
+    /// ```
+    pub fn namespace(&self, generation: u64) -> Namespace {
+        Namespace {
+            kvstore: self,
+            generation,
+        }
+    }
+
+    /// Delete every key of the namespace of `generation` by writing a single
+    /// marker, however many keys it holds. Reads of the namespace return
+    /// [`KvStoreError::NoneType`] and writes [`KvStoreError::NamespaceDropped`]
+    /// from then on.
+    ///
+    /// `TransactionDB` rejects RocksDB range deletions, so the keys are
+    /// removed by a compaction filter when RocksDB compacts the files holding
+    /// them, and disk space is reclaimed gradually. The marker is kept, so a
+    /// dropped generation cannot be used again.
+    pub fn drop_namespace(&self, generation: u64) -> Result<(), KvStoreError> {
+        let mut batch = WriteBatchWithTransaction::<true>::default();
+        batch.put(dropped_marker_key(generation), []);
+        self.write(batch, KvStoreError::DropNamespace)?;
+
+        self.dropped_namespaces.insert(generation);
+
+        Ok(())
+    }
+
+    /// Write every key under `prefix` and its value to `writer`, and return
+    /// the number of exported keys. All of them must decode as `K` and `V`.
+    ///
+    /// Keys and values are stored as JSON in a versioned, length-prefixed
+    /// format that [`KvStore::import()`] reads back, so the export can be
+    /// imported by a build with the other encoding feature or another
+    /// version of RocksDB.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::{Compression, KvStore};
+    ///
+    /// let database = KvStore::new_in_memory().unwrap();
+    /// for height in 0..10u64 {
+    ///     database.put(&("block", height), &height).unwrap();
+    /// }
+    ///
+    /// let mut export = Vec::new();
+    /// database
+    ///     .export::<_, (String, u64), u64>(&("block",), &mut export, Compression::Gzip)
+    ///     .unwrap();
+    ///
+    /// let other_database = KvStore::new_in_memory().unwrap();
+    /// let imported = other_database
+    ///     .import::<(String, u64), u64>(export.as_slice())
+    ///     .unwrap();
+    /// assert_eq!(imported, 10);
+    /// ```
+    pub fn export<P, K, V>(
+        &self,
+        prefix: &P,
+        writer: impl Write,
+        compression: Compression,
+    ) -> Result<usize, KvStoreError>
+    where
+        P: Debug + Serialize,
+        K: Debug + DeserializeOwned + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let prefix_vec = serialize_prefix(prefix)?;
+
+        let mut export_writer = ExportWriter::new(writer, compression)?;
+        let mut exported = 0;
+        for item in self.database.iterator_opt(
+            IteratorMode::From(&prefix_vec, Direction::Forward),
+            prefix_read_options(&prefix_vec),
+        ) {
+            let (key, value) = item.map_err(KvStoreError::Iterator)?;
+            if !key.starts_with(&prefix_vec) {
+                break;
+            }
+
+            let key: K = deserialize(key)?;
+            let value: V = self.codec.deserialize(value)?;
+            export_writer.write(&key, &value)?;
+            exported += 1;
+        }
+        export_writer.finish()?;
+
+        Ok(exported)
+    }
+
+    /// Write the keys and values of an export made with
+    /// [`KvStore::export()`] and return the number of imported keys.
+    ///
+    /// Keys are written in batches, so an import that fails halfway leaves
+    /// the keys of the previous batches in place.
+    pub fn import<K, V>(&self, reader: impl Read) -> Result<usize, KvStoreError>
+    where
+        K: Debug + DeserializeOwned + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        const BATCH_SIZE: usize = 1024;
+
+        let mut export_reader = ExportReader::new(reader)?;
+        let mut batch = WriteBatchWithTransaction::<true>::default();
+        let mut imported = 0;
+        while let Some((key, value)) = export_reader.read::<K, V>()? {
+            batch.put(serialize(&key)?, self.codec.serialize(&value)?);
+            imported += 1;
+
+            if batch.len() == BATCH_SIZE {
+                self.write(std::mem::take(&mut batch), KvStoreError::CommitImport)?;
+            }
+        }
+        self.write(batch, KvStoreError::CommitImport)?;
+
+        Ok(imported)
+    }
+
+    /// Compact the keys in `[start, end)` of the database at `path`. `None`
+    /// means the beginning or the end of the key space respectively.
+    ///
+    /// The `rocksdb` crate does not expose manual compaction for a
+    /// [`TransactionDB`], so the database is opened without transactions for
+    /// the duration of the compaction. RocksDB locks the database while it is
+    /// open, so this fails with [`KvStoreError::Open`] unless it is called
+    /// before [`KvStoreBuilder::build()`] or after every [`KvStore`] handle
+    /// for `path` has been dropped.
+    pub fn compact_range<S, E>(
+        path: impl AsRef<Path>,
+        start: Option<&S>,
+        end: Option<&E>,
+    ) -> Result<(), KvStoreError>
+    where
+        S: Debug + Serialize,
+        E: Debug + Serialize,
+    {
+        let start_vec = start.map(serialize_prefix).transpose()?;
+        let end_vec = end.map(serialize_prefix).transpose()?;
+
+        let database = DB::open(&Options::default(), path).map_err(KvStoreError::Open)?;
+        database.compact_range(start_vec, end_vec);
+
+        Ok(())
+    }
+
+    /// Write the records of a commit passed to the sink of
+    /// [`KvStoreBuilder::commit_hook()`] of another database, e.g. on a
+    /// standby node. The records are written atomically.
+    ///
+    /// Both databases must be built with the same encoding feature, since
+    /// records hold keys and values as stored.
+    pub fn apply_commit(&self, commit: &Commit) -> Result<(), KvStoreError> {
+        let mut batch = WriteBatchWithTransaction::<true>::default();
+        for record in &commit.records {
+            match record {
+                CommitRecord::Put { key, value } => batch.put(key, value),
+                CommitRecord::Delete { key } => batch.delete(key),
+            }
+        }
+        self.write(batch, KvStoreError::ApplyCommit)?;
+
+        for record in &commit.records {
+            if let CommitRecord::Put { key, .. } = record {
+                self.dropped_namespaces.insert_marker(key);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn commit(
+        &self,
+        transaction: Transaction<'_, TransactionDB>,
+        map_error: fn(rocksdb::Error) -> KvStoreError,
+    ) -> Result<(), KvStoreError> {
+        commit(self.commit_hook.as_deref(), transaction, map_error)
+    }
+
+    fn write(
+        &self,
+        batch: WriteBatchWithTransaction<true>,
+        map_error: fn(rocksdb::Error) -> KvStoreError,
+    ) -> Result<(), KvStoreError> {
+        match &self.commit_hook {
+            Some(commit_hook) => commit_hook.write(&self.database, batch, map_error),
+            None => self.database.write(batch).map_err(map_error),
+        }
+    }
+
+    /// A transaction that expires after [`KvStoreBuilder::lock_ttl()`].
+    fn lock_transaction(&self) -> Transaction<'_, TransactionDB> {
+        match self.lock_ttl {
+            Some(lock_ttl) => {
+                let mut transaction_options = TransactionOptions::default();
+                transaction_options.set_expiration(lock_ttl.as_millis() as i64);
+
+                self.database
+                    .transaction_opt(&WriteOptions::default(), &transaction_options)
+            }
+            None => self.database.transaction(),
+        }
+    }
+
+    fn lock<'db, K, V>(
+        &'db self,
+        transaction: Transaction<'db, TransactionDB>,
+        key: &K,
+        key_vec: Vec<u8>,
+        value: V,
+        location: &'static Location<'static>,
+    ) -> Lock<'db, V>
+    where
+        K: Debug,
+        V: Debug + Serialize + DeserializeOwned,
+    {
+        Lock {
+            transaction: Some(transaction),
+            commit_hook: self.commit_hook.as_deref(),
+            codec: self.codec,
+            key_vec,
+            value,
+            location,
+            _tracked_lock: self
+                .lock_tracker
+                .as_ref()
+                .map(|lock_tracker| lock_tracker.track(format!("{:?}", key), location)),
+        }
+    }
+
+    /// The locks held for at least `min_held_for`, the longest held first.
+    /// Always empty unless enabled with [`KvStoreBuilder::track_locks()`].
+    pub fn held_locks(&self, min_held_for: Duration) -> Vec<HeldLock> {
+        match &self.lock_tracker {
+            Some(lock_tracker) => lock_tracker.held_locks(min_held_for),
+            None => Vec::new(),
+        }
+    }
+
+    /// Move every key under the prefixes of [`KvStoreBuilder::cold_tier()`]
+    /// to the cold tier and return the number of moved keys. Does nothing
+    /// without a cold tier.
+    ///
+    /// Each batch of keys is locked, written to the cold tier and then
+    /// deleted from this database, so a key is readable from either at any
+    /// time. Keys held by a [`Lock`] or a [`Session`] are skipped until the
+    /// next call. The deletes are passed to the commit hook like any other
+    /// write, so a standby drops the moved keys as well and only has them
+    /// if it reads the same cold tier.
+    pub fn move_to_cold_tier(&self) -> Result<usize, KvStoreError> {
+        let cold_tier = match &self.cold_tier {
+            Some(cold_tier) => cold_tier,
+            None => return Ok(0),
+        };
+
+        let mut moved = 0;
+        for prefix_vec in cold_tier.prefixes() {
+            let mut start_vec = prefix_vec.clone();
+            loop {
+                let mut key_vecs = Vec::with_capacity(cold_tier.batch_size());
+                for item in self.database.iterator_opt(
+                    IteratorMode::From(&start_vec, Direction::Forward),
+                    prefix_read_options(prefix_vec),
+                ) {
+                    let (key, _value) = item.map_err(KvStoreError::Iterator)?;
+                    if !key.starts_with(prefix_vec) || key_vecs.len() == cold_tier.batch_size() {
+                        break;
+                    }
+
+                    key_vecs.push(key);
+                }
+
+                // Resume after the last key of the batch, so that skipped
+                // keys are not visited again.
+                start_vec = match key_vecs.last() {
+                    Some(key_vec) => [key_vec.as_ref(), &[0]].concat(),
+                    None => break,
+                };
+
+                let transaction = self.database.transaction();
+                let mut batch = WriteBatch::default();
+                for key_vec in &key_vecs {
+                    match transaction.get_for_update(key_vec, true) {
+                        Ok(Some(value_vec)) => {
+                            batch.put(key_vec, value_vec);
+                            transaction
+                                .delete(key_vec)
+                                .map_err(KvStoreError::MoveToColdTier)?;
+                        }
+                        Ok(None) => {}
+                        Err(error)
+                            if matches!(error.kind(), ErrorKind::TimedOut | ErrorKind::Busy) => {}
+                        Err(error) => return Err(KvStoreError::MoveToColdTier(error)),
+                    }
+                }
+
+                moved += batch.len();
+                cold_tier.write(batch)?;
+                self.commit(transaction, KvStoreError::MoveToColdTier)?;
+            }
+        }
+
+        Ok(moved)
+    }
+
+    /// Spawn a task on the current Tokio runtime that calls
+    /// [`KvStore::move_to_cold_tier()`] every `interval` on a blocking
+    /// thread. The task runs until the returned handle is aborted, or stops
+    /// with the first error, [`KvStoreError::TieringTask`] if a call
+    /// panicked.
+    pub fn spawn_tiering(&self, interval: Duration) -> JoinHandle<Result<(), KvStoreError>> {
+        let kvstore = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+
+                let kvstore = kvstore.clone();
+                tokio::task::spawn_blocking(move || kvstore.move_to_cold_tier())
+                    .await
+                    .map_err(KvStoreError::TieringTask)??;
+            }
+        })
+    }
+
+    fn get_cold(&self, key_vec: &[u8]) -> Result<Option<Vec<u8>>, KvStoreError> {
+        match &self.cold_tier {
+            Some(cold_tier) => cold_tier.get(key_vec),
+            None => Ok(None),
+        }
+    }
+
+    /// `value_vec` read from this database, or the value in the cold tier if
+    /// there is none.
+    fn or_cold(
+        &self,
+        key_vec: &[u8],
+        value_vec: Option<Vec<u8>>,
+    ) -> Result<Option<Vec<u8>>, KvStoreError> {
+        match value_vec {
+            Some(value_vec) => Ok(Some(value_vec)),
+            None => self.get_cold(key_vec),
+        }
+    }
+}
+
+/// Shared by [`KvStore::increment()`] and [`Session::increment()`], the
+/// caller commits `transaction`.
+fn update_counter<K, F>(
+    transaction: &Transaction<'_, TransactionDB>,
+    codec: ValueCodec,
+    key: &K,
+    operation: F,
+) -> Result<u64, KvStoreError>
+where
+    K: Debug + Serialize,
+    F: FnOnce(u64) -> Result<u64, KvStoreError>,
+{
+    let key_vec = serialize(key)?;
+
+    let value_vec = transaction
+        .get_for_update(&key_vec, true)
+        .map_err(KvStoreError::GetMut)?;
+    let count: u64 = match value_vec {
+        Some(value_vec) => codec.deserialize(value_vec)?,
+        None => 0,
+    };
+
+    let count = operation(count)?;
+    let value_vec = codec.serialize(&count)?;
+
+    transaction
+        .put(&key_vec, value_vec)
+        .map_err(KvStoreError::Put)?;
+
+    Ok(count)
+}
+
+fn commit(
+    commit_hook: Option<&CommitHook>,
+    transaction: Transaction<'_, TransactionDB>,
+    map_error: fn(rocksdb::Error) -> KvStoreError,
+) -> Result<(), KvStoreError> {
+    match commit_hook {
+        Some(commit_hook) => commit_hook.commit(transaction, map_error),
+        None => transaction.commit().map_err(map_error),
+    }
+}
+
+/// Iterating from `prefix_vec` only visits keys with its model ID when a
+/// prefix extractor is set, so fall back to a total order seek for prefixes
+/// shorter than a model ID.
+fn prefix_read_options(prefix_vec: &[u8]) -> ReadOptions {
+    let mut read_options = ReadOptions::default();
+    read_options.set_total_order_seek(model_id_prefix_length(prefix_vec).is_none());
+
+    read_options
+}
+
+/// Storage used by a model, see [`KvStore::prefix_stats()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrefixStats {
+    pub keys: u64,
+    pub key_bytes: u64,
+    pub value_bytes: u64,
+}
+
+impl PrefixStats {
+    pub fn total_bytes(&self) -> u64 {
+        self.key_bytes + self.value_bytes
+    }
+}
+
+/// A transaction spanning multiple [`KvStore`] operations.
+///
+/// Reads observe the writes made earlier in the same session, and every key
+/// read through the session is locked until [`Session::commit()`] or
+/// [`Session::rollback()`], so other writers cannot change it in between.
+/// Nothing is visible to other readers until the session is committed.
+/// Dropping a session without committing discards its writes.
+pub struct Session<'db> {
+    transaction: Transaction<'db, TransactionDB>,
+    commit_hook: Option<&'db CommitHook>,
+    codec: ValueCodec,
+    cold_tier: Option<&'db ColdDatabase>,
+    /// Deleted from the cold tier once the session is committed.
+    cold_deletes: RefCell<Vec<Vec<u8>>>,
+}
+
+impl Session<'_> {
+    pub fn put<K, V>(&self, key: &K, value: &V) -> Result<(), KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let key_vec = serialize(key)?;
+        let value_vec = self.codec.serialize(value)?;
+
+        self.transaction
+            .put(key_vec, value_vec)
+            .map_err(KvStoreError::Put)
+    }
+
+    pub fn get<K, V>(&self, key: &K) -> Result<V, KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let key_vec = serialize(key)?;
+
+        let value_vec = self
+            .transaction
+            .get_for_update(&key_vec, true)
+            .map_err(KvStoreError::GetMut)?;
+        let value_vec = self
+            .or_cold(&key_vec, value_vec)?
+            .ok_or(KvStoreError::NoneType)?;
+        let value: V = self.codec.deserialize(value_vec)?;
+
+        Ok(value)
+    }
+
+    pub fn get_or<K, V, F>(&self, key: &K, function: F) -> Result<V, KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+        F: FnOnce() -> V,
+    {
+        let key_vec = serialize(key)?;
+
+        let value_vec = self
+            .transaction
+            .get_for_update(&key_vec, true)
+            .map_err(KvStoreError::GetMut)?;
+
+        match self.or_cold(&key_vec, value_vec)? {
+            Some(value_vec) => self.codec.deserialize(value_vec),
+            None => Ok(function()),
+        }
+    }
+
+    pub fn delete<K>(&self, key: &K) -> Result<(), KvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        let key_vec = serialize(key)?;
+
+        self.transaction
+            .delete(&key_vec)
+            .map_err(KvStoreError::Delete)?;
+        if self
+            .cold_tier
+            .is_some_and(|cold_tier| cold_tier.is_tiered(&key_vec))
+        {
+            self.cold_deletes.borrow_mut().push(key_vec);
+        }
+
+        Ok(())
+    }
+
+    /// Add `amount` to the counter at `key` within the session, see
+    /// [`KvStore::increment()`]. The counter stays locked until the session
+    /// ends, so a rolled back session never skips a count.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use radius_sequencer_sdk::kvstore::KvStore;
+    ///
+    /// let database = KvStore::open("database").unwrap();
+    ///
+    /// let session = database.session();
+    /// let order = session.increment(&("order", "rollup_id"), 1).unwrap();
+    /// session
+    ///     .put(&("transaction", "rollup_id", order), &"transaction")
+    ///     .unwrap();
+    /// session.commit().unwrap();
+    /// ```
+    pub fn increment<K>(&self, key: &K, amount: u64) -> Result<u64, KvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        update_counter(&self.transaction, self.codec, key, |count| {
+            count
+                .checked_add(amount)
+                .ok_or(KvStoreError::CounterOverflow)
+        })
+    }
+
+    /// Subtract `amount` from the counter at `key` within the session, see
+    /// [`KvStore::decrement()`].
+    pub fn decrement<K>(&self, key: &K, amount: u64) -> Result<u64, KvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        update_counter(&self.transaction, self.codec, key, |count| {
+            count
+                .checked_sub(amount)
+                .ok_or(KvStoreError::CounterUnderflow)
+        })
+    }
+
+    pub fn commit(self) -> Result<(), KvStoreError> {
+        commit(
+            self.commit_hook,
+            self.transaction,
+            KvStoreError::CommitSession,
+        )?;
+
+        match self.cold_tier {
+            Some(cold_tier) => cold_tier.delete(self.cold_deletes.into_inner()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn rollback(self) -> Result<(), KvStoreError> {
+        self.transaction.rollback().map_err(KvStoreError::Rollback)
+    }
+
+    /// See [`KvStore::or_cold()`].
+    fn or_cold(
+        &self,
+        key_vec: &[u8],
+        value_vec: Option<Vec<u8>>,
+    ) -> Result<Option<Vec<u8>>, KvStoreError> {
+        match (value_vec, self.cold_tier) {
+            (Some(value_vec), _) => Ok(Some(value_vec)),
+            (None, Some(cold_tier)) => cold_tier.get(key_vec),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+/// The keys of a generation, see [`KvStore::namespace()`].
+pub struct Namespace<'db> {
+    kvstore: &'db KvStore,
+    generation: u64,
+}
+
+impl Namespace<'_> {
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn put<K, V>(&self, key: &K, value: &V) -> Result<(), KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let key_vec = self.key_vec(key)?;
+        let value_vec = self.kvstore.codec.serialize(value)?;
+        if self.is_dropped() {
+            return Err(KvStoreError::NamespaceDropped(self.generation));
+        }
+
+        let transaction = self.kvstore.database.transaction();
+
+        transaction
+            .put(key_vec, value_vec)
+            .map_err(KvStoreError::Put)?;
+        self.kvstore.commit(transaction, KvStoreError::CommitPut)?;
+
+        Ok(())
+    }
+
+    pub fn get<K, V>(&self, key: &K) -> Result<V, KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        self.get_or_none(key)?.ok_or(KvStoreError::NoneType)
+    }
+
+    pub fn get_or<K, V, F>(&self, key: &K, function: F) -> Result<V, KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+        F: FnOnce() -> V,
+    {
+        Ok(self.get_or_none(key)?.unwrap_or_else(function))
+    }
+
+    pub fn delete<K>(&self, key: &K) -> Result<(), KvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        let key_vec = self.key_vec(key)?;
+
+        let transaction = self.kvstore.database.transaction();
+
+        transaction.delete(key_vec).map_err(KvStoreError::Delete)?;
+        self.kvstore
+            .commit(transaction, KvStoreError::CommitDelete)?;
+
+        Ok(())
+    }
+
+    fn get_or_none<K, V>(&self, key: &K) -> Result<Option<V>, KvStoreError>
+    where
+        K: Debug + Serialize,
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let key_vec = self.key_vec(key)?;
+
+        let value_slice = self
+            .kvstore
+            .database
+            .get_pinned(key_vec)
+            .map_err(KvStoreError::Get)?;
+
+        // Keys of a dropped generation stay on disk until compaction.
+        match value_slice {
+            Some(value_slice) if !self.is_dropped() => {
+                Ok(Some(self.kvstore.codec.deserialize(value_slice)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn key_vec<K>(&self, key: &K) -> Result<Vec<u8>, KvStoreError>
+    where
+        K: Debug + Serialize,
+    {
+        Ok(namespaced_key(self.generation, &serialize(key)?))
+    }
+
+    fn is_dropped(&self) -> bool {
+        self.kvstore.dropped_namespaces.contains(self.generation)
+    }
+}
+
+pub struct Lock<'db, V>
+where
+    V: Debug + Serialize + DeserializeOwned,
+{
+    transaction: Option<Transaction<'db, TransactionDB>>,
+    commit_hook: Option<&'db CommitHook>,
+    codec: ValueCodec,
+    key_vec: Vec<u8>,
+    value: V,
+    location: &'static Location<'static>,
+    _tracked_lock: Option<TrackedLock>,
+}
+
+impl<V> std::ops::Deref for Lock<'_, V>
+where
+    V: Debug + Serialize + DeserializeOwned,
+{
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<V> std::ops::DerefMut for Lock<'_, V>
+where
+    V: Debug + Serialize + DeserializeOwned,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<'db, V> Lock<'db, V>
+where
+    V: Debug + Serialize + DeserializeOwned,
+{
+    #[track_caller]
+    pub fn new(
+        transaction: Option<Transaction<'db, TransactionDB>>,
+        key_vec: Vec<u8>,
+        value: V,
+    ) -> Self {
+        Self {
+            transaction,
+            commit_hook: None,
+            codec: ValueCodec::default(),
+            key_vec,
+            value,
+            location: Location::caller(),
+            _tracked_lock: None,
+        }
+    }
+
+    pub fn update(mut self) -> Result<(), KvStoreError> {
+        if let Some(transaction) = self.transaction.take() {
+            let value_vec = self.codec.serialize(&self.value)?;
+
+            transaction
+                .put(&self.key_vec, value_vec)
+                .map_err(|error| self.map_expired(error, KvStoreError::Update))?;
+            commit(self.commit_hook, transaction, KvStoreError::CommitUpdate).map_err(|error| {
+                match error {
+                    KvStoreError::CommitUpdate(error) => {
+                        self.map_expired(error, KvStoreError::CommitUpdate)
+                    }
+                    error => error,
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Where the lock was taken, e.g. the call to [`KvStore::get_mut()`].
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    fn map_expired(
+        &self,
+        error: rocksdb::Error,
+        map_error: fn(rocksdb::Error) -> KvStoreError,
+    ) -> KvStoreError {
+        match error.kind() {
+            ErrorKind::Expired => KvStoreError::LockExpired(self.location),
+            _others => map_error(error),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum KvStoreError {
+    Open(rocksdb::Error),
+    DataType(crate::data_type::DataTypeError),
+    Get(rocksdb::Error),
+    GetMut(rocksdb::Error),
+    Put(rocksdb::Error),
+    CommitPut(rocksdb::Error),
+    Delete(rocksdb::Error),
+    CommitDelete(rocksdb::Error),
+    Iterator(rocksdb::Error),
+    DeleteRange(rocksdb::Error),
+    Update(rocksdb::Error),
+    CommitUpdate(rocksdb::Error),
+    CommitSession(rocksdb::Error),
+    Rollback(rocksdb::Error),
+    Export(std::io::Error),
+    Import(std::io::Error),
+    CommitImport(rocksdb::Error),
+    ApplyCommit(rocksdb::Error),
+    DropNamespace(rocksdb::Error),
+    NamespaceDropped(u64),
+    CommitSink(crate::CommitSinkError),
+    ReadCommitLog(std::io::Error),
+    TruncatedCommitLog,
+    InvalidCommitOp(u8),
+    EncodeRecord(serde_json::Error),
+    DecodeRecord(serde_json::Error),
+    InvalidExportHeader,
+    UnsupportedExportVersion(u8),
+    UnsupportedCompression(u8),
+    TruncatedExport,
+    RecordTooLarge,
+    /// The value was written as another type, or without a type
+    /// fingerprint if `found` is `None`, see
+    /// [`KvStoreBuilder::strict_types()`].
+    TypeMismatch {
+        expected: TypeFingerprint,
+        found: Option<TypeFingerprint>,
+    },
+    CounterOverflow,
+    CounterUnderflow,
+    /// A [`Lock`] taken at this location outlived
+    /// [`KvStoreBuilder::lock_ttl()`] and its changes were discarded.
+    LockExpired(&'static Location<'static>),
+    ColdTier(rocksdb::Error),
+    MoveToColdTier(rocksdb::Error),
+    TieringTask(tokio::task::JoinError),
+    NoneType,
+    Initialize,
+}
+
+impl std::fmt::Display for KvStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for KvStoreError {}
+
+impl From<crate::data_type::DataTypeError> for KvStoreError {
+    fn from(value: crate::data_type::DataTypeError) -> Self {
+        Self::DataType(value)
+    }
+}
+
+impl KvStoreError {
+    pub fn is_none_type(&self) -> bool {
+        match self {
+            Self::NoneType => true,
+            _others => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiered_database() -> KvStore {
+        KvStoreBuilder::default()
+            .cold_tier(ColdTier::new("cold", &[("block",)]).unwrap())
+            .build_in_memory()
+            .unwrap()
+    }
+
+    fn is_hot(database: &KvStore, key: &(&str, u64)) -> bool {
+        let key_vec = serialize(key).unwrap();
+
+        database.database.get(key_vec).unwrap().is_some()
+    }
+
+    fn is_cold(database: &KvStore, key: &(&str, u64)) -> bool {
+        let key_vec = serialize(key).unwrap();
+        let cold_tier = database.cold_tier.as_ref().unwrap();
+
+        cold_tier.get(&key_vec).unwrap().is_some()
+    }
+
+    #[test]
+    fn test_cold_tier_get() {
+        let database = tiered_database();
+        database.put(&("block", 1u64), &"0x01").unwrap();
+        database.put(&("state", 1u64), &"0x02").unwrap();
+
+        assert_eq!(database.move_to_cold_tier().unwrap(), 1);
+        assert!(!is_hot(&database, &("block", 1)));
+        assert!(is_cold(&database, &("block", 1)));
+        assert!(is_hot(&database, &("state", 1)));
+
+        assert_eq!(database.get::<_, String>(&("block", 1u64)).unwrap(), "0x01");
+        assert!(database.contains_key(&("block", 1u64)).unwrap());
+        assert_eq!(database.move_to_cold_tier().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_cold_tier_overwrite() {
+        let database = tiered_database();
+        database.put(&("block", 1u64), &"0x01").unwrap();
+        database.move_to_cold_tier().unwrap();
+
+        // The hot copy is read first until it is moved over the cold one.
+        database.put(&("block", 1u64), &"0x02").unwrap();
+        assert_eq!(database.get::<_, String>(&("block", 1u64)).unwrap(), "0x02");
+
+        assert_eq!(database.move_to_cold_tier().unwrap(), 1);
+        assert!(!is_hot(&database, &("block", 1)));
+        assert_eq!(database.get::<_, String>(&("block", 1u64)).unwrap(), "0x02");
+    }
+
+    #[test]
+    fn test_cold_tier_delete() {
+        let database = tiered_database();
+        database.put(&("block", 1u64), &"0x01").unwrap();
+        database.put(&("block", 2u64), &"0x02").unwrap();
+        database.move_to_cold_tier().unwrap();
+        database.put(&("block", 2u64), &"0x03").unwrap();
+
+        database.delete(&("block", 1u64)).unwrap();
+        database.delete(&("block", 2u64)).unwrap();
+
+        for key in [("block", 1), ("block", 2)] {
+            assert!(!is_hot(&database, &key));
+            assert!(!is_cold(&database, &key));
+            assert!(database.get::<_, String>(&key).unwrap_err().is_none_type());
         }
     }
 }
@@ -0,0 +1,190 @@
+use rocksdb::{BlockBasedOptions, Cache, Options, SliceTransform};
+
+use crate::data_type::model_id_prefix_length;
+
+/// Read and write tuning applied through
+/// [`crate::KvStoreBuilder::options()`].
+///
+/// Start from a preset and adjust individual settings. The write settings
+/// keep the RocksDB defaults unless set:
+///
+/// ```rust
+/// use radius_sequencer_sdk::kvstore::{KvStoreBuilder, KvStoreOptions};
+///
+/// let database = KvStoreBuilder::default()
+///     .options(
+///         KvStoreOptions::prefix_scan()
+///             .block_cache_size(256 * 1024 * 1024)
+///             .write_buffer_size(128 * 1024 * 1024)
+///             .max_write_buffer_number(4)
+///             .max_background_jobs(8),
+///     )
+///     .build("database")
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct KvStoreOptions {
+    block_cache_size: usize,
+    bloom_filter_bits_per_key: Option<f64>,
+    pin_l0_filter_and_index_blocks: bool,
+    prefix_extractor: bool,
+    write_buffer_size: Option<usize>,
+    max_write_buffer_number: Option<i32>,
+    max_total_wal_size: Option<u64>,
+    max_background_jobs: Option<i32>,
+    bulk_load_mode: bool,
+}
+
+impl KvStoreOptions {
+    const DEFAULT_BLOCK_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+    /// For workloads dominated by `get()` on full keys: a bloom filter over
+    /// whole keys and index and filter blocks kept in the block cache.
+    pub fn point_lookup() -> Self {
+        Self {
+            block_cache_size: Self::DEFAULT_BLOCK_CACHE_SIZE,
+            bloom_filter_bits_per_key: Some(10.0),
+            pin_l0_filter_and_index_blocks: true,
+            prefix_extractor: false,
+            write_buffer_size: None,
+            max_write_buffer_number: None,
+            max_total_wal_size: None,
+            max_background_jobs: None,
+            bulk_load_mode: false,
+        }
+    }
+
+    /// For workloads that also iterate over the keys of a model, such as
+    /// [`crate::KvStore::approximate_sizes()`] and
+    /// [`crate::KvStore::delete_range()`]: same as
+    /// [`KvStoreOptions::point_lookup()`] plus bloom filters over the model
+    /// ID, the first field of every key.
+    pub fn prefix_scan() -> Self {
+        Self {
+            prefix_extractor: true,
+            ..Self::point_lookup()
+        }
+    }
+
+    /// Size in bytes of the LRU cache for uncompressed blocks.
+    pub fn block_cache_size(mut self, block_cache_size: usize) -> Self {
+        self.block_cache_size = block_cache_size;
+
+        self
+    }
+
+    /// `None` disables bloom filters.
+    pub fn bloom_filter_bits_per_key(mut self, bloom_filter_bits_per_key: Option<f64>) -> Self {
+        self.bloom_filter_bits_per_key = bloom_filter_bits_per_key;
+
+        self
+    }
+
+    /// Keep the index and filter blocks of level-0 files in the block cache
+    /// so that lookups on recently flushed data never read them from disk.
+    pub fn pin_l0_filter_and_index_blocks(mut self, pin_l0_filter_and_index_blocks: bool) -> Self {
+        self.pin_l0_filter_and_index_blocks = pin_l0_filter_and_index_blocks;
+
+        self
+    }
+
+    /// Extract the model ID of every key as its prefix for bloom filters and
+    /// prefix seeks.
+    pub fn prefix_extractor(mut self, prefix_extractor: bool) -> Self {
+        self.prefix_extractor = prefix_extractor;
+
+        self
+    }
+
+    /// Size in bytes of a memtable. Larger memtables absorb longer write
+    /// bursts before a flush, at the cost of memory and recovery time.
+    pub fn write_buffer_size(mut self, write_buffer_size: usize) -> Self {
+        self.write_buffer_size = Some(write_buffer_size);
+
+        self
+    }
+
+    /// The number of memtables kept in memory, including the ones being
+    /// flushed. Writes stall when all of them are full.
+    pub fn max_write_buffer_number(mut self, max_write_buffer_number: i32) -> Self {
+        self.max_write_buffer_number = Some(max_write_buffer_number);
+
+        self
+    }
+
+    /// Size in bytes of the write-ahead log after which the memtables it
+    /// covers are flushed, so that the log cannot grow without bound.
+    pub fn max_total_wal_size(mut self, max_total_wal_size: u64) -> Self {
+        self.max_total_wal_size = Some(max_total_wal_size);
+
+        self
+    }
+
+    /// The number of background threads for flushes and compactions.
+    pub fn max_background_jobs(mut self, max_background_jobs: i32) -> Self {
+        self.max_background_jobs = Some(max_background_jobs);
+
+        self
+    }
+
+    /// Disable automatic compaction and the write stalls it causes, e.g.
+    /// while a node syncs historical rollup data. Reads get slower as
+    /// level-0 files pile up, so once the sync is done, drop the
+    /// [`crate::KvStore`], call [`crate::KvStore::compact_range()`] over the
+    /// whole database and reopen it without bulk load mode. The other write
+    /// settings override the ones chosen by this mode.
+    pub fn bulk_load_mode(mut self, bulk_load_mode: bool) -> Self {
+        self.bulk_load_mode = bulk_load_mode;
+
+        self
+    }
+
+    pub(crate) fn apply(&self, database_options: &mut Options) {
+        let mut table_options = BlockBasedOptions::default();
+        table_options.set_block_cache(&Cache::new_lru_cache(self.block_cache_size));
+        if let Some(bloom_filter_bits_per_key) = self.bloom_filter_bits_per_key {
+            table_options.set_bloom_filter(bloom_filter_bits_per_key, false);
+        }
+        if self.pin_l0_filter_and_index_blocks {
+            table_options.set_cache_index_and_filter_blocks(true);
+            table_options.set_pin_l0_filter_and_index_blocks_in_cache(true);
+        }
+        database_options.set_block_based_table_factory(&table_options);
+
+        if self.prefix_extractor {
+            database_options.set_prefix_extractor(SliceTransform::create(
+                "kvstore.model_id",
+                model_id_prefix,
+                Some(is_model_id_prefixed),
+            ));
+            database_options.set_memtable_prefix_bloom_ratio(0.1);
+        }
+
+        if self.bulk_load_mode {
+            database_options.prepare_for_bulk_load();
+        }
+        if let Some(write_buffer_size) = self.write_buffer_size {
+            database_options.set_write_buffer_size(write_buffer_size);
+        }
+        if let Some(max_write_buffer_number) = self.max_write_buffer_number {
+            database_options.set_max_write_buffer_number(max_write_buffer_number);
+        }
+        if let Some(max_total_wal_size) = self.max_total_wal_size {
+            database_options.set_max_total_wal_size(max_total_wal_size);
+        }
+        if let Some(max_background_jobs) = self.max_background_jobs {
+            database_options.set_max_background_jobs(max_background_jobs);
+        }
+    }
+}
+
+fn model_id_prefix(key: &[u8]) -> &[u8] {
+    match model_id_prefix_length(key) {
+        Some(length) => &key[..length],
+        None => key,
+    }
+}
+
+fn is_model_id_prefixed(key: &[u8]) -> bool {
+    model_id_prefix_length(key).is_some()
+}
@@ -0,0 +1,130 @@
+use serde::{ser::SerializeTuple, Serialize, Serializer};
+
+/// Iteration order of [`crate::KvStore::get_range()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// From the smallest key up.
+    Forward,
+    /// From the largest key down, e.g. for the latest blocks first.
+    Reverse,
+}
+
+/// A key field whose serialized form sorts in the same order as its values,
+/// so that [`crate::KvStore::get_range()`] returns keys in value order.
+///
+/// Integers serialized as is do not sort numerically in either data type:
+/// JSON writes `10` before `9` and bincode writes them little-endian.
+/// [`OrderedKey`] encodes integers big-endian with the sign bit flipped and
+/// strings prefixed with their length, so the encoded fields never run into
+/// each other.
+///
+/// # Examples
+///
+/// ```rust
+/// use radius_sequencer_sdk::kvstore::{Direction, KvStore, OrderedKey};
+///
+/// let database = KvStore::open("database").unwrap();
+/// for height in 0..100u64 {
+///     let key = ("block", "rollup_id", OrderedKey::new().u64(height));
+///     database.put(&key, &height).unwrap();
+/// }
+///
+/// // The latest 10 blocks, starting with block 99.
+/// let blocks: Vec<u64> = database
+///     .get_range(
+///         &("block", "rollup_id", OrderedKey::new().u64(0)),
+///         &("block", "rollup_id", OrderedKey::new().u64(u64::MAX)),
+///         10,
+///         Direction::Reverse,
+///     )
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderedKey(Vec<u8>);
+
+impl OrderedKey {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn u8(mut self, value: u8) -> Self {
+        self.0.push(value);
+
+        self
+    }
+
+    pub fn u16(mut self, value: u16) -> Self {
+        self.0.extend_from_slice(&value.to_be_bytes());
+
+        self
+    }
+
+    pub fn u32(mut self, value: u32) -> Self {
+        self.0.extend_from_slice(&value.to_be_bytes());
+
+        self
+    }
+
+    pub fn u64(mut self, value: u64) -> Self {
+        self.0.extend_from_slice(&value.to_be_bytes());
+
+        self
+    }
+
+    pub fn u128(mut self, value: u128) -> Self {
+        self.0.extend_from_slice(&value.to_be_bytes());
+
+        self
+    }
+
+    /// Flipping the sign bit sorts negative values before positive ones.
+    pub fn i32(self, value: i32) -> Self {
+        self.u32((value as u32) ^ (1 << 31))
+    }
+
+    pub fn i64(self, value: i64) -> Self {
+        self.u64((value as u64) ^ (1 << 63))
+    }
+
+    /// Strings are prefixed with their length as a `u32`, so shorter strings
+    /// sort first and strings of the same length sort by their bytes.
+    pub fn str(mut self, value: impl AsRef<str>) -> Self {
+        let value = value.as_ref().as_bytes();
+        self.0
+            .extend_from_slice(&(value.len() as u32).to_be_bytes());
+        self.0.extend_from_slice(value);
+
+        self
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Human-readable formats such as JSON get a hex string, which sorts like the
+/// bytes. Binary formats get the bytes as a tuple, which, unlike a byte
+/// array, is not prefixed with its length.
+impl Serialize for OrderedKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let mut hex = String::with_capacity(self.0.len() * 2);
+            for byte in &self.0 {
+                hex.push(char::from_digit((byte >> 4) as u32, 16).unwrap());
+                hex.push(char::from_digit((byte & 0x0f) as u32, 16).unwrap());
+            }
+
+            serializer.serialize_str(&hex)
+        } else {
+            let mut tuple = serializer.serialize_tuple(self.0.len())?;
+            for byte in &self.0 {
+                tuple.serialize_element(byte)?;
+            }
+
+            tuple.end()
+        }
+    }
+}
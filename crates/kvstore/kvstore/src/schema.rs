@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+/// What `#[derive(Model)]` stores under the key of a model, see
+/// [`ModelInfo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelKind {
+    /// The model as a whole.
+    Model,
+    /// A `u64` counter.
+    Counter,
+    /// A list of models.
+    List,
+}
+
+/// A field of the key of a model, as declared in `#[kvstore(key(..))]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct ModelKey {
+    pub name: &'static str,
+    pub type_name: &'static str,
+}
+
+/// A model derived with `#[derive(Model)]` in the binary, listed by
+/// [`crate::KvStore::models()`].
+///
+/// Every key of the model is its [`ModelInfo::id`] followed by the
+/// [`ModelInfo::key`] fields in order, e.g. `("Block", height)` for
+/// `#[kvstore(key(height: u64))]`. The elements of a [`ModelKind::List`]
+/// have the index of the element appended, e.g. `("Block", height, index)`.
+/// Two models with the same ID share their keys, so an ID listed more than
+/// once is a collision.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct ModelInfo {
+    /// The `ID` of [`crate::Model`].
+    pub id: &'static str,
+    /// The path of the type in the crate it is defined in.
+    pub type_name: &'static str,
+    pub kind: ModelKind,
+    pub key: &'static [ModelKey],
+}
+
+inventory::collect!(ModelInfo);
+
+/// Every model linked into the binary, sorted by ID. Models register
+/// themselves before `main` through `inventory`, so the list does not depend
+/// on which models have been used.
+pub(crate) fn models() -> Vec<ModelInfo> {
+    let mut models: Vec<ModelInfo> = inventory::iter::<ModelInfo>().copied().collect();
+    models.sort_by(|a, b| a.id.cmp(b.id).then(a.type_name.cmp(b.type_name)));
+
+    models
+}
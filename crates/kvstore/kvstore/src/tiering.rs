@@ -0,0 +1,165 @@
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
+
+use rocksdb::{Direction, Env, IteratorMode, Options, WriteBatch, WriteOptions, DB};
+use serde::ser::Serialize;
+
+use crate::{data_type::serialize_prefix, KvStoreError, KvStoreOptions};
+
+/// A second database, usually on slower storage, that keeps the keys under
+/// `prefixes` once [`crate::KvStore::move_to_cold_tier()`] moved them there,
+/// see [`crate::KvStoreBuilder::cold_tier()`].
+///
+/// A prefix is the leading fields of a key as a tuple, e.g. `("block",)` for
+/// keys of the form `("block", height)`.
+#[derive(Clone, Debug)]
+pub struct ColdTier {
+    path: PathBuf,
+    prefixes: Vec<Vec<u8>>,
+    options: Option<KvStoreOptions>,
+    batch_size: usize,
+}
+
+impl ColdTier {
+    const DEFAULT_BATCH_SIZE: usize = 1000;
+
+    pub fn new<P>(path: impl AsRef<Path>, prefixes: &[P]) -> Result<Self, KvStoreError>
+    where
+        P: Debug + Serialize,
+    {
+        let prefixes = prefixes
+            .iter()
+            .map(serialize_prefix)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            path: path.as_ref().to_owned(),
+            prefixes,
+            options: None,
+            batch_size: Self::DEFAULT_BATCH_SIZE,
+        })
+    }
+
+    /// Tuning of the cold database, which otherwise uses the RocksDB
+    /// defaults.
+    pub fn options(mut self, options: KvStoreOptions) -> Self {
+        self.options = Some(options);
+
+        self
+    }
+
+    /// The number of keys moved per transaction, 1000 by default.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+
+        self
+    }
+
+    pub(crate) fn open(self, env: Option<&Env>) -> Result<ColdDatabase, KvStoreError> {
+        let mut database_options = Options::default();
+        database_options.create_if_missing(true);
+        if let Some(options) = &self.options {
+            options.apply(&mut database_options);
+        }
+        // The memory environment only opens absolute paths, and the path is
+        // irrelevant to a database kept in memory.
+        let path = match env {
+            Some(env) => {
+                database_options.set_env(env);
+
+                std::env::temp_dir().join("kvstore-in-memory-cold")
+            }
+            None => self.path,
+        };
+
+        let database = DB::open(&database_options, path).map_err(KvStoreError::Open)?;
+
+        Ok(ColdDatabase {
+            database,
+            prefixes: self.prefixes,
+            batch_size: self.batch_size,
+        })
+    }
+}
+
+/// The opened [`ColdTier`].
+pub(crate) struct ColdDatabase {
+    database: DB,
+    prefixes: Vec<Vec<u8>>,
+    batch_size: usize,
+}
+
+impl ColdDatabase {
+    pub fn prefixes(&self) -> &[Vec<u8>] {
+        &self.prefixes
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    pub fn is_tiered(&self, key_vec: &[u8]) -> bool {
+        self.prefixes
+            .iter()
+            .any(|prefix_vec| key_vec.starts_with(prefix_vec))
+    }
+
+    /// Only keys under the prefixes are looked up, so reads of other keys
+    /// that miss the hot tier do not touch the slower storage.
+    pub fn get(&self, key_vec: &[u8]) -> Result<Option<Vec<u8>>, KvStoreError> {
+        if !self.is_tiered(key_vec) {
+            return Ok(None);
+        }
+
+        self.database.get(key_vec).map_err(KvStoreError::ColdTier)
+    }
+
+    /// Synced before returning, as the keys are deleted from the hot tier
+    /// right after.
+    pub fn write(&self, batch: WriteBatch) -> Result<(), KvStoreError> {
+        let mut write_options = WriteOptions::default();
+        write_options.set_sync(true);
+
+        self.database
+            .write_opt(batch, &write_options)
+            .map_err(KvStoreError::ColdTier)
+    }
+
+    pub fn delete<I>(&self, key_vecs: I) -> Result<(), KvStoreError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mut batch = WriteBatch::default();
+        for key_vec in key_vecs {
+            if self.is_tiered(key_vec.as_ref()) {
+                batch.delete(key_vec);
+            }
+        }
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        self.write(batch)
+    }
+
+    /// The keys under `prefix_vec`.
+    pub fn keys(&self, prefix_vec: &[u8]) -> Result<Vec<Box<[u8]>>, KvStoreError> {
+        let mut key_vecs = Vec::new();
+        for item in self
+            .database
+            .iterator(IteratorMode::From(prefix_vec, Direction::Forward))
+        {
+            let (key, _value) = item.map_err(KvStoreError::ColdTier)?;
+            if !key.starts_with(prefix_vec) {
+                break;
+            }
+
+            key_vecs.push(key);
+        }
+
+        Ok(key_vecs)
+    }
+}
@@ -0,0 +1,267 @@
+//! Type fingerprints stored in front of values by
+//! [`crate::KvStoreBuilder::strict_types()`].
+use std::{any, fmt::Debug};
+
+use serde::{
+    de::{self, DeserializeOwned, Visitor},
+    ser::Serialize,
+};
+
+use crate::{
+    data_type::{deserialize, serialize},
+    KvStoreError,
+};
+
+/// Starts every tagged value. JSON values never start with `0xfe`, so values
+/// written before strict mode was enabled are reported as untagged. A
+/// bincode value may start with it by chance, but then fails to match the
+/// expected fingerprint all the same.
+const TAG_MAGIC: [u8; 2] = [0xfe, 0x01];
+
+/// The type a value was written as: its Rust type name and a hash of the
+/// shape its `Deserialize` implementation asks for, i.e. the struct name and
+/// field names, enum variants or primitive type. The hash changes when
+/// fields are added, removed or renamed even if the type name does not.
+///
+/// Type names are not guaranteed to be stable across compiler versions, so
+/// a toolchain upgrade may report mismatches for unchanged types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeFingerprint {
+    pub type_name: String,
+    pub schema_hash: u64,
+}
+
+impl TypeFingerprint {
+    pub fn of<V>() -> Self
+    where
+        V: DeserializeOwned,
+    {
+        let mut schema = String::new();
+        let _ = V::deserialize(SchemaProbe {
+            schema: &mut schema,
+        });
+
+        Self {
+            type_name: any::type_name::<V>().to_owned(),
+            schema_hash: fnv1a(schema.as_bytes()),
+        }
+    }
+
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&TAG_MAGIC);
+        buffer.extend_from_slice(&(self.type_name.len() as u16).to_le_bytes());
+        buffer.extend_from_slice(self.type_name.as_bytes());
+        buffer.extend_from_slice(&self.schema_hash.to_le_bytes());
+    }
+
+    /// Split a tagged value into its fingerprint and the encoded value.
+    fn decode(value: &[u8]) -> Option<(Self, &[u8])> {
+        let value = value.strip_prefix(&TAG_MAGIC)?;
+        let (length, value) = value.split_first_chunk::<2>()?;
+        let length = u16::from_le_bytes(*length) as usize;
+        if value.len() < length {
+            return None;
+        }
+
+        let (type_name, value) = value.split_at(length);
+        let (schema_hash, value) = value.split_first_chunk::<8>()?;
+        let fingerprint = Self {
+            type_name: std::str::from_utf8(type_name).ok()?.to_owned(),
+            schema_hash: u64::from_le_bytes(*schema_hash),
+        };
+
+        Some((fingerprint, value))
+    }
+}
+
+/// Encodes values as stored, with a [`TypeFingerprint`] in front in strict
+/// mode.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ValueCodec {
+    pub strict_types: bool,
+}
+
+impl ValueCodec {
+    pub fn serialize<V>(&self, value: &V) -> Result<Vec<u8>, KvStoreError>
+    where
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        let value_vec = serialize(value)?;
+        if !self.strict_types {
+            return Ok(value_vec);
+        }
+
+        let mut tagged_value_vec = Vec::with_capacity(value_vec.len() + 64);
+        TypeFingerprint::of::<V>().encode(&mut tagged_value_vec);
+        tagged_value_vec.extend_from_slice(&value_vec);
+
+        Ok(tagged_value_vec)
+    }
+
+    pub fn deserialize<V>(&self, value: impl AsRef<[u8]>) -> Result<V, KvStoreError>
+    where
+        V: Debug + DeserializeOwned + Serialize,
+    {
+        if !self.strict_types {
+            return Ok(deserialize(value)?);
+        }
+
+        let expected = TypeFingerprint::of::<V>();
+        match TypeFingerprint::decode(value.as_ref()) {
+            Some((found, value)) if found == expected => Ok(deserialize(value)?),
+            Some((found, _value)) => Err(KvStoreError::TypeMismatch {
+                expected,
+                found: Some(found),
+            }),
+            None => Err(KvStoreError::TypeMismatch {
+                expected,
+                found: None,
+            }),
+        }
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// A deserializer that records the shape requested by a `Deserialize`
+/// implementation and fails instead of producing a value. Newtypes and
+/// options are followed to their inner type.
+struct SchemaProbe<'a> {
+    schema: &'a mut String,
+}
+
+#[derive(Debug)]
+struct ProbeError;
+
+impl std::fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ProbeError {}
+
+impl de::Error for ProbeError {
+    fn custom<T: std::fmt::Display>(_message: T) -> Self {
+        Self
+    }
+}
+
+macro_rules! probe_primitives {
+    ($($method:ident => $name:literal),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+                self.schema.push_str($name);
+
+                Err(ProbeError)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for SchemaProbe<'_> {
+    type Error = ProbeError;
+
+    probe_primitives! {
+        deserialize_any => "any",
+        deserialize_bool => "bool",
+        deserialize_i8 => "i8",
+        deserialize_i16 => "i16",
+        deserialize_i32 => "i32",
+        deserialize_i64 => "i64",
+        deserialize_i128 => "i128",
+        deserialize_u8 => "u8",
+        deserialize_u16 => "u16",
+        deserialize_u32 => "u32",
+        deserialize_u64 => "u64",
+        deserialize_u128 => "u128",
+        deserialize_f32 => "f32",
+        deserialize_f64 => "f64",
+        deserialize_char => "char",
+        deserialize_str => "str",
+        deserialize_string => "str",
+        deserialize_bytes => "bytes",
+        deserialize_byte_buf => "bytes",
+        deserialize_unit => "unit",
+        deserialize_seq => "seq",
+        deserialize_map => "map",
+        deserialize_identifier => "identifier",
+        deserialize_ignored_any => "ignored",
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.schema.push_str("option ");
+
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.schema.push_str(name);
+
+        Err(ProbeError)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.schema.push_str(name);
+        self.schema.push(' ');
+
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        length: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.schema.push_str(&format!("tuple {}", length));
+
+        Err(ProbeError)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        length: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.schema.push_str(&format!("{} {}", name, length));
+
+        Err(ProbeError)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.schema
+            .push_str(&format!("{} {{{}}}", name, fields.join(",")));
+
+        Err(ProbeError)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.schema
+            .push_str(&format!("{} ({})", name, variants.join("|")));
+
+        Err(ProbeError)
+    }
+}
@@ -1,3 +1,6 @@
+pub mod metrics;
 pub mod publisher;
 pub mod subscriber;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 pub mod types;
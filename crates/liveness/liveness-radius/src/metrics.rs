@@ -0,0 +1,68 @@
+use crate::publisher::TransactionError;
+
+/// Instrumentation of [`crate::publisher::Publisher`] and
+/// [`crate::subscriber::Subscriber`], registered with
+/// [`crate::publisher::Publisher::with_metrics()`] and
+/// [`crate::subscriber::Subscriber::metrics()`], e.g. to update Prometheus
+/// counters and gauges.
+///
+/// Both methods are called on the task sending the transaction or
+/// delivering the events, so they should only update in-memory state.
+///
+/// # Examples
+///
+/// ```
+/// struct Metrics {
+///     transactions: IntCounterVec,
+///     failed_transactions: IntCounterVec,
+///     gas_used: IntCounterVec,
+///     subscriber_lag: IntGauge,
+/// }
+///
+/// impl LivenessMetrics for Metrics {
+///     fn on_transaction(&self, record: &TransactionRecord<'_>) {
+///         self.transactions.with_label_values(&[record.method]).inc();
+///         if record.error.is_some() {
+///             self.failed_transactions
+///                 .with_label_values(&[record.method])
+///                 .inc();
+///         }
+///         if let Some(gas_used) = record.gas_used {
+///             self.gas_used
+///                 .with_label_values(&[record.method])
+///                 .inc_by(gas_used);
+///         }
+///     }
+///
+///     fn on_subscriber_lag(&self, lag: u64) {
+///         self.subscriber_lag.set(lag as i64);
+///     }
+/// }
+/// ```
+pub trait LivenessMetrics: Send + Sync + 'static {
+    /// Called once a transaction sent by the publisher succeeded or failed.
+    fn on_transaction(&self, record: &TransactionRecord<'_>) {
+        let _ = record;
+    }
+
+    /// Called with the number of blocks between the chain head and the last
+    /// block whose events were delivered, once the events of a new block
+    /// were delivered. With [`crate::subscriber::Subscriber::confirmation()`],
+    /// the lag includes the confirmations.
+    fn on_subscriber_lag(&self, lag: u64) {
+        let _ = lag;
+    }
+}
+
+/// A transaction sent by [`crate::publisher::Publisher`], passed to
+/// [`LivenessMetrics::on_transaction()`].
+#[derive(Debug)]
+pub struct TransactionRecord<'a> {
+    /// The contract function called, e.g. `addRollup`.
+    pub method: &'static str,
+    /// `None` if the transaction was not mined, e.g. because it would have
+    /// reverted or its receipt could not be fetched.
+    pub gas_used: Option<u64>,
+    /// `None` if the transaction succeeded.
+    pub error: Option<&'a TransactionError>,
+}
@@ -1,23 +1,29 @@
-use std::str::FromStr;
+use std::{num::NonZeroUsize, str::FromStr, sync::Arc};
 
 use alloy::{
-    contract,
+    contract::{self, SolCallBuilder},
     network::{Ethereum, EthereumWallet},
-    primitives::{Address, FixedBytes, Uint},
+    primitives::{Address, Bytes, FixedBytes, Uint},
     providers::{
         fillers::{
             BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller,
             WalletFiller,
         },
-        Identity, PendingTransactionBuilder, Provider, ProviderBuilder, RootProvider,
-        WalletProvider,
+        Identity, Provider, ProviderBuilder, RootProvider, WalletProvider,
     },
+    rpc::types::{state::StateOverride, TransactionReceipt},
     signers::local::LocalSigner,
-    sol_types::SolEvent,
+    sol_types::{decode_revert_reason, SolCall, SolEvent},
     transports::http::{reqwest::Url, Client, Http},
 };
+use futures::{stream, Stream, TryStreamExt};
 
-use crate::types::*;
+#[cfg(feature = "test-utils")]
+use crate::test_utils::ViewOverrides;
+use crate::{
+    metrics::{LivenessMetrics, TransactionRecord},
+    types::*,
+};
 
 type EthereumHttpProvider = FillProvider<
     JoinFill<
@@ -51,6 +57,11 @@ type LivenessContract = Liveness::LivenessInstance<
 pub struct Publisher {
     provider: EthereumHttpProvider,
     liveness_contract: LivenessContract,
+    multicall_address: Address,
+    default_block_tag: BlockTag,
+    metrics: Option<Arc<dyn LivenessMetrics>>,
+    #[cfg(feature = "test-utils")]
+    view_overrides: Option<ViewOverrides>,
 }
 
 pub struct ValidationInfo {
@@ -103,9 +114,54 @@ impl Publisher {
         Ok(Self {
             provider,
             liveness_contract,
+            multicall_address: MULTICALL3_ADDRESS,
+            default_block_tag: BlockTag::Latest,
+            metrics: None,
+            #[cfg(feature = "test-utils")]
+            view_overrides: None,
         })
     }
 
+    /// Use the Multicall3 contract at `multicall_address` instead of
+    /// [`MULTICALL3_ADDRESS`], e.g. on a local chain.
+    pub fn with_multicall_address(
+        mut self,
+        multicall_address: impl AsRef<str>,
+    ) -> Result<Self, PublisherError> {
+        self.multicall_address =
+            Address::from_str(multicall_address.as_ref()).map_err(|error| {
+                PublisherError::ParseAddress(multicall_address.as_ref().to_owned(), error)
+            })?;
+
+        Ok(self)
+    }
+
+    /// Read the contract state at `default_block_tag` in the view methods
+    /// called without a block, e.g. [`BlockTag::Finalized`] to never act on
+    /// state that a reorg may undo. Defaults to [`BlockTag::Latest`].
+    pub fn with_default_block_tag(mut self, default_block_tag: BlockTag) -> Self {
+        self.default_block_tag = default_block_tag;
+
+        self
+    }
+
+    /// Report every transaction sent, with the gas it used and whether it
+    /// failed, to `metrics`.
+    pub fn with_metrics(mut self, metrics: impl LivenessMetrics) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+
+        self
+    }
+
+    /// Make the view calls with `view_overrides`, e.g. at another block
+    /// number than the chain head in integration tests.
+    #[cfg(feature = "test-utils")]
+    pub fn with_view_overrides(mut self, view_overrides: ViewOverrides) -> Self {
+        self.view_overrides = Some(view_overrides);
+
+        self
+    }
+
     /// Get the address for the wallet used by [`Publisher`].
     ///
     /// # Examples
@@ -139,6 +195,15 @@ impl Publisher {
     /// let ethereum_latest_block_number = publisher.get_block_number().await.unwrap();
     /// ```
     pub async fn get_block_number(&self) -> Result<u64, PublisherError> {
+        #[cfg(feature = "test-utils")]
+        if let Some(block_number) = self
+            .view_overrides
+            .as_ref()
+            .and_then(ViewOverrides::block_number_override)
+        {
+            return Ok(block_number);
+        }
+
         let block_number = self
             .provider
             .get_block_number()
@@ -164,13 +229,17 @@ impl Publisher {
     /// )
     /// .unwrap();
     ///
-    /// let block_margin = publisher.get_block_margin().await.unwrap();
+    /// let block_margin = publisher.get_block_margin(None).await.unwrap();
     /// ```
-    pub async fn get_block_margin(&self) -> Result<Uint<256, 4>, PublisherError> {
+    pub async fn get_block_margin(
+        &self,
+        block: Option<BlockTag>,
+    ) -> Result<Uint<256, 4>, PublisherError> {
         let block_margin = self
-            .liveness_contract
-            .BLOCK_MARGIN()
-            .call()
+            .view(
+                self.liveness_contract.BLOCK_MARGIN(),
+                block.unwrap_or(self.default_block_tag),
+            )
             .await
             .map_err(PublisherError::GetBlockMargin)?
             ._0;
@@ -192,27 +261,98 @@ impl Publisher {
     /// )
     /// .unwrap();
     ///
-    /// let event = publisher.initialize_cluster("radius").await?;
+    /// let cluster_id = ClusterId::new("radius").unwrap();
+    /// let event = publisher.initialize_cluster(&cluster_id).await?;
     ///
     /// println!(r"Owner: {}\Cluster ID: {}", event.owner, event.clusterId);
     /// ```
     pub async fn initialize_cluster(
         &self,
-        cluster_id: impl AsRef<str>,
+        cluster_id: &ClusterId,
         max_sequencer_number: Uint<256, 4>,
     ) -> Result<Liveness::InitializedCluster, PublisherError> {
         let contract_call = self
             .liveness_contract
-            .initializeCluster(cluster_id.as_ref().to_string(), max_sequencer_number);
-        let pending_transaction = contract_call.send().await;
+            .initializeCluster(cluster_id.to_string(), max_sequencer_number);
         let event: Liveness::InitializedCluster = self
-            .extract_event_from_pending_transaction(pending_transaction)
+            .send_transaction(contract_call)
             .await
             .map_err(PublisherError::InitializedCluster)?;
 
         Ok(event)
     }
 
+    /// Estimate the gas required by [`Publisher::initialize_cluster()`]
+    /// without sending the transaction. A revert is returned as
+    /// [`PublisherError::Revert`] with the decoded reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+    /// )
+    /// .unwrap();
+    ///
+    /// let gas = publisher
+    ///     .estimate_gas_initialize_cluster(&cluster_id, Uint::from(30), None)
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn estimate_gas_initialize_cluster(
+        &self,
+        cluster_id: &ClusterId,
+        max_sequencer_number: Uint<256, 4>,
+        state_override: Option<StateOverride>,
+    ) -> Result<u64, PublisherError> {
+        let contract_call = self
+            .liveness_contract
+            .initializeCluster(cluster_id.to_string(), max_sequencer_number);
+
+        Self::estimate_gas(contract_call, state_override).await
+    }
+
+    /// Simulate [`Publisher::initialize_cluster()`] with `eth_call` on top of
+    /// the optional state override. A revert is returned as
+    /// [`PublisherError::Revert`] with the decoded reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+    /// )
+    /// .unwrap();
+    ///
+    /// match publisher
+    ///     .simulate_initialize_cluster(&cluster_id, Uint::from(30), None)
+    ///     .await
+    /// {
+    ///     Ok(()) => publisher
+    ///         .initialize_cluster(&cluster_id, Uint::from(30))
+    ///         .await
+    ///         .unwrap(),
+    ///     Err(PublisherError::Revert(revert_reason)) => println!("{:?}", revert_reason),
+    ///     Err(error) => panic!("{}", error),
+    /// }
+    /// ```
+    pub async fn simulate_initialize_cluster(
+        &self,
+        cluster_id: &ClusterId,
+        max_sequencer_number: Uint<256, 4>,
+        state_override: Option<StateOverride>,
+    ) -> Result<(), PublisherError> {
+        let contract_call = self
+            .liveness_contract
+            .initializeCluster(cluster_id.to_string(), max_sequencer_number);
+
+        Self::simulate(contract_call, state_override).await
+    }
+
     /// Send transaction to add the rollup and wait for the event
     /// to return.
     ///
@@ -226,7 +366,7 @@ impl Publisher {
     /// )
     /// .unwrap();
     ///
-    /// let event = publisher.add_rollup("radius", "rollup_1", "0x67d269191c92Caf3cD7723F116c85e6E9bf55933", "txHash", {platform: "ethereum", serviceProvider: "eigen_layer"}).await?;
+    /// let event = publisher.add_rollup(&cluster_id, &rollup_id, "0x67d269191c92Caf3cD7723F116c85e6E9bf55933", "txHash", {platform: "ethereum", serviceProvider: "eigen_layer"}).await?;
     ///
     /// println!(
     ///     "Cluster ID: {}\Rollup ID: {}\Rollup Owner: {}",
@@ -236,8 +376,8 @@ impl Publisher {
     #[allow(clippy::too_many_arguments)]
     pub async fn add_rollup(
         &self,
-        cluster_id: impl AsRef<str>,
-        rollup_id: impl AsRef<str>,
+        cluster_id: &ClusterId,
+        rollup_id: &RollupId,
         rollup_type: impl AsRef<str>,
         rollup_owner_address: impl AsRef<str>,
         order_commitment_type: impl AsRef<str>,
@@ -245,6 +385,103 @@ impl Publisher {
         validation_info: ValidationInfo,
         executor_address: impl AsRef<str>,
     ) -> Result<Liveness::AddedRollup, PublisherError> {
+        let new_rollup = Self::new_rollup(
+            rollup_id,
+            rollup_type,
+            rollup_owner_address,
+            order_commitment_type,
+            encrypted_transaction_type,
+            validation_info,
+            executor_address,
+        )?;
+
+        let contract_call = self
+            .liveness_contract
+            .addRollup(cluster_id.to_string(), new_rollup);
+
+        let event: Liveness::AddedRollup = self
+            .send_transaction(contract_call)
+            .await
+            .map_err(PublisherError::AddedRollup)?;
+
+        Ok(event)
+    }
+
+    /// Estimate the gas required by [`Publisher::add_rollup()`] without
+    /// sending the transaction. A revert is returned as
+    /// [`PublisherError::Revert`] with the decoded reason.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn estimate_gas_add_rollup(
+        &self,
+        cluster_id: &ClusterId,
+        rollup_id: &RollupId,
+        rollup_type: impl AsRef<str>,
+        rollup_owner_address: impl AsRef<str>,
+        order_commitment_type: impl AsRef<str>,
+        encrypted_transaction_type: impl AsRef<str>,
+        validation_info: ValidationInfo,
+        executor_address: impl AsRef<str>,
+        state_override: Option<StateOverride>,
+    ) -> Result<u64, PublisherError> {
+        let new_rollup = Self::new_rollup(
+            rollup_id,
+            rollup_type,
+            rollup_owner_address,
+            order_commitment_type,
+            encrypted_transaction_type,
+            validation_info,
+            executor_address,
+        )?;
+
+        let contract_call = self
+            .liveness_contract
+            .addRollup(cluster_id.to_string(), new_rollup);
+
+        Self::estimate_gas(contract_call, state_override).await
+    }
+
+    /// Simulate [`Publisher::add_rollup()`] with `eth_call` on top of the
+    /// optional state override. A revert is returned as
+    /// [`PublisherError::Revert`] with the decoded reason.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn simulate_add_rollup(
+        &self,
+        cluster_id: &ClusterId,
+        rollup_id: &RollupId,
+        rollup_type: impl AsRef<str>,
+        rollup_owner_address: impl AsRef<str>,
+        order_commitment_type: impl AsRef<str>,
+        encrypted_transaction_type: impl AsRef<str>,
+        validation_info: ValidationInfo,
+        executor_address: impl AsRef<str>,
+        state_override: Option<StateOverride>,
+    ) -> Result<(), PublisherError> {
+        let new_rollup = Self::new_rollup(
+            rollup_id,
+            rollup_type,
+            rollup_owner_address,
+            order_commitment_type,
+            encrypted_transaction_type,
+            validation_info,
+            executor_address,
+        )?;
+
+        let contract_call = self
+            .liveness_contract
+            .addRollup(cluster_id.to_string(), new_rollup);
+
+        Self::simulate(contract_call, state_override).await
+    }
+
+    fn new_rollup(
+        rollup_id: &RollupId,
+        rollup_type: impl AsRef<str>,
+        rollup_owner_address: impl AsRef<str>,
+        order_commitment_type: impl AsRef<str>,
+        encrypted_transaction_type: impl AsRef<str>,
+        validation_info: ValidationInfo,
+        executor_address: impl AsRef<str>,
+    ) -> Result<ILivenessRadius::NewRollup, PublisherError> {
         let rollup_owner_address =
             Address::from_str(rollup_owner_address.as_ref()).map_err(|error| {
                 PublisherError::ParseAddress(rollup_owner_address.as_ref().to_owned(), error)
@@ -260,27 +497,15 @@ impl Publisher {
             validationServiceManager: validation_info.validation_service_manager,
         };
 
-        let new_rollup = ILivenessRadius::NewRollup {
-            rollupId: rollup_id.as_ref().to_string(),
+        Ok(ILivenessRadius::NewRollup {
+            rollupId: rollup_id.to_string(),
             owner: rollup_owner_address,
             rollupType: rollup_type.as_ref().to_string(),
             encryptedTransactionType: encrypted_transaction_type.as_ref().to_string(),
             validationInfo: validation_info,
             orderCommitmentType: order_commitment_type.as_ref().to_string(),
             executor: executor_address,
-        };
-
-        let contract_call = self
-            .liveness_contract
-            .addRollup(cluster_id.as_ref().to_string(), new_rollup);
-
-        let pending_transaction = contract_call.send().await;
-        let event: Liveness::AddedRollup = self
-            .extract_event_from_pending_transaction(pending_transaction)
-            .await
-            .map_err(PublisherError::AddedRollup)?;
-
-        Ok(event)
+        })
     }
 
     /// Send transaction to add rollup executor and wait for the event
@@ -299,8 +524,8 @@ impl Publisher {
     ///
     /// let event = publisher
     ///     .register_rollup_executor(
-    ///         "radius",
-    ///         "rollup_1",
+    ///         &cluster_id,
+    ///         &rollup_id,
     ///         "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
     ///     )
     ///     .await?;
@@ -312,8 +537,8 @@ impl Publisher {
     /// ```
     pub async fn register_rollup_executor(
         &self,
-        cluster_id: impl AsRef<str>,
-        rollup_id: impl AsRef<str>,
+        cluster_id: &ClusterId,
+        rollup_id: &RollupId,
         rollup_executor_address: impl AsRef<str>,
     ) -> Result<Liveness::RegisteredRollupExecutor, PublisherError> {
         let rollup_executor_address =
@@ -322,14 +547,13 @@ impl Publisher {
             })?;
 
         let contract_call = self.liveness_contract.registerRollupExecutor(
-            cluster_id.as_ref().to_string(),
-            rollup_id.as_ref().to_string(),
+            cluster_id.to_string(),
+            rollup_id.to_string(),
             rollup_executor_address,
         );
 
-        let pending_transaction = contract_call.send().await;
         let event: Liveness::RegisteredRollupExecutor = self
-            .extract_event_from_pending_transaction(pending_transaction)
+            .send_transaction(contract_call)
             .await
             .map_err(PublisherError::RegisteredRollupExecutor)?;
 
@@ -350,30 +574,34 @@ impl Publisher {
     /// )
     /// .unwrap();
     ///
-    /// let event = publisher
-    ///     .register_sequencer("0xdd45347e5d10daaadb40f185225fc8d860d2888b5c411aca387e17a265e2f491")
-    ///     .await
-    ///     .unwrap();
+    /// let cluster_id =
+    ///     ClusterId::new("0xdd45347e5d10daaadb40f185225fc8d860d2888b5c411aca387e17a265e2f491")
+    ///         .unwrap();
+    /// let event = publisher.register_sequencer(&cluster_id).await.unwrap();
     ///
-    /// assert!(event.sequencerAddress == publisher.address());
+    /// assert!(event.sequencer == publisher.address());
     /// ```
     pub async fn register_sequencer(
         &self,
-        cluster_id: impl AsRef<str>,
+        cluster_id: &ClusterId,
     ) -> Result<Liveness::RegisteredSequencer, PublisherError> {
         let contract_call = self
             .liveness_contract
-            .registerSequencer(cluster_id.as_ref().to_string());
-        let pending_transaction = contract_call.send().await;
+            .registerSequencer(cluster_id.to_string());
         let event: Liveness::RegisteredSequencer = self
-            .extract_event_from_pending_transaction(pending_transaction)
+            .send_transaction(contract_call)
             .await
             .map_err(PublisherError::RegisteredSequencer)?;
 
         Ok(event)
     }
 
-    /// Deregister the publisher's address from the cluster.
+    /// Register the current [`Publisher`] instance as a sequencer of the
+    /// cluster with `value` wei attached, through
+    /// [`Publisher::send_payable()`], for Liveness contracts whose
+    /// `registerSequencer` takes a bond. `registerSequencer` is not payable
+    /// in the bundled ABI, so a contract deployed from it reverts if `value`
+    /// is not 0.
     ///
     /// # Examples
     ///
@@ -385,23 +613,116 @@ impl Publisher {
     /// )
     /// .unwrap();
     ///
+    /// let bond = Uint::from(10).pow(Uint::from(18));
     /// let event = publisher
-    ///     .deregister_sequencer("0xdd45347e5d10daaadb40f185225fc8d860d2888b5c411aca387e17a265e2f491")
+    ///     .register_sequencer_with_value(&cluster_id, bond)
     ///     .await
     ///     .unwrap();
     ///
-    /// assert!(event.sequencerAddress == publisher.address());
+    /// assert!(event.sequencer == publisher.address());
+    /// ```
+    pub async fn register_sequencer_with_value(
+        &self,
+        cluster_id: &ClusterId,
+        value: Uint<256, 4>,
+    ) -> Result<Liveness::RegisteredSequencer, PublisherError> {
+        let call = Liveness::registerSequencerCall {
+            clusterId: cluster_id.to_string(),
+        };
+
+        self.send_payable(call, value).await
+    }
+
+    /// Send `call` to the Liveness contract with `value` wei attached and
+    /// return the first event the transaction emitted, for the payable
+    /// functions of Liveness contracts extending the bundled ABI, e.g. a
+    /// registration requiring a bond. Fails with
+    /// [`TransactionError::InsufficientBalance`] without sending the
+    /// transaction if the balance of [`Publisher::address()`] does not cover
+    /// `value` and the gas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// alloy::sol! {
+    ///     function registerSequencerWithBond(string clusterId) external payable;
+    /// }
+    ///
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+    /// )
+    /// .unwrap();
+    ///
+    /// let bond = Uint::from(10).pow(Uint::from(18));
+    /// let event: Liveness::RegisteredSequencer = publisher
+    ///     .send_payable(
+    ///         registerSequencerWithBondCall {
+    ///             clusterId: cluster_id.to_string(),
+    ///         },
+    ///         bond,
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// assert!(event.sequencer == publisher.address());
+    /// ```
+    pub async fn send_payable<C, T>(
+        &self,
+        call: C,
+        value: Uint<256, 4>,
+    ) -> Result<T, PublisherError>
+    where
+        C: SolCall,
+        T: SolEvent,
+    {
+        let contract_call = self.liveness_contract.call_builder(&call);
+
+        self.send_transaction_with_value(contract_call, value)
+            .await
+            .map_err(PublisherError::SendPayable)
+    }
+
+    /// Get the balance of [`Publisher::address()`] in wei.
+    pub async fn get_balance(&self) -> Result<Uint<256, 4>, PublisherError> {
+        let balance = self
+            .provider
+            .get_balance(self.address())
+            .await
+            .map_err(PublisherError::GetBalance)?;
+
+        Ok(balance)
+    }
+
+    /// Deregister the publisher's address from the cluster.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+    /// )
+    /// .unwrap();
+    ///
+    /// let cluster_id =
+    ///     ClusterId::new("0xdd45347e5d10daaadb40f185225fc8d860d2888b5c411aca387e17a265e2f491")
+    ///         .unwrap();
+    /// let event = publisher.deregister_sequencer(&cluster_id).await.unwrap();
+    ///
+    /// assert!(event.sequencer == publisher.address());
     /// ```
     pub async fn deregister_sequencer(
         &self,
-        cluster_id: impl AsRef<str>,
+        cluster_id: &ClusterId,
     ) -> Result<Liveness::DeregisteredSequencer, PublisherError> {
         let contract_call = self
             .liveness_contract
-            .deregisterSequencer(cluster_id.as_ref().to_string());
-        let pending_transaction = contract_call.send().await;
+            .deregisterSequencer(cluster_id.to_string());
         let event: Liveness::DeregisteredSequencer = self
-            .extract_event_from_pending_transaction(pending_transaction)
+            .send_transaction(contract_call)
             .await
             .map_err(PublisherError::DeregisteredSequencer)?;
 
@@ -422,7 +743,7 @@ impl Publisher {
     ///
     /// let block_number = publisher.get_block_number().await.unwrap();
     /// let sequencer_list = publisher
-    ///     .get_sequencer_list(cluster_id, block_number)
+    ///     .get_sequencer_list(&cluster_id, Some(block_number.into()))
     ///     .await
     ///     .unwrap();
     ///
@@ -430,14 +751,14 @@ impl Publisher {
     /// ```
     pub async fn get_sequencer_list(
         &self,
-        cluster_id: impl AsRef<str>,
-        block_number: u64,
+        cluster_id: &ClusterId,
+        block: Option<BlockTag>,
     ) -> Result<Vec<Address>, PublisherError> {
         let sequencer_list = self
-            .liveness_contract
-            .getSequencers(cluster_id.as_ref().to_string())
-            .call()
-            .block(block_number.into())
+            .view(
+                self.liveness_contract.getSequencers(cluster_id.to_string()),
+                block.unwrap_or(self.default_block_tag),
+            )
             .await
             .map_err(PublisherError::GetSequencers)?
             ._0;
@@ -445,6 +766,105 @@ impl Publisher {
         Ok(sequencer_list)
     }
 
+    /// Get `limit` sequencers of the sequencer list at `block` from
+    /// `offset`. Pass the same block number for every page so that the pages
+    /// add up to the list at a single block even if sequencers register or
+    /// deregister in between.
+    ///
+    /// The paging is local: the Liveness contract has no range getter, so
+    /// each page still costs a full `getSequencers` call on the RPC node.
+    /// Prefer [`Publisher::sequencer_list_stream()`] to go through every
+    /// page, and [`Publisher::is_sequencer_registered()`] to check a single
+    /// sequencer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+    /// )?;
+    ///
+    /// let block_number = publisher.get_block_number().await.unwrap();
+    /// let limit = NonZeroUsize::new(10).unwrap();
+    /// let mut offset = Some(0);
+    /// while let Some(current_offset) = offset {
+    ///     let page = publisher
+    ///         .get_sequencer_list_page(
+    ///             &cluster_id,
+    ///             Some(block_number.into()),
+    ///             current_offset,
+    ///             limit,
+    ///         )
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     println!("{:?}", page.sequencer_list);
+    ///     offset = page.next_offset;
+    /// }
+    /// ```
+    pub async fn get_sequencer_list_page(
+        &self,
+        cluster_id: &ClusterId,
+        block: Option<BlockTag>,
+        offset: usize,
+        limit: NonZeroUsize,
+    ) -> Result<SequencerListPage, PublisherError> {
+        let sequencer_list = self.get_sequencer_list(cluster_id, block).await?;
+        let end = offset.saturating_add(limit.get()).min(sequencer_list.len());
+
+        Ok(SequencerListPage {
+            sequencer_list: sequencer_list.get(offset..end).unwrap_or_default().to_vec(),
+            next_offset: (end < sequencer_list.len()).then_some(end),
+        })
+    }
+
+    /// Stream the sequencer list at `block` in chunks of
+    /// `chunk_size` sequencers, e.g. to connect to the sequencers of a large
+    /// cluster a chunk at a time. The chunking is local: the whole list is
+    /// fetched with a single `getSequencers` call when the stream is first
+    /// polled, as the Liveness contract has no range getter, so this costs
+    /// the RPC node as much as [`Publisher::get_sequencer_list()`]. Use
+    /// [`Publisher::is_sequencer_registered()`] to check a single sequencer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+    /// )?;
+    ///
+    /// let block_number = publisher.get_block_number().await.unwrap();
+    /// let mut sequencer_list_stream = pin!(publisher.sequencer_list_stream(
+    ///     &cluster_id,
+    ///     Some(block_number.into()),
+    ///     NonZeroUsize::new(10).unwrap(),
+    /// ));
+    /// while let Some(sequencer_list) = sequencer_list_stream.next().await {
+    ///     println!("{:?}", sequencer_list.unwrap());
+    /// }
+    /// ```
+    pub fn sequencer_list_stream<'a>(
+        &'a self,
+        cluster_id: &'a ClusterId,
+        block: Option<BlockTag>,
+        chunk_size: NonZeroUsize,
+    ) -> impl Stream<Item = Result<Vec<Address>, PublisherError>> + 'a {
+        stream::once(self.get_sequencer_list(cluster_id, block))
+            .map_ok(move |sequencer_list| {
+                let chunks: Vec<Result<Vec<Address>, PublisherError>> = sequencer_list
+                    .chunks(chunk_size.get())
+                    .map(|chunk| Ok(chunk.to_vec()))
+                    .collect();
+
+                stream::iter(chunks)
+            })
+            .try_flatten()
+    }
+
     /// Get the addresses of registered rollups in a given cluster for a
     /// given block number.
     ///
@@ -459,7 +879,7 @@ impl Publisher {
     ///
     /// let block_number = publisher.get_block_number().await.unwrap();
     /// let executor_list = publisher
-    ///     .get_executor_list(cluster_id, rollup_id, block_number)
+    ///     .get_executor_list(&cluster_id, &rollup_id, Some(block_number.into()))
     ///     .await
     ///     .unwrap();
     ///
@@ -467,18 +887,16 @@ impl Publisher {
     /// ```
     pub async fn get_executor_list(
         &self,
-        cluster_id: impl AsRef<str>,
-        rollup_id: impl AsRef<str>,
-        block_number: u64,
+        cluster_id: &ClusterId,
+        rollup_id: &RollupId,
+        block: Option<BlockTag>,
     ) -> Result<Vec<Address>, PublisherError> {
         let executor_list = self
-            .liveness_contract
-            .getExecutors(
-                cluster_id.as_ref().to_string(),
-                rollup_id.as_ref().to_string(),
+            .view(
+                self.liveness_contract
+                    .getExecutors(cluster_id.to_string(), rollup_id.to_string()),
+                block.unwrap_or(self.default_block_tag),
             )
-            .call()
-            .block(block_number.into())
             .await
             .map_err(PublisherError::GetSequencers)?
             ._0;
@@ -493,14 +911,14 @@ impl Publisher {
 
     pub async fn get_rollup_info_list(
         &self,
-        cluster_id: impl AsRef<str>,
-        block_number: u64,
+        cluster_id: &ClusterId,
+        block: Option<BlockTag>,
     ) -> Result<Vec<ILivenessRadius::Rollup>, PublisherError> {
         let executor_list = self
-            .liveness_contract
-            .getRollups(cluster_id.as_ref().to_string())
-            .call()
-            .block(block_number.into())
+            .view(
+                self.liveness_contract.getRollups(cluster_id.to_string()),
+                block.unwrap_or(self.default_block_tag),
+            )
             .await
             .map_err(PublisherError::GetRollups)?
             ._0;
@@ -510,18 +928,16 @@ impl Publisher {
 
     pub async fn get_rollup_info(
         &self,
-        cluster_id: impl AsRef<str>,
-        rollup_id: impl AsRef<str>,
-        block_number: u64,
+        cluster_id: &ClusterId,
+        rollup_id: &RollupId,
+        block: Option<BlockTag>,
     ) -> Result<ILivenessRadius::Rollup, PublisherError> {
         let rollup_info = self
-            .liveness_contract
-            .getRollup(
-                cluster_id.as_ref().to_string(),
-                rollup_id.as_ref().to_string(),
+            .view(
+                self.liveness_contract
+                    .getRollup(cluster_id.to_string(), rollup_id.to_string()),
+                block.unwrap_or(self.default_block_tag),
             )
-            .call()
-            .block(block_number.into())
             .await
             .map_err(PublisherError::GetRollup)?
             ._0;
@@ -542,18 +958,21 @@ impl Publisher {
     /// .unwrap();
     ///
     /// let max_sequencer_number = publisher
-    ///     .get_max_sequencer_number(cluster_id)
+    ///     .get_max_sequencer_number(&cluster_id, None)
     ///     .await
     ///     .unwrap();
     /// ```
     pub async fn get_max_sequencer_number(
         &self,
-        cluster_id: impl AsRef<str>,
+        cluster_id: &ClusterId,
+        block: Option<BlockTag>,
     ) -> Result<Uint<256, 4>, PublisherError> {
         let max_sequencer_number = self
-            .liveness_contract
-            .getMaxSequencerNumber(cluster_id.as_ref().to_string())
-            .call()
+            .view(
+                self.liveness_contract
+                    .getMaxSequencerNumber(cluster_id.to_string()),
+                block.unwrap_or(self.default_block_tag),
+            )
             .await
             .map_err(PublisherError::GetBlockMargin)?
             ._0;
@@ -561,18 +980,103 @@ impl Publisher {
         Ok(max_sequencer_number)
     }
 
+    /// Get the sequencer list, the rollup list and the maximum number of
+    /// sequencers of a cluster at `block` in a single `eth_call`
+    /// through Multicall3.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+    /// )
+    /// .unwrap();
+    ///
+    /// let cluster_snapshot = publisher
+    ///     .get_cluster_snapshot(&cluster_id, Some(BlockTag::Finalized))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("{:?}", cluster_snapshot.sequencer_list);
+    /// ```
+    pub async fn get_cluster_snapshot(
+        &self,
+        cluster_id: &ClusterId,
+        block: Option<BlockTag>,
+    ) -> Result<ClusterSnapshot, PublisherError> {
+        let cluster_id = cluster_id.to_string();
+        let call_data = [
+            Liveness::getSequencersCall {
+                clusterId: cluster_id.clone(),
+            }
+            .abi_encode(),
+            Liveness::getRollupsCall {
+                clusterId: cluster_id.clone(),
+            }
+            .abi_encode(),
+            Liveness::getMaxSequencerNumberCall {
+                clusterId: cluster_id,
+            }
+            .abi_encode(),
+        ];
+        let calls = call_data
+            .into_iter()
+            .map(|call_data| IMulticall3::Call3 {
+                target: *self.liveness_contract.address(),
+                allowFailure: false,
+                callData: call_data.into(),
+            })
+            .collect();
+
+        let multicall =
+            IMulticall3::IMulticall3Instance::new(self.multicall_address, &self.provider);
+        let return_data: Vec<Bytes> = self
+            .view(
+                multicall.aggregate3(calls),
+                block.unwrap_or(self.default_block_tag),
+            )
+            .await
+            .map_err(PublisherError::Multicall)?
+            .returnData
+            .into_iter()
+            .map(|result| result.returnData)
+            .collect();
+        if return_data.len() != 3 {
+            return Err(PublisherError::MulticallLength(return_data.len()));
+        }
+
+        let sequencer_list = Liveness::getSequencersCall::abi_decode_returns(&return_data[0], true)
+            .map_err(PublisherError::DecodeMulticall)?
+            ._0;
+        let rollup_info_list = Liveness::getRollupsCall::abi_decode_returns(&return_data[1], true)
+            .map_err(PublisherError::DecodeMulticall)?
+            ._0;
+        let max_sequencer_number =
+            Liveness::getMaxSequencerNumberCall::abi_decode_returns(&return_data[2], true)
+                .map_err(PublisherError::DecodeMulticall)?
+                ._0;
+
+        Ok(ClusterSnapshot {
+            sequencer_list,
+            rollup_info_list,
+            max_sequencer_number,
+        })
+    }
+
     pub async fn is_added_rollup(
         &self,
-        cluster_id: impl AsRef<str>,
-        rollup_id: impl AsRef<str>,
+        cluster_id: &ClusterId,
+        rollup_id: &RollupId,
+        block: Option<BlockTag>,
     ) -> Result<bool, PublisherError> {
         let is_rollup_added: bool = self
-            .liveness_contract
-            .isRollupAdded(
-                cluster_id.as_ref().to_string(),
-                rollup_id.as_ref().to_string(),
+            .view(
+                self.liveness_contract
+                    .isRollupAdded(cluster_id.to_string(), rollup_id.to_string()),
+                block.unwrap_or(self.default_block_tag),
             )
-            .call()
             .await
             .map_err(PublisherError::IsRegistered)?
             ._0;
@@ -582,18 +1086,20 @@ impl Publisher {
 
     pub async fn is_rollup_executor_registered(
         &self,
-        cluster_id: impl AsRef<str>,
-        rollup_id: impl AsRef<str>,
+        cluster_id: &ClusterId,
+        rollup_id: &RollupId,
         executor_address: Address,
+        block: Option<BlockTag>,
     ) -> Result<bool, PublisherError> {
         let is_rollup_executor_registered: bool = self
-            .liveness_contract
-            .isRollupExecutorRegistered(
-                cluster_id.as_ref().to_string(),
-                rollup_id.as_ref().to_string(),
-                executor_address,
+            .view(
+                self.liveness_contract.isRollupExecutorRegistered(
+                    cluster_id.to_string(),
+                    rollup_id.to_string(),
+                    executor_address,
+                ),
+                block.unwrap_or(self.default_block_tag),
             )
-            .call()
             .await
             .map_err(PublisherError::IsRegistered)?
             ._0;
@@ -614,41 +1120,232 @@ impl Publisher {
     /// )
     /// .unwrap();
     ///
-    /// let is_registered_sequencer = publisher.is_registered_sequencer(cluster_id).await.unwrap();
+    /// let is_registered_sequencer = publisher
+    ///     .is_registered_sequencer(&cluster_id, None)
+    ///     .await
+    ///     .unwrap();
     ///
     /// assert!(is_registered_sequencer == true);
     /// ```
     pub async fn is_registered_sequencer(
         &self,
-        cluster_id: impl AsRef<str>,
+        cluster_id: &ClusterId,
+        block: Option<BlockTag>,
     ) -> Result<bool, PublisherError> {
-        let is_registered_sequencer: bool = self
-            .liveness_contract
-            .isSequencerRegistered(cluster_id.as_ref().to_string(), self.address())
-            .call()
+        self.is_sequencer_registered(cluster_id, self.address(), block)
+            .await
+    }
+
+    /// Check if `sequencer_address` is registered as a sequencer in the
+    /// cluster without fetching the sequencer list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+    /// )
+    /// .unwrap();
+    ///
+    /// let is_sequencer_registered = publisher
+    ///     .is_sequencer_registered(&cluster_id, sequencer_address, None)
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn is_sequencer_registered(
+        &self,
+        cluster_id: &ClusterId,
+        sequencer_address: Address,
+        block: Option<BlockTag>,
+    ) -> Result<bool, PublisherError> {
+        let is_sequencer_registered: bool = self
+            .view(
+                self.liveness_contract
+                    .isSequencerRegistered(cluster_id.to_string(), sequencer_address),
+                block.unwrap_or(self.default_block_tag),
+            )
             .await
             .map_err(PublisherError::IsRegistered)?
             ._0;
 
-        Ok(is_registered_sequencer)
+        Ok(is_sequencer_registered)
     }
 
-    async fn extract_event_from_pending_transaction<T>(
+    /// Make the view call `contract_call` at `block`, with the overrides of
+    /// `Publisher::with_view_overrides()` if any.
+    async fn view<P, C>(
         &self,
-        pending_transaction: Result<
-            PendingTransactionBuilder<Http<Client>, Ethereum>,
-            contract::Error,
-        >,
+        contract_call: SolCallBuilder<Http<Client>, P, C>,
+        block: BlockTag,
+    ) -> Result<C::Return, contract::Error>
+    where
+        P: Provider<Http<Client>>,
+        C: SolCall + Unpin,
+    {
+        #[cfg(feature = "test-utils")]
+        if let Some(view_overrides) = &self.view_overrides {
+            return view_overrides
+                .call::<C, _>(
+                    &self.provider,
+                    contract_call.into_transaction_request(),
+                    block.into(),
+                )
+                .await;
+        }
+
+        contract_call.call().block(block.into()).await
+    }
+
+    async fn estimate_gas<C>(
+        contract_call: SolCallBuilder<Http<Client>, &EthereumHttpProvider, C>,
+        state_override: Option<StateOverride>,
+    ) -> Result<u64, PublisherError>
+    where
+        C: SolCall,
+    {
+        let contract_call = match state_override {
+            Some(state_override) => contract_call.state(state_override),
+            None => contract_call,
+        };
+
+        contract_call
+            .estimate_gas()
+            .await
+            .map_err(|error| match RevertReason::from_contract_error(&error) {
+                Some(revert_reason) => PublisherError::Revert(revert_reason),
+                None => PublisherError::EstimateGas(error),
+            })
+    }
+
+    async fn simulate<C>(
+        contract_call: SolCallBuilder<Http<Client>, &EthereumHttpProvider, C>,
+        state_override: Option<StateOverride>,
+    ) -> Result<(), PublisherError>
+    where
+        C: SolCall,
+    {
+        let contract_call = match state_override {
+            Some(state_override) => contract_call.state(state_override),
+            None => contract_call,
+        };
+
+        contract_call
+            .call_raw()
+            .await
+            .map_err(|error| match RevertReason::from_contract_error(&error) {
+                Some(revert_reason) => PublisherError::Revert(revert_reason),
+                None => PublisherError::Simulate(error),
+            })?;
+
+        Ok(())
+    }
+
+    /// Send the transaction of `contract_call`, wait for it to be mined and
+    /// return the first event it emitted.
+    async fn send_transaction<C, T>(
+        &self,
+        contract_call: SolCallBuilder<Http<Client>, &EthereumHttpProvider, C>,
     ) -> Result<T, TransactionError>
     where
+        C: SolCall,
         T: SolEvent,
     {
-        let transaction_receipt = pending_transaction
-            .map_err(TransactionError::SendTransaction)?
+        let transaction_receipt = Self::get_receipt(contract_call).await;
+        let gas_used = transaction_receipt
+            .as_ref()
+            .ok()
+            .map(|transaction_receipt| transaction_receipt.gas_used as u64);
+        let event = transaction_receipt.and_then(Self::extract_event);
+
+        if let Some(metrics) = &self.metrics {
+            let method = C::SIGNATURE
+                .split_once('(')
+                .map_or(C::SIGNATURE, |(method, _)| method);
+
+            metrics.on_transaction(&TransactionRecord {
+                method,
+                gas_used,
+                error: event.as_ref().err(),
+            });
+        }
+
+        event
+    }
+
+    /// Send the transaction of `contract_call` with `value` wei attached,
+    /// like [`Publisher::send_transaction()`], once the balance of the
+    /// publisher is known to cover `value` and the gas at the maximum fee
+    /// per gas, so that an underfunded account fails before paying for a
+    /// reverted transaction.
+    async fn send_transaction_with_value<C, T>(
+        &self,
+        contract_call: SolCallBuilder<Http<Client>, &EthereumHttpProvider, C>,
+        value: Uint<256, 4>,
+    ) -> Result<T, TransactionError>
+    where
+        C: SolCall,
+        T: SolEvent,
+    {
+        let contract_call = contract_call.value(value);
+
+        let balance = self
+            .provider
+            .get_balance(self.address())
+            .await
+            .map_err(TransactionError::GetBalance)?;
+        if balance < value {
+            return Err(TransactionError::InsufficientBalance {
+                balance,
+                required: value,
+            });
+        }
+
+        let gas = contract_call.estimate_gas().await.map_err(|error| {
+            match RevertReason::from_contract_error(&error) {
+                Some(revert_reason) => TransactionError::Revert(revert_reason),
+                None => TransactionError::SendTransaction(error),
+            }
+        })?;
+        let fees = self
+            .provider
+            .estimate_eip1559_fees(None)
+            .await
+            .map_err(TransactionError::EstimateFees)?;
+        let required =
+            value.saturating_add(Uint::from(gas).saturating_mul(Uint::from(fees.max_fee_per_gas)));
+        if balance < required {
+            return Err(TransactionError::InsufficientBalance { balance, required });
+        }
+
+        self.send_transaction(contract_call.gas(gas)).await
+    }
+
+    async fn get_receipt<C>(
+        contract_call: SolCallBuilder<Http<Client>, &EthereumHttpProvider, C>,
+    ) -> Result<TransactionReceipt, TransactionError>
+    where
+        C: SolCall,
+    {
+        // Sending estimates the gas first, so a transaction that would revert
+        // fails here with the revert data of the estimation.
+        contract_call
+            .send()
+            .await
+            .map_err(|error| match RevertReason::from_contract_error(&error) {
+                Some(revert_reason) => TransactionError::Revert(revert_reason),
+                None => TransactionError::SendTransaction(error),
+            })?
             .get_receipt()
             .await
-            .map_err(TransactionError::GetReceipt)?;
+            .map_err(TransactionError::GetReceipt)
+    }
 
+    fn extract_event<T>(transaction_receipt: TransactionReceipt) -> Result<T, TransactionError>
+    where
+        T: SolEvent,
+    {
         match transaction_receipt.as_ref().is_success() {
             true => {
                 let log = transaction_receipt
@@ -668,9 +1365,50 @@ impl Publisher {
     }
 }
 
+/// Why a call or transaction reverted, matched against the custom errors in
+/// the liveness contract ABI first.
+#[derive(Debug)]
+pub enum RevertReason {
+    /// A custom error defined by the liveness contract.
+    Liveness(Liveness::LivenessErrors),
+    /// `Error(string)` or `Panic(uint256)`.
+    Message(String),
+    /// Revert data that could not be decoded.
+    Unknown(Bytes),
+}
+
+impl RevertReason {
+    fn from_contract_error(error: &contract::Error) -> Option<Self> {
+        let error_payload = match error {
+            contract::Error::TransportError(transport_error) => transport_error.as_error_resp()?,
+            _others => return None,
+        };
+
+        if let Some(liveness_error) =
+            error_payload.as_decoded_error::<Liveness::LivenessErrors>(false)
+        {
+            return Some(Self::Liveness(liveness_error));
+        }
+
+        let revert_data = error_payload.as_revert_data()?;
+        match decode_revert_reason(&revert_data) {
+            Some(message) => Some(Self::Message(message)),
+            None => Some(Self::Unknown(revert_data)),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum TransactionError {
     SendTransaction(alloy::contract::Error),
+    Revert(RevertReason),
+    GetBalance(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    EstimateFees(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    /// The balance does not cover the value and the gas of a payable call.
+    InsufficientBalance {
+        balance: Uint<256, 4>,
+        required: Uint<256, 4>,
+    },
     GetReceipt(alloy::providers::PendingTransactionError),
     FailedTransaction(FixedBytes<32>),
     EmptyLogs,
@@ -691,16 +1429,24 @@ pub enum PublisherError {
     ParseSigningKey(alloy::signers::local::LocalSignerError),
     ParseAddress(String, alloy::hex::FromHexError),
     GetBlockNumber(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    GetBalance(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
     GetBlockMargin(alloy::contract::Error),
     InitializedCluster(TransactionError),
     AddedRollup(TransactionError),
     RegisteredRollupExecutor(TransactionError),
     RegisteredSequencer(TransactionError),
     DeregisteredSequencer(TransactionError),
+    SendPayable(TransactionError),
     GetSequencers(alloy::contract::Error),
     GetRollups(alloy::contract::Error),
     GetRollup(alloy::contract::Error),
     IsRegistered(alloy::contract::Error),
+    EstimateGas(alloy::contract::Error),
+    Simulate(alloy::contract::Error),
+    Revert(RevertReason),
+    Multicall(alloy::contract::Error),
+    MulticallLength(usize),
+    DecodeMulticall(alloy::sol_types::Error),
 }
 
 impl std::fmt::Display for PublisherError {
@@ -710,3 +1456,92 @@ impl std::fmt::Display for PublisherError {
 }
 
 impl std::error::Error for PublisherError {}
+
+impl PublisherError {
+    /// The decoded reason if the call or transaction reverted, e.g. to tell
+    /// an already registered sequencer from a full cluster.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// match publisher.register_sequencer(&cluster_id).await {
+    ///     Ok(event) => println!("{:?}", event),
+    ///     Err(error) => match error.revert_reason() {
+    ///         Some(RevertReason::Liveness(Liveness::LivenessErrors::AlreadyRegisteredSequencer(
+    ///             _,
+    ///         ))) => println!("Already registered"),
+    ///         Some(RevertReason::Liveness(Liveness::LivenessErrors::ExceededMaxSequencerNumber(
+    ///             _,
+    ///         ))) => println!("The cluster is full"),
+    ///         _others => panic!("{}", error),
+    ///     },
+    /// }
+    /// ```
+    pub fn revert_reason(&self) -> Option<&RevertReason> {
+        match self {
+            Self::Revert(revert_reason) => Some(revert_reason),
+            Self::InitializedCluster(TransactionError::Revert(revert_reason))
+            | Self::AddedRollup(TransactionError::Revert(revert_reason))
+            | Self::RegisteredRollupExecutor(TransactionError::Revert(revert_reason))
+            | Self::RegisteredSequencer(TransactionError::Revert(revert_reason))
+            | Self::DeregisteredSequencer(TransactionError::Revert(revert_reason))
+            | Self::SendPayable(TransactionError::Revert(revert_reason)) => Some(revert_reason),
+            _others => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        rpc::json_rpc::ErrorPayload,
+        sol_types::{Revert, SolError},
+        transports::RpcError,
+    };
+
+    use super::*;
+
+    fn contract_error(message: &'static str, revert_data: &[u8]) -> contract::Error {
+        let data =
+            serde_json::value::to_raw_value(&alloy::hex::encode_prefixed(revert_data)).unwrap();
+
+        contract::Error::TransportError(RpcError::ErrorResp(ErrorPayload {
+            code: 3,
+            message: message.into(),
+            data: Some(data),
+        }))
+    }
+
+    #[test]
+    fn test_revert_reason() {
+        let error = contract_error(
+            "execution reverted",
+            &Liveness::AlreadyRegisteredSequencer {}.abi_encode(),
+        );
+        assert!(matches!(
+            RevertReason::from_contract_error(&error),
+            Some(RevertReason::Liveness(
+                Liveness::LivenessErrors::AlreadyRegisteredSequencer(_)
+            ))
+        ));
+
+        let error = contract_error(
+            "execution reverted",
+            &Revert::from("cluster is full").abi_encode(),
+        );
+        assert!(matches!(
+            RevertReason::from_contract_error(&error),
+            Some(RevertReason::Message(message)) if message == "revert: cluster is full"
+        ));
+
+        let error = contract_error("execution reverted", &[0xde, 0xad, 0xbe, 0xef]);
+        assert!(matches!(
+            RevertReason::from_contract_error(&error),
+            Some(RevertReason::Unknown(revert_data)) if revert_data[..] == [0xde, 0xad, 0xbe, 0xef]
+        ));
+
+        // Only reverts carry a reason.
+        let error = contract_error("nonce too low", &[]);
+        assert!(RevertReason::from_contract_error(&error).is_none());
+    }
+}
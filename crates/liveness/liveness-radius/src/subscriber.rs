@@ -1,25 +1,90 @@
 use std::{
+    collections::BTreeMap,
     future::Future,
+    ops::RangeInclusive,
     pin::Pin,
     str::FromStr,
+    sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use alloy::{
     eips::BlockNumberOrTag,
     primitives::Address,
     providers::{Provider, ProviderBuilder, WsConnect},
-    rpc::types::{Filter, Header, Log},
+    rpc::types::{BlockTransactionsKind, Filter, Header, Log},
     sol_types::SolEvent,
+    transports::Transport,
 };
-use futures::{stream::select_all, Stream, StreamExt};
+use futures::{future::try_join_all, stream::select_all, Stream, StreamExt};
 use pin_project::pin_project;
+use tokio::{
+    sync::mpsc::UnboundedSender,
+    time::{sleep, timeout_at, Instant},
+};
 
-use crate::types::{Events, Liveness};
+use crate::{
+    metrics::LivenessMetrics,
+    types::{ClusterId, Events, Liveness, RollupId},
+};
 
 pub struct Subscriber {
     connection_detail: WsConnect,
     liveness_contract_address: Address,
+    heartbeat: Option<Duration>,
+    confirmation: Option<Confirmation>,
+    cluster_id: Option<ClusterId>,
+    rollup_id: Option<RollupId>,
+    block_contents: Option<BlockContents>,
+    failure_policy: FailurePolicy,
+    failure_sender: Option<UnboundedSender<CallbackFailure>>,
+    metrics: Option<Arc<dyn LivenessMetrics>>,
+}
+
+/// When a block is considered safe from reorgs, see
+/// [`Subscriber::confirmation()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Confirmation {
+    /// The block has at least this many blocks built on top of it.
+    Blocks(u64),
+    /// The block is at or below the `finalized` block of the beacon chain.
+    Finalized,
+}
+
+/// What the subscriber does when the callback returns an error, see
+/// [`Subscriber::failure_policy()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Call the callback again with the same event and sequence number after
+    /// `delay`, up to `max_retries` times, then halt.
+    Retry { max_retries: u32, delay: Duration },
+    /// Move on to the next event.
+    Skip,
+    /// Stop and return [`SubscriberError::CallbackFailed`].
+    Halt,
+}
+
+/// An error returned by the callback, see [`Subscriber::failure_sender()`].
+#[derive(Debug)]
+pub struct CallbackFailure {
+    /// The sequence number of the event the callback failed for.
+    pub sequence: u64,
+    /// 0 for the first call, increased by one with every retry.
+    pub attempt: u32,
+    pub error: Box<dyn std::error::Error + Send + Sync>,
+}
+
+/// What the subscriber fetches for each new block, see
+/// [`Subscriber::block_contents()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockContents {
+    /// The block with its full transactions.
+    Transactions,
+    /// The block with its full transactions, and the receipts of the
+    /// transactions sent from one of these addresses, e.g. to check that the
+    /// transactions of the sequencer were included and succeeded.
+    Receipts(Vec<Address>),
 }
 
 impl Subscriber {
@@ -51,11 +116,103 @@ impl Subscriber {
         Ok(Self {
             connection_detail,
             liveness_contract_address,
+            heartbeat: None,
+            confirmation: None,
+            cluster_id: None,
+            rollup_id: None,
+            block_contents: None,
+            failure_policy: FailurePolicy::Halt,
+            failure_sender: None,
+            metrics: None,
         })
     }
 
+    /// Emit [`Events::Stalled`] and reconnect when no block arrives within
+    /// `heartbeat`, which guards against half-open websocket connections
+    /// that never deliver another event. The contract events still waiting
+    /// for their block are delivered before [`Events::Stalled`], and the
+    /// contract events emitted while reconnecting are read from the chain
+    /// after it, so that none is missed. Disabled by default.
+    pub fn heartbeat(mut self, heartbeat: Duration) -> Self {
+        self.heartbeat = Some(heartbeat);
+
+        self
+    }
+
+    /// Deliver contract events only once their block is confirmed, so that
+    /// events from blocks that get reorged out are never delivered. Events
+    /// are delivered right after the [`Events::Block`] that confirms them,
+    /// and are re-read from the canonical chain at that point instead of
+    /// being taken from the log subscription. Disabled by default.
+    pub fn confirmation(mut self, confirmation: Confirmation) -> Self {
+        self.confirmation = Some(confirmation);
+
+        self
+    }
+
+    /// Only deliver contract events of `cluster_id`. The Liveness contract
+    /// does not index cluster IDs, so the node only filters logs down to
+    /// Liveness events and the cluster ID is matched after decoding, before
+    /// the callback is called.
+    pub fn cluster_id(mut self, cluster_id: ClusterId) -> Self {
+        self.cluster_id = Some(cluster_id);
+
+        self
+    }
+
+    /// Only deliver the rollup events of `rollup_id`, in addition to
+    /// [`Subscriber::cluster_id()`]. Events that do not concern a single
+    /// rollup, such as `RegisteredSequencer`, are still delivered.
+    pub fn rollup_id(mut self, rollup_id: RollupId) -> Self {
+        self.rollup_id = Some(rollup_id);
+
+        self
+    }
+
+    /// Fetch the block of each new head, with `block_contents`, and deliver
+    /// it as [`Events::FullBlock`] instead of [`Events::Block`]. The fetch
+    /// takes one more request per block, and one per selected receipt.
+    /// Disabled by default.
+    pub fn block_contents(mut self, block_contents: BlockContents) -> Self {
+        self.block_contents = Some(block_contents);
+
+        self
+    }
+
+    /// Set what happens when the callback returns an error. Defaults to
+    /// [`FailurePolicy::Halt`], so that no event is dropped unnoticed.
+    pub fn failure_policy(mut self, failure_policy: FailurePolicy) -> Self {
+        self.failure_policy = failure_policy;
+
+        self
+    }
+
+    /// Send the errors returned by the callback to `failure_sender`, except
+    /// the one that halts the subscriber, which is returned in
+    /// [`SubscriberError::CallbackFailed`] instead.
+    pub fn failure_sender(mut self, failure_sender: UnboundedSender<CallbackFailure>) -> Self {
+        self.failure_sender = Some(failure_sender);
+
+        self
+    }
+
+    /// Report how many blocks the delivered events are behind the chain
+    /// head to `metrics`. Reading the chain head takes one more request per
+    /// block.
+    pub fn metrics(mut self, metrics: impl LivenessMetrics) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+
+        self
+    }
+
     /// Start listening to the Ethereum block creation and contract events.
     ///
+    /// Events are delivered in block order: the contract events of a block
+    /// come after the [`Events::Block`] or [`Events::FullBlock`] of that
+    /// block, even if the node sends the logs first. An event is done once
+    /// `callback` returns `Ok`; an error is handled according to
+    /// [`Subscriber::failure_policy()`] before the next event is delivered.
+    ///
     /// # WARNING
     ///
     /// This is a blocking operation unless spawned in a separate thread.
@@ -64,6 +221,13 @@ impl Subscriber {
     ///
     /// ```
     /// let context = Arc::new(String::from("context"));
+    /// let (failure_sender, mut failure_receiver) = tokio::sync::mpsc::unbounded_channel();
+    ///
+    /// tokio::spawn(async move {
+    ///     while let Some(failure) = failure_receiver.recv().await {
+    ///         // Log or report `failure.error`.
+    ///     }
+    /// });
     ///
     /// tokio::spawn(async move {
     ///     Subscriber::new(
@@ -71,16 +235,28 @@ impl Subscriber {
     ///         "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
     ///     )
     ///     .unwrap()
+    ///     .heartbeat(Duration::from_secs(60))
+    ///     .confirmation(Confirmation::Blocks(12))
+    ///     .cluster_id(ClusterId::new("cluster_id").unwrap())
+    ///     .failure_policy(FailurePolicy::Retry {
+    ///         max_retries: 5,
+    ///         delay: Duration::from_secs(1),
+    ///     })
+    ///     .failure_sender(failure_sender)
     ///     .initialize_event_handler(callback, ())
     ///     .await
     ///     .unwrap();
     /// });
     ///
-    /// async fn callback(events: Events, context: Arc<String>) {
+    /// async fn callback(events: Events, context: Arc<String>) -> Result<(), Error> {
     ///     match events {
     ///         Events::Block(block) => {
     ///             // Handle Ethereum block creation event.
     ///         }
+    ///         Events::FullBlock(block, receipts) => {
+    ///             // Handle Ethereum block creation event with
+    ///             // `Subscriber::block_contents()`.
+    ///         }
     ///         Events::LivenessEvents(liveness_event, log) => match liveness_event {
     ///             LivenessEvents::InitializeCluster(event) => {
     ///                 // Handle `InitializeCluster` event.
@@ -99,9 +275,11 @@ impl Subscriber {
     ///             }
     ///         },
     ///     }
+    ///
+    ///     Ok(())
     /// }
     /// ```
-    pub async fn initialize_event_handler<CB, CTX, F>(
+    pub async fn initialize_event_handler<CB, CTX, F, E>(
         &self,
         callback: CB,
         context: CTX,
@@ -109,39 +287,516 @@ impl Subscriber {
     where
         CB: Fn(Events, CTX) -> F,
         CTX: Clone + Send + Sync,
-        F: Future<Output = ()>,
+        F: Future<Output = Result<(), E>>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.initialize_sequenced_event_handler(
+            |_sequence, events, context| callback(events, context),
+            context,
+        )
+        .await
+    }
+
+    /// Same as [`Subscriber::initialize_event_handler()`], but also pass the
+    /// sequence number of each event to `callback`. Sequence numbers start at
+    /// 0 and increase by one with every event, including
+    /// [`Events::Stalled`], across reconnections, so a consumer that
+    /// persists the last one can tell where it stopped. A retried event keeps
+    /// its sequence number.
+    pub async fn initialize_sequenced_event_handler<CB, CTX, F, E>(
+        &self,
+        callback: CB,
+        context: CTX,
+    ) -> Result<(), SubscriberError>
+    where
+        CB: Fn(u64, Events, CTX) -> F,
+        CTX: Clone + Send + Sync,
+        F: Future<Output = Result<(), E>>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
     {
-        let provider = ProviderBuilder::new()
-            .on_ws(self.connection_detail.clone())
+        // The first block whose events are not delivered yet when waiting for
+        // confirmations. Kept across reconnections so that no block is
+        // skipped.
+        let mut next_block_number = None;
+        let mut sequence = 0;
+        let mut reorder_buffer = ReorderBuffer::default();
+        loop {
+            let provider = ProviderBuilder::new()
+                .on_ws(self.connection_detail.clone())
+                .await
+                .map_err(SubscriberError::WebsocketProvider)?;
+
+            let block_stream: EventStream = provider
+                .subscribe_blocks()
+                .await
+                .map_err(SubscriberError::SubscribeToBlock)?
+                .into_stream()
+                .boxed()
+                .into();
+
+            let mut event_streams = vec![block_stream];
+            if self.confirmation.is_none() {
+                let filter = self.log_filter().from_block(BlockNumberOrTag::Latest);
+
+                let liveness_event_stream: EventStream = provider
+                    .subscribe_logs(&filter)
+                    .await
+                    .map_err(SubscriberError::SubscribeToLogs)?
+                    .into_stream()
+                    .boxed()
+                    .into();
+                event_streams.push(liveness_event_stream);
+            }
+
+            // Without confirmations, the events emitted since the last block
+            // delivered before reconnecting are read from the chain. The live
+            // logs of these blocks are then duplicates.
+            let mut backfilled_block_number = None;
+            if let (None, Some(block_number)) =
+                (self.confirmation, reorder_buffer.head_block_number)
+            {
+                let (missed_events, head_block_number) =
+                    self.get_missed_events(&provider, block_number).await?;
+                for event in missed_events {
+                    self.deliver(&callback, sequence, event, &context).await?;
+                    sequence += 1;
+                }
+                reorder_buffer.head_block_number = Some(head_block_number);
+                backfilled_block_number = Some(head_block_number);
+            }
+
+            let mut event_stream = select_all(event_streams);
+            let mut deadline = self.heartbeat.map(|heartbeat| Instant::now() + heartbeat);
+            loop {
+                let event = match deadline {
+                    Some(deadline) => match timeout_at(deadline, event_stream.next()).await {
+                        Ok(event) => event,
+                        Err(_elapsed) => {
+                            // The new connection may never deliver the blocks
+                            // the held back events wait for.
+                            for event in reorder_buffer.flush() {
+                                self.deliver(&callback, sequence, event, &context).await?;
+                                sequence += 1;
+                            }
+                            self.deliver(&callback, sequence, Events::Stalled, &context)
+                                .await?;
+                            sequence += 1;
+                            break;
+                        }
+                    },
+                    None => event_stream.next().await,
+                };
+
+                let mut ready_events = Vec::new();
+                let mut delivered_block_number = None;
+                match event {
+                    Some(event) if !self.is_relevant(&event) => {}
+                    Some(event)
+                        if log_block_number(&event).is_some_and(|log_block_number| {
+                            Some(log_block_number) <= backfilled_block_number
+                        }) => {}
+                    Some(event) => {
+                        let event = match (event, &self.block_contents) {
+                            (Events::Block(header), Some(block_contents)) => {
+                                self.get_full_block(&provider, header, block_contents)
+                                    .await?
+                            }
+                            (event, _) => event,
+                        };
+                        let block_number = event_block_number(&event);
+                        if let (Some(_), Some(heartbeat)) = (block_number, self.heartbeat) {
+                            deadline = Some(Instant::now() + heartbeat);
+                        }
+
+                        ready_events = reorder_buffer.push(event);
+                        delivered_block_number = block_number;
+                    }
+                    None => return Err(SubscriberError::EventStreamDisconnected),
+                }
+
+                for event in ready_events {
+                    self.deliver(&callback, sequence, event, &context).await?;
+                    sequence += 1;
+                }
+
+                if let (Some(confirmation), Some(block_number)) =
+                    (self.confirmation, delivered_block_number)
+                {
+                    let confirmed_events = self
+                        .get_confirmed_events(
+                            &provider,
+                            confirmation,
+                            block_number,
+                            &mut next_block_number,
+                        )
+                        .await?;
+                    for event in confirmed_events {
+                        self.deliver(&callback, sequence, event, &context).await?;
+                        sequence += 1;
+                    }
+                }
+
+                // Without confirmations, the events of a block are
+                // delivered with the block itself.
+                let processed_block_number = match self.confirmation {
+                    Some(_) => {
+                        next_block_number.and_then(|block_number| block_number.checked_sub(1))
+                    }
+                    None => delivered_block_number,
+                };
+                if let (Some(_), Some(processed_block_number)) =
+                    (delivered_block_number, processed_block_number)
+                {
+                    self.report_lag(&provider, processed_block_number).await;
+                }
+            }
+        }
+    }
+
+    async fn report_lag<P, T>(&self, provider: &P, processed_block_number: u64)
+    where
+        P: Provider<T>,
+        T: Transport + Clone,
+    {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+
+        // Missing a sample is not a reason to stop delivering events.
+        if let Ok(head_block_number) = provider.get_block_number().await {
+            metrics.on_subscriber_lag(head_block_number.saturating_sub(processed_block_number));
+        }
+    }
+
+    /// Call `callback` with `event` and apply the failure policy if it fails.
+    async fn deliver<CB, CTX, F, E>(
+        &self,
+        callback: &CB,
+        sequence: u64,
+        event: Events,
+        context: &CTX,
+    ) -> Result<(), SubscriberError>
+    where
+        CB: Fn(u64, Events, CTX) -> F,
+        CTX: Clone,
+        F: Future<Output = Result<(), E>>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let mut event = event;
+        let mut attempt = 0;
+        loop {
+            // The callback takes the event, so keep a copy to retry with.
+            let retry_event = match self.failure_policy {
+                FailurePolicy::Retry { max_retries, .. } if attempt < max_retries => {
+                    copy_event(&event)
+                }
+                _ => None,
+            };
+
+            let error = match callback(sequence, event, context.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(error) => error.into(),
+            };
+            let failure = CallbackFailure {
+                sequence,
+                attempt,
+                error,
+            };
+
+            match (self.failure_policy, retry_event) {
+                (FailurePolicy::Skip, _) => {
+                    self.report_failure(failure);
+
+                    return Ok(());
+                }
+                (FailurePolicy::Retry { delay, .. }, Some(retry_event)) => {
+                    self.report_failure(failure);
+                    sleep(delay).await;
+
+                    event = retry_event;
+                    attempt += 1;
+                }
+                _ => return Err(SubscriberError::CallbackFailed(failure)),
+            }
+        }
+    }
+
+    fn report_failure(&self, failure: CallbackFailure) {
+        if let Some(failure_sender) = &self.failure_sender {
+            // Nobody listening is not a reason to stop delivering events.
+            let _ = failure_sender.send(failure);
+        }
+    }
+
+    /// Return [`Events::FullBlock`] for `header`, or [`Events::Block`] if the
+    /// block was reorged out before it could be fetched.
+    async fn get_full_block<P, T>(
+        &self,
+        provider: &P,
+        header: Header,
+        block_contents: &BlockContents,
+    ) -> Result<Events, SubscriberError>
+    where
+        P: Provider<T>,
+        T: Transport + Clone,
+    {
+        let Some(block) = provider
+            .get_block_by_hash(header.hash, BlockTransactionsKind::Full)
             .await
-            .map_err(SubscriberError::WebsocketProvider)?;
+            .map_err(SubscriberError::GetBlock)?
+        else {
+            return Ok(Events::Block(header));
+        };
+
+        let receipts = match block_contents {
+            BlockContents::Transactions => Vec::new(),
+            BlockContents::Receipts(addresses) => {
+                let receipts = block
+                    .transactions
+                    .txns()
+                    .filter(|transaction| addresses.contains(&transaction.from))
+                    .map(|transaction| {
+                        provider.get_transaction_receipt(*transaction.inner.tx_hash())
+                    });
 
-        let block_stream: EventStream = provider
-            .subscribe_blocks()
+                try_join_all(receipts)
+                    .await
+                    .map_err(SubscriberError::GetTransactionReceipt)?
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            }
+        };
+
+        Ok(Events::FullBlock(block, receipts))
+    }
+
+    /// Return the contract events of the blocks after `block_number` up to
+    /// the chain head, and the chain head.
+    async fn get_missed_events<P, T>(
+        &self,
+        provider: &P,
+        block_number: u64,
+    ) -> Result<(Vec<Events>, u64), SubscriberError>
+    where
+        P: Provider<T>,
+        T: Transport + Clone,
+    {
+        let head_block_number = provider
+            .get_block_number()
             .await
-            .map_err(SubscriberError::SubscribeToBlock)?
-            .into_stream()
-            .boxed()
-            .into();
+            .map_err(SubscriberError::GetBlockNumber)?;
+        if head_block_number <= block_number {
+            return Ok((Vec::new(), block_number));
+        }
 
-        let filter = Filter::new()
-            .address(self.liveness_contract_address)
-            .from_block(BlockNumberOrTag::Latest);
+        let filter = self
+            .log_filter()
+            .from_block(block_number + 1)
+            .to_block(head_block_number);
+        let logs = provider
+            .get_logs(&filter)
+            .await
+            .map_err(SubscriberError::GetLogs)?;
+        let events = logs
+            .into_iter()
+            .filter_map(EventStream::decode_log)
+            .filter(|event| self.is_relevant(event))
+            .collect();
 
-        let liveness_event_stream: EventStream = provider
-            .subscribe_logs(&filter)
+        Ok((events, head_block_number))
+    }
+
+    /// Return the events of the blocks from `next_block_number` up to the
+    /// latest block confirmed at `block_number`, and advance
+    /// `next_block_number` past them.
+    async fn get_confirmed_events<P, T>(
+        &self,
+        provider: &P,
+        confirmation: Confirmation,
+        block_number: u64,
+        next_block_number: &mut Option<u64>,
+    ) -> Result<Vec<Events>, SubscriberError>
+    where
+        P: Provider<T>,
+        T: Transport + Clone,
+    {
+        let confirmed_block_number = match confirmation {
+            Confirmation::Blocks(confirmations) => block_number.checked_sub(confirmations),
+            Confirmation::Finalized => provider
+                .get_block_by_number(BlockNumberOrTag::Finalized, BlockTransactionsKind::Hashes)
+                .await
+                .map_err(SubscriberError::GetFinalizedBlock)?
+                .map(|block| block.header.number),
+        };
+        let Some(confirmed_block_number) = confirmed_block_number else {
+            return Ok(Vec::new());
+        };
+
+        let Some(block_range) = confirmed_block_range(next_block_number, confirmed_block_number)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let filter = self
+            .log_filter()
+            .from_block(*block_range.start())
+            .to_block(*block_range.end());
+        let logs = provider
+            .get_logs(&filter)
             .await
-            .map_err(SubscriberError::SubscribeToLogs)?
-            .into_stream()
-            .boxed()
-            .into();
-
-        let mut event_stream = select_all(vec![block_stream, liveness_event_stream]);
-        while let Some(event) = event_stream.next().await {
-            callback(event, context.clone()).await;
+            .map_err(SubscriberError::GetLogs)?;
+
+        Ok(logs
+            .into_iter()
+            .filter_map(EventStream::decode_log)
+            .filter(|event| self.is_relevant(event))
+            .collect())
+    }
+
+    /// Match the logs of the Liveness events decoded by
+    /// [`EventStream::decode_log()`] emitted by the contract.
+    fn log_filter(&self) -> Filter {
+        Filter::new()
+            .address(self.liveness_contract_address)
+            .event_signature(vec![
+                Liveness::InitializedCluster::SIGNATURE_HASH,
+                Liveness::RegisteredSequencer::SIGNATURE_HASH,
+                Liveness::DeregisteredSequencer::SIGNATURE_HASH,
+                Liveness::AddedRollup::SIGNATURE_HASH,
+                Liveness::RegisteredRollupExecutor::SIGNATURE_HASH,
+            ])
+    }
+
+    fn is_relevant(&self, event: &Events) -> bool {
+        let Events::LivenessEvents(event, _log) = event else {
+            return true;
+        };
+
+        let (cluster_id, rollup_id) = match event {
+            Liveness::LivenessEvents::InitializedCluster(event) => (&event.clusterId, None),
+            Liveness::LivenessEvents::RegisteredSequencer(event) => (&event.clusterId, None),
+            Liveness::LivenessEvents::DeregisteredSequencer(event) => (&event.clusterId, None),
+            Liveness::LivenessEvents::AddedRollup(event) => {
+                (&event.clusterId, Some(&event.rollupId))
+            }
+            Liveness::LivenessEvents::RegisteredRollupExecutor(event) => {
+                (&event.clusterId, Some(&event.rollupId))
+            }
+            _ => return true,
+        };
+
+        let is_cluster_relevant = self
+            .cluster_id
+            .as_ref()
+            .map(|expected| expected == cluster_id)
+            .unwrap_or(true);
+        let is_rollup_relevant = match (&self.rollup_id, rollup_id) {
+            (Some(expected), Some(rollup_id)) => expected == rollup_id,
+            _ => true,
+        };
+
+        is_cluster_relevant && is_rollup_relevant
+    }
+}
+
+/// Holds back the contract events of blocks newer than the latest block
+/// delivered, as the node may send the logs of a block before the block
+/// itself.
+#[derive(Default)]
+struct ReorderBuffer {
+    head_block_number: Option<u64>,
+    pending_events: BTreeMap<u64, Vec<Events>>,
+}
+
+impl ReorderBuffer {
+    /// Return the events to deliver now that `event` arrived, in order: a
+    /// block is followed by the held back events of the blocks up to it.
+    fn push(&mut self, event: Events) -> Vec<Events> {
+        if let Some(block_number) = event_block_number(&event) {
+            self.head_block_number = self.head_block_number.max(Some(block_number));
+            let later_events = self.pending_events.split_off(&(block_number + 1));
+
+            return std::iter::once(event)
+                .chain(
+                    std::mem::replace(&mut self.pending_events, later_events)
+                        .into_values()
+                        .flatten(),
+                )
+                .collect();
+        }
+
+        match log_block_number(&event) {
+            Some(log_block_number) if Some(log_block_number) > self.head_block_number => {
+                self.pending_events
+                    .entry(log_block_number)
+                    .or_default()
+                    .push(event);
+
+                Vec::new()
+            }
+            _ => vec![event],
+        }
+    }
+
+    /// Return the held back events in block order. Their blocks count as
+    /// delivered afterwards.
+    fn flush(&mut self) -> Vec<Events> {
+        if let Some((&block_number, _)) = self.pending_events.last_key_value() {
+            self.head_block_number = self.head_block_number.max(Some(block_number));
         }
 
-        Err(SubscriberError::EventStreamDisconnected)
+        std::mem::take(&mut self.pending_events)
+            .into_values()
+            .flatten()
+            .collect()
+    }
+}
+
+/// The blocks whose events are delivered once `confirmed_block_number` is
+/// confirmed: from `next_block_number`, or on the first call from the blocks
+/// confirmed after the subscription started, up to `confirmed_block_number`.
+/// `next_block_number` is advanced past them.
+fn confirmed_block_range(
+    next_block_number: &mut Option<u64>,
+    confirmed_block_number: u64,
+) -> Option<RangeInclusive<u64>> {
+    let from_block_number = *next_block_number.get_or_insert(confirmed_block_number + 1);
+    if from_block_number > confirmed_block_number {
+        return None;
+    }
+    *next_block_number = Some(confirmed_block_number + 1);
+
+    Some(from_block_number..=confirmed_block_number)
+}
+
+/// The block number of [`Events::Block`] and [`Events::FullBlock`].
+fn event_block_number(event: &Events) -> Option<u64> {
+    match event {
+        Events::Block(header) => Some(header.number),
+        Events::FullBlock(block, _) => Some(block.header.number),
+        _ => None,
+    }
+}
+
+/// The block number of the log of a contract event.
+fn log_block_number(event: &Events) -> Option<u64> {
+    match event {
+        Events::LivenessEvents(_, log) => log.block_number,
+        _ => None,
+    }
+}
+
+/// Copy `event` to deliver it again. The contract events are not `Clone`,
+/// so they are decoded again from their log.
+fn copy_event(event: &Events) -> Option<Events> {
+    match event {
+        Events::Block(header) => Some(Events::Block(header.clone())),
+        Events::FullBlock(block, receipts) => {
+            Some(Events::FullBlock(block.clone(), receipts.clone()))
+        }
+        Events::LivenessEvents(_, log) => EventStream::decode_log(log.clone()),
+        Events::Stalled => Some(Events::Stalled),
     }
 }
 
@@ -241,7 +896,15 @@ pub enum SubscriberError {
     NewBlockEventStream(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
     SubscribeToBlock(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
     SubscribeToLogs(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    GetBlockNumber(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    GetFinalizedBlock(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    GetLogs(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    GetBlock(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    GetTransactionReceipt(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
     EventStreamDisconnected,
+    /// The callback failed and [`Subscriber::failure_policy()`] gave up on
+    /// the event.
+    CallbackFailed(CallbackFailure),
 }
 
 impl std::fmt::Display for SubscriberError {
@@ -251,3 +914,229 @@ impl std::fmt::Display for SubscriberError {
 }
 
 impl std::error::Error for SubscriberError {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use alloy::{
+        consensus,
+        primitives::{self, U256},
+        sol_types::SolEvent,
+    };
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+    use super::*;
+
+    fn block(block_number: u64) -> Events {
+        Events::Block(Header {
+            inner: consensus::Header {
+                number: block_number,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    fn registered_sequencer(block_number: u64, log_index: u64) -> Events {
+        let event = Liveness::RegisteredSequencer {
+            clusterId: "cluster".to_owned(),
+            sequencer: Address::ZERO,
+            index: U256::from(log_index),
+        };
+        let log = Log {
+            inner: primitives::Log {
+                address: Address::ZERO,
+                data: event.encode_log_data(),
+            },
+            block_number: Some(block_number),
+            log_index: Some(log_index),
+            ..Default::default()
+        };
+
+        EventStream::decode_log(log).unwrap()
+    }
+
+    /// The block number of each event, with the log index of contract
+    /// events.
+    fn positions(events: &[Events]) -> Vec<(u64, Option<u64>)> {
+        events
+            .iter()
+            .map(|event| match event {
+                Events::LivenessEvents(_, log) => (log.block_number.unwrap(), log.log_index),
+                event => (event_block_number(event).unwrap(), None),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reorder_buffer_block_order() {
+        let mut reorder_buffer = ReorderBuffer::default();
+
+        assert_eq!(positions(&reorder_buffer.push(block(1))), [(1, None)]);
+        assert!(reorder_buffer.push(registered_sequencer(3, 0)).is_empty());
+        assert!(reorder_buffer.push(registered_sequencer(2, 0)).is_empty());
+        assert_eq!(
+            positions(&reorder_buffer.push(block(2))),
+            [(2, None), (2, Some(0))]
+        );
+        assert_eq!(
+            positions(&reorder_buffer.push(block(3))),
+            [(3, None), (3, Some(0))]
+        );
+
+        // The logs of a delivered block are not held back.
+        assert_eq!(
+            positions(&reorder_buffer.push(registered_sequencer(3, 1))),
+            [(3, Some(1))]
+        );
+    }
+
+    #[test]
+    fn test_reorder_buffer_log_order() {
+        let mut reorder_buffer = ReorderBuffer::default();
+        reorder_buffer.push(block(1));
+
+        for log_index in 0..3 {
+            assert!(reorder_buffer
+                .push(registered_sequencer(2, log_index))
+                .is_empty());
+        }
+        assert_eq!(
+            positions(&reorder_buffer.push(block(2))),
+            [(2, None), (2, Some(0)), (2, Some(1)), (2, Some(2))]
+        );
+    }
+
+    #[test]
+    fn test_reorder_buffer_flush() {
+        let mut reorder_buffer = ReorderBuffer::default();
+        reorder_buffer.push(block(1));
+        reorder_buffer.push(registered_sequencer(3, 0));
+        reorder_buffer.push(registered_sequencer(2, 0));
+        reorder_buffer.push(registered_sequencer(2, 1));
+
+        assert_eq!(
+            positions(&reorder_buffer.flush()),
+            [(2, Some(0)), (2, Some(1)), (3, Some(0))]
+        );
+        assert!(reorder_buffer.flush().is_empty());
+        assert_eq!(reorder_buffer.head_block_number, Some(3));
+        assert_eq!(positions(&reorder_buffer.push(block(3))), [(3, None)]);
+    }
+
+    #[test]
+    fn test_confirmed_block_range() {
+        let mut next_block_number = None;
+
+        // Blocks confirmed before the subscription started are not delivered.
+        assert_eq!(confirmed_block_range(&mut next_block_number, 88), None);
+        assert_eq!(next_block_number, Some(89));
+        assert_eq!(confirmed_block_range(&mut next_block_number, 88), None);
+
+        assert_eq!(
+            confirmed_block_range(&mut next_block_number, 89),
+            Some(89..=89)
+        );
+        assert_eq!(next_block_number, Some(90));
+
+        // Blocks confirmed together, e.g. after a reconnection, are delivered
+        // together, and none of them twice.
+        assert_eq!(
+            confirmed_block_range(&mut next_block_number, 93),
+            Some(90..=93)
+        );
+        assert_eq!(next_block_number, Some(94));
+        assert_eq!(confirmed_block_range(&mut next_block_number, 93), None);
+    }
+
+    fn subscriber(
+        failure_policy: FailurePolicy,
+    ) -> (Subscriber, UnboundedReceiver<CallbackFailure>) {
+        let (failure_sender, failure_receiver) = unbounded_channel();
+        let subscriber = Subscriber::new(
+            "ws://127.0.0.1:8545",
+            "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+        )
+        .unwrap()
+        .failure_policy(failure_policy)
+        .failure_sender(failure_sender);
+
+        (subscriber, failure_receiver)
+    }
+
+    /// Deliver an event to a callback failing its first `failures` calls and
+    /// return the result and the number of calls.
+    async fn deliver(subscriber: &Subscriber, failures: u32) -> (Result<(), SubscriberError>, u32) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let callback = move |_sequence, _event, calls: Arc<AtomicU32>| async move {
+            match calls.fetch_add(1, Ordering::SeqCst) < failures {
+                true => Err("callback failed"),
+                false => Ok(()),
+            }
+        };
+        let result = subscriber
+            .deliver(&callback, 7, registered_sequencer(1, 0), &calls)
+            .await;
+
+        (result, calls.load(Ordering::SeqCst))
+    }
+
+    fn attempts(failure_receiver: &mut UnboundedReceiver<CallbackFailure>) -> Vec<(u64, u32)> {
+        std::iter::from_fn(|| failure_receiver.try_recv().ok())
+            .map(|failure| (failure.sequence, failure.attempt))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_failure_policy_retry() {
+        let (subscriber, mut failure_receiver) = subscriber(FailurePolicy::Retry {
+            max_retries: 2,
+            delay: Duration::ZERO,
+        });
+
+        let (result, calls) = deliver(&subscriber, 1).await;
+        assert!(result.is_ok());
+        assert_eq!(calls, 2);
+        assert_eq!(attempts(&mut failure_receiver), [(7, 0)]);
+
+        let (result, calls) = deliver(&subscriber, u32::MAX).await;
+        assert!(matches!(
+            result,
+            Err(SubscriberError::CallbackFailed(CallbackFailure {
+                sequence: 7,
+                attempt: 2,
+                ..
+            }))
+        ));
+        assert_eq!(calls, 3);
+        assert_eq!(attempts(&mut failure_receiver), [(7, 0), (7, 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_failure_policy_skip() {
+        let (subscriber, mut failure_receiver) = subscriber(FailurePolicy::Skip);
+
+        let (result, calls) = deliver(&subscriber, u32::MAX).await;
+        assert!(result.is_ok());
+        assert_eq!(calls, 1);
+        assert_eq!(attempts(&mut failure_receiver), [(7, 0)]);
+    }
+
+    #[tokio::test]
+    async fn test_failure_policy_halt() {
+        let (subscriber, mut failure_receiver) = subscriber(FailurePolicy::Halt);
+
+        let (result, calls) = deliver(&subscriber, u32::MAX).await;
+        assert!(matches!(
+            result,
+            Err(SubscriberError::CallbackFailed(CallbackFailure {
+                sequence: 7,
+                attempt: 0,
+                ..
+            }))
+        ));
+        assert_eq!(calls, 1);
+        assert!(attempts(&mut failure_receiver).is_empty());
+    }
+}
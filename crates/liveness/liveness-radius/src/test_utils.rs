@@ -0,0 +1,368 @@
+//! A local devnet for integration tests, behind the `test-utils` feature.
+//!
+//! [`Devnet::spawn()`] starts `anvil`, which must be on `PATH`, and deploys
+//! the Liveness contract from its first account. The node is killed when the
+//! [`Devnet`] is dropped.
+//!
+//! ```no_run
+//! use liveness_radius::{
+//!     test_utils::Devnet,
+//!     types::{primitives::U256, ClusterId},
+//! };
+//!
+//! # async fn run() {
+//! let devnet = Devnet::spawn().await.unwrap();
+//! let publisher = devnet.publisher(0).unwrap();
+//! publisher
+//!     .initialize_cluster(&ClusterId::new("cluster").unwrap(), U256::from(30))
+//!     .await
+//!     .unwrap();
+//!
+//! let subscriber = devnet.subscriber().unwrap();
+//! # }
+//! ```
+//!
+//! [`ViewOverrides`] runs the view calls of a [`Publisher`] as if they were
+//! made at another block number and timestamp, or against a fork of the
+//! chain, to test logic that depends on the block margin.
+use alloy::{
+    contract,
+    eips::BlockId,
+    network::EthereumWallet,
+    node_bindings::{Anvil, AnvilInstance},
+    primitives::{Address, Bytes, U256},
+    providers::{Provider, ProviderBuilder, RootProvider},
+    rpc::types::{state::StateOverride, BlockOverrides, TransactionRequest},
+    signers::local::{LocalSigner, PrivateKeySigner},
+    sol_types::SolCall,
+    transports::http::{reqwest::Url, Client, Http},
+};
+
+use crate::{
+    publisher::{Publisher, PublisherError},
+    subscriber::{Subscriber, SubscriberError},
+    types::Liveness,
+};
+
+#[derive(Default)]
+pub struct DevnetBuilder {
+    block_time: Option<u64>,
+    chain_id: Option<u64>,
+}
+
+impl DevnetBuilder {
+    /// Mine a block every `block_time` seconds instead of one per
+    /// transaction.
+    pub fn block_time(mut self, block_time: u64) -> Self {
+        self.block_time = Some(block_time);
+
+        self
+    }
+
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+
+        self
+    }
+
+    pub async fn spawn(self) -> Result<Devnet, DevnetError> {
+        let mut anvil = Anvil::new();
+        if let Some(block_time) = self.block_time {
+            anvil = anvil.block_time(block_time);
+        }
+        if let Some(chain_id) = self.chain_id {
+            anvil = anvil.chain_id(chain_id);
+        }
+        let anvil = anvil.try_spawn().map_err(DevnetError::SpawnAnvil)?;
+
+        let deployer = LocalSigner::from(anvil.keys()[0].clone());
+        let provider = ProviderBuilder::new()
+            .with_recommended_fillers()
+            .wallet(EthereumWallet::new(deployer))
+            .on_http(anvil.endpoint_url());
+        let liveness_contract = Liveness::deploy(&provider)
+            .await
+            .map_err(DevnetError::DeployLiveness)?;
+
+        Ok(Devnet {
+            liveness_contract_address: *liveness_contract.address(),
+            anvil,
+        })
+    }
+}
+
+/// An `anvil` node with the Liveness contract deployed, see the
+/// [module documentation](self).
+pub struct Devnet {
+    anvil: AnvilInstance,
+    liveness_contract_address: Address,
+}
+
+impl Devnet {
+    pub fn builder() -> DevnetBuilder {
+        DevnetBuilder::default()
+    }
+
+    /// Spawn a devnet that mines a block per transaction.
+    pub async fn spawn() -> Result<Self, DevnetError> {
+        Self::builder().spawn().await
+    }
+
+    pub fn rpc_url(&self) -> String {
+        self.anvil.endpoint()
+    }
+
+    pub fn websocket_url(&self) -> String {
+        self.anvil.ws_endpoint()
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.anvil.chain_id()
+    }
+
+    pub fn liveness_contract_address(&self) -> Address {
+        self.liveness_contract_address
+    }
+
+    /// The addresses of the prefunded accounts of `anvil`. The first one
+    /// deployed the Liveness contract.
+    pub fn addresses(&self) -> &[Address] {
+        self.anvil.addresses()
+    }
+
+    /// The hex-encoded signing key of the prefunded account at `index`.
+    pub fn signing_key(&self, index: usize) -> Result<String, DevnetError> {
+        let key = self
+            .anvil
+            .keys()
+            .get(index)
+            .ok_or(DevnetError::AccountIndex(index))?;
+
+        Ok(alloy::hex::encode_prefixed(key.to_bytes()))
+    }
+
+    /// A [`Publisher`] sending transactions from the prefunded account at
+    /// `index`.
+    ///
+    /// `anvil` has no Multicall3 contract, so the methods of [`Publisher`]
+    /// that batch calls need [`Publisher::with_multicall_address()`] and a
+    /// Multicall3 deployment of the test's own.
+    pub fn publisher(&self, index: usize) -> Result<Publisher, DevnetError> {
+        Publisher::new(
+            self.rpc_url(),
+            self.signing_key(index)?,
+            self.liveness_contract_address.to_string(),
+        )
+        .map_err(DevnetError::Publisher)
+    }
+
+    pub fn subscriber(&self) -> Result<Subscriber, DevnetError> {
+        Subscriber::new(
+            self.websocket_url(),
+            self.liveness_contract_address.to_string(),
+        )
+        .map_err(DevnetError::Subscriber)
+    }
+
+    /// Set the balance of `address` to `balance` wei.
+    pub async fn fund(&self, address: Address, balance: U256) -> Result<(), DevnetError> {
+        let provider = ProviderBuilder::new().on_http(self.anvil.endpoint_url());
+        provider
+            .raw_request::<_, ()>("anvil_setBalance".into(), (address, balance))
+            .await
+            .map_err(DevnetError::Fund)
+    }
+
+    /// Create an account holding `balance` wei and return its hex-encoded
+    /// signing key, e.g. for a sequencer that must not share a nonce with
+    /// the other accounts of the test.
+    pub async fn funded_signing_key(&self, balance: U256) -> Result<String, DevnetError> {
+        let signer = PrivateKeySigner::random();
+        self.fund(signer.address(), balance).await?;
+
+        Ok(alloy::hex::encode_prefixed(signer.to_bytes()))
+    }
+}
+
+/// Overrides of the view calls of a [`Publisher`], set with
+/// [`Publisher::with_view_overrides()`].
+///
+/// Block overrides are the fourth parameter of `eth_call`, supported by
+/// `anvil` and geth but not by every RPC provider.
+///
+/// ```no_run
+/// use liveness_radius::{
+///     test_utils::{Devnet, ViewOverrides},
+///     types::ClusterId,
+/// };
+///
+/// # async fn run() {
+/// let devnet = Devnet::spawn().await.unwrap();
+/// let publisher = devnet.publisher(0).unwrap().with_view_overrides(
+///     ViewOverrides::new()
+///         .block_number(1_000)
+///         .block_timestamp(1_700_000_000),
+/// );
+///
+/// // Read at block 990 as seen from block 1000.
+/// let sequencer_list = publisher
+///     .get_sequencer_list(&ClusterId::new("cluster").unwrap(), Some(990.into()))
+///     .await;
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ViewOverrides {
+    provider: Option<RootProvider<Http<Client>>>,
+    block_overrides: BlockOverrides,
+}
+
+impl ViewOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send the view calls to `rpc_url` instead, e.g. an `anvil --fork-url`
+    /// of the chain the publisher sends its transactions to.
+    pub fn rpc_url(mut self, rpc_url: impl AsRef<str>) -> Result<Self, PublisherError> {
+        let rpc_url: Url = rpc_url
+            .as_ref()
+            .parse()
+            .map_err(|error| PublisherError::ParseEthereumRpcUrl(Box::new(error)))?;
+        self.provider = Some(ProviderBuilder::new().on_http(rpc_url));
+
+        Ok(self)
+    }
+
+    /// Run the view calls with `block.number` set to `block_number`. It is
+    /// also what [`Publisher::get_block_number()`] returns.
+    pub fn block_number(mut self, block_number: u64) -> Self {
+        self.block_overrides.number = Some(U256::from(block_number));
+
+        self
+    }
+
+    /// Run the view calls with `block.timestamp` set to `block_timestamp`, in
+    /// seconds.
+    pub fn block_timestamp(mut self, block_timestamp: u64) -> Self {
+        self.block_overrides.time = Some(block_timestamp);
+
+        self
+    }
+
+    pub(crate) fn block_number_override(&self) -> Option<u64> {
+        self.block_overrides.number.map(|number| number.to())
+    }
+
+    pub(crate) async fn call<C, P>(
+        &self,
+        provider: &P,
+        request: TransactionRequest,
+        block: BlockId,
+    ) -> Result<C::Return, contract::Error>
+    where
+        C: SolCall,
+        P: Provider<Http<Client>>,
+    {
+        let parameters = (
+            request,
+            block,
+            StateOverride::default(),
+            &self.block_overrides,
+        );
+        let output: Bytes = match &self.provider {
+            Some(view_provider) => {
+                view_provider
+                    .client()
+                    .request("eth_call", &parameters)
+                    .await?
+            }
+            None => provider.client().request("eth_call", &parameters).await?,
+        };
+
+        Ok(C::abi_decode_returns(&output, true)?)
+    }
+}
+
+#[derive(Debug)]
+pub enum DevnetError {
+    SpawnAnvil(alloy::node_bindings::NodeError),
+    DeployLiveness(alloy::contract::Error),
+    AccountIndex(usize),
+    Fund(alloy::transports::TransportError),
+    Publisher(PublisherError),
+    Subscriber(SubscriberError),
+}
+
+impl std::fmt::Display for DevnetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for DevnetError {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::{
+        sync::mpsc::{unbounded_channel, UnboundedSender},
+        time::timeout,
+    };
+
+    use super::*;
+    use crate::types::{ClusterId, Events};
+
+    #[tokio::test]
+    async fn test_register_sequencer_event() {
+        let devnet = Devnet::builder().block_time(1).spawn().await.unwrap();
+        let cluster_id = ClusterId::new("cluster").unwrap();
+        devnet
+            .publisher(0)
+            .unwrap()
+            .initialize_cluster(&cluster_id, U256::from(30))
+            .await
+            .unwrap();
+
+        let (event_sender, mut event_receiver) = unbounded_channel();
+        let subscriber = devnet.subscriber().unwrap().cluster_id(cluster_id.clone());
+        tokio::spawn(async move {
+            subscriber
+                .initialize_event_handler(
+                    |events, event_sender: UnboundedSender<Events>| async move {
+                        event_sender.send(events).map_err(|_| "the test ended")
+                    },
+                    event_sender,
+                )
+                .await
+        });
+
+        // The subscription is live once the first block is delivered.
+        let events = timeout(Duration::from_secs(10), event_receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(events, Events::Block(_)));
+
+        let publisher = devnet.publisher(1).unwrap();
+        publisher.register_sequencer(&cluster_id).await.unwrap();
+
+        let registered_sequencer = timeout(Duration::from_secs(10), async {
+            while let Some(events) = event_receiver.recv().await {
+                if let Events::LivenessEvents(
+                    Liveness::LivenessEvents::RegisteredSequencer(event),
+                    _log,
+                ) = events
+                {
+                    return event;
+                }
+            }
+
+            panic!("the subscriber stopped");
+        })
+        .await
+        .unwrap();
+        assert_eq!(registered_sequencer.clusterId, cluster_id.as_str());
+        assert_eq!(registered_sequencer.sequencer, publisher.address());
+    }
+}
@@ -1,13 +1,237 @@
-pub use alloy::{primitives, rpc};
+pub use alloy::{eips, primitives, rpc};
+use serde::{Deserialize, Serialize};
 
 alloy::sol!(
     #[allow(missing_docs)]
     #[sol(rpc)]
+    #[derive(Debug)]
     Liveness,
     "src/contract/LivenessRadius.json"
 );
 
+alloy::sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Call3Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Call3Result[] memory returnData);
+    }
+);
+
+/// The address Multicall3 is deployed at on most EVM chains.
+pub const MULTICALL3_ADDRESS: primitives::Address =
+    primitives::address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// The block the view methods of [`crate::publisher::Publisher`] read the
+/// contract state at. Reading at [`BlockTag::Safe`] or
+/// [`BlockTag::Finalized`] avoids acting on state that a reorg may undo.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlockTag {
+    #[default]
+    Latest,
+    Safe,
+    Finalized,
+    Number(u64),
+}
+
+impl From<u64> for BlockTag {
+    fn from(value: u64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<BlockTag> for eips::BlockId {
+    fn from(value: BlockTag) -> Self {
+        match value {
+            BlockTag::Latest => eips::BlockNumberOrTag::Latest.into(),
+            BlockTag::Safe => eips::BlockNumberOrTag::Safe.into(),
+            BlockTag::Finalized => eips::BlockNumberOrTag::Finalized.into(),
+            BlockTag::Number(block_number) => block_number.into(),
+        }
+    }
+}
+
+/// A page of the sequencer list returned by
+/// [`crate::publisher::Publisher::get_sequencer_list_page()`].
+#[derive(Clone, Debug)]
+pub struct SequencerListPage {
+    pub sequencer_list: Vec<primitives::Address>,
+    /// The offset of the next page, or `None` if this is the last page.
+    pub next_offset: Option<usize>,
+}
+
+/// The cluster state returned by
+/// [`crate::publisher::Publisher::get_cluster_snapshot()`].
+#[derive(Clone, Debug)]
+pub struct ClusterSnapshot {
+    pub sequencer_list: Vec<primitives::Address>,
+    pub rollup_info_list: Vec<ILivenessRadius::Rollup>,
+    pub max_sequencer_number: primitives::U256,
+}
+
 pub enum Events {
     Block(rpc::types::Header),
     LivenessEvents(Liveness::LivenessEvents, rpc::types::Log),
+    /// A new block with the contents selected with
+    /// [`crate::subscriber::Subscriber::block_contents()`], delivered instead
+    /// of [`Events::Block`]. The receipts are empty unless
+    /// [`crate::subscriber::BlockContents::Receipts`] is selected.
+    FullBlock(rpc::types::Block, Vec<rpc::types::TransactionReceipt>),
+    /// No block arrived within the heartbeat set by
+    /// [`crate::subscriber::Subscriber::heartbeat()`]. The subscriber
+    /// reconnects after emitting this event.
+    Stalled,
+}
+
+macro_rules! impl_id {
+    ($(#[$attribute:meta])* $name:ident, $kind:literal) => {
+        $(#[$attribute])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(id: impl Into<String>) -> Result<Self, IdError> {
+                let id = id.into();
+                validate_id($kind, &id)?;
+
+                Ok(Self(id))
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = IdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::new(s)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = IdError;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                Self::new(value)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = IdError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                Self::new(value)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+impl_id!(
+    /// The ID of a cluster in the Liveness contract, which only rejects
+    /// empty IDs. Serialized as a plain string.
+    ClusterId,
+    "cluster"
+);
+
+impl_id!(
+    /// The ID of a rollup within a cluster, which like [`ClusterId`] must
+    /// not be empty.
+    RollupId,
+    "rollup"
+);
+
+fn validate_id(kind: &'static str, id: &str) -> Result<(), IdError> {
+    match id.is_empty() {
+        true => Err(IdError::Empty(kind)),
+        false => Ok(()),
+    }
+}
+
+/// Each variant names the kind of ID, `"cluster"` or `"rollup"`.
+#[derive(Debug)]
+pub enum IdError {
+    Empty(&'static str),
+}
+
+impl std::fmt::Display for IdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for IdError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id() {
+        let cluster_id = ClusterId::new("cluster").unwrap();
+        assert_eq!(cluster_id.as_str(), "cluster");
+        assert_eq!(cluster_id, "cluster".parse::<ClusterId>().unwrap());
+        assert_eq!(cluster_id, ClusterId::try_from("cluster").unwrap());
+        assert_eq!(cluster_id.into_inner(), "cluster");
+
+        // Any non-empty string is accepted, as by the contract.
+        assert!(RollupId::new("rollup id/with:any chars").is_ok());
+
+        assert!(matches!(ClusterId::new(""), Err(IdError::Empty("cluster"))));
+        assert!(matches!(RollupId::new(""), Err(IdError::Empty("rollup"))));
+    }
+
+    #[test]
+    fn test_id_serde() {
+        let rollup_id = RollupId::new("rollup").unwrap();
+        let serialized = serde_json::to_string(&rollup_id).unwrap();
+        assert_eq!(serialized, r#""rollup""#);
+        assert_eq!(
+            serde_json::from_str::<RollupId>(&serialized).unwrap(),
+            rollup_id
+        );
+
+        assert!(serde_json::from_str::<RollupId>(r#""""#).is_err());
+    }
 }
@@ -1,8 +1,9 @@
 use std::hash::Hash;
 
 use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
 
-use crate::{chain_type::*, error::SignatureError, Builder};
+use crate::{chain_type::*, error::SignatureError};
 
 #[derive(Clone, Debug, Eq, Hash, Deserialize, Serialize)]
 #[serde(try_from = "AddressType")]
@@ -15,9 +16,11 @@ enum AddressType {
     String(String),
 }
 
+/// Compare in constant time with respect to the contents. Only the lengths
+/// are compared in variable time.
 impl<T: AsRef<[u8]>> std::cmp::PartialEq<T> for Address {
     fn eq(&self, other: &T) -> bool {
-        self.0.as_slice() == other.as_ref()
+        self.0.as_slice().ct_eq(other.as_ref()).into()
     }
 }
 
@@ -58,6 +61,18 @@ impl Address {
         chain_type.address_builder().build_from_str(str)
     }
 
+    /// Derive the address of a public key, SEC1-encoded (compressed or
+    /// uncompressed) for [`ChainType::Ethereum`] and 32 bytes for
+    /// [`ChainType::Ed25519`].
+    pub fn from_public_key(
+        chain_type: ChainType,
+        public_key: &[u8],
+    ) -> Result<Self, SignatureError> {
+        chain_type
+            .public_key_builder()
+            .build_from_public_key(public_key)
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -0,0 +1,71 @@
+//! Conversions between [`Address`], [`Signature`] and their alloy
+//! counterparts, e.g. to pass a sequencer address or signature as a contract
+//! call argument.
+//!
+//! Only Ethereum values convert to alloy types: conversions from an address
+//! that is not 20 bytes long or a signature that is not 65 bytes long fail.
+use alloy::primitives;
+
+use crate::{Address, Signature, SignatureError};
+
+impl From<primitives::Address> for Address {
+    fn from(value: primitives::Address) -> Self {
+        value.to_vec().into()
+    }
+}
+
+impl From<&primitives::Address> for Address {
+    fn from(value: &primitives::Address) -> Self {
+        value.to_vec().into()
+    }
+}
+
+impl TryFrom<&Address> for primitives::Address {
+    type Error = SignatureError;
+
+    fn try_from(value: &Address) -> Result<Self, Self::Error> {
+        let address = <[u8; 20]>::try_from(value.as_ref())
+            .map_err(|_| SignatureError::InvalidAlloyAddressLength(value.len()))?;
+
+        Ok(Self::from(address))
+    }
+}
+
+impl TryFrom<Address> for primitives::Address {
+    type Error = SignatureError;
+
+    fn try_from(value: Address) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+/// The 65-byte `r || s || v` encoding, with `v` as 27 or 28, the same as
+/// [`crate::PrivateKeySigner::sign_message()`] for
+/// [`crate::ChainType::Ethereum`].
+impl From<primitives::PrimitiveSignature> for Signature {
+    fn from(value: primitives::PrimitiveSignature) -> Self {
+        value.as_bytes().to_vec().into()
+    }
+}
+
+impl From<&primitives::PrimitiveSignature> for Signature {
+    fn from(value: &primitives::PrimitiveSignature) -> Self {
+        value.as_bytes().to_vec().into()
+    }
+}
+
+impl TryFrom<&Signature> for primitives::PrimitiveSignature {
+    type Error = SignatureError;
+
+    fn try_from(value: &Signature) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_bytes()).map_err(SignatureError::ConvertAlloySignature)
+    }
+}
+
+impl TryFrom<Signature> for primitives::PrimitiveSignature {
+    type Error = SignatureError;
+
+    fn try_from(value: Signature) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
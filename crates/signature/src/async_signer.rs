@@ -0,0 +1,132 @@
+use std::future::{ready, Future};
+
+use serde::Serialize;
+
+use crate::{
+    address::Address, domain::SigningDomain, error::SignatureError, signature::Signature,
+    signer::PrivateKeySigner,
+};
+
+/// A signer whose signatures are made asynchronously, so that handlers
+/// running on an async runtime can sign without blocking, whether the key is
+/// held in memory by a [`PrivateKeySigner`] or by a remote service such as a
+/// KMS or an HSM.
+///
+/// Implementations only sign bytes. [`AsyncSigner::sign_message()`] and
+/// [`AsyncSigner::sign_message_in_domain()`] serialize messages the same way
+/// as [`PrivateKeySigner`], so signatures of any implementation verify with
+/// [`Signature::verify_message()`] and
+/// [`Signature::verify_message_in_domain()`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::future::Future;
+///
+/// use signature::{Address, AsyncSigner, Signature, SignatureError};
+///
+/// /// Stands in for a client of a remote signing service.
+/// struct RemoteSigner {
+///     address: Address,
+/// }
+///
+/// impl AsyncSigner for RemoteSigner {
+///     fn address(&self) -> &Address {
+///         &self.address
+///     }
+///
+///     fn sign_bytes(
+///         &self,
+///         message: &[u8],
+///     ) -> impl Future<Output = Result<Signature, SignatureError>> + Send {
+///         let message = message.to_vec();
+///
+///         async move {
+///             // Send `message` to the signing service and await the response.
+///             Ok(Signature::from(message))
+///         }
+///     }
+/// }
+/// ```
+pub trait AsyncSigner: Send + Sync {
+    fn address(&self) -> &Address;
+
+    /// Sign `message` as is, like [`crate::Signer::sign_message()`].
+    fn sign_bytes(
+        &self,
+        message: &[u8],
+    ) -> impl Future<Output = Result<Signature, SignatureError>> + Send;
+
+    /// Sign `message` serialized like
+    /// [`PrivateKeySigner::sign_message()`] does.
+    fn sign_message<T>(
+        &self,
+        message: &T,
+    ) -> impl Future<Output = Result<Signature, SignatureError>> + Send
+    where
+        T: Serialize + Sync,
+    {
+        async move {
+            let message_bytes =
+                bincode::serialize(message).map_err(SignatureError::SerializeMessage)?;
+
+            self.sign_bytes(&message_bytes).await
+        }
+    }
+
+    /// Sign `message` bound to `domain`, like
+    /// [`PrivateKeySigner::sign_message_in_domain()`] does.
+    fn sign_message_in_domain<T>(
+        &self,
+        domain: &SigningDomain,
+        message: &T,
+    ) -> impl Future<Output = Result<Signature, SignatureError>> + Send
+    where
+        T: Serialize + Sync,
+    {
+        async move {
+            let message_bytes = domain.serialize(message)?;
+
+            self.sign_bytes(&message_bytes).await
+        }
+    }
+}
+
+/// Signing with a key in memory takes microseconds, so the futures are ready
+/// as soon as they are created. The [`crate::SigningHook`] is called as for
+/// the synchronous methods.
+impl AsyncSigner for PrivateKeySigner {
+    fn address(&self) -> &Address {
+        PrivateKeySigner::address(self)
+    }
+
+    fn sign_bytes(
+        &self,
+        message: &[u8],
+    ) -> impl Future<Output = Result<Signature, SignatureError>> + Send {
+        ready(self.sign_with_hook(None, message))
+    }
+
+    fn sign_message<T>(
+        &self,
+        message: &T,
+    ) -> impl Future<Output = Result<Signature, SignatureError>> + Send
+    where
+        T: Serialize + Sync,
+    {
+        ready(PrivateKeySigner::sign_message(self, message))
+    }
+
+    fn sign_message_in_domain<T>(
+        &self,
+        domain: &SigningDomain,
+        message: &T,
+    ) -> impl Future<Output = Result<Signature, SignatureError>> + Send
+    where
+        T: Serialize + Sync,
+    {
+        ready(PrivateKeySigner::sign_message_in_domain(
+            self, domain, message,
+        ))
+    }
+}
@@ -0,0 +1,222 @@
+use std::sync::RwLock;
+
+#[cfg(feature = "signer")]
+use crate::signer::PrivateKeySigner;
+use crate::{address::Address, traits::*, SignatureError};
+
+static CUSTOM_CHAINS: RwLock<Vec<&'static ChainRegistration>> = RwLock::new(Vec::new());
+
+/// The [`Verifier`] and [`Builder`]s of a chain that is not built into the
+/// crate, registered with [`crate::ChainType::register()`].
+///
+/// A chain needs a verifier and an address builder. Without a public key
+/// builder or a signer builder, [`Address::from_public_key()`] or
+/// [`PrivateKeySigner::from_str()`] fail with
+/// [`SignatureError::UnsupportedChainType`] for the chain, as do random and
+/// mnemonic keys.
+pub struct ChainRegistration {
+    name: &'static str,
+    verifier: Box<dyn Verifier + Send + Sync>,
+    address_builder: Box<dyn Builder<Output = Address> + Send + Sync>,
+    public_key_builder: Option<Box<dyn PublicKeyBuilder<Output = Address> + Send + Sync>>,
+    #[cfg(feature = "signer")]
+    signer_builder: Option<Box<dyn Builder<Output = PrivateKeySigner> + Send + Sync>>,
+    unsupported: Unsupported,
+}
+
+impl ChainRegistration {
+    /// `name` is the identifier of the chain when (de)serialized, e.g.
+    /// `"bls12_381"`.
+    pub fn new(
+        name: &'static str,
+        verifier: impl Verifier + Send + Sync + 'static,
+        address_builder: impl Builder<Output = Address> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name,
+            verifier: Box::new(verifier),
+            address_builder: Box::new(address_builder),
+            public_key_builder: None,
+            #[cfg(feature = "signer")]
+            signer_builder: None,
+            unsupported: Unsupported(name),
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn public_key_builder(
+        mut self,
+        public_key_builder: impl PublicKeyBuilder<Output = Address> + Send + Sync + 'static,
+    ) -> Self {
+        self.public_key_builder = Some(Box::new(public_key_builder));
+
+        self
+    }
+
+    /// Build [`PrivateKeySigner`]s of the chain, usually from a
+    /// [`Signer`] with [`PrivateKeySigner::from()`].
+    #[cfg(feature = "signer")]
+    pub fn signer_builder(
+        mut self,
+        signer_builder: impl Builder<Output = PrivateKeySigner> + Send + Sync + 'static,
+    ) -> Self {
+        self.signer_builder = Some(Box::new(signer_builder));
+
+        self
+    }
+}
+
+/// A chain registered with [`crate::ChainType::register()`], see
+/// [`crate::ChainType::Custom`].
+#[derive(Clone, Copy)]
+pub struct CustomChain(&'static ChainRegistration);
+
+impl std::fmt::Debug for CustomChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0.name)
+    }
+}
+
+impl PartialEq for CustomChain {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.name == other.0.name
+    }
+}
+
+impl Eq for CustomChain {}
+
+impl std::hash::Hash for CustomChain {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.name.hash(state)
+    }
+}
+
+impl CustomChain {
+    /// Registrations live for the rest of the program, so that chain types
+    /// stay `Copy` and the builders can be borrowed for `'static`.
+    pub(crate) fn register(registration: ChainRegistration) -> Result<Self, SignatureError> {
+        let mut custom_chains = CUSTOM_CHAINS.write().unwrap();
+        if custom_chains
+            .iter()
+            .any(|custom_chain| custom_chain.name == registration.name)
+        {
+            return Err(SignatureError::ChainTypeAlreadyRegistered(
+                registration.name.to_owned(),
+            ));
+        }
+
+        let registration = Box::leak(Box::new(registration));
+        custom_chains.push(registration);
+
+        Ok(Self(registration))
+    }
+
+    pub(crate) fn get(name: &str) -> Option<Self> {
+        CUSTOM_CHAINS
+            .read()
+            .unwrap()
+            .iter()
+            .find(|custom_chain| custom_chain.name == name)
+            .map(|custom_chain| Self(custom_chain))
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.0.name
+    }
+
+    pub(crate) fn address_builder(&self) -> &'static dyn Builder<Output = Address> {
+        self.0.address_builder.as_ref()
+    }
+
+    pub(crate) fn public_key_builder(&self) -> &'static dyn PublicKeyBuilder<Output = Address> {
+        match &self.0.public_key_builder {
+            Some(public_key_builder) => public_key_builder.as_ref(),
+            None => &self.0.unsupported,
+        }
+    }
+
+    #[cfg(feature = "signer")]
+    pub(crate) fn signer_builder(&self) -> &'static dyn Builder<Output = PrivateKeySigner> {
+        match &self.0.signer_builder {
+            Some(signer_builder) => signer_builder.as_ref(),
+            None => &self.0.unsupported,
+        }
+    }
+
+    #[cfg(feature = "os-rng")]
+    pub(crate) fn signer_builder_random(
+        &self,
+    ) -> &'static dyn RandomBuilder<Output = (PrivateKeySigner, String)> {
+        &self.0.unsupported
+    }
+
+    #[cfg(feature = "mnemonic")]
+    pub(crate) fn signer_builder_mnemonic(
+        &self,
+    ) -> &'static dyn MnemonicBuilder<Output = PrivateKeySigner> {
+        &self.0.unsupported
+    }
+
+    pub(crate) fn verifier(&self) -> &'static dyn Verifier {
+        self.0.verifier.as_ref()
+    }
+}
+
+/// Stands in for the builders a custom chain was registered without.
+struct Unsupported(&'static str);
+
+impl Unsupported {
+    fn error(&self) -> SignatureError {
+        SignatureError::UnsupportedChainType(self.0.to_owned())
+    }
+}
+
+impl PublicKeyBuilder for Unsupported {
+    type Output = Address;
+
+    fn build_from_public_key(&self, _public_key: &[u8]) -> Result<Self::Output, SignatureError> {
+        Err(self.error())
+    }
+}
+
+#[cfg(feature = "signer")]
+impl Builder for Unsupported {
+    type Output = PrivateKeySigner;
+
+    fn build_from_slice(&self, _slice: &[u8]) -> Result<Self::Output, SignatureError> {
+        Err(self.error())
+    }
+
+    fn build_from_str(&self, _str: &str) -> Result<Self::Output, SignatureError> {
+        Err(self.error())
+    }
+}
+
+#[cfg(feature = "os-rng")]
+impl RandomBuilder for Unsupported {
+    type Output = (PrivateKeySigner, String);
+
+    fn build_from_random(&self) -> Result<Self::Output, SignatureError> {
+        Err(self.error())
+    }
+}
+
+#[cfg(feature = "mnemonic")]
+impl MnemonicBuilder for Unsupported {
+    type Output = PrivateKeySigner;
+
+    fn build_from_mnemonic(
+        &self,
+        _phrase: &str,
+        _derivation_path: &str,
+    ) -> Result<Self::Output, SignatureError> {
+        Err(self.error())
+    }
+
+    fn derivation_path(&self, _index: u32) -> String {
+        String::new()
+    }
+}
@@ -0,0 +1,288 @@
+#[cfg(feature = "mnemonic")]
+use coins_bip39::{English, Mnemonic};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+#[cfg(feature = "signer")]
+use ed25519_dalek::{Signer, SigningKey};
+#[cfg(feature = "mnemonic")]
+use hmac::{Hmac, Mac};
+#[cfg(feature = "os-rng")]
+use rand_core::{OsRng, RngCore};
+#[cfg(feature = "mnemonic")]
+use sha2::Sha512;
+
+/// The address is the 32-byte public key itself, as for Move-based chains
+/// that identify accounts by their Ed25519 key.
+pub struct Ed25519AddressBuilder;
+
+impl crate::Builder for Ed25519AddressBuilder {
+    type Output = crate::Address;
+
+    fn build_from_slice(&self, slice: &[u8]) -> Result<Self::Output, crate::SignatureError> {
+        let verifying_key = parse_verifying_key(slice)?;
+
+        Ok(verifying_key.to_bytes().to_vec().into())
+    }
+
+    fn build_from_str(&self, str: &str) -> Result<Self::Output, crate::SignatureError> {
+        let public_key = const_hex::decode(str).map_err(Ed25519Error::ParseAddressStr)?;
+
+        self.build_from_slice(&public_key)
+    }
+}
+
+impl crate::PublicKeyBuilder for Ed25519AddressBuilder {
+    type Output = crate::Address;
+
+    fn build_from_public_key(
+        &self,
+        public_key: &[u8],
+    ) -> Result<Self::Output, crate::SignatureError> {
+        <Self as crate::Builder>::build_from_slice(self, public_key)
+    }
+}
+
+fn parse_verifying_key(public_key: &[u8]) -> Result<VerifyingKey, crate::SignatureError> {
+    let public_key: &[u8; 32] = public_key
+        .try_into()
+        .map_err(|_| Ed25519Error::InvalidAddressLength(public_key.len()))?;
+
+    Ok(VerifyingKey::from_bytes(public_key).map_err(Ed25519Error::ParsePublicKey)?)
+}
+
+#[cfg(feature = "signer")]
+pub struct Ed25519SignerBuilder;
+
+#[cfg(feature = "signer")]
+impl crate::Builder for Ed25519SignerBuilder {
+    type Output = crate::PrivateKeySigner;
+
+    fn build_from_slice(&self, slice: &[u8]) -> Result<Self::Output, crate::SignatureError> {
+        Ok(Ed25519Signer::from_slice(slice)?.into())
+    }
+
+    fn build_from_str(&self, str: &str) -> Result<Self::Output, crate::SignatureError> {
+        let signing_key =
+            const_hex::decode_to_array::<_, 32>(str).map_err(Ed25519Error::ParseSigningKeyStr)?;
+
+        Ok(Ed25519Signer::from_slice(&signing_key)?.into())
+    }
+}
+
+#[cfg(feature = "os-rng")]
+impl crate::RandomBuilder for Ed25519SignerBuilder {
+    type Output = (crate::PrivateKeySigner, String);
+
+    fn build_from_random(&self) -> Result<Self::Output, crate::SignatureError> {
+        let (signer, private_key_random) = Ed25519Signer::from_random()?;
+
+        Ok((signer.into(), private_key_random))
+    }
+}
+
+#[cfg(feature = "mnemonic")]
+impl crate::MnemonicBuilder for Ed25519SignerBuilder {
+    type Output = crate::PrivateKeySigner;
+
+    fn build_from_mnemonic(
+        &self,
+        phrase: &str,
+        derivation_path: &str,
+    ) -> Result<Self::Output, crate::SignatureError> {
+        Ok(Ed25519Signer::from_mnemonic(phrase, derivation_path)?.into())
+    }
+
+    /// SLIP-0010 path used by Sui wallets, `m/44'/784'/{index}'/0'/0'`.
+    fn derivation_path(&self, index: u32) -> String {
+        format!("m/44'/784'/{}'/0'/0'", index)
+    }
+}
+
+#[cfg(feature = "signer")]
+pub struct Ed25519Signer {
+    signing_key: SigningKey,
+    address: crate::Address,
+}
+
+#[cfg(feature = "signer")]
+impl crate::Signer for Ed25519Signer {
+    fn address(&self) -> &crate::Address {
+        &self.address
+    }
+
+    /// Ed25519 public keys have a single 32-byte encoding, so `compressed` is
+    /// ignored.
+    fn public_key(&self, _compressed: bool) -> Vec<u8> {
+        self.signing_key.verifying_key().to_bytes().to_vec()
+    }
+
+    fn sign_message(&self, message: &[u8]) -> Result<crate::Signature, crate::SignatureError> {
+        let signature = self.signing_key.sign(message);
+
+        Ok(signature.to_bytes().to_vec().into())
+    }
+}
+
+#[cfg(feature = "signer")]
+impl Ed25519Signer {
+    pub fn from_slice(signing_key_slice: &[u8]) -> Result<Self, crate::SignatureError> {
+        let signing_key: &[u8; 32] = signing_key_slice
+            .try_into()
+            .map_err(|_| Ed25519Error::InvalidSigningKeyLength(signing_key_slice.len()))?;
+        let signing_key = SigningKey::from_bytes(signing_key);
+        let address = signing_key.verifying_key().to_bytes().to_vec().into();
+
+        Ok(Self {
+            signing_key,
+            address,
+        })
+    }
+
+    #[cfg(feature = "os-rng")]
+    pub fn from_random() -> Result<(Self, String), crate::SignatureError> {
+        let mut signing_key = [0u8; 32];
+        OsRng.fill_bytes(&mut signing_key);
+        let signing_key_hex_string = const_hex::encode_prefixed(signing_key);
+
+        Ok((Self::from_slice(&signing_key)?, signing_key_hex_string))
+    }
+
+    #[cfg(feature = "mnemonic")]
+    pub fn from_mnemonic(
+        phrase: &str,
+        derivation_path: &str,
+    ) -> Result<Self, crate::SignatureError> {
+        let mnemonic =
+            Mnemonic::<English>::new_from_phrase(phrase).map_err(Ed25519Error::ParseMnemonic)?;
+        let seed = mnemonic
+            .to_seed(None)
+            .map_err(Ed25519Error::ParseMnemonic)?;
+        let signing_key = slip10_derive(&seed, derivation_path)?;
+
+        Self::from_slice(&signing_key)
+    }
+}
+
+/// Derive the Ed25519 signing key at `derivation_path` from `seed` with
+/// SLIP-0010, which only defines hardened derivation for Ed25519.
+#[cfg(feature = "mnemonic")]
+pub(crate) fn slip10_derive(
+    seed: &[u8],
+    derivation_path: &str,
+) -> Result<[u8; 32], crate::SignatureError> {
+    let mut segments = derivation_path.split('/');
+    if segments.next() != Some("m") {
+        return Err(Ed25519Error::InvalidDerivationPath(derivation_path.to_owned()).into());
+    }
+
+    let (mut signing_key, mut chain_code) = slip10_hmac(b"ed25519 seed", seed);
+    for segment in segments {
+        let index = segment
+            .strip_suffix('\'')
+            .and_then(|index| index.parse::<u32>().ok())
+            .filter(|index| *index < 1 << 31)
+            .ok_or_else(|| Ed25519Error::InvalidDerivationPath(derivation_path.to_owned()))?;
+
+        let mut data = Vec::with_capacity(37);
+        data.push(0);
+        data.extend_from_slice(&signing_key);
+        data.extend_from_slice(&(index | 1 << 31).to_be_bytes());
+        (signing_key, chain_code) = slip10_hmac(&chain_code, &data);
+    }
+
+    Ok(signing_key)
+}
+
+/// Split `HMAC-SHA512(key, data)` into a signing key and a chain code.
+#[cfg(feature = "mnemonic")]
+fn slip10_hmac(key: &[u8], data: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    let output = mac.finalize().into_bytes();
+
+    let mut signing_key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    signing_key.copy_from_slice(&output[..32]);
+    chain_code.copy_from_slice(&output[32..]);
+
+    (signing_key, chain_code)
+}
+
+pub struct Ed25519Verifier;
+
+impl Ed25519Verifier {
+    fn verify(
+        signature: &[u8],
+        message: &[u8],
+        address: &[u8],
+        is_strict: bool,
+    ) -> Result<(), crate::SignatureError> {
+        let verifying_key = parse_verifying_key(address)?;
+        let signature = Signature::from_slice(signature)
+            .map_err(|_| Ed25519Error::InvalidSignatureLength(signature.len()))?;
+
+        // `verify_strict()` also rejects weak public keys and non-canonical
+        // `R` encodings, which make several signatures valid for a message.
+        match is_strict {
+            true => verifying_key.verify_strict(message, &signature),
+            false => verifying_key.verify(message, &signature),
+        }
+        .map_err(Ed25519Error::VerifySignature)?;
+
+        Ok(())
+    }
+}
+
+impl crate::Verifier for Ed25519Verifier {
+    fn verify_message(
+        &self,
+        signature: &[u8],
+        message: &[u8],
+        address: &[u8],
+    ) -> Result<(), crate::SignatureError> {
+        Self::verify(signature, message, address, false)
+    }
+
+    fn verify_message_strict(
+        &self,
+        signature: &[u8],
+        message: &[u8],
+        address: &[u8],
+    ) -> Result<(), crate::SignatureError> {
+        Self::verify(signature, message, address, true)
+    }
+
+    fn recover_public_key(
+        &self,
+        _signature: &[u8],
+        _message: &[u8],
+        _compressed: bool,
+    ) -> Result<Vec<u8>, crate::SignatureError> {
+        Err(Ed25519Error::UnrecoverablePublicKey.into())
+    }
+}
+
+#[derive(Debug)]
+pub enum Ed25519Error {
+    ParseSigningKeyStr(const_hex::FromHexError),
+    InvalidSigningKeyLength(usize),
+    ParseAddressStr(const_hex::FromHexError),
+    InvalidAddressLength(usize),
+    ParsePublicKey(ed25519_dalek::SignatureError),
+    InvalidSignatureLength(usize),
+    VerifySignature(ed25519_dalek::SignatureError),
+    /// Ed25519 signatures do not carry the public key, so verify against the
+    /// address instead.
+    UnrecoverablePublicKey,
+    #[cfg(feature = "mnemonic")]
+    ParseMnemonic(coins_bip39::MnemonicError),
+    #[cfg(feature = "mnemonic")]
+    InvalidDerivationPath(String),
+}
+
+impl std::fmt::Display for Ed25519Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for Ed25519Error {}
@@ -1,49 +1,31 @@
+#[cfg(feature = "mnemonic")]
+use coins_bip39::{English, Mnemonic};
+#[cfg(feature = "signer")]
+use k256::ecdsa::{RecoveryId, SigningKey};
 use k256::{
-    ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey},
+    ecdsa::{Signature, VerifyingKey},
     elliptic_curve::sec1::ToEncodedPoint,
 };
+#[cfg(feature = "os-rng")]
 use rand_core::OsRng;
-use sha3::{Digest, Keccak256};
 
-pub const EIP191_PREFIX: &str = "\x19Ethereum Signed Message:\n";
-
-fn eip191_hash_message(message: &[u8]) -> Vec<u8> {
-    let len = message.len();
-    let mut len_string_buffer = itoa::Buffer::new();
-    let len_string = len_string_buffer.format(len);
-
-    let mut ethereum_message = Vec::with_capacity(EIP191_PREFIX.len() + len_string.len() + len);
-    ethereum_message.extend_from_slice(EIP191_PREFIX.as_bytes());
-    ethereum_message.extend_from_slice(len_string.as_bytes());
-    ethereum_message.extend_from_slice(message);
-
-    let mut hasher = Keccak256::new();
-    hasher.update(ethereum_message);
-    let output = hasher.finalize_reset();
-
-    output.to_vec()
-}
+use crate::{
+    ecrecover::{self, recovery_id_from_y_parity_byte, EcrecoverError},
+    hash::{eip191_hash_message, keccak256},
+};
 
+#[cfg(feature = "signer")]
 fn y_parity_byte_non_eip155_from_recovery_id(recovery_id: RecoveryId) -> Option<u8> {
     recovery_id.to_byte().checked_add(27)
 }
 
-fn recovery_id_from_y_parity_byte(parity_byte: u8) -> Option<RecoveryId> {
-    match parity_byte.checked_sub(27) {
-        Some(byte) => RecoveryId::from_byte(byte),
-        None => None,
-    }
-}
-
 pub struct EthereumAddressBuilder;
 
 impl crate::Builder for EthereumAddressBuilder {
     type Output = crate::Address;
 
     fn build_from_slice(&self, slice: &[u8]) -> Result<Self::Output, crate::SignatureError> {
-        let mut hasher = Keccak256::new();
-        hasher.update(&slice[1..]);
-        let output = hasher.finalize_reset()[12..].to_vec();
+        let output = keccak256(&slice[1..])[12..].to_vec();
 
         Ok(output.into())
     }
@@ -55,8 +37,26 @@ impl crate::Builder for EthereumAddressBuilder {
     }
 }
 
+impl crate::PublicKeyBuilder for EthereumAddressBuilder {
+    type Output = crate::Address;
+
+    fn build_from_public_key(
+        &self,
+        public_key: &[u8],
+    ) -> Result<Self::Output, crate::SignatureError> {
+        let public_key = VerifyingKey::from_sec1_bytes(public_key)
+            .map_err(EthereumError::ParsePublicKey)?
+            .as_affine()
+            .to_encoded_point(false);
+
+        <Self as crate::Builder>::build_from_slice(self, public_key.as_bytes())
+    }
+}
+
+#[cfg(feature = "signer")]
 pub struct EthereumSignerBuilder;
 
+#[cfg(feature = "signer")]
 impl crate::Builder for EthereumSignerBuilder {
     type Output = crate::PrivateKeySigner;
 
@@ -72,6 +72,7 @@ impl crate::Builder for EthereumSignerBuilder {
     }
 }
 
+#[cfg(feature = "os-rng")]
 impl crate::RandomBuilder for EthereumSignerBuilder {
     type Output = (crate::PrivateKeySigner, String);
 
@@ -82,16 +83,45 @@ impl crate::RandomBuilder for EthereumSignerBuilder {
     }
 }
 
+#[cfg(feature = "mnemonic")]
+impl crate::MnemonicBuilder for EthereumSignerBuilder {
+    type Output = crate::PrivateKeySigner;
+
+    fn build_from_mnemonic(
+        &self,
+        phrase: &str,
+        derivation_path: &str,
+    ) -> Result<Self::Output, crate::SignatureError> {
+        Ok(EthereumSigner::from_mnemonic(phrase, derivation_path)?.into())
+    }
+
+    /// BIP-44 path used by Ethereum wallets, `m/44'/60'/0'/0/{index}`.
+    fn derivation_path(&self, index: u32) -> String {
+        format!("m/44'/60'/0'/0/{}", index)
+    }
+}
+
+#[cfg(feature = "signer")]
 pub struct EthereumSigner {
     signing_key: SigningKey,
     address: crate::Address,
 }
 
+#[cfg(feature = "signer")]
 impl crate::Signer for EthereumSigner {
     fn address(&self) -> &crate::Address {
         &self.address
     }
 
+    fn public_key(&self, compressed: bool) -> Vec<u8> {
+        self.signing_key
+            .verifying_key()
+            .as_affine()
+            .to_encoded_point(compressed)
+            .as_bytes()
+            .to_vec()
+    }
+
     fn sign_message(&self, message: &[u8]) -> Result<crate::Signature, crate::SignatureError> {
         let message = eip191_hash_message(message);
 
@@ -110,6 +140,7 @@ impl crate::Signer for EthereumSigner {
     }
 }
 
+#[cfg(feature = "signer")]
 impl EthereumSigner {
     pub fn from_slice(signing_key_slice: &[u8]) -> Result<Self, crate::SignatureError> {
         let signing_key =
@@ -129,6 +160,7 @@ impl EthereumSigner {
         })
     }
 
+    #[cfg(feature = "os-rng")]
     pub fn from_random() -> Result<(Self, String), crate::SignatureError> {
         let signing_key = SigningKey::random(&mut OsRng);
         let signing_key_hex_string = const_hex::encode_prefixed(signing_key.to_bytes());
@@ -148,10 +180,39 @@ impl EthereumSigner {
 
         Ok((signer, signing_key_hex_string))
     }
+
+    #[cfg(feature = "mnemonic")]
+    pub fn from_mnemonic(
+        phrase: &str,
+        derivation_path: &str,
+    ) -> Result<Self, crate::SignatureError> {
+        let mnemonic =
+            Mnemonic::<English>::new_from_phrase(phrase).map_err(EthereumError::ParseMnemonic)?;
+        let extended_private_key = mnemonic
+            .derive_key(derivation_path, None)
+            .map_err(EthereumError::DeriveKey)?;
+        let signing_key: &SigningKey = extended_private_key.as_ref();
+
+        Self::from_slice(&signing_key.to_bytes())
+    }
 }
 
 pub struct EthereumVerifier;
 
+impl EthereumVerifier {
+    fn recover(
+        signature: &[u8],
+        message: &[u8],
+        is_strict: bool,
+    ) -> Result<VerifyingKey, crate::SignatureError> {
+        let verifying_key =
+            ecrecover::recover_verifying_key(signature, &eip191_hash_message(message), is_strict)
+                .map_err(EthereumError::from)?;
+
+        Ok(verifying_key)
+    }
+}
+
 impl crate::Verifier for EthereumVerifier {
     fn verify_message(
         &self,
@@ -159,34 +220,118 @@ impl crate::Verifier for EthereumVerifier {
         message: &[u8],
         address: &[u8],
     ) -> Result<(), crate::SignatureError> {
-        if signature.len() != 65 {
-            return Err(EthereumError::InvalidSignatureLength(signature.len()))?;
-        }
+        ecrecover::verify_message(signature, message, address).map_err(EthereumError::from)?;
 
-        let message = eip191_hash_message(message);
+        Ok(())
+    }
 
-        let parsed_signature =
-            Signature::from_slice(&signature[0..64]).map_err(EthereumError::ParseSignature)?;
-        let parsed_recovery_id = recovery_id_from_y_parity_byte(signature[64])
-            .ok_or(EthereumError::ParseRecoveryId(signature[64]))?;
+    fn verify_message_strict(
+        &self,
+        signature: &[u8],
+        message: &[u8],
+        address: &[u8],
+    ) -> Result<(), crate::SignatureError> {
+        ecrecover::verify_message_strict(signature, message, address)
+            .map_err(EthereumError::from)?;
 
-        let public_key =
-            VerifyingKey::recover_from_prehash(&message, &parsed_signature, parsed_recovery_id)
-                .map_err(EthereumError::RecoverVerifyingKey)?
-                .as_affine()
-                .to_encoded_point(false);
+        Ok(())
+    }
 
-        let parsed_address = <EthereumAddressBuilder as crate::Builder>::build_from_slice(
-            &EthereumAddressBuilder,
-            public_key.as_bytes(),
-        )?;
-        match parsed_address == address {
-            true => Ok(()),
-            false => Err(EthereumError::AddressMismatch)?,
-        }
+    fn recover_public_key(
+        &self,
+        signature: &[u8],
+        message: &[u8],
+        compressed: bool,
+    ) -> Result<Vec<u8>, crate::SignatureError> {
+        let public_key = Self::recover(signature, message, true)?
+            .as_affine()
+            .to_encoded_point(compressed);
+
+        Ok(public_key.as_bytes().to_vec())
     }
 }
 
+/// Split an `r || s || v` signature into its ECDSA part and `y` parity.
+fn split_signature(signature: &[u8]) -> Result<(Signature, bool), crate::SignatureError> {
+    if signature.len() != 65 {
+        return Err(EthereumError::InvalidSignatureLength(signature.len()).into());
+    }
+
+    let parsed_signature =
+        Signature::from_slice(&signature[0..64]).map_err(EthereumError::ParseSignature)?;
+    let parsed_recovery_id = recovery_id_from_y_parity_byte(signature[64])
+        .ok_or(EthereumError::ParseRecoveryId(signature[64]))?;
+    if parsed_recovery_id.is_x_reduced() {
+        return Err(EthereumError::NonCanonicalRecoveryId(signature[64]).into());
+    }
+
+    Ok((parsed_signature, parsed_recovery_id.is_y_odd()))
+}
+
+fn join_signature(signature: Signature, is_y_odd: bool) -> crate::Signature {
+    let mut signature_vec = Vec::<u8>::with_capacity(65);
+    signature_vec.extend_from_slice(signature.to_bytes().as_ref());
+    signature_vec.push(27 + is_y_odd as u8);
+
+    signature_vec.into()
+}
+
+pub(crate) fn to_der(signature: &[u8]) -> Result<Vec<u8>, crate::SignatureError> {
+    let (signature, _) = split_signature(signature)?;
+
+    Ok(signature.to_der().as_bytes().to_vec())
+}
+
+pub(crate) fn from_der(
+    der: &[u8],
+    is_y_odd: bool,
+) -> Result<crate::Signature, crate::SignatureError> {
+    let signature = Signature::from_der(der).map_err(EthereumError::ParseSignature)?;
+
+    Ok(join_signature(signature, is_y_odd))
+}
+
+pub(crate) fn to_compact(signature: &[u8]) -> Result<Vec<u8>, crate::SignatureError> {
+    let (signature, _) = split_signature(signature)?;
+
+    Ok(signature.to_bytes().to_vec())
+}
+
+pub(crate) fn from_compact(
+    compact: &[u8],
+    is_y_odd: bool,
+) -> Result<crate::Signature, crate::SignatureError> {
+    let signature = Signature::from_slice(compact).map_err(EthereumError::ParseSignature)?;
+
+    Ok(join_signature(signature, is_y_odd))
+}
+
+pub(crate) fn to_eip2098(signature: &[u8]) -> Result<Vec<u8>, crate::SignatureError> {
+    let (signature, is_y_odd) = split_signature(signature)?;
+    // The top bit of a low `s` is always clear, which is where EIP-2098
+    // stores the `y` parity.
+    if signature.normalize_s().is_some() {
+        return Err(EthereumError::MalleableSignature.into());
+    }
+
+    let mut compact = signature.to_bytes().to_vec();
+    compact[32] |= (is_y_odd as u8) << 7;
+
+    Ok(compact)
+}
+
+pub(crate) fn from_eip2098(compact: &[u8]) -> Result<crate::Signature, crate::SignatureError> {
+    if compact.len() != 64 {
+        return Err(EthereumError::InvalidSignatureLength(compact.len()).into());
+    }
+
+    let mut compact = compact.to_vec();
+    let is_y_odd = compact[32] & 0x80 != 0;
+    compact[32] &= 0x7f;
+
+    from_compact(&compact, is_y_odd)
+}
+
 #[derive(Debug)]
 pub enum EthereumError {
     ParseSigningKey(k256::ecdsa::signature::Error),
@@ -194,10 +339,35 @@ pub enum EthereumError {
     SignMessage(k256::ecdsa::signature::Error),
     ParityByte(u8),
     InvalidSignatureLength(usize),
+    InvalidAddressLength(usize),
     ParseSignature(k256::ecdsa::signature::Error),
+    MalleableSignature,
     ParseRecoveryId(u8),
+    NonCanonicalRecoveryId(u8),
     RecoverVerifyingKey(k256::ecdsa::signature::Error),
     AddressMismatch,
+    ParsePublicKey(k256::ecdsa::signature::Error),
+    #[cfg(feature = "mnemonic")]
+    ParseMnemonic(coins_bip39::MnemonicError),
+    #[cfg(feature = "mnemonic")]
+    DeriveKey(coins_bip39::MnemonicError),
+}
+
+impl From<EcrecoverError> for EthereumError {
+    fn from(value: EcrecoverError) -> Self {
+        match value {
+            EcrecoverError::InvalidSignatureLength(length) => Self::InvalidSignatureLength(length),
+            EcrecoverError::InvalidAddressLength(length) => Self::InvalidAddressLength(length),
+            EcrecoverError::ParseSignature(error) => Self::ParseSignature(error),
+            EcrecoverError::MalleableSignature => Self::MalleableSignature,
+            EcrecoverError::ParseRecoveryId(parity_byte) => Self::ParseRecoveryId(parity_byte),
+            EcrecoverError::NonCanonicalRecoveryId(parity_byte) => {
+                Self::NonCanonicalRecoveryId(parity_byte)
+            }
+            EcrecoverError::RecoverVerifyingKey(error) => Self::RecoverVerifyingKey(error),
+            EcrecoverError::AddressMismatch => Self::AddressMismatch,
+        }
+    }
 }
 
 impl std::fmt::Display for EthereumError {
@@ -1,16 +1,32 @@
+pub(crate) mod custom;
+pub(crate) mod ed25519;
 pub(crate) mod ethereum;
+pub(crate) mod p256;
 
 use std::hash::Hash;
 
+pub use custom::{ChainRegistration, CustomChain};
 use serde::{Deserialize, Serialize};
 
-use crate::{address::Address, signer::PrivateKeySigner, traits::*, SignatureError};
+#[cfg(feature = "signer")]
+use crate::signer::PrivateKeySigner;
+use crate::{address::Address, traits::*, SignatureError};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
-#[serde(rename_all = "snake_case")]
-#[serde(try_from = "String")]
+#[serde(try_from = "String", into = "String")]
 pub enum ChainType {
     Ethereum,
+    /// Ed25519 keys with the raw 32-byte public key as the address, e.g. for
+    /// sequencers settling to Move-based chains such as Sui or Aptos.
+    Ed25519,
+    /// ECDSA over secp256r1 (P-256) with SHA-256, as used by secure enclaves
+    /// and WebAuthn passkeys, with the compressed SEC1 public key as the
+    /// address. Signatures are the 64-byte `r || s`, see
+    /// [`crate::Signature::from_p256_der()`] for the DER signatures of
+    /// passkeys.
+    P256,
+    /// A chain registered at runtime with [`ChainType::register()`].
+    Custom(CustomChain),
 }
 
 impl TryFrom<String> for ChainType {
@@ -19,34 +35,115 @@ impl TryFrom<String> for ChainType {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         match value.as_str() {
             "ethereum" => Ok(Self::Ethereum),
-            _others => Err(SignatureError::UnsupportedChainType(value)),
+            "ed25519" => Ok(Self::Ed25519),
+            "p256" => Ok(Self::P256),
+            others => match CustomChain::get(others) {
+                Some(custom_chain) => Ok(Self::Custom(custom_chain)),
+                None => Err(SignatureError::UnsupportedChainType(value)),
+            },
         }
     }
 }
+
+impl From<ChainType> for String {
+    fn from(value: ChainType) -> Self {
+        value.as_str().to_owned()
+    }
+}
+
 impl ChainType {
-    pub(crate) fn address_builder(&self) -> impl Builder<Output = Address> {
+    /// Register the verifier and builders of a chain that is not built into
+    /// the crate, so that it can be used like any other [`ChainType`] and be
+    /// deserialized from its name.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let chain_type = ChainType::register(
+    ///     ChainRegistration::new("bls12_381", BlsVerifier, BlsAddressBuilder)
+    ///         .public_key_builder(BlsAddressBuilder),
+    /// )?;
+    ///
+    /// let chain_type: ChainType = serde_json::from_str("\"bls12_381\"")?;
+    /// ```
+    pub fn register(registration: ChainRegistration) -> Result<Self, SignatureError> {
+        if Self::try_from(registration.name().to_owned()).is_ok() {
+            return Err(SignatureError::ChainTypeAlreadyRegistered(
+                registration.name().to_owned(),
+            ));
+        }
+
+        CustomChain::register(registration).map(Self::Custom)
+    }
+
+    /// The identifier of the chain, e.g. `"ethereum"`.
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Self::Ethereum => ethereum::EthereumAddressBuilder,
+            Self::Ethereum => "ethereum",
+            Self::Ed25519 => "ed25519",
+            Self::P256 => "p256",
+            Self::Custom(custom_chain) => custom_chain.name(),
         }
     }
 
-    pub(crate) fn signer_builder(&self) -> impl Builder<Output = PrivateKeySigner> {
+    pub(crate) fn address_builder(&self) -> &'static dyn Builder<Output = Address> {
         match self {
-            Self::Ethereum => ethereum::EthereumSignerBuilder,
+            Self::Ethereum => &ethereum::EthereumAddressBuilder,
+            Self::Ed25519 => &ed25519::Ed25519AddressBuilder,
+            Self::P256 => &p256::P256AddressBuilder,
+            Self::Custom(custom_chain) => custom_chain.address_builder(),
         }
     }
 
+    pub(crate) fn public_key_builder(&self) -> &'static dyn PublicKeyBuilder<Output = Address> {
+        match self {
+            Self::Ethereum => &ethereum::EthereumAddressBuilder,
+            Self::Ed25519 => &ed25519::Ed25519AddressBuilder,
+            Self::P256 => &p256::P256AddressBuilder,
+            Self::Custom(custom_chain) => custom_chain.public_key_builder(),
+        }
+    }
+
+    #[cfg(feature = "signer")]
+    pub(crate) fn signer_builder(&self) -> &'static dyn Builder<Output = PrivateKeySigner> {
+        match self {
+            Self::Ethereum => &ethereum::EthereumSignerBuilder,
+            Self::Ed25519 => &ed25519::Ed25519SignerBuilder,
+            Self::P256 => &p256::P256SignerBuilder,
+            Self::Custom(custom_chain) => custom_chain.signer_builder(),
+        }
+    }
+
+    #[cfg(feature = "os-rng")]
     pub(crate) fn signer_builder_random(
         &self,
-    ) -> impl RandomBuilder<Output = (PrivateKeySigner, String)> {
+    ) -> &'static dyn RandomBuilder<Output = (PrivateKeySigner, String)> {
+        match self {
+            Self::Ethereum => &ethereum::EthereumSignerBuilder,
+            Self::Ed25519 => &ed25519::Ed25519SignerBuilder,
+            Self::P256 => &p256::P256SignerBuilder,
+            Self::Custom(custom_chain) => custom_chain.signer_builder_random(),
+        }
+    }
+
+    #[cfg(feature = "mnemonic")]
+    pub(crate) fn signer_builder_mnemonic(
+        &self,
+    ) -> &'static dyn MnemonicBuilder<Output = PrivateKeySigner> {
         match self {
-            Self::Ethereum => ethereum::EthereumSignerBuilder,
+            Self::Ethereum => &ethereum::EthereumSignerBuilder,
+            Self::Ed25519 => &ed25519::Ed25519SignerBuilder,
+            Self::P256 => &p256::P256SignerBuilder,
+            Self::Custom(custom_chain) => custom_chain.signer_builder_mnemonic(),
         }
     }
 
-    pub(crate) fn verifier(&self) -> impl Verifier {
+    pub(crate) fn verifier(&self) -> &'static dyn Verifier {
         match self {
-            Self::Ethereum => ethereum::EthereumVerifier,
+            Self::Ethereum => &ethereum::EthereumVerifier,
+            Self::Ed25519 => &ed25519::Ed25519Verifier,
+            Self::P256 => &p256::P256Verifier,
+            Self::Custom(custom_chain) => custom_chain.verifier(),
         }
     }
 }
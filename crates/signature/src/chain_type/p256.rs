@@ -0,0 +1,255 @@
+#[cfg(feature = "signer")]
+use ::p256::ecdsa::{signature::Signer, SigningKey};
+use ::p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+#[cfg(feature = "os-rng")]
+use rand_core::OsRng;
+
+/// The address is the compressed SEC1 public key, as passkeys and secure
+/// enclaves identify a key by its public key rather than a hash of it.
+/// Uncompressed keys are accepted and compressed.
+pub struct P256AddressBuilder;
+
+impl crate::Builder for P256AddressBuilder {
+    type Output = crate::Address;
+
+    fn build_from_slice(&self, slice: &[u8]) -> Result<Self::Output, crate::SignatureError> {
+        let verifying_key = parse_verifying_key(slice)?;
+
+        Ok(verifying_key
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec()
+            .into())
+    }
+
+    fn build_from_str(&self, str: &str) -> Result<Self::Output, crate::SignatureError> {
+        let public_key = const_hex::decode(str).map_err(P256Error::ParseAddressStr)?;
+
+        self.build_from_slice(&public_key)
+    }
+}
+
+impl crate::PublicKeyBuilder for P256AddressBuilder {
+    type Output = crate::Address;
+
+    fn build_from_public_key(
+        &self,
+        public_key: &[u8],
+    ) -> Result<Self::Output, crate::SignatureError> {
+        <Self as crate::Builder>::build_from_slice(self, public_key)
+    }
+}
+
+fn parse_verifying_key(public_key: &[u8]) -> Result<VerifyingKey, crate::SignatureError> {
+    if public_key.len() != 33 && public_key.len() != 65 {
+        return Err(P256Error::InvalidAddressLength(public_key.len()).into());
+    }
+
+    Ok(VerifyingKey::from_sec1_bytes(public_key).map_err(P256Error::ParsePublicKey)?)
+}
+
+#[cfg(feature = "signer")]
+pub struct P256SignerBuilder;
+
+#[cfg(feature = "signer")]
+impl crate::Builder for P256SignerBuilder {
+    type Output = crate::PrivateKeySigner;
+
+    fn build_from_slice(&self, slice: &[u8]) -> Result<Self::Output, crate::SignatureError> {
+        Ok(P256Signer::from_slice(slice)?.into())
+    }
+
+    fn build_from_str(&self, str: &str) -> Result<Self::Output, crate::SignatureError> {
+        let signing_key =
+            const_hex::decode_to_array::<_, 32>(str).map_err(P256Error::ParseSigningKeyStr)?;
+
+        Ok(P256Signer::from_slice(&signing_key)?.into())
+    }
+}
+
+#[cfg(feature = "os-rng")]
+impl crate::RandomBuilder for P256SignerBuilder {
+    type Output = (crate::PrivateKeySigner, String);
+
+    fn build_from_random(&self) -> Result<Self::Output, crate::SignatureError> {
+        let (signer, private_key_random) = P256Signer::from_random()?;
+
+        Ok((signer.into(), private_key_random))
+    }
+}
+
+/// Keys of secure enclaves and passkeys are generated on the device and are
+/// never derived from a mnemonic, so derivation always fails.
+#[cfg(feature = "mnemonic")]
+impl crate::MnemonicBuilder for P256SignerBuilder {
+    type Output = crate::PrivateKeySigner;
+
+    fn build_from_mnemonic(
+        &self,
+        _phrase: &str,
+        _derivation_path: &str,
+    ) -> Result<Self::Output, crate::SignatureError> {
+        Err(P256Error::UnsupportedMnemonic.into())
+    }
+
+    fn derivation_path(&self, index: u32) -> String {
+        format!("m/{}'", index)
+    }
+}
+
+/// A software P-256 signer, e.g. to test verification of signatures made by
+/// secure enclaves.
+#[cfg(feature = "signer")]
+pub struct P256Signer {
+    signing_key: SigningKey,
+    address: crate::Address,
+}
+
+#[cfg(feature = "signer")]
+impl crate::Signer for P256Signer {
+    fn address(&self) -> &crate::Address {
+        &self.address
+    }
+
+    fn public_key(&self, compressed: bool) -> Vec<u8> {
+        self.signing_key
+            .verifying_key()
+            .to_encoded_point(compressed)
+            .as_bytes()
+            .to_vec()
+    }
+
+    /// Sign the SHA-256 digest of `message` (ES256) and return the 64-byte
+    /// `r || s` with a low `s`.
+    fn sign_message(&self, message: &[u8]) -> Result<crate::Signature, crate::SignatureError> {
+        let signature: Signature = self
+            .signing_key
+            .try_sign(message)
+            .map_err(P256Error::SignMessage)?;
+        let signature = signature.normalize_s().unwrap_or(signature);
+
+        Ok(signature.to_bytes().to_vec().into())
+    }
+}
+
+#[cfg(feature = "signer")]
+impl P256Signer {
+    pub fn from_slice(signing_key_slice: &[u8]) -> Result<Self, crate::SignatureError> {
+        let signing_key =
+            SigningKey::from_slice(signing_key_slice).map_err(P256Error::ParseSigningKey)?;
+        let address = signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec()
+            .into();
+
+        Ok(Self {
+            signing_key,
+            address,
+        })
+    }
+
+    #[cfg(feature = "os-rng")]
+    pub fn from_random() -> Result<(Self, String), crate::SignatureError> {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let signing_key_hex_string = const_hex::encode_prefixed(signing_key.to_bytes());
+
+        Ok((
+            Self::from_slice(&signing_key.to_bytes())?,
+            signing_key_hex_string,
+        ))
+    }
+}
+
+pub struct P256Verifier;
+
+impl P256Verifier {
+    fn verify(
+        signature: &[u8],
+        message: &[u8],
+        address: &[u8],
+        is_strict: bool,
+    ) -> Result<(), crate::SignatureError> {
+        let verifying_key = parse_verifying_key(address)?;
+        if signature.len() != 64 {
+            return Err(P256Error::InvalidSignatureLength(signature.len()).into());
+        }
+        let signature = Signature::from_slice(signature).map_err(P256Error::ParseSignature)?;
+
+        // Unlike secp256k1 in Ethereum, P-256 signers are not required to
+        // produce a low `s`, e.g. WebAuthn authenticators, so only the
+        // strict check rejects the high-S twin.
+        if is_strict && signature.normalize_s().is_some() {
+            return Err(P256Error::MalleableSignature.into());
+        }
+
+        verifying_key
+            .verify(message, &signature)
+            .map_err(P256Error::VerifySignature)?;
+
+        Ok(())
+    }
+}
+
+impl crate::Verifier for P256Verifier {
+    fn verify_message(
+        &self,
+        signature: &[u8],
+        message: &[u8],
+        address: &[u8],
+    ) -> Result<(), crate::SignatureError> {
+        Self::verify(signature, message, address, false)
+    }
+
+    fn verify_message_strict(
+        &self,
+        signature: &[u8],
+        message: &[u8],
+        address: &[u8],
+    ) -> Result<(), crate::SignatureError> {
+        Self::verify(signature, message, address, true)
+    }
+
+    fn recover_public_key(
+        &self,
+        _signature: &[u8],
+        _message: &[u8],
+        _compressed: bool,
+    ) -> Result<Vec<u8>, crate::SignatureError> {
+        Err(P256Error::UnrecoverablePublicKey.into())
+    }
+}
+
+pub(crate) fn from_der(der: &[u8]) -> Result<crate::Signature, crate::SignatureError> {
+    let signature = Signature::from_der(der).map_err(P256Error::ParseSignature)?;
+
+    Ok(signature.to_bytes().to_vec().into())
+}
+
+#[derive(Debug)]
+pub enum P256Error {
+    ParseSigningKeyStr(const_hex::FromHexError),
+    ParseSigningKey(::p256::ecdsa::Error),
+    ParseAddressStr(const_hex::FromHexError),
+    InvalidAddressLength(usize),
+    ParsePublicKey(::p256::ecdsa::Error),
+    SignMessage(::p256::ecdsa::Error),
+    InvalidSignatureLength(usize),
+    ParseSignature(::p256::ecdsa::Error),
+    MalleableSignature,
+    VerifySignature(::p256::ecdsa::Error),
+    /// The signatures carry no recovery ID, so verify against the address
+    /// instead.
+    UnrecoverablePublicKey,
+    #[cfg(feature = "mnemonic")]
+    UnsupportedMnemonic,
+}
+
+impl std::fmt::Display for P256Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for P256Error {}
@@ -0,0 +1,39 @@
+use std::borrow::Cow;
+
+use serde::Serialize;
+
+use crate::error::SignatureError;
+
+/// A tag bound into every signature made with
+/// [`crate::PrivateKeySigner::sign_message_in_domain()`], such as
+/// `radius.order_commitment.v1`.
+///
+/// A signature only verifies under the domain it was made in, so a signature
+/// over one kind of protocol message cannot be replayed as another kind
+/// whose serialization happens to match. Version the tag whenever the
+/// message format changes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SigningDomain(Cow<'static, str>);
+
+impl SigningDomain {
+    pub const fn from_static(tag: &'static str) -> Self {
+        Self(Cow::Borrowed(tag))
+    }
+
+    pub fn new(tag: impl Into<Cow<'static, str>>) -> Self {
+        Self(tag.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Serialize `message` prefixed with the length-prefixed tag, so that no
+    /// pair of domain and message encodes to the same bytes as another.
+    pub(crate) fn serialize<T>(&self, message: &T) -> Result<Vec<u8>, SignatureError>
+    where
+        T: Serialize,
+    {
+        bincode::serialize(&(self.as_str(), message)).map_err(SignatureError::SerializeMessage)
+    }
+}
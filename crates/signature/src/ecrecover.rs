@@ -0,0 +1,152 @@
+//! Recovery and verification of `ChainType::Ethereum` signatures without the
+//! standard library or an allocator, e.g. inside a zkVM guest proving that
+//! sequencer commitments were signed.
+//!
+//! With `default-features = false`, the crate is `no_std` and has only this
+//! module and [`crate::hash`]. The `std` feature adds everything else, and
+//! `ChainType::Ethereum` verifies with the same functions.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use signature::ecrecover;
+//!
+//! # fn check(signature: &[u8; 65], commitment: &[u8], sequencer: &[u8; 20]) {
+//! if ecrecover::verify_message(signature, commitment, sequencer).is_err() {
+//!     panic!("the commitment was not signed by the sequencer");
+//! }
+//! # }
+//! ```
+use k256::{
+    ecdsa::{RecoveryId, Signature, VerifyingKey},
+    elliptic_curve::sec1::ToEncodedPoint,
+};
+
+use crate::hash::{eip191_hash_message, keccak256};
+
+/// Return the address of the signer of `message`, hashed with
+/// [`eip191_hash_message()`], from an `r || s || v` signature with `v` 27
+/// or 28 (or 29 and 30 unless `is_strict`).
+pub fn recover_address(
+    signature: &[u8],
+    message: &[u8],
+    is_strict: bool,
+) -> Result<[u8; 20], EcrecoverError> {
+    recover_address_from_prehash(signature, &eip191_hash_message(message), is_strict)
+}
+
+/// Same as [`recover_address()`] for a message that was already hashed.
+pub fn recover_address_from_prehash(
+    signature: &[u8],
+    prehash: &[u8; 32],
+    is_strict: bool,
+) -> Result<[u8; 20], EcrecoverError> {
+    let verifying_key = recover_verifying_key(signature, prehash, is_strict)?;
+
+    Ok(address(&verifying_key))
+}
+
+/// Check that `message` was signed by `address`, accepting the parity
+/// bytes 29 and 30 as `Verifier::verify_message()` does.
+pub fn verify_message(
+    signature: &[u8],
+    message: &[u8],
+    address: &[u8],
+) -> Result<(), EcrecoverError> {
+    verify(signature, message, address, false)
+}
+
+/// Check that `message` was signed by `address`, rejecting the parity
+/// bytes 29 and 30 as `Verifier::verify_message_strict()` does.
+pub fn verify_message_strict(
+    signature: &[u8],
+    message: &[u8],
+    address: &[u8],
+) -> Result<(), EcrecoverError> {
+    verify(signature, message, address, true)
+}
+
+fn verify(
+    signature: &[u8],
+    message: &[u8],
+    address: &[u8],
+    is_strict: bool,
+) -> Result<(), EcrecoverError> {
+    if address.len() != 20 {
+        return Err(EcrecoverError::InvalidAddressLength(address.len()));
+    }
+
+    match recover_address(signature, message, is_strict)? == address {
+        true => Ok(()),
+        false => Err(EcrecoverError::AddressMismatch),
+    }
+}
+
+pub(crate) fn recover_verifying_key(
+    signature: &[u8],
+    prehash: &[u8; 32],
+    is_strict: bool,
+) -> Result<VerifyingKey, EcrecoverError> {
+    if signature.len() != 65 {
+        return Err(EcrecoverError::InvalidSignatureLength(signature.len()));
+    }
+
+    let parsed_signature =
+        Signature::from_slice(&signature[0..64]).map_err(EcrecoverError::ParseSignature)?;
+    // `normalize_s()` returns the low-S form only if `s` is in the upper
+    // half of the curve order. Recovery would fail on it anyway, but with
+    // an error that does not tell why.
+    if parsed_signature.normalize_s().is_some() {
+        return Err(EcrecoverError::MalleableSignature);
+    }
+
+    let parsed_recovery_id = recovery_id_from_y_parity_byte(signature[64])
+        .ok_or(EcrecoverError::ParseRecoveryId(signature[64]))?;
+    // Parity bytes 29 and 30 mark an `r` reduced modulo the curve order,
+    // which honest signers do not produce.
+    if is_strict && parsed_recovery_id.is_x_reduced() {
+        return Err(EcrecoverError::NonCanonicalRecoveryId(signature[64]));
+    }
+
+    VerifyingKey::recover_from_prehash(prehash, &parsed_signature, parsed_recovery_id)
+        .map_err(EcrecoverError::RecoverVerifyingKey)
+}
+
+pub(crate) fn recovery_id_from_y_parity_byte(parity_byte: u8) -> Option<RecoveryId> {
+    match parity_byte.checked_sub(27) {
+        Some(byte) => RecoveryId::from_byte(byte),
+        None => None,
+    }
+}
+
+/// The last 20 bytes of the Keccak-256 hash of the uncompressed public key
+/// without its `0x04` tag.
+pub(crate) fn address(verifying_key: &VerifyingKey) -> [u8; 20] {
+    let public_key = verifying_key.as_affine().to_encoded_point(false);
+    let hash = keccak256(&public_key.as_bytes()[1..]);
+
+    let mut address = [0; 20];
+    address.copy_from_slice(&hash[12..]);
+
+    address
+}
+
+#[derive(Debug)]
+pub enum EcrecoverError {
+    InvalidSignatureLength(usize),
+    InvalidAddressLength(usize),
+    ParseSignature(k256::ecdsa::signature::Error),
+    MalleableSignature,
+    ParseRecoveryId(u8),
+    NonCanonicalRecoveryId(u8),
+    RecoverVerifyingKey(k256::ecdsa::signature::Error),
+    AddressMismatch,
+}
+
+impl core::fmt::Display for EcrecoverError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl core::error::Error for EcrecoverError {}
@@ -0,0 +1,157 @@
+use std::{marker::PhantomData, str::FromStr};
+
+use alloy::{
+    primitives::{Address, FixedBytes},
+    providers::{Provider, ProviderBuilder, RootProvider},
+    rpc::json_rpc::ErrorPayload,
+    sol,
+    transports::{
+        http::{reqwest::Url, Client, Http},
+        RpcError, Transport,
+    },
+};
+use serde::Serialize;
+
+use crate::{hash::eip191_hash_message, ChainType, Signature, SigningDomain};
+
+sol! {
+    #[sol(rpc)]
+    interface IERC1271 {
+        function isValidSignature(bytes32 hash, bytes memory signature) external view returns (bytes4 magicValue);
+    }
+}
+
+/// Returned by `isValidSignature()` for a valid signature.
+const MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Verifies [`ChainType::Ethereum`] signatures of smart contract wallets
+/// with [ERC-1271](https://eips.ethereum.org/EIPS/eip-1271) as well as those
+/// of externally owned accounts.
+///
+/// Signatures recovering to the address are accepted without a request, so
+/// EOAs cost no more than with [`Signature::verify_message_strict()`].
+/// Otherwise, if the address holds code, the wallet is asked whether the
+/// signature is valid for the EIP-191 hash of the message, the hash that
+/// [`crate::PrivateKeySigner`] signs.
+pub struct ContractVerifier<P, T = Http<Client>> {
+    provider: P,
+    _transport: PhantomData<T>,
+}
+
+impl ContractVerifier<RootProvider<Http<Client>>> {
+    pub fn new(rpc_url: impl AsRef<str>) -> Result<Self, crate::SignatureError> {
+        let rpc_url = Url::from_str(rpc_url.as_ref())
+            .map_err(|_| Erc1271Error::InvalidRpcUrl(rpc_url.as_ref().to_owned()))?;
+        let provider = ProviderBuilder::new().on_http(rpc_url);
+
+        Ok(Self::from_provider(provider))
+    }
+}
+
+impl<P, T> ContractVerifier<P, T>
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    pub fn from_provider(provider: P) -> Self {
+        Self {
+            provider,
+            _transport: PhantomData,
+        }
+    }
+
+    pub async fn verify_message<M: Serialize>(
+        &self,
+        signature: &Signature,
+        message: &M,
+        address: impl AsRef<[u8]>,
+    ) -> Result<(), crate::SignatureError> {
+        let message_bytes =
+            bincode::serialize(message).map_err(crate::SignatureError::SerializeMessage)?;
+
+        self.verify(signature.as_bytes(), &message_bytes, address.as_ref())
+            .await
+    }
+
+    pub async fn verify_message_in_domain<M: Serialize>(
+        &self,
+        signature: &Signature,
+        domain: &SigningDomain,
+        message: &M,
+        address: impl AsRef<[u8]>,
+    ) -> Result<(), crate::SignatureError> {
+        let message_bytes = domain.serialize(message)?;
+
+        self.verify(signature.as_bytes(), &message_bytes, address.as_ref())
+            .await
+    }
+
+    async fn verify(
+        &self,
+        signature: &[u8],
+        message: &[u8],
+        address: &[u8],
+    ) -> Result<(), crate::SignatureError> {
+        let recover_error = match ChainType::Ethereum
+            .verifier()
+            .verify_message_strict(signature, message, address)
+        {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+
+        let address = Address::try_from(address)
+            .map_err(|_| Erc1271Error::InvalidAddressLength(address.len()))?;
+        let code = self
+            .provider
+            .get_code_at(address)
+            .await
+            .map_err(Erc1271Error::GetCode)?;
+        if code.is_empty() {
+            return Err(recover_error);
+        }
+
+        let hash = FixedBytes::from(eip191_hash_message(message));
+        let wallet = IERC1271::new(address, &self.provider);
+        match wallet
+            .isValidSignature(hash, signature.to_vec().into())
+            .call()
+            .await
+        {
+            Ok(output) if output.magicValue == MAGIC_VALUE => Ok(()),
+            Ok(_) => Err(Erc1271Error::InvalidSignature.into()),
+            // Wallets may revert instead of returning another value. Other
+            // errors of the node, e.g. rate limits, are not a verdict.
+            Err(alloy::contract::Error::TransportError(RpcError::ErrorResp(payload)))
+                if is_revert(&payload) =>
+            {
+                Err(Erc1271Error::InvalidSignature.into())
+            }
+            Err(error) => Err(Erc1271Error::IsValidSignature(error).into()),
+        }
+    }
+}
+
+/// Whether the node answered with a reverted execution: code 3 with the
+/// revert data, or a message such as `execution reverted` without data.
+pub(crate) fn is_revert(payload: &ErrorPayload) -> bool {
+    payload.code == 3 || payload.message.contains("revert")
+}
+
+#[derive(Debug)]
+pub enum Erc1271Error {
+    InvalidRpcUrl(String),
+    InvalidAddressLength(usize),
+    GetCode(alloy::transports::TransportError),
+    IsValidSignature(alloy::contract::Error),
+    /// The wallet rejected the signature.
+    InvalidSignature,
+}
+
+impl std::fmt::Display for Erc1271Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for Erc1271Error {}
@@ -1,10 +1,25 @@
 #[derive(Debug)]
 pub enum SignatureError {
     UnsupportedChainType(String),
+    ChainTypeAlreadyRegistered(String),
     DeserializeAddress(const_hex::FromHexError),
     DeserializeSignature(const_hex::FromHexError),
     SerializeMessage(bincode::Error),
     Ethereum(crate::chain_type::ethereum::EthereumError),
+    Ed25519(crate::chain_type::ed25519::Ed25519Error),
+    P256(crate::chain_type::p256::P256Error),
+    /// Fewer than `threshold` of the allowed signers signed the message, see
+    /// [`crate::MultiSignature::verify_threshold()`].
+    ThresholdNotMet {
+        threshold: usize,
+        valid_signatures: usize,
+    },
+    #[cfg(feature = "erc1271")]
+    Erc1271(crate::erc1271::Erc1271Error),
+    #[cfg(feature = "alloy-compat")]
+    InvalidAlloyAddressLength(usize),
+    #[cfg(feature = "alloy-compat")]
+    ConvertAlloySignature(alloy::primitives::SignatureError),
 }
 
 impl std::fmt::Display for SignatureError {
@@ -20,3 +35,22 @@ impl From<crate::chain_type::ethereum::EthereumError> for SignatureError {
         Self::Ethereum(value)
     }
 }
+
+impl From<crate::chain_type::ed25519::Ed25519Error> for SignatureError {
+    fn from(value: crate::chain_type::ed25519::Ed25519Error) -> Self {
+        Self::Ed25519(value)
+    }
+}
+
+impl From<crate::chain_type::p256::P256Error> for SignatureError {
+    fn from(value: crate::chain_type::p256::P256Error) -> Self {
+        Self::P256(value)
+    }
+}
+
+#[cfg(feature = "erc1271")]
+impl From<crate::erc1271::Erc1271Error> for SignatureError {
+    fn from(value: crate::erc1271::Erc1271Error) -> Self {
+        Self::Erc1271(value)
+    }
+}
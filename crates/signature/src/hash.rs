@@ -0,0 +1,104 @@
+//! The hash functions used by the signers, for crates that need the same
+//! digests without depending on the hash crates themselves.
+//!
+//! [`eip191_hash_message()`] is the prehash that [`crate::ChainType::Ethereum`]
+//! signs and verifies, so a message hashed with it elsewhere matches the
+//! signature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use signature::hash::{keccak256, Hasher, Keccak256};
+//!
+//! let mut hasher = Keccak256::default();
+//! hasher.update(b"hello ");
+//! hasher.update(b"world");
+//!
+//! assert_eq!(hasher.finalize(), keccak256(b"hello world"));
+//! ```
+use sha2::Digest;
+
+pub const EIP191_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+
+/// A hash function fed in chunks, e.g. to hash a message without
+/// concatenating its parts first.
+pub trait Hasher: Default {
+    fn update(&mut self, data: impl AsRef<[u8]>);
+
+    fn finalize(self) -> [u8; 32];
+}
+
+#[derive(Clone, Default)]
+pub struct Keccak256(sha3::Keccak256);
+
+impl Hasher for Keccak256 {
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Sha256(sha2::Sha256);
+
+impl Hasher for Sha256 {
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+#[cfg(feature = "blake3")]
+#[derive(Clone, Default)]
+pub struct Blake3(blake3::Hasher);
+
+#[cfg(feature = "blake3")]
+impl Hasher for Blake3 {
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.0.update(data.as_ref());
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+pub fn keccak256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    hash::<Keccak256>(data)
+}
+
+pub fn sha256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    hash::<Sha256>(data)
+}
+
+#[cfg(feature = "blake3")]
+pub fn blake3(data: impl AsRef<[u8]>) -> [u8; 32] {
+    hash::<Blake3>(data)
+}
+
+/// Return the Keccak-256 hash of `message` prefixed with [`EIP191_PREFIX`]
+/// and its length in decimal, as signed by `personal_sign`.
+pub fn eip191_hash_message(message: impl AsRef<[u8]>) -> [u8; 32] {
+    let message = message.as_ref();
+    let mut length_buffer = itoa::Buffer::new();
+
+    let mut hasher = Keccak256::default();
+    hasher.update(EIP191_PREFIX);
+    hasher.update(length_buffer.format(message.len()));
+    hasher.update(message);
+
+    hasher.finalize()
+}
+
+fn hash<H: Hasher>(data: impl AsRef<[u8]>) -> [u8; 32] {
+    let mut hasher = H::default();
+    hasher.update(data);
+
+    hasher.finalize()
+}
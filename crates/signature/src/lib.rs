@@ -1,17 +1,60 @@
+//! Signers, verifiers and addresses of the chains sequencers sign with.
+//!
+//! Without the `std` feature, on by default, the crate is `no_std` and only
+//! has [`ecrecover`] and [`hash`].
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
 mod address;
+#[cfg(feature = "alloy-compat")]
+mod alloy_compat;
+#[cfg(feature = "signer")]
+mod async_signer;
+#[cfg(feature = "std")]
 mod chain_type;
+#[cfg(feature = "std")]
+mod domain;
+pub mod ecrecover;
+#[cfg(feature = "erc1271")]
+mod erc1271;
+#[cfg(feature = "std")]
 mod error;
+pub mod hash;
+#[cfg(feature = "std")]
+mod multi_signature;
+#[cfg(feature = "std")]
 mod signature;
+#[cfg(feature = "signer")]
 mod signer;
+#[cfg(feature = "signer")]
+mod signing_hook;
+#[cfg(feature = "std")]
 mod traits;
 
+#[cfg(feature = "std")]
 pub use address::Address;
-pub use chain_type::ChainType;
+#[cfg(feature = "signer")]
+pub use async_signer::AsyncSigner;
+#[cfg(feature = "std")]
+pub use chain_type::{ChainRegistration, ChainType, CustomChain};
+#[cfg(feature = "std")]
+pub use domain::SigningDomain;
+#[cfg(feature = "erc1271")]
+pub use erc1271::{ContractVerifier, Erc1271Error};
+#[cfg(feature = "std")]
 pub use error::SignatureError;
-pub use signature::Signature;
+#[cfg(feature = "std")]
+pub use multi_signature::MultiSignature;
+#[cfg(feature = "std")]
+pub use signature::{signature_base64, signature_hex, Signature};
+#[cfg(feature = "signer")]
 pub use signer::PrivateKeySigner;
+#[cfg(feature = "signer")]
+pub use signing_hook::{SigningHook, SigningRecord};
+#[cfg(feature = "std")]
 pub use traits::*;
 
+#[cfg(feature = "signer")]
 #[test]
 fn test_address_comparison() {
     pub fn get_alloy_address(signing_key: &str) -> alloy::primitives::Address {
@@ -48,6 +91,7 @@ fn test_address_comparison() {
     assert!(parsed_address == alloy_address);
 }
 
+#[cfg(feature = "signer")]
 #[test]
 fn test_signature_verification() {
     pub fn verify_signature<T: serde::Serialize>(signing_key: &str, message: &T) {
@@ -100,6 +144,7 @@ fn test_signature_verification() {
     verify_signature(signing_key, &user);
 }
 
+#[cfg(feature = "os-rng")]
 #[test]
 fn test_random() {
     use std::str::FromStr;
@@ -118,6 +163,7 @@ fn test_random() {
     assert!(*sequencer_address == alloy_address);
 }
 
+#[cfg(feature = "os-rng")]
 #[test]
 fn test_polymorphic_type_conversion() {
     use std::str::FromStr;
@@ -146,6 +192,7 @@ fn test_polymorphic_type_conversion() {
     assert!(address_from_string == address_from_array);
 }
 
+#[cfg(feature = "os-rng")]
 #[test]
 fn test_hex_conversion() {
     let (sequencer_signer, _) = PrivateKeySigner::from_random(ChainType::Ethereum).unwrap();
@@ -162,3 +209,718 @@ fn test_hex_conversion() {
     let parsed_signature: Signature = serde_json::from_str(&signature_json).unwrap();
     assert!(signature == parsed_signature);
 }
+
+#[cfg(feature = "mnemonic")]
+#[test]
+fn test_mnemonic() {
+    let phrase = "test test test test test test test test test test test junk";
+
+    let signer =
+        PrivateKeySigner::from_mnemonic(ChainType::Ethereum, phrase, "m/44'/60'/0'/0/0").unwrap();
+    let address = Address::from_str(
+        ChainType::Ethereum,
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+    )
+    .unwrap();
+    assert!(*signer.address() == address);
+
+    let signer = PrivateKeySigner::from_mnemonic_index(ChainType::Ethereum, phrase, 1).unwrap();
+    let address = Address::from_str(
+        ChainType::Ethereum,
+        "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+    )
+    .unwrap();
+    assert!(*signer.address() == address);
+
+    assert!(PrivateKeySigner::from_mnemonic_index(ChainType::Ethereum, "test junk", 0).is_err());
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_strict_verification() {
+    use k256::ecdsa;
+
+    use crate::chain_type::ethereum::EthereumError;
+
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let signer = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap();
+    let signature = signer.sign_message("message").unwrap();
+    signature
+        .verify_message_strict(ChainType::Ethereum, &"message", signer.address())
+        .unwrap();
+
+    // Negate `s` and flip the parity byte to get the high-S twin.
+    let bytes = signature.as_bytes();
+    let low_s = ecdsa::Signature::from_slice(&bytes[0..64]).unwrap();
+    let (r, s) = low_s.split_scalars();
+    let high_s = ecdsa::Signature::from_scalars(r, -*s).unwrap();
+    let mut malleated = high_s.to_bytes().to_vec();
+    malleated.push(if bytes[64] == 27 { 28 } else { 27 });
+    let malleated = Signature::from(malleated);
+    assert!(matches!(
+        malleated.verify_message(ChainType::Ethereum, &"message", signer.address()),
+        Err(SignatureError::Ethereum(EthereumError::MalleableSignature))
+    ));
+
+    let mut x_reduced = bytes.to_vec();
+    x_reduced[64] += 2;
+    let x_reduced = Signature::from(x_reduced);
+    assert!(matches!(
+        x_reduced.verify_message_strict(ChainType::Ethereum, &"message", signer.address()),
+        Err(SignatureError::Ethereum(
+            EthereumError::NonCanonicalRecoveryId(29 | 30)
+        ))
+    ));
+
+    assert!(matches!(
+        signature.verify_message_strict(ChainType::Ethereum, &"message", [0u8; 32]),
+        Err(SignatureError::Ethereum(
+            EthereumError::InvalidAddressLength(32)
+        ))
+    ));
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_public_key() {
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let signer = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap();
+
+    let compressed = signer.public_key(true);
+    let uncompressed = signer.public_key(false);
+    assert_eq!(compressed.len(), 33);
+    assert_eq!(uncompressed.len(), 65);
+
+    let address = Address::from_public_key(ChainType::Ethereum, &compressed).unwrap();
+    assert!(address == *signer.address());
+    let address = Address::from_public_key(ChainType::Ethereum, &uncompressed).unwrap();
+    assert!(address == *signer.address());
+
+    let signature = signer.sign_message("message").unwrap();
+    let recovered = signature
+        .recover_public_key(ChainType::Ethereum, &"message", true)
+        .unwrap();
+    assert_eq!(recovered, compressed);
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_signing_domain() {
+    const ORDER_COMMITMENT: SigningDomain =
+        SigningDomain::from_static("radius.order_commitment.v1");
+
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let signer = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap();
+    let signature = signer
+        .sign_message_in_domain(&ORDER_COMMITMENT, "message")
+        .unwrap();
+
+    signature
+        .verify_message_in_domain(
+            ChainType::Ethereum,
+            &ORDER_COMMITMENT,
+            &"message",
+            signer.address(),
+        )
+        .unwrap();
+    signature
+        .verify_message_strict_in_domain(
+            ChainType::Ethereum,
+            &ORDER_COMMITMENT,
+            &"message",
+            signer.address(),
+        )
+        .unwrap();
+
+    let other_domain = SigningDomain::new(format!("radius.{}.v1", "sync_transaction"));
+    assert!(signature
+        .verify_message_in_domain(
+            ChainType::Ethereum,
+            &other_domain,
+            &"message",
+            signer.address()
+        )
+        .is_err());
+    assert!(signature
+        .verify_message(ChainType::Ethereum, &"message", signer.address())
+        .is_err());
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_signing_hook() {
+    use std::sync::{Arc, Mutex};
+
+    const ORDER_COMMITMENT: SigningDomain =
+        SigningDomain::from_static("radius.order_commitment.v1");
+
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let hook_records = records.clone();
+
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let signer = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key)
+        .unwrap()
+        .with_signing_hook(move |record: &SigningRecord| {
+            hook_records.lock().unwrap().push((
+                record.domain.map(|domain| domain.as_str().to_owned()),
+                record.message_hash,
+                record.key_id.clone(),
+                record.signature.clone(),
+            ));
+        });
+
+    let signature = signer.sign_message("message").unwrap();
+    let domain_signature = signer
+        .clone()
+        .sign_message_in_domain(&ORDER_COMMITMENT, "message")
+        .unwrap();
+
+    let records = records.lock().unwrap();
+    assert_eq!(records.len(), 2);
+
+    let (domain, message_hash, key_id, record_signature) = &records[0];
+    assert_eq!(*domain, None);
+    assert_eq!(
+        *message_hash,
+        hash::sha256(bincode::serialize("message").unwrap())
+    );
+    assert!(key_id == signer.address());
+    assert!(*record_signature == signature);
+
+    let (domain, message_hash, _, record_signature) = &records[1];
+    assert_eq!(domain.as_deref(), Some(ORDER_COMMITMENT.as_str()));
+    assert_eq!(
+        *message_hash,
+        hash::sha256(bincode::serialize(&(ORDER_COMMITMENT.as_str(), "message")).unwrap())
+    );
+    assert!(*record_signature == domain_signature);
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_async_signer() {
+    const ORDER_COMMITMENT: SigningDomain =
+        SigningDomain::from_static("radius.order_commitment.v1");
+
+    // Signers are moved to the threads of the runtime.
+    fn sign_on_thread<S>(signer: S) -> Result<Signature, SignatureError>
+    where
+        S: AsyncSigner + 'static,
+    {
+        std::thread::spawn(move || {
+            futures::executor::block_on(AsyncSigner::sign_message(&signer, &"message"))
+        })
+        .join()
+        .unwrap()
+    }
+
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let signer = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap();
+
+    let signature = sign_on_thread(signer.clone()).unwrap();
+    assert!(signature == signer.sign_message("message").unwrap());
+    signature
+        .verify_message_strict(ChainType::Ethereum, &"message", signer.address())
+        .unwrap();
+
+    let signature = futures::executor::block_on(AsyncSigner::sign_message_in_domain(
+        &signer,
+        &ORDER_COMMITMENT,
+        &"message",
+    ))
+    .unwrap();
+    signature
+        .verify_message_strict_in_domain(
+            ChainType::Ethereum,
+            &ORDER_COMMITMENT,
+            &"message",
+            signer.address(),
+        )
+        .unwrap();
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_signature_formats() {
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let signer = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap();
+
+    for message in ["message", "another message", "yet another message"] {
+        let signature = signer.sign_message(message).unwrap();
+        let is_y_odd = signature.as_bytes()[64] == 28;
+
+        let der = signature.to_der().unwrap();
+        assert_eq!(der[0], 0x30);
+        assert!(Signature::from_der(&der, is_y_odd).unwrap() == signature);
+
+        let compact = signature.to_compact().unwrap();
+        assert_eq!(compact.as_slice(), &signature.as_bytes()[0..64]);
+        assert!(Signature::from_compact(&compact, is_y_odd).unwrap() == signature);
+
+        let eip2098 = signature.to_eip2098().unwrap();
+        assert_eq!(eip2098.len(), 64);
+        assert_eq!(eip2098[32] & 0x80 != 0, is_y_odd);
+        let parsed_signature = Signature::from_eip2098(&eip2098).unwrap();
+        assert!(parsed_signature == signature);
+        parsed_signature
+            .verify_message_strict(ChainType::Ethereum, &message, signer.address())
+            .unwrap();
+    }
+
+    assert!(Signature::from(vec![0u8; 64]).to_der().is_err());
+    assert!(Signature::from_eip2098(&[0u8; 65]).is_err());
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_signature_serde_formats() {
+    #[derive(serde::Deserialize, serde::Serialize)]
+    struct Message {
+        #[serde(with = "signature_hex")]
+        hex: Signature,
+        #[serde(with = "signature_base64")]
+        base64: Signature,
+    }
+
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let signer = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap();
+    let signature = signer.sign_message("message").unwrap();
+
+    let message = Message {
+        hex: signature.clone(),
+        base64: signature.clone(),
+    };
+    let message_json = serde_json::to_value(&message).unwrap();
+    assert_eq!(message_json["hex"], signature.as_hex_string());
+    assert_eq!(message_json["base64"], signature.as_base64_string());
+
+    let parsed_message: Message = serde_json::from_value(message_json).unwrap();
+    assert!(parsed_message.hex == signature);
+    assert!(parsed_message.base64 == signature);
+
+    let parsed_signature: Signature =
+        serde_json::from_value(signature.as_base64_string().into()).unwrap();
+    assert!(parsed_signature == signature);
+    assert!(serde_json::from_value::<Signature>("not a signature!".into()).is_err());
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_multi_signature() {
+    let signers: Vec<PrivateKeySigner> = [
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+        "0x7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6",
+    ]
+    .iter()
+    .map(|signing_key| PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap())
+    .collect();
+    let cluster: Vec<Address> = signers[..3]
+        .iter()
+        .map(|signer| signer.address().clone())
+        .collect();
+
+    let mut multi_signature = MultiSignature::new();
+    assert!(multi_signature.add(
+        signers[0].address().clone(),
+        signers[0].sign_message("commitment").unwrap()
+    ));
+    assert!(!multi_signature.add(
+        signers[0].address().clone(),
+        signers[0].sign_message("commitment").unwrap()
+    ));
+    // Outside of the cluster.
+    assert!(multi_signature.add(
+        signers[3].address().clone(),
+        signers[3].sign_message("commitment").unwrap()
+    ));
+    // Over another message.
+    assert!(multi_signature.add(
+        signers[1].address().clone(),
+        signers[1].sign_message("another commitment").unwrap()
+    ));
+    assert_eq!(multi_signature.len(), 3);
+
+    assert!(matches!(
+        multi_signature.verify_threshold(ChainType::Ethereum, &"commitment", 2, &cluster),
+        Err(SignatureError::ThresholdNotMet {
+            threshold: 2,
+            valid_signatures: 1,
+        })
+    ));
+
+    multi_signature.add(
+        signers[2].address().clone(),
+        signers[2].sign_message("commitment").unwrap(),
+    );
+    multi_signature
+        .verify_threshold(ChainType::Ethereum, &"commitment", 2, &cluster)
+        .unwrap();
+
+    let parsed_multi_signature: MultiSignature =
+        serde_json::from_value(serde_json::to_value(&multi_signature).unwrap()).unwrap();
+    assert!(parsed_multi_signature == multi_signature);
+
+    // A received envelope repeating one signer, which add() would reject.
+    let signature = signers[0].sign_message("commitment").unwrap();
+    let repeated_multi_signature: MultiSignature = serde_json::from_value(serde_json::json!({
+        "signatures": [
+            [signers[0].address(), signature],
+            [signers[0].address(), signature],
+        ]
+    }))
+    .unwrap();
+    assert_eq!(repeated_multi_signature.len(), 2);
+    assert!(matches!(
+        repeated_multi_signature.verify_threshold(ChainType::Ethereum, &"commitment", 2, &cluster),
+        Err(SignatureError::ThresholdNotMet {
+            threshold: 2,
+            valid_signatures: 1,
+        })
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_verification_without_signer() {
+    use std::str::FromStr;
+
+    use alloy::signers::{local::LocalSigner, SignerSync};
+
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let alloy_signer = LocalSigner::from_str(signing_key).unwrap();
+    let message_serialized = bincode::serialize("message").unwrap();
+    let alloy_signature = alloy_signer.sign_message_sync(&message_serialized).unwrap();
+
+    let signature = Signature::from(alloy_signature.as_bytes().to_vec());
+    signature
+        .verify_message_strict(ChainType::Ethereum, &"message", alloy_signer.address())
+        .unwrap();
+    assert!(signature
+        .verify_message(
+            ChainType::Ethereum,
+            &"another message",
+            alloy_signer.address()
+        )
+        .is_err());
+
+    let public_key = signature
+        .recover_public_key(ChainType::Ethereum, &"message", false)
+        .unwrap();
+    let address = Address::from_public_key(ChainType::Ethereum, &public_key).unwrap();
+    assert!(address == alloy_signer.address());
+}
+
+#[test]
+fn test_ecrecover() {
+    use core::str::FromStr;
+
+    use alloy::signers::{local::LocalSigner, SignerSync};
+
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let alloy_signer = LocalSigner::from_str(signing_key).unwrap();
+    let alloy_address = alloy_signer.address();
+    let signature = alloy_signer
+        .sign_message_sync(b"commitment")
+        .unwrap()
+        .as_bytes();
+
+    let address = ecrecover::recover_address(&signature, b"commitment", true).unwrap();
+    assert_eq!(address, alloy_address.as_slice());
+    ecrecover::verify_message_strict(&signature, b"commitment", alloy_address.as_slice()).unwrap();
+    assert!(matches!(
+        ecrecover::verify_message(&signature, b"another commitment", alloy_address.as_slice()),
+        Err(ecrecover::EcrecoverError::AddressMismatch)
+    ));
+    assert!(matches!(
+        ecrecover::verify_message(&signature[..64], b"commitment", alloy_address.as_slice()),
+        Err(ecrecover::EcrecoverError::InvalidSignatureLength(64))
+    ));
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_ed25519() {
+    // RFC 8032, section 7.1, test 1.
+    let signing_key = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+    let public_key = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+
+    let signer = PrivateKeySigner::from_str(ChainType::Ed25519, signing_key).unwrap();
+    assert!(*signer.address() == const_hex::decode(public_key).unwrap());
+    assert!(signer.public_key(true) == signer.public_key(false));
+    assert!(
+        Address::from_public_key(ChainType::Ed25519, &signer.public_key(true)).unwrap()
+            == signer.address()
+    );
+    assert!(Address::from_str(ChainType::Ed25519, public_key).unwrap() == signer.address());
+    assert!(Address::from_slice(ChainType::Ed25519, &[0u8; 20]).is_err());
+
+    let signature = signer.sign_message("message").unwrap();
+    assert_eq!(signature.len(), 64);
+    signature
+        .verify_message_strict(ChainType::Ed25519, &"message", signer.address())
+        .unwrap();
+    assert!(signature
+        .verify_message(ChainType::Ed25519, &"another message", signer.address())
+        .is_err());
+    assert!(signature
+        .verify_message(ChainType::Ethereum, &"message", signer.address())
+        .is_err());
+    assert!(signature
+        .recover_public_key(ChainType::Ed25519, &"message", true)
+        .is_err());
+
+    let chain_type: ChainType = serde_json::from_str("\"ed25519\"").unwrap();
+    assert_eq!(chain_type, ChainType::Ed25519);
+}
+
+#[cfg(feature = "mnemonic")]
+#[test]
+fn test_ed25519_mnemonic() {
+    use chain_type::ed25519::slip10_derive;
+
+    // SLIP-0010, test vector 1 for ed25519.
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    assert_eq!(
+        const_hex::encode(slip10_derive(&seed, "m").unwrap()),
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+    );
+    assert_eq!(
+        const_hex::encode(slip10_derive(&seed, "m/0'/1'/2'/2'/1000000000'").unwrap()),
+        "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"
+    );
+    assert!(slip10_derive(&seed, "m/0").is_err());
+
+    let phrase = "test test test test test test test test test test test junk";
+    let signer = PrivateKeySigner::from_mnemonic_index(ChainType::Ed25519, phrase, 0).unwrap();
+    let same_signer =
+        PrivateKeySigner::from_mnemonic(ChainType::Ed25519, phrase, "m/44'/784'/0'/0'/0'").unwrap();
+    assert!(signer.address() == same_signer.address());
+    let other_signer =
+        PrivateKeySigner::from_mnemonic_index(ChainType::Ed25519, phrase, 1).unwrap();
+    assert!(signer.address() != other_signer.address());
+}
+
+#[cfg(feature = "signer")]
+#[test]
+fn test_p256() {
+    use crate::chain_type::p256::P256Error;
+
+    // RFC 6979, section A.2.5, SHA-256 over "sample".
+    let signing_key = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+    let public_key = "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299";
+    let rfc6979_signature = "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8";
+
+    let signer = PrivateKeySigner::from_str(ChainType::P256, signing_key).unwrap();
+    assert_eq!(signer.address().len(), 33);
+    assert_eq!(
+        signer.public_key(false),
+        const_hex::decode(public_key).unwrap()
+    );
+    assert!(Address::from_str(ChainType::P256, public_key).unwrap() == signer.address());
+    assert!(Address::from_slice(ChainType::P256, &[0u8; 20]).is_err());
+
+    // The test vector has a high `s`, which only the strict check rejects.
+    let verifier = ChainType::P256.verifier();
+    let rfc6979_signature = const_hex::decode(rfc6979_signature).unwrap();
+    verifier
+        .verify_message(&rfc6979_signature, b"sample", signer.address().as_ref())
+        .unwrap();
+    assert!(matches!(
+        verifier.verify_message_strict(&rfc6979_signature, b"sample", signer.address().as_ref()),
+        Err(SignatureError::P256(P256Error::MalleableSignature))
+    ));
+
+    let signature = signer.sign_message("message").unwrap();
+    assert_eq!(signature.len(), 64);
+    signature
+        .verify_message_strict(ChainType::P256, &"message", signer.address())
+        .unwrap();
+    signature
+        .verify_message(ChainType::P256, &"message", signer.public_key(false))
+        .unwrap();
+    assert!(signature
+        .verify_message(ChainType::P256, &"another message", signer.address())
+        .is_err());
+    assert!(signature
+        .verify_message(ChainType::Ethereum, &"message", signer.address())
+        .is_err());
+    assert!(signature
+        .recover_public_key(ChainType::P256, &"message", true)
+        .is_err());
+
+    let der = p256::ecdsa::Signature::from_slice(signature.as_bytes())
+        .unwrap()
+        .to_der();
+    assert!(Signature::from_p256_der(der.as_bytes()).unwrap() == signature);
+
+    let chain_type: ChainType = serde_json::from_str("\"p256\"").unwrap();
+    assert_eq!(chain_type, ChainType::P256);
+}
+
+#[cfg(all(feature = "signer", feature = "alloy-compat"))]
+#[test]
+fn test_alloy_compat() {
+    use std::str::FromStr;
+
+    use alloy::signers::{local::LocalSigner, SignerSync};
+
+    let signing_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let alloy_signer = LocalSigner::from_str(signing_key).unwrap();
+    let sequencer_signer = PrivateKeySigner::from_str(ChainType::Ethereum, signing_key).unwrap();
+
+    let address = Address::from(alloy_signer.address());
+    assert!(address == *sequencer_signer.address());
+    assert_eq!(
+        alloy::primitives::Address::try_from(sequencer_signer.address()).unwrap(),
+        alloy_signer.address()
+    );
+    assert!(matches!(
+        alloy::primitives::Address::try_from(Address::from(vec![0; 32])),
+        Err(SignatureError::InvalidAlloyAddressLength(32))
+    ));
+
+    let message = bincode::serialize("message").unwrap();
+    let alloy_signature = alloy_signer.sign_message_sync(&message).unwrap();
+    let signature = sequencer_signer.sign_message("message").unwrap();
+    assert!(Signature::from(alloy_signature) == signature);
+    assert_eq!(
+        alloy::primitives::PrimitiveSignature::try_from(&signature).unwrap(),
+        alloy_signature
+    );
+    assert!(alloy::primitives::PrimitiveSignature::try_from(Signature::from(vec![0; 64])).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_custom_chain_type() {
+    // A keyed hash standing in for an experimental signature scheme.
+    struct HashVerifier;
+
+    impl Verifier for HashVerifier {
+        fn verify_message(
+            &self,
+            signature: &[u8],
+            message: &[u8],
+            address: &[u8],
+        ) -> Result<(), SignatureError> {
+            match hash::sha256([address, message].concat()) == signature {
+                true => Ok(()),
+                false => Err(SignatureError::UnsupportedChainType("invalid".into())),
+            }
+        }
+
+        fn verify_message_strict(
+            &self,
+            signature: &[u8],
+            message: &[u8],
+            address: &[u8],
+        ) -> Result<(), SignatureError> {
+            self.verify_message(signature, message, address)
+        }
+
+        fn recover_public_key(
+            &self,
+            _signature: &[u8],
+            _message: &[u8],
+            _compressed: bool,
+        ) -> Result<Vec<u8>, SignatureError> {
+            Err(SignatureError::UnsupportedChainType("hash".into()))
+        }
+    }
+
+    struct HashAddressBuilder;
+
+    impl Builder for HashAddressBuilder {
+        type Output = Address;
+
+        fn build_from_slice(&self, slice: &[u8]) -> Result<Self::Output, SignatureError> {
+            Ok(Address::from(slice.to_vec()))
+        }
+
+        fn build_from_str(&self, str: &str) -> Result<Self::Output, SignatureError> {
+            Ok(Address::from(str.as_bytes().to_vec()))
+        }
+    }
+
+    let chain_type = ChainType::register(ChainRegistration::new(
+        "hash",
+        HashVerifier,
+        HashAddressBuilder,
+    ))
+    .unwrap();
+    assert!(matches!(
+        ChainType::register(ChainRegistration::new(
+            "hash",
+            HashVerifier,
+            HashAddressBuilder
+        )),
+        Err(SignatureError::ChainTypeAlreadyRegistered(_))
+    ));
+    assert!(matches!(
+        ChainType::register(ChainRegistration::new(
+            "ethereum",
+            HashVerifier,
+            HashAddressBuilder
+        )),
+        Err(SignatureError::ChainTypeAlreadyRegistered(_))
+    ));
+
+    assert_eq!(serde_json::to_string(&chain_type).unwrap(), "\"hash\"");
+    assert!(serde_json::from_str::<ChainType>("\"hash\"").unwrap() == chain_type);
+    assert_eq!(
+        serde_json::to_string(&ChainType::Ethereum).unwrap(),
+        "\"ethereum\""
+    );
+
+    let address = Address::from_slice(chain_type, &[1; 8]).unwrap();
+    let message = bincode::serialize("message").unwrap();
+    let signature = Signature::from(hash::sha256([address.as_ref(), &message].concat()).to_vec());
+    signature
+        .verify_message(chain_type, &"message", &address)
+        .unwrap();
+    assert!(signature
+        .verify_message(chain_type, &"another message", &address)
+        .is_err());
+
+    // Registered without a public key builder.
+    assert!(matches!(
+        Address::from_public_key(chain_type, &[1; 8]),
+        Err(SignatureError::UnsupportedChainType(_))
+    ));
+    #[cfg(feature = "signer")]
+    assert!(matches!(
+        PrivateKeySigner::from_str(chain_type, "key"),
+        Err(SignatureError::UnsupportedChainType(_))
+    ));
+}
+
+#[cfg(feature = "erc1271")]
+#[test]
+fn test_erc1271_revert() {
+    use alloy::rpc::json_rpc::ErrorPayload;
+
+    let error_payload = |code: i64, message: &str| ErrorPayload {
+        code,
+        message: message.to_owned().into(),
+        data: None,
+    };
+
+    assert!(erc1271::is_revert(&error_payload(
+        3,
+        "execution reverted: "
+    )));
+    assert!(erc1271::is_revert(&error_payload(
+        -32000,
+        "execution reverted"
+    )));
+    assert!(!erc1271::is_revert(&error_payload(
+        -32005,
+        "rate limit exceeded"
+    )));
+    assert!(!erc1271::is_revert(&error_payload(
+        -32601,
+        "method not found"
+    )));
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{address::Address, chain_type::ChainType, error::SignatureError, signature::Signature};
+
+/// Signatures of the same message by distinct signers, collected one at a
+/// time, e.g. the approvals of an order commitment by the sequencers of a
+/// cluster.
+///
+/// # Examples
+#[cfg_attr(feature = "signer", doc = "```rust")]
+#[cfg_attr(not(feature = "signer"), doc = "```rust,ignore")]
+/// use signature::{ChainType, MultiSignature, PrivateKeySigner};
+///
+/// let signers: Vec<PrivateKeySigner> = [
+///     "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+///     "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+/// ]
+/// .iter()
+/// .map(|signing_key| PrivateKeySigner::from_str(ChainType::Ethereum,
+/// signing_key).unwrap()) .collect();
+/// let cluster: Vec<_> = signers
+///     .iter()
+///     .map(|signer| signer.address().clone())
+///     .collect();
+///
+/// let mut multi_signature = MultiSignature::new();
+/// for signer in &signers[..2] {
+///     let signature = signer.sign_message("commitment").unwrap();
+///     multi_signature.add(signer.address().clone(), signature);
+/// }
+///
+/// multi_signature
+///     .verify_threshold(ChainType::Ethereum, &"commitment", 2, &cluster)
+///     .unwrap();
+/// assert!(multi_signature
+///     .verify_threshold(ChainType::Ethereum, &"commitment", 3, &cluster)
+///     .is_err());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct MultiSignature {
+    signatures: Vec<(Address, Signature)>,
+}
+
+impl MultiSignature {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the signature of `address`. Return `false` without adding it if
+    /// a signature of `address` was already added. The signature is only
+    /// checked by [`MultiSignature::verify_threshold()`].
+    pub fn add(&mut self, address: Address, signature: Signature) -> bool {
+        if self.contains(&address) {
+            return false;
+        }
+        self.signatures.push((address, signature));
+
+        true
+    }
+
+    pub fn contains(&self, address: &Address) -> bool {
+        self.signatures.iter().any(|(signer, _)| signer == address)
+    }
+
+    pub fn signers(&self) -> impl Iterator<Item = &Address> {
+        self.signatures.iter().map(|(signer, _)| signer)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Address, &Signature)> {
+        self.signatures
+            .iter()
+            .map(|(signer, signature)| (signer, signature))
+    }
+
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    /// Check that at least `threshold` of `allowed_signers` signed
+    /// `message`. Signatures of other signers and signatures that fail
+    /// [`Signature::verify_message_strict()`] are not counted, so a single
+    /// invalid approval does not reject an otherwise sufficient set.
+    ///
+    /// Each signer counts once. A deserialized [`MultiSignature`] has not
+    /// been through [`MultiSignature::add()`], so it may list a signer more
+    /// than once.
+    pub fn verify_threshold<T: Serialize>(
+        &self,
+        chain_type: ChainType,
+        message: &T,
+        threshold: usize,
+        allowed_signers: &[Address],
+    ) -> Result<(), SignatureError> {
+        let message_bytes =
+            bincode::serialize(message).map_err(SignatureError::SerializeMessage)?;
+        let verifier = chain_type.verifier();
+
+        let mut valid_signers: HashSet<&Address> = HashSet::new();
+        for (signer, signature) in &self.signatures {
+            if valid_signers.len() >= threshold {
+                break;
+            }
+            if valid_signers.contains(signer) {
+                continue;
+            }
+
+            let is_allowed = allowed_signers
+                .iter()
+                .any(|allowed_signer| allowed_signer == signer);
+            if is_allowed
+                && verifier
+                    .verify_message_strict(signature.as_bytes(), &message_bytes, signer.as_ref())
+                    .is_ok()
+            {
+                valid_signers.insert(signer);
+            }
+        }
+
+        let valid_signatures = valid_signers.len();
+        match valid_signatures >= threshold {
+            true => Ok(()),
+            false => Err(SignatureError::ThresholdNotMet {
+                threshold,
+                valid_signatures,
+            }),
+        }
+    }
+}
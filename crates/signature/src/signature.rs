@@ -1,8 +1,10 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
 use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
 
-use crate::{chain_type::*, error::SignatureError, Verifier};
+use crate::{chain_type::*, domain::SigningDomain, error::SignatureError};
 
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, Deserialize, Serialize)]
 #[serde(try_from = "SignatureType")]
 pub struct Signature(Vec<u8>);
 
@@ -13,6 +15,14 @@ enum SignatureType {
     String(String),
 }
 
+/// Compare in constant time with respect to the contents. Only the lengths
+/// are compared in variable time.
+impl std::cmp::PartialEq for Signature {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_slice().ct_eq(other.0.as_slice()).into()
+    }
+}
+
 impl From<&[u8]> for Signature {
     fn from(value: &[u8]) -> Self {
         Self(value.to_owned())
@@ -31,12 +41,14 @@ impl TryFrom<SignatureType> for Signature {
     fn try_from(value: SignatureType) -> Result<Self, Self::Error> {
         match value {
             SignatureType::Array(signature) => Ok(Self(signature)),
-            SignatureType::String(signature) => {
-                let signature =
-                    const_hex::decode(signature).map_err(SignatureError::DeserializeSignature)?;
-
-                Ok(Self(signature))
-            }
+            SignatureType::String(signature) => match const_hex::decode(&signature) {
+                Ok(signature) => Ok(Self(signature)),
+                // Hex is tried first as the digits are also valid base64.
+                Err(error) => BASE64_STANDARD
+                    .decode(&signature)
+                    .map(Self)
+                    .map_err(|_| SignatureError::DeserializeSignature(error)),
+            },
         }
     }
 }
@@ -56,6 +68,76 @@ impl Signature {
             .verify_message(&self.0, &message_bytes, address.as_ref())
     }
 
+    /// Same as [`Signature::verify_message()`] but also reject encodings that
+    /// honest signers never produce, such as ECDSA parity bytes flagging a
+    /// reduced `r`, so that each message has a single accepted signature.
+    ///
+    /// Use this wherever a signature authenticates a peer or is stored and
+    /// compared later.
+    pub fn verify_message_strict<T: Serialize>(
+        &self,
+        chain_type: ChainType,
+        message: &T,
+        address: impl AsRef<[u8]>,
+    ) -> Result<(), SignatureError> {
+        let message_bytes =
+            bincode::serialize(message).map_err(SignatureError::SerializeMessage)?;
+
+        chain_type
+            .verifier()
+            .verify_message_strict(&self.0, &message_bytes, address.as_ref())
+    }
+
+    /// Verify a signature made with
+    /// [`crate::PrivateKeySigner::sign_message_in_domain()`]. Fails if the
+    /// message was signed under another domain or without one.
+    pub fn verify_message_in_domain<T: Serialize>(
+        &self,
+        chain_type: ChainType,
+        domain: &SigningDomain,
+        message: &T,
+        address: impl AsRef<[u8]>,
+    ) -> Result<(), SignatureError> {
+        let message_bytes = domain.serialize(message)?;
+
+        chain_type
+            .verifier()
+            .verify_message(&self.0, &message_bytes, address.as_ref())
+    }
+
+    /// Same as [`Signature::verify_message_in_domain()`] with the checks of
+    /// [`Signature::verify_message_strict()`].
+    pub fn verify_message_strict_in_domain<T: Serialize>(
+        &self,
+        chain_type: ChainType,
+        domain: &SigningDomain,
+        message: &T,
+        address: impl AsRef<[u8]>,
+    ) -> Result<(), SignatureError> {
+        let message_bytes = domain.serialize(message)?;
+
+        chain_type
+            .verifier()
+            .verify_message_strict(&self.0, &message_bytes, address.as_ref())
+    }
+
+    /// Recover the SEC1-encoded public key that signed `message`, with the
+    /// same checks as [`Signature::verify_message_strict()`]. Ed25519 and
+    /// P-256 signatures do not allow recovery and always fail.
+    pub fn recover_public_key<T: Serialize>(
+        &self,
+        chain_type: ChainType,
+        message: &T,
+        compressed: bool,
+    ) -> Result<Vec<u8>, SignatureError> {
+        let message_bytes =
+            bincode::serialize(message).map_err(SignatureError::SerializeMessage)?;
+
+        chain_type
+            .verifier()
+            .recover_public_key(&self.0, &message_bytes, compressed)
+    }
+
     pub fn as_bytes(&self) -> &[u8] {
         self.0.as_slice()
     }
@@ -71,4 +153,88 @@ impl Signature {
     pub fn as_hex_string(&self) -> String {
         const_hex::encode_prefixed(&self.0)
     }
+
+    pub fn as_base64_string(&self) -> String {
+        BASE64_STANDARD.encode(&self.0)
+    }
+
+    /// Encode a 65-byte secp256k1 `r || s || v` signature, as produced for
+    /// [`ChainType::Ethereum`], in ASN.1 DER. The `y` parity is not part of
+    /// DER and is lost.
+    pub fn to_der(&self) -> Result<Vec<u8>, SignatureError> {
+        ethereum::to_der(&self.0)
+    }
+
+    /// Decode an ASN.1 DER secp256k1 signature into `r || s || v`.
+    pub fn from_der(der: &[u8], is_y_odd: bool) -> Result<Self, SignatureError> {
+        ethereum::from_der(der, is_y_odd)
+    }
+
+    /// Encode a 65-byte secp256k1 `r || s || v` signature as the 64-byte
+    /// `r || s` without the `y` parity.
+    pub fn to_compact(&self) -> Result<Vec<u8>, SignatureError> {
+        ethereum::to_compact(&self.0)
+    }
+
+    /// Decode a 64-byte secp256k1 `r || s` signature into `r || s || v`.
+    pub fn from_compact(compact: &[u8], is_y_odd: bool) -> Result<Self, SignatureError> {
+        ethereum::from_compact(compact, is_y_odd)
+    }
+
+    /// Encode a 65-byte secp256k1 `r || s || v` signature in the 64-byte
+    /// form of [EIP-2098](https://eips.ethereum.org/EIPS/eip-2098), which
+    /// stores the `y` parity in the top bit of `s`. Fails on a high `s`.
+    pub fn to_eip2098(&self) -> Result<Vec<u8>, SignatureError> {
+        ethereum::to_eip2098(&self.0)
+    }
+
+    /// Decode a 64-byte EIP-2098 signature into `r || s || v`.
+    pub fn from_eip2098(compact: &[u8]) -> Result<Self, SignatureError> {
+        ethereum::from_eip2098(compact)
+    }
+
+    /// Decode an ASN.1 DER P-256 signature, as returned by WebAuthn
+    /// authenticators and secure enclaves, into the 64-byte `r || s` of
+    /// [`ChainType::P256`].
+    pub fn from_p256_der(der: &[u8]) -> Result<Self, SignatureError> {
+        p256::from_der(der)
+    }
+}
+
+/// `#[serde(with = "signature::signature_hex")]` serializes a [`Signature`]
+/// as a `0x`-prefixed hex string instead of an array of bytes.
+pub mod signature_hex {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Signature;
+
+    pub fn serialize<S: Serializer>(
+        signature: &Signature,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&signature.as_hex_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Signature, D::Error> {
+        Signature::deserialize(deserializer)
+    }
+}
+
+/// `#[serde(with = "signature::signature_base64")]` serializes a
+/// [`Signature`] as a standard base64 string instead of an array of bytes.
+pub mod signature_base64 {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Signature;
+
+    pub fn serialize<S: Serializer>(
+        signature: &Signature,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&signature.as_base64_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Signature, D::Error> {
+        Signature::deserialize(deserializer)
+    }
 }
@@ -1,34 +1,43 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
 use serde::Serialize;
 
 use crate::{
-    address::Address, chain_type::ChainType, error::SignatureError, signature::Signature, traits::*,
+    address::Address,
+    chain_type::ChainType,
+    domain::SigningDomain,
+    error::SignatureError,
+    hash::sha256,
+    signature::Signature,
+    signing_hook::{SigningHook, SigningRecord},
+    traits::*,
 };
 
+/// Signers are shared across threads, so the [`Signer`] it is built from
+/// must be `Send` and `Sync`. See [`crate::AsyncSigner`] for signing from
+/// async code.
 pub struct PrivateKeySigner {
-    inner: Arc<dyn Signer>,
+    inner: Arc<dyn Signer + Send + Sync>,
+    signing_hook: Option<Arc<dyn SigningHook>>,
 }
 
-unsafe impl Send for PrivateKeySigner {}
-
-unsafe impl Sync for PrivateKeySigner {}
-
 impl Clone for PrivateKeySigner {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            signing_hook: self.signing_hook.clone(),
         }
     }
 }
 
 impl<T> From<T> for PrivateKeySigner
 where
-    T: Signer + 'static,
+    T: Signer + Send + Sync + 'static,
 {
     fn from(value: T) -> Self {
         Self {
             inner: Arc::new(value),
+            signing_hook: None,
         }
     }
 }
@@ -42,14 +51,58 @@ impl PrivateKeySigner {
         chain_type.signer_builder().build_from_str(private_key)
     }
 
+    #[cfg(feature = "os-rng")]
     pub fn from_random(chain_type: ChainType) -> Result<(Self, String), SignatureError> {
         chain_type.signer_builder_random().build_from_random()
     }
 
+    /// Derive the signer at `derivation_path` (e.g. `m/44'/60'/0'/0/0`) from
+    /// a BIP-39 mnemonic phrase.
+    #[cfg(feature = "mnemonic")]
+    pub fn from_mnemonic(
+        chain_type: ChainType,
+        phrase: &str,
+        derivation_path: &str,
+    ) -> Result<Self, SignatureError> {
+        chain_type
+            .signer_builder_mnemonic()
+            .build_from_mnemonic(phrase, derivation_path)
+    }
+
+    /// Derive the signer at `index` of the chain's BIP-44 derivation path, so
+    /// that one mnemonic can hold a separate identity for each cluster.
+    #[cfg(feature = "mnemonic")]
+    pub fn from_mnemonic_index(
+        chain_type: ChainType,
+        phrase: &str,
+        index: u32,
+    ) -> Result<Self, SignatureError> {
+        let signer_builder = chain_type.signer_builder_mnemonic();
+        let derivation_path = signer_builder.derivation_path(index);
+
+        signer_builder.build_from_mnemonic(phrase, &derivation_path)
+    }
+
+    /// Call `signing_hook` after every signature made by this signer and
+    /// its clones made afterwards, see [`SigningHook`].
+    pub fn with_signing_hook(mut self, signing_hook: impl SigningHook) -> Self {
+        self.signing_hook = Some(Arc::new(signing_hook));
+
+        self
+    }
+
     pub fn address(&self) -> &Address {
         self.inner.address()
     }
 
+    /// Return the SEC1-encoded public key, 33 bytes if `compressed` and 65
+    /// bytes otherwise for secp256k1 and P-256, or the 32-byte key for
+    /// Ed25519. Unlike the address, the public key lets peers verify
+    /// signatures and derive shared keys.
+    pub fn public_key(&self, compressed: bool) -> Vec<u8> {
+        self.inner.public_key(compressed)
+    }
+
     pub fn sign_message<T>(&self, message: T) -> Result<Signature, SignatureError>
     where
         T: Serialize,
@@ -57,6 +110,41 @@ impl PrivateKeySigner {
         let message_bytes =
             bincode::serialize(&message).map_err(SignatureError::SerializeMessage)?;
 
-        self.inner.sign_message(&message_bytes)
+        self.sign_with_hook(None, &message_bytes)
+    }
+
+    /// Sign `message` bound to `domain`. The signature only verifies with
+    /// [`Signature::verify_message_in_domain()`] under the same domain.
+    pub fn sign_message_in_domain<T>(
+        &self,
+        domain: &SigningDomain,
+        message: T,
+    ) -> Result<Signature, SignatureError>
+    where
+        T: Serialize,
+    {
+        let message_bytes = domain.serialize(&message)?;
+
+        self.sign_with_hook(Some(domain), &message_bytes)
+    }
+
+    pub(crate) fn sign_with_hook(
+        &self,
+        domain: Option<&SigningDomain>,
+        message_bytes: &[u8],
+    ) -> Result<Signature, SignatureError> {
+        let signature = self.inner.sign_message(message_bytes)?;
+
+        if let Some(signing_hook) = &self.signing_hook {
+            signing_hook.on_sign(&SigningRecord {
+                domain,
+                message_hash: sha256(message_bytes),
+                timestamp: SystemTime::now(),
+                key_id: self.address(),
+                signature: &signature,
+            });
+        }
+
+        Ok(signature)
     }
 }
@@ -0,0 +1,61 @@
+use std::time::SystemTime;
+
+use crate::{address::Address, domain::SigningDomain, signature::Signature};
+
+/// Called by [`crate::PrivateKeySigner`] after every successful signature,
+/// registered with [`crate::PrivateKeySigner::with_signing_hook()`], e.g. to
+/// append what the sequencer key signed to an audit log. Chaining the hash
+/// of each entry into the next one makes the log tamper-evident.
+///
+/// The hook runs on the signing thread, so it should hand slow work such as
+/// disk writes off to another thread. Signers without a hook pay nothing.
+///
+/// Any `Fn(&SigningRecord)` closure is a hook:
+///
+/// ```rust
+/// use signature::{ChainType, PrivateKeySigner, SigningRecord};
+///
+/// let signer = PrivateKeySigner::from_str(
+///     ChainType::Ethereum,
+///     "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+/// )
+/// .unwrap()
+/// .with_signing_hook(|record: &SigningRecord| {
+///     println!(
+///         "{:?} signed {:?} in {:?}",
+///         record.key_id,
+///         record.message_hash,
+///         record.domain.map(|domain| domain.as_str()),
+///     );
+/// });
+///
+/// signer.sign_message("message").unwrap();
+/// ```
+pub trait SigningHook: Send + Sync + 'static {
+    fn on_sign(&self, record: &SigningRecord<'_>);
+}
+
+impl<F> SigningHook for F
+where
+    F: Fn(&SigningRecord<'_>) + Send + Sync + 'static,
+{
+    fn on_sign(&self, record: &SigningRecord<'_>) {
+        self(record)
+    }
+}
+
+/// What a [`crate::PrivateKeySigner`] signed, passed to its [`SigningHook`].
+#[derive(Debug)]
+pub struct SigningRecord<'a> {
+    /// `None` for [`crate::PrivateKeySigner::sign_message()`].
+    pub domain: Option<&'a SigningDomain>,
+    /// SHA-256 of the serialized message, including the domain tag if any,
+    /// i.e. of the exact bytes that were signed.
+    pub message_hash: [u8; 32],
+    /// The time of signing. Reading the system clock panics on
+    /// `wasm32-unknown-unknown`, so hooks are not supported there.
+    pub timestamp: SystemTime,
+    /// The address of the signing key.
+    pub key_id: &'a Address,
+    pub signature: &'a Signature,
+}
@@ -1,4 +1,6 @@
-use crate::{address::Address, error::SignatureError, signature::Signature};
+use crate::error::SignatureError;
+#[cfg(feature = "signer")]
+use crate::{address::Address, signature::Signature};
 
 pub trait Builder {
     type Output;
@@ -8,15 +10,39 @@ pub trait Builder {
     fn build_from_str(&self, str: &str) -> Result<Self::Output, SignatureError>;
 }
 
+pub trait PublicKeyBuilder {
+    type Output;
+
+    fn build_from_public_key(&self, public_key: &[u8]) -> Result<Self::Output, SignatureError>;
+}
+
+#[cfg(feature = "signer")]
 pub trait RandomBuilder {
     type Output;
 
     fn build_from_random(&self) -> Result<Self::Output, SignatureError>;
 }
 
+#[cfg(feature = "mnemonic")]
+pub trait MnemonicBuilder {
+    type Output;
+
+    fn build_from_mnemonic(
+        &self,
+        phrase: &str,
+        derivation_path: &str,
+    ) -> Result<Self::Output, SignatureError>;
+
+    fn derivation_path(&self, index: u32) -> String;
+}
+
+#[cfg(feature = "signer")]
 pub trait Signer {
     fn address(&self) -> &Address;
 
+    /// SEC1-encoded public key, compressed or uncompressed.
+    fn public_key(&self, compressed: bool) -> Vec<u8>;
+
     fn sign_message(&self, message: &[u8]) -> Result<Signature, SignatureError>;
 }
 
@@ -27,4 +53,18 @@ pub trait Verifier {
         message: &[u8],
         address: &[u8],
     ) -> Result<(), SignatureError>;
+
+    fn verify_message_strict(
+        &self,
+        signature: &[u8],
+        message: &[u8],
+        address: &[u8],
+    ) -> Result<(), SignatureError>;
+
+    fn recover_public_key(
+        &self,
+        signature: &[u8],
+        message: &[u8],
+        compressed: bool,
+    ) -> Result<Vec<u8>, SignatureError>;
 }
@@ -1,3 +1,5 @@
 pub mod publisher;
+pub mod quorum;
+pub mod rewards;
 pub mod subscriber;
 pub mod types;
@@ -8,14 +8,17 @@ use alloy::{
             BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller,
             WalletFiller,
         },
-        Identity, PendingTransactionBuilder, ProviderBuilder, RootProvider, WalletProvider,
+        Identity, PendingTransactionBuilder, Provider, ProviderBuilder, RootProvider,
+        WalletProvider,
     },
+    rpc::types::Filter,
     signers::{k256::ecdsa::SigningKey, local::LocalSigner, Signer},
+    sol_types::SolEvent,
     transports::http::{reqwest::Url, Client, Http},
 };
 use chrono::Utc;
 
-use crate::types::*;
+use crate::{quorum::*, rewards::*, types::*};
 
 type EthereumHttpProvider = FillProvider<
     JoinFill<
@@ -94,6 +97,38 @@ type AvsContract = Avs::AvsInstance<
     >,
 >;
 
+type RewardsCoordinatorContract = RewardsCoordinator::RewardsCoordinatorInstance<
+    Http<Client>,
+    FillProvider<
+        JoinFill<
+            JoinFill<
+                Identity,
+                JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
+            >,
+            WalletFiller<EthereumWallet>,
+        >,
+        RootProvider<Http<Client>>,
+        Http<Client>,
+        Ethereum,
+    >,
+>;
+
+type RegistryCoordinatorContract = IRegistryCoordinator::IRegistryCoordinatorInstance<
+    Http<Client>,
+    FillProvider<
+        JoinFill<
+            JoinFill<
+                Identity,
+                JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
+            >,
+            WalletFiller<EthereumWallet>,
+        >,
+        RootProvider<Http<Client>>,
+        Http<Client>,
+        Ethereum,
+    >,
+>;
+
 pub struct Publisher {
     provider: EthereumHttpProvider,
     signer: LocalSigner<SigningKey>,
@@ -101,6 +136,8 @@ pub struct Publisher {
     avs_directory_contract: AvsDirectoryContract,
     ecdsa_stake_registry_contract: EcdsaStakeRegistryContract,
     avs_contract: AvsContract,
+    rewards_coordinator_contract: Option<RewardsCoordinatorContract>,
+    registry_coordinator_contract: Option<RegistryCoordinatorContract>,
 }
 
 impl Publisher {
@@ -189,9 +226,55 @@ impl Publisher {
             avs_directory_contract,
             ecdsa_stake_registry_contract,
             avs_contract,
+            rewards_coordinator_contract: None,
+            registry_coordinator_contract: None,
         })
     }
 
+    /// Use the registry coordinator at `registry_coordinator_contract_address`
+    /// for [`Publisher::update_socket()`] and
+    /// [`Publisher::get_operator_socket()`].
+    pub fn with_registry_coordinator_address(
+        mut self,
+        registry_coordinator_contract_address: impl AsRef<str>,
+    ) -> Result<Self, PublisherError> {
+        let registry_coordinator_contract_address =
+            Address::from_str(registry_coordinator_contract_address.as_ref()).map_err(|error| {
+                PublisherError::ParseContractAddress(
+                    registry_coordinator_contract_address.as_ref().to_owned(),
+                    error,
+                )
+            })?;
+        self.registry_coordinator_contract = Some(IRegistryCoordinator::new(
+            registry_coordinator_contract_address,
+            self.provider.clone(),
+        ));
+
+        Ok(self)
+    }
+
+    /// Use the rewards coordinator at `rewards_coordinator_contract_address`
+    /// for the distribution roots and the claims of rewards, e.g.
+    /// [`Publisher::get_claimable_rewards()`].
+    pub fn with_rewards_coordinator_address(
+        mut self,
+        rewards_coordinator_contract_address: impl AsRef<str>,
+    ) -> Result<Self, PublisherError> {
+        let rewards_coordinator_contract_address =
+            Address::from_str(rewards_coordinator_contract_address.as_ref()).map_err(|error| {
+                PublisherError::ParseContractAddress(
+                    rewards_coordinator_contract_address.as_ref().to_owned(),
+                    error,
+                )
+            })?;
+        self.rewards_coordinator_contract = Some(RewardsCoordinator::new(
+            rewards_coordinator_contract_address,
+            self.provider.clone(),
+        ));
+
+        Ok(self)
+    }
+
     /// Get the address for the wallet used by [`Publisher`].
     ///
     /// # Examples
@@ -213,6 +296,12 @@ impl Publisher {
         self.provider.default_signer_address()
     }
 
+    fn rewards_coordinator_contract(&self) -> Result<&RewardsCoordinatorContract, PublisherError> {
+        self.rewards_coordinator_contract
+            .as_ref()
+            .ok_or(PublisherError::RewardsCoordinatorNotSet)
+    }
+
     fn signer(&self) -> &LocalSigner<SigningKey> {
         &self.signer
     }
@@ -288,6 +377,129 @@ impl Publisher {
         Ok(transaction_hash)
     }
 
+    /// Point the EigenLayer operator metadata of `self` to `metadata_uri`, a
+    /// URL of the metadata JSON.
+    pub async fn update_operator_metadata_uri(
+        &self,
+        metadata_uri: impl AsRef<str>,
+    ) -> Result<FixedBytes<32>, PublisherError> {
+        let transaction = self
+            .delegation_manager_contract
+            .updateOperatorMetadataURI(metadata_uri.as_ref().to_owned());
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::UpdateOperatorMetadataUri)?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Get the latest metadata URI of `operator`, or `None` if it was never
+    /// set.
+    ///
+    /// The URI is only recorded in event logs, which are searched from
+    /// `from_block` (e.g. the block the `DelegationManager` was deployed at)
+    /// to the latest block.
+    pub async fn get_operator_metadata_uri(
+        &self,
+        operator: Address,
+        from_block: u64,
+    ) -> Result<Option<String>, PublisherError> {
+        let filter = Filter::new()
+            .address(*self.delegation_manager_contract.address())
+            .event_signature(DelegationManager::OperatorMetadataURIUpdated::SIGNATURE_HASH)
+            .topic1(operator.into_word())
+            .from_block(from_block);
+
+        let logs = self
+            .provider
+            .get_logs(&filter)
+            .await
+            .map_err(PublisherError::GetLogs)?;
+        let metadata_uri = match logs.last() {
+            Some(log) => Some(
+                log.log_decode::<DelegationManager::OperatorMetadataURIUpdated>()
+                    .map_err(PublisherError::DecodeLog)?
+                    .inner
+                    .data
+                    .metadataURI,
+            ),
+            None => None,
+        };
+
+        Ok(metadata_uri)
+    }
+
+    /// Register `socket` (e.g. `https://operator.example.com:8545`) as the
+    /// endpoint of `self` on the registry coordinator set with
+    /// [`Publisher::with_registry_coordinator_address()`].
+    pub async fn update_socket(
+        &self,
+        socket: impl AsRef<str>,
+    ) -> Result<FixedBytes<32>, PublisherError> {
+        let registry_coordinator_contract = self
+            .registry_coordinator_contract
+            .as_ref()
+            .ok_or(PublisherError::RegistryCoordinatorNotSet)?;
+
+        let transaction = registry_coordinator_contract.updateSocket(socket.as_ref().to_owned());
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::UpdateSocket)?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Get the latest socket of `operator`, or `None` if it was never set.
+    ///
+    /// Sockets are only recorded in event logs, which are searched from
+    /// `from_block` (e.g. the block the registry coordinator was deployed at)
+    /// to the latest block.
+    pub async fn get_operator_socket(
+        &self,
+        operator: Address,
+        from_block: u64,
+    ) -> Result<Option<String>, PublisherError> {
+        let registry_coordinator_contract = self
+            .registry_coordinator_contract
+            .as_ref()
+            .ok_or(PublisherError::RegistryCoordinatorNotSet)?;
+
+        let operator_id = registry_coordinator_contract
+            .getOperatorId(operator)
+            .call()
+            .await
+            .map_err(PublisherError::GetOperatorId)?
+            ._0;
+
+        let filter = Filter::new()
+            .address(*registry_coordinator_contract.address())
+            .event_signature(IRegistryCoordinator::OperatorSocketUpdate::SIGNATURE_HASH)
+            .topic1(operator_id)
+            .from_block(from_block);
+
+        let logs = self
+            .provider
+            .get_logs(&filter)
+            .await
+            .map_err(PublisherError::GetLogs)?;
+        let socket = match logs.last() {
+            Some(log) => Some(
+                log.log_decode::<IRegistryCoordinator::OperatorSocketUpdate>()
+                    .map_err(PublisherError::DecodeLog)?
+                    .inner
+                    .data
+                    .socket,
+            ),
+            None => None,
+        };
+
+        Ok(socket)
+    }
+
     /// Return true if the operator is registered on Radius AVS.
     pub async fn is_operator_registered_on_avs(&self) -> Result<bool, PublisherError> {
         let is_avs = self
@@ -384,12 +596,7 @@ impl Publisher {
     /// publisher.register_operator_on_avs().await.unwrap();
     ///
     /// let transaction_hash = publisher
-    ///     .register_block_commitment(
-    ///         [0; 100],
-    ///         62364477,
-    ///         0,
-    ///         "0x38a941d2d4959baae54ba9c14502abe54ffd4ad0db290295f453ef9d7d5a3f2d",
-    ///     )
+    ///     .register_block_commitment("cluster_id", "rollup_id", 0, [0; 32])
     ///     .await
     ///     .unwrap();
     /// println!("{:?}", transaction_hash);
@@ -401,20 +608,21 @@ impl Publisher {
         block_number: u64,
         block_commitment: impl AsRef<[u8]>,
     ) -> Result<FixedBytes<32>, PublisherError> {
-        let cluster_id = cluster_id.as_ref().to_owned();
-        let rollup_id = rollup_id.as_ref().to_owned();
-        let block_commitment = {
-            let length = block_commitment.as_ref().len();
-            if length != 32 {
-                return Err(PublisherError::BlockCommitmentLength(length));
-            }
-
-            Bytes::copy_from_slice(block_commitment.as_ref())
-        };
+        let task = BlockCommitmentTask::new(cluster_id, rollup_id, block_number, block_commitment)
+            .map_err(PublisherError::BlockCommitmentTask)?;
 
-        let transaction =
-            self.avs_contract
-                .createNewTask(block_commitment, block_number, rollup_id, cluster_id);
+        self.register_block_commitment_task(task).await
+    }
+
+    /// Register a [`BlockCommitmentTask`], which has the same shape for every
+    /// validation provider.
+    pub async fn register_block_commitment_task(
+        &self,
+        task: BlockCommitmentTask,
+    ) -> Result<FixedBytes<32>, PublisherError> {
+        let transaction = self
+            .avs_contract
+            .call_builder(&Avs::createNewTaskCall::from(task));
         let pending_transaction = transaction.send().await;
         let transaction_hash = self
             .extract_transaction_hash_from_pending_transaction(pending_transaction)
@@ -442,6 +650,232 @@ impl Publisher {
 
         Ok(transaction_hash)
     }
+
+    /// Return the weights of `responders` against the stake threshold at the
+    /// block `task` was created in, which is the reference block the
+    /// `ECDSAStakeRegistry` checks signatures of the task against.
+    ///
+    /// The registry only answers for mined blocks, so this fails for a task
+    /// created in the latest block.
+    pub async fn get_quorum_status(
+        &self,
+        task: &IValidationServiceManager::Task,
+        responders: &[Address],
+    ) -> Result<QuorumStatus, PublisherError> {
+        let reference_block = task.taskCreatedBlock;
+
+        let mut operator_weights = Vec::with_capacity(responders.len());
+        for responder in unique_responders(responders) {
+            let operator_weight = self
+                .ecdsa_stake_registry_contract
+                .getOperatorWeightAtBlock(responder, reference_block)
+                .call()
+                .await
+                .map_err(PublisherError::GetOperatorWeight)?
+                ._0;
+            operator_weights.push(operator_weight);
+        }
+
+        let total_weight = self
+            .ecdsa_stake_registry_contract
+            .getLastCheckpointTotalWeightAtBlock(reference_block)
+            .call()
+            .await
+            .map_err(PublisherError::GetTotalWeight)?
+            ._0;
+        let threshold_weight = self
+            .ecdsa_stake_registry_contract
+            .getLastCheckpointThresholdWeightAtBlock(reference_block)
+            .call()
+            .await
+            .map_err(PublisherError::GetThresholdWeight)?
+            ._0;
+
+        Ok(QuorumStatus::new(
+            reference_block,
+            operator_weights,
+            total_weight,
+            threshold_weight,
+        ))
+    }
+
+    /// Return `true` if the stake of `responders` reaches the threshold the
+    /// `ECDSAStakeRegistry` requires for `task`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9",
+    ///     "0x5FC8d32690cc91D4c39d9d3abcBD16989F875707",
+    ///     "0xa82fF9aFd8f496c3d6ac40E2a0F282E47488CFc9",
+    ///     "0x9E545E3C0baAB3E08CdfD552C960A1050f373042",
+    /// )
+    /// .unwrap();
+    ///
+    /// // `task` and `responders` are collected from `NewTaskCreated` and
+    /// // `TaskResponded` events.
+    /// if publisher
+    ///     .is_quorum_reached(&task, &responders)
+    ///     .await
+    ///     .unwrap()
+    /// {
+    ///     println!("task {} is validated", task.blockNumber);
+    /// }
+    /// ```
+    pub async fn is_quorum_reached(
+        &self,
+        task: &IValidationServiceManager::Task,
+        responders: &[Address],
+    ) -> Result<bool, PublisherError> {
+        let quorum_status = self.get_quorum_status(task, responders).await?;
+
+        Ok(quorum_status.is_reached())
+    }
+
+    /// Get the latest distribution root that has passed its activation delay.
+    pub async fn get_current_claimable_distribution_root(
+        &self,
+    ) -> Result<IRewardsCoordinator::DistributionRoot, PublisherError> {
+        let distribution_root = self
+            .rewards_coordinator_contract()?
+            .getCurrentClaimableDistributionRoot()
+            .call()
+            .await
+            .map_err(PublisherError::GetDistributionRoot)?
+            ._0;
+
+        Ok(distribution_root)
+    }
+
+    /// Get the index of the distribution root which must be passed as
+    /// `rootIndex` of the claim.
+    pub async fn get_root_index_from_hash(
+        &self,
+        root: FixedBytes<32>,
+    ) -> Result<u32, PublisherError> {
+        let root_index = self
+            .rewards_coordinator_contract()?
+            .getRootIndexFromHash(root)
+            .call()
+            .await
+            .map_err(PublisherError::GetRootIndex)?
+            ._0;
+
+        Ok(root_index)
+    }
+
+    /// Get the cumulative amount of `token` already claimed by `self`.
+    pub async fn get_cumulative_claimed(&self, token: Address) -> Result<U256, PublisherError> {
+        let cumulative_claimed = self
+            .rewards_coordinator_contract()?
+            .cumulativeClaimed(self.address(), token)
+            .call()
+            .await
+            .map_err(PublisherError::GetCumulativeClaimed)?
+            ._0;
+
+        Ok(cumulative_claimed)
+    }
+
+    /// Return `(token, amount)` pairs of rewards `self` can claim from the
+    /// distribution, excluding the amount already claimed.
+    pub async fn get_claimable_rewards(
+        &self,
+        distribution: &RewardsDistribution,
+    ) -> Result<Vec<(Address, U256)>, PublisherError> {
+        let earner = self.address();
+        let token_leaves = distribution
+            .token_leaves(earner)
+            .ok_or(PublisherError::BuildClaim(RewardsError::EarnerNotFound(
+                earner,
+            )))?;
+
+        let mut claimable_rewards = Vec::with_capacity(token_leaves.len());
+        for token_leaf in token_leaves {
+            let cumulative_claimed = self.get_cumulative_claimed(token_leaf.token).await?;
+            claimable_rewards.push((
+                token_leaf.token,
+                token_leaf
+                    .cumulativeEarnings
+                    .saturating_sub(cumulative_claimed),
+            ));
+        }
+
+        Ok(claimable_rewards)
+    }
+
+    /// Return `true` if the claim passes the contract's validation.
+    pub async fn check_claim(
+        &self,
+        claim: IRewardsCoordinator::RewardsMerkleClaim,
+    ) -> Result<bool, PublisherError> {
+        let is_valid = self
+            .rewards_coordinator_contract()?
+            .checkClaim(claim)
+            .call()
+            .await
+            .map_err(PublisherError::CheckClaim)?
+            ._0;
+
+        Ok(is_valid)
+    }
+
+    pub async fn process_claim(
+        &self,
+        claim: IRewardsCoordinator::RewardsMerkleClaim,
+        recipient: Address,
+    ) -> Result<FixedBytes<32>, PublisherError> {
+        let transaction = self
+            .rewards_coordinator_contract()?
+            .processClaim(claim, recipient);
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::ProcessClaim)?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Claim the rewards of `self` for `tokens` (every token if empty) from
+    /// the distribution and send them to `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let publisher = Publisher::new(
+    ///     "http://127.0.0.1:8545",
+    ///     "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    ///     "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9",
+    ///     "0x5FC8d32690cc91D4c39d9d3abcBD16989F875707",
+    ///     "0xa82fF9aFd8f496c3d6ac40E2a0F282E47488CFc9",
+    ///     "0x9E545E3C0baAB3E08CdfD552C960A1050f373042",
+    /// )
+    /// .unwrap()
+    /// .with_rewards_coordinator_address("0x0165878A594ca255338adfa4d48449f69242Eb8F")
+    /// .unwrap();
+    ///
+    /// // `earners` is reconstructed from the published distribution data.
+    /// let distribution = RewardsDistribution::new(earners).unwrap();
+    ///
+    /// let transaction_hash = publisher.claim_rewards(&distribution, &[]).await.unwrap();
+    /// println!("{:?}", transaction_hash);
+    /// ```
+    pub async fn claim_rewards(
+        &self,
+        distribution: &RewardsDistribution,
+        tokens: &[Address],
+    ) -> Result<FixedBytes<32>, PublisherError> {
+        let root_index = self.get_root_index_from_hash(distribution.root()).await?;
+        let claim = distribution
+            .build_claim(root_index, self.address(), tokens)
+            .map_err(PublisherError::BuildClaim)?;
+
+        self.process_claim(claim, self.address()).await
+    }
 }
 
 #[derive(Debug)]
@@ -469,13 +903,29 @@ pub enum PublisherError {
     ParseProposerSetId(alloy::hex::FromHexError),
     IsOperator(alloy::contract::Error),
     RegisterAsOperator(TransactionError),
+    UpdateOperatorMetadataUri(TransactionError),
+    RegistryCoordinatorNotSet,
+    RewardsCoordinatorNotSet,
+    UpdateSocket(TransactionError),
+    GetOperatorId(alloy::contract::Error),
+    GetLogs(alloy::transports::TransportError),
+    DecodeLog(alloy::sol_types::Error),
     IsOperatorRegisteredOnAvs(alloy::contract::Error),
     AvsRegistrationDigestHash(alloy::contract::Error),
     OperatorSignature(alloy::signers::Error),
     RegisterOperatorOnAvs(TransactionError),
-    BlockCommitmentLength(usize),
+    BlockCommitmentTask(BlockCommitmentTaskError),
     RegisterBlockCommitment(TransactionError),
     RespondToTask(TransactionError),
+    GetOperatorWeight(alloy::contract::Error),
+    GetTotalWeight(alloy::contract::Error),
+    GetThresholdWeight(alloy::contract::Error),
+    GetDistributionRoot(alloy::contract::Error),
+    GetRootIndex(alloy::contract::Error),
+    GetCumulativeClaimed(alloy::contract::Error),
+    CheckClaim(alloy::contract::Error),
+    BuildClaim(RewardsError),
+    ProcessClaim(TransactionError),
 }
 
 impl std::fmt::Display for PublisherError {
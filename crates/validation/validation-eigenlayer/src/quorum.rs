@@ -0,0 +1,105 @@
+//! Off-chain evaluation of the stake threshold of `ECDSAStakeRegistry`.
+//!
+//! The registry accepts a set of signatures if the summed weight of the
+//! signers at the reference block is at least the threshold weight at that
+//! block and does not exceed the total weight, matching
+//! `ECDSAStakeRegistry._validateThresholdStake()`.
+use crate::types::*;
+
+/// The weights a set of responders is checked against, read at the
+/// reference block of a task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuorumStatus {
+    pub reference_block: u32,
+    pub signed_weight: U256,
+    pub total_weight: U256,
+    pub threshold_weight: U256,
+}
+
+impl QuorumStatus {
+    pub fn new(
+        reference_block: u32,
+        operator_weights: impl IntoIterator<Item = U256>,
+        total_weight: U256,
+        threshold_weight: U256,
+    ) -> Self {
+        let signed_weight = operator_weights
+            .into_iter()
+            .fold(U256::ZERO, |signed_weight, operator_weight| {
+                signed_weight.saturating_add(operator_weight)
+            });
+
+        Self {
+            reference_block,
+            signed_weight,
+            total_weight,
+            threshold_weight,
+        }
+    }
+
+    pub fn is_reached(&self) -> bool {
+        self.signed_weight <= self.total_weight && self.signed_weight >= self.threshold_weight
+    }
+
+    /// Return the weight still missing to reach the threshold.
+    pub fn missing_weight(&self) -> U256 {
+        self.threshold_weight.saturating_sub(self.signed_weight)
+    }
+}
+
+/// Remove duplicated responders so that each operator's weight is counted
+/// once, keeping the first occurrence.
+pub fn unique_responders(responders: &[Address]) -> Vec<Address> {
+    let mut unique_responders = Vec::with_capacity(responders.len());
+    for responder in responders {
+        if !unique_responders.contains(responder) {
+            unique_responders.push(*responder);
+        }
+    }
+
+    unique_responders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quorum_status() {
+        let status = QuorumStatus::new(
+            10,
+            [U256::from(30), U256::from(40)],
+            U256::from(100),
+            U256::from(66),
+        );
+        assert_eq!(status.signed_weight, U256::from(70));
+        assert!(status.is_reached());
+        assert_eq!(status.missing_weight(), U256::ZERO);
+
+        let status = QuorumStatus::new(10, [U256::from(30)], U256::from(100), U256::from(66));
+        assert!(!status.is_reached());
+        assert_eq!(status.missing_weight(), U256::from(36));
+
+        // Exactly at the threshold.
+        let status = QuorumStatus::new(10, [U256::from(66)], U256::from(100), U256::from(66));
+        assert!(status.is_reached());
+
+        // More weight than the registry had at the reference block.
+        let status = QuorumStatus::new(10, [U256::from(101)], U256::from(100), U256::from(66));
+        assert!(!status.is_reached());
+    }
+
+    #[test]
+    fn test_unique_responders() {
+        let responders = [
+            Address::repeat_byte(2),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+        ];
+
+        assert_eq!(
+            unique_responders(&responders),
+            vec![Address::repeat_byte(2), Address::repeat_byte(1)]
+        );
+    }
+}
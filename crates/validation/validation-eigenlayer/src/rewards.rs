@@ -0,0 +1,290 @@
+//! Off-chain helpers to build `RewardsCoordinator::processClaim` arguments.
+//!
+//! The rewards distribution posted by EigenLayer is a two-level Merkle tree.
+//! The earner tree commits to `(earner, earner_token_root)` leaves and each
+//! earner token root commits to `(token, cumulative_earnings)` leaves. Both
+//! trees are padded with zero leaves to the next power of two and hashed
+//! without sorting, matching `Merkle.verifyInclusionKeccak()`.
+use crate::types::*;
+
+pub const EARNER_LEAF_SALT: u8 = 0;
+pub const TOKEN_LEAF_SALT: u8 = 1;
+
+/// Equivalent to `RewardsCoordinator.calculateEarnerLeafHash()`.
+pub fn earner_leaf_hash(leaf: &IRewardsCoordinator::EarnerTreeMerkleLeaf) -> FixedBytes<32> {
+    let mut packed = Vec::with_capacity(1 + 20 + 32);
+    packed.push(EARNER_LEAF_SALT);
+    packed.extend_from_slice(leaf.earner.as_slice());
+    packed.extend_from_slice(leaf.earnerTokenRoot.as_slice());
+
+    keccak256(packed)
+}
+
+/// Equivalent to `RewardsCoordinator.calculateTokenLeafHash()`.
+pub fn token_leaf_hash(leaf: &IRewardsCoordinator::TokenTreeMerkleLeaf) -> FixedBytes<32> {
+    let mut packed = Vec::with_capacity(1 + 20 + 32);
+    packed.push(TOKEN_LEAF_SALT);
+    packed.extend_from_slice(leaf.token.as_slice());
+    packed.extend_from_slice(&leaf.cumulativeEarnings.to_be_bytes::<32>());
+
+    keccak256(packed)
+}
+
+pub struct MerkleTree {
+    layers: Vec<Vec<FixedBytes<32>>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<FixedBytes<32>>) -> Result<Self, RewardsError> {
+        if leaves.is_empty() {
+            return Err(RewardsError::EmptyTree);
+        }
+
+        let mut layer = leaves;
+        layer.resize(layer.len().next_power_of_two(), FixedBytes::ZERO);
+
+        let mut layers = vec![layer];
+        while layers.last().unwrap().len() > 1 {
+            let next_layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| {
+                    let mut packed = [0u8; 64];
+                    packed[..32].copy_from_slice(pair[0].as_slice());
+                    packed[32..].copy_from_slice(pair[1].as_slice());
+
+                    keccak256(packed)
+                })
+                .collect();
+            layers.push(next_layer);
+        }
+
+        Ok(Self { layers })
+    }
+
+    pub fn root(&self) -> FixedBytes<32> {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// Return the concatenated sibling hashes from the leaf at `index` up to
+    /// the root.
+    pub fn proof(&self, index: usize) -> Result<Bytes, RewardsError> {
+        if index >= self.layers[0].len() {
+            return Err(RewardsError::IndexOutOfBounds(index));
+        }
+
+        let mut proof = Vec::with_capacity(32 * (self.layers.len() - 1));
+        let mut index = index;
+        for layer in self.layers.iter().take(self.layers.len() - 1) {
+            proof.extend_from_slice(layer[index ^ 1].as_slice());
+            index /= 2;
+        }
+
+        Ok(proof.into())
+    }
+}
+
+pub struct EarnerRewards {
+    pub earner: Address,
+    pub token_leaves: Vec<IRewardsCoordinator::TokenTreeMerkleLeaf>,
+}
+
+/// A full rewards distribution reconstructed from the off-chain data the
+/// distribution root was calculated from.
+pub struct RewardsDistribution {
+    earners: Vec<EarnerRewards>,
+    earner_tree: MerkleTree,
+    token_trees: Vec<MerkleTree>,
+}
+
+impl RewardsDistribution {
+    pub fn new(earners: Vec<EarnerRewards>) -> Result<Self, RewardsError> {
+        let token_trees = earners
+            .iter()
+            .map(|earner| {
+                MerkleTree::new(earner.token_leaves.iter().map(token_leaf_hash).collect())
+            })
+            .collect::<Result<Vec<MerkleTree>, RewardsError>>()?;
+
+        let earner_leaves = earners
+            .iter()
+            .zip(token_trees.iter())
+            .map(|(earner, token_tree)| {
+                earner_leaf_hash(&IRewardsCoordinator::EarnerTreeMerkleLeaf {
+                    earner: earner.earner,
+                    earnerTokenRoot: token_tree.root(),
+                })
+            })
+            .collect();
+        let earner_tree = MerkleTree::new(earner_leaves)?;
+
+        Ok(Self {
+            earners,
+            earner_tree,
+            token_trees,
+        })
+    }
+
+    pub fn root(&self) -> FixedBytes<32> {
+        self.earner_tree.root()
+    }
+
+    pub fn token_leaves(
+        &self,
+        earner: Address,
+    ) -> Option<&[IRewardsCoordinator::TokenTreeMerkleLeaf]> {
+        self.earners
+            .iter()
+            .find(|earner_rewards| earner_rewards.earner == earner)
+            .map(|earner_rewards| earner_rewards.token_leaves.as_slice())
+    }
+
+    /// Build the claim for `earner` covering `tokens`. Claim every token in
+    /// the earner's tree if `tokens` is empty.
+    pub fn build_claim(
+        &self,
+        root_index: u32,
+        earner: Address,
+        tokens: &[Address],
+    ) -> Result<IRewardsCoordinator::RewardsMerkleClaim, RewardsError> {
+        let earner_index = self
+            .earners
+            .iter()
+            .position(|earner_rewards| earner_rewards.earner == earner)
+            .ok_or(RewardsError::EarnerNotFound(earner))?;
+        let earner_rewards = &self.earners[earner_index];
+        let token_tree = &self.token_trees[earner_index];
+
+        let token_indices: Vec<usize> = match tokens.is_empty() {
+            true => (0..earner_rewards.token_leaves.len()).collect(),
+            false => tokens
+                .iter()
+                .map(|token| {
+                    earner_rewards
+                        .token_leaves
+                        .iter()
+                        .position(|token_leaf| token_leaf.token == *token)
+                        .ok_or(RewardsError::TokenNotFound(*token))
+                })
+                .collect::<Result<Vec<usize>, RewardsError>>()?,
+        };
+
+        let token_tree_proofs = token_indices
+            .iter()
+            .map(|token_index| token_tree.proof(*token_index))
+            .collect::<Result<Vec<Bytes>, RewardsError>>()?;
+        let token_leaves = token_indices
+            .iter()
+            .map(|token_index| earner_rewards.token_leaves[*token_index].clone())
+            .collect();
+
+        Ok(IRewardsCoordinator::RewardsMerkleClaim {
+            rootIndex: root_index,
+            earnerIndex: earner_index as u32,
+            earnerTreeProof: self.earner_tree.proof(earner_index)?,
+            earnerLeaf: IRewardsCoordinator::EarnerTreeMerkleLeaf {
+                earner,
+                earnerTokenRoot: token_tree.root(),
+            },
+            tokenIndices: token_indices
+                .into_iter()
+                .map(|index| index as u32)
+                .collect(),
+            tokenTreeProofs: token_tree_proofs,
+            tokenLeaves: token_leaves,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum RewardsError {
+    EmptyTree,
+    IndexOutOfBounds(usize),
+    EarnerNotFound(Address),
+    TokenNotFound(Address),
+}
+
+impl std::fmt::Display for RewardsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for RewardsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Port of `Merkle.processInclusionProofKeccak()`.
+    fn process_inclusion_proof(proof: &[u8], leaf: FixedBytes<32>, index: usize) -> FixedBytes<32> {
+        let mut computed_hash = leaf;
+        let mut index = index;
+        for sibling in proof.chunks(32) {
+            let mut packed = [0u8; 64];
+            match index % 2 {
+                0 => {
+                    packed[..32].copy_from_slice(computed_hash.as_slice());
+                    packed[32..].copy_from_slice(sibling);
+                }
+                _ => {
+                    packed[..32].copy_from_slice(sibling);
+                    packed[32..].copy_from_slice(computed_hash.as_slice());
+                }
+            }
+            computed_hash = keccak256(packed);
+            index /= 2;
+        }
+
+        computed_hash
+    }
+
+    #[test]
+    fn test_build_claim() {
+        let token_a = Address::repeat_byte(0xaa);
+        let token_b = Address::repeat_byte(0xbb);
+        let earners: Vec<EarnerRewards> = (1..=3u8)
+            .map(|byte| EarnerRewards {
+                earner: Address::repeat_byte(byte),
+                token_leaves: vec![
+                    IRewardsCoordinator::TokenTreeMerkleLeaf {
+                        token: token_a,
+                        cumulativeEarnings: U256::from(byte as u64 * 100),
+                    },
+                    IRewardsCoordinator::TokenTreeMerkleLeaf {
+                        token: token_b,
+                        cumulativeEarnings: U256::from(byte as u64 * 10),
+                    },
+                ],
+            })
+            .collect();
+        let distribution = RewardsDistribution::new(earners).unwrap();
+
+        let claim = distribution
+            .build_claim(7, Address::repeat_byte(3), &[token_b])
+            .unwrap();
+        assert_eq!(claim.rootIndex, 7);
+        assert_eq!(claim.earnerIndex, 2);
+        assert_eq!(claim.tokenIndices, vec![1]);
+        assert_eq!(claim.tokenLeaves[0].cumulativeEarnings, U256::from(30));
+
+        let earner_root = process_inclusion_proof(
+            &claim.earnerTreeProof,
+            earner_leaf_hash(&claim.earnerLeaf),
+            claim.earnerIndex as usize,
+        );
+        assert_eq!(earner_root, distribution.root());
+
+        let token_root = process_inclusion_proof(
+            &claim.tokenTreeProofs[0],
+            token_leaf_hash(&claim.tokenLeaves[0]),
+            claim.tokenIndices[0] as usize,
+        );
+        assert_eq!(token_root, claim.earnerLeaf.earnerTokenRoot);
+
+        assert!(distribution
+            .build_claim(0, Address::repeat_byte(4), &[])
+            .is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use std::{future::Future, str::FromStr};
 
-use alloy::providers::{ProviderBuilder, WsConnect};
+use alloy::providers::{Provider, ProviderBuilder, WsConnect};
 use futures::StreamExt;
 
 use crate::types::*;
@@ -8,6 +8,28 @@ use crate::types::*;
 pub struct Subscriber {
     connection_detail: WsConnect,
     avs_contract_address: Address,
+    max_task_age: Option<u64>,
+    skip_stale_tasks: bool,
+}
+
+/// A task received by [`Subscriber::initialize_task_handler()`] with the
+/// chain head at the time it was received, or the block the task was created
+/// at if the chain head could not be fetched.
+#[derive(Clone)]
+pub struct ReceivedTask {
+    pub event: Avs::NewTaskCreated,
+    pub chain_head: u64,
+    /// Whether the task is older than [`Subscriber::max_task_age()`], in
+    /// which case responding to it likely wastes gas.
+    pub is_stale: bool,
+}
+
+impl ReceivedTask {
+    /// The number of blocks since the task was created.
+    pub fn age(&self) -> u64 {
+        self.chain_head
+            .saturating_sub(self.event.taskCreatedBlock.into())
+    }
 }
 
 impl Subscriber {
@@ -40,9 +62,28 @@ impl Subscriber {
         Ok(Self {
             connection_detail,
             avs_contract_address,
+            max_task_age: None,
+            skip_stale_tasks: false,
         })
     }
 
+    /// Flag tasks created more than `max_task_age` blocks before the chain
+    /// head as stale, see [`ReceivedTask::is_stale`]. No task is stale
+    /// unless set.
+    pub fn max_task_age(mut self, max_task_age: u64) -> Self {
+        self.max_task_age = Some(max_task_age);
+
+        self
+    }
+
+    /// Do not pass stale tasks to the callback of either event handler, see
+    /// [`Subscriber::max_task_age()`].
+    pub fn skip_stale_tasks(mut self, skip_stale_tasks: bool) -> Self {
+        self.skip_stale_tasks = skip_stale_tasks;
+
+        self
+    }
+
     /// Start listening to the Block commitment registration event.
     ///
     /// # WARNING
@@ -93,11 +134,117 @@ impl Subscriber {
             .into_stream();
 
         while let Some(Ok(event)) = avs_contract_event_stream.next().await {
-            callback(event.0, context.clone()).await;
+            if self.skip_stale_tasks {
+                let received_task = self.receive_task(&provider, event.0).await;
+                if !received_task.is_stale {
+                    callback(received_task.event, context.clone()).await;
+                }
+            } else {
+                callback(event.0, context.clone()).await;
+            }
+        }
+
+        Err(SubscriberError::EventStreamDisconnected)
+    }
+
+    /// Same as [`Subscriber::initialize_event_handler()`], but the callback
+    /// also receives the chain head and whether the task is stale, e.g. to
+    /// log tasks that are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let context = Arc::new(String::from("context"));
+    ///
+    /// tokio::spawn(async move {
+    ///     Subscriber::new(
+    ///         "ws://127.0.0.1:8545",
+    ///         "0x67d269191c92Caf3cD7723F116c85e6E9bf55933",
+    ///     )
+    ///     .unwrap()
+    ///     .max_task_age(100)
+    ///     .initialize_task_handler(callback, context.clone())
+    ///     .await
+    ///     .unwrap();
+    /// });
+    ///
+    /// async fn callback(task: ReceivedTask, _context: Arc<String>) {
+    ///     if task.is_stale {
+    ///         println!("Skipping a task {} blocks old", task.age());
+    ///         return;
+    ///     }
+    ///
+    ///     let block_commitment_task = match BlockCommitmentTask::try_from(task.event.task) {
+    ///         Ok(block_commitment_task) => block_commitment_task,
+    ///         Err(error) => {
+    ///             println!("Skipping an invalid task: {:?}", error);
+    ///             return;
+    ///         }
+    ///     };
+    ///
+    ///     println!(
+    ///         "Validating the commitment {} of block {} of rollup {}",
+    ///         block_commitment_task.block_commitment,
+    ///         block_commitment_task.block_number,
+    ///         block_commitment_task.rollup_id
+    ///     );
+    /// }
+    /// ```
+    pub async fn initialize_task_handler<CB, CTX, F>(
+        &self,
+        callback: CB,
+        context: CTX,
+    ) -> Result<(), SubscriberError>
+    where
+        CB: Fn(ReceivedTask, CTX) -> F,
+        CTX: Clone + Send + Sync,
+        F: Future<Output = ()>,
+    {
+        let provider = ProviderBuilder::new()
+            .on_ws(self.connection_detail.clone())
+            .await
+            .map_err(SubscriberError::WebsocketProvider)?;
+
+        let avs_contract = Avs::AvsInstance::new(self.avs_contract_address, provider.clone());
+        let mut avs_contract_event_stream = avs_contract
+            .NewTaskCreated_filter()
+            .subscribe()
+            .await
+            .map_err(SubscriberError::SubscribeToAvsContract)?
+            .into_stream();
+
+        while let Some(Ok(event)) = avs_contract_event_stream.next().await {
+            let received_task = self.receive_task(&provider, event.0).await;
+            if !(self.skip_stale_tasks && received_task.is_stale) {
+                callback(received_task, context.clone()).await;
+            }
         }
 
         Err(SubscriberError::EventStreamDisconnected)
     }
+
+    async fn receive_task(
+        &self,
+        provider: &impl Provider<alloy::pubsub::PubSubFrontend>,
+        event: Avs::NewTaskCreated,
+    ) -> ReceivedTask {
+        // Missing the chain head is not a reason to stop delivering tasks,
+        // the task is delivered as fresh instead.
+        let chain_head = match provider.get_block_number().await {
+            Ok(chain_head) => chain_head,
+            Err(_error) => event.taskCreatedBlock.into(),
+        };
+        let mut received_task = ReceivedTask {
+            event,
+            chain_head,
+            is_stale: false,
+        };
+        received_task.is_stale = self
+            .max_task_age
+            .is_some_and(|max_task_age| received_task.age() > max_task_age);
+
+        received_task
+    }
 }
 
 #[derive(Debug)]
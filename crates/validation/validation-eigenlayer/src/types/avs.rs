@@ -1,6 +1,34 @@
+use validation_types::{BlockCommitmentTask, BlockCommitmentTaskError};
+
 alloy::sol!(
     #[allow(missing_docs)]
     #[sol(rpc)]
     Avs,
     "src/contract/AVS.json"
 );
+
+impl From<BlockCommitmentTask> for Avs::createNewTaskCall {
+    fn from(task: BlockCommitmentTask) -> Self {
+        Self {
+            _commitment: alloy::primitives::Bytes::copy_from_slice(
+                task.block_commitment.as_slice(),
+            ),
+            _blockNumber: task.block_number,
+            _rollupId: task.rollup_id,
+            _clusterId: task.cluster_id,
+        }
+    }
+}
+
+impl TryFrom<IValidationServiceManager::Task> for BlockCommitmentTask {
+    type Error = BlockCommitmentTaskError;
+
+    fn try_from(task: IValidationServiceManager::Task) -> Result<Self, Self::Error> {
+        Self::new(
+            task.clusterId,
+            task.rollupId,
+            task.blockNumber,
+            task.commitment,
+        )
+    }
+}
@@ -2,9 +2,14 @@ mod avs;
 mod avs_directory;
 mod delegation_manager;
 mod ecdsa_stake_registry;
+mod registry_coordinator;
+mod rewards_coordinator;
 
 pub use alloy::{primitives::*, rpc::types::Log};
 pub use avs::{Avs, IValidationServiceManager};
 pub use avs_directory::{AVSDirectory, IAVSDirectory};
 pub use delegation_manager::{DelegationManager, IDelegationManager};
 pub use ecdsa_stake_registry::{EcdsaStakeRegistry, ISignatureUtils};
+pub use registry_coordinator::IRegistryCoordinator;
+pub use rewards_coordinator::{IRewardsCoordinator, RewardsCoordinator};
+pub use validation_types::{BlockCommitmentTask, BlockCommitmentTaskError};
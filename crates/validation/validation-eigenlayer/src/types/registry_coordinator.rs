@@ -0,0 +1,14 @@
+// The Radius AVS does not ship a registry coordinator, so only the socket
+// functions shared by EigenLayer middleware `RegistryCoordinator` deployments
+// are declared.
+alloy::sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface IRegistryCoordinator {
+        event OperatorSocketUpdate(bytes32 indexed operatorId, string socket);
+
+        function getOperatorId(address operator) external view returns (bytes32);
+
+        function updateSocket(string memory socket) external;
+    }
+);
@@ -0,0 +1,6 @@
+alloy::sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    RewardsCoordinator,
+    "src/contract/IRewardsCoordinator.json"
+);
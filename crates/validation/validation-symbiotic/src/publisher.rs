@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{future::IntoFuture, str::FromStr};
 
 use alloy::{
     contract,
@@ -8,9 +8,12 @@ use alloy::{
             BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller,
             WalletFiller,
         },
-        Identity, PendingTransactionBuilder, ProviderBuilder, RootProvider, WalletProvider,
+        Identity, PendingTransactionBuilder, Provider, ProviderBuilder, RootProvider,
+        WalletProvider,
     },
+    rpc::types::{BlockNumberOrTag, BlockTransactionsKind, TransactionReceipt},
     signers::local::LocalSigner,
+    sol_types::SolEvent,
     transports::http::{reqwest::Url, Client, Http},
 };
 
@@ -45,9 +48,59 @@ type ValidationContract = ValidationServiceManager::ValidationServiceManagerInst
     >,
 >;
 
+type OperatorRegistryContract = IOperatorRegistry::IOperatorRegistryInstance<
+    Http<Client>,
+    FillProvider<
+        JoinFill<
+            JoinFill<
+                Identity,
+                JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
+            >,
+            WalletFiller<EthereumWallet>,
+        >,
+        RootProvider<Http<Client>>,
+        Http<Client>,
+        Ethereum,
+    >,
+>;
+
+type OptInServiceContract = IOptInService::IOptInServiceInstance<
+    Http<Client>,
+    FillProvider<
+        JoinFill<
+            JoinFill<
+                Identity,
+                JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
+            >,
+            WalletFiller<EthereumWallet>,
+        >,
+        RootProvider<Http<Client>>,
+        Http<Client>,
+        Ethereum,
+    >,
+>;
+
+type VetoSlasherContract = IVetoSlasher::IVetoSlasherInstance<
+    Http<Client>,
+    FillProvider<
+        JoinFill<
+            JoinFill<
+                Identity,
+                JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
+            >,
+            WalletFiller<EthereumWallet>,
+        >,
+        RootProvider<Http<Client>>,
+        Http<Client>,
+        Ethereum,
+    >,
+>;
+
 pub struct Publisher {
     provider: EthereumHttpProvider,
     validation_contract: ValidationContract,
+    operator_registry_contract: Option<OperatorRegistryContract>,
+    vault_opt_in_service_contract: Option<OptInServiceContract>,
 }
 
 impl Publisher {
@@ -84,9 +137,57 @@ impl Publisher {
         Ok(Self {
             provider,
             validation_contract,
+            operator_registry_contract: None,
+            vault_opt_in_service_contract: None,
         })
     }
 
+    /// Use the Symbiotic `OperatorRegistry` at
+    /// `operator_registry_contract_address` for
+    /// [`Publisher::register_operator()`] and
+    /// [`Publisher::is_registered_operator()`].
+    pub fn with_operator_registry_address(
+        mut self,
+        operator_registry_contract_address: impl AsRef<str>,
+    ) -> Result<Self, PublisherError> {
+        let operator_registry_contract_address =
+            Address::from_str(operator_registry_contract_address.as_ref()).map_err(|error| {
+                PublisherError::ParseContractAddress(
+                    operator_registry_contract_address.as_ref().to_owned(),
+                    error,
+                )
+            })?;
+        self.operator_registry_contract = Some(IOperatorRegistry::new(
+            operator_registry_contract_address,
+            self.provider.clone(),
+        ));
+
+        Ok(self)
+    }
+
+    /// Use the Symbiotic vault `OptInService` at
+    /// `vault_opt_in_service_contract_address` for
+    /// [`Publisher::opt_in_vault()`], [`Publisher::opt_out_vault()`] and
+    /// [`Publisher::is_opted_in_vault()`].
+    pub fn with_vault_opt_in_service_address(
+        mut self,
+        vault_opt_in_service_contract_address: impl AsRef<str>,
+    ) -> Result<Self, PublisherError> {
+        let vault_opt_in_service_contract_address =
+            Address::from_str(vault_opt_in_service_contract_address.as_ref()).map_err(|error| {
+                PublisherError::ParseContractAddress(
+                    vault_opt_in_service_contract_address.as_ref().to_owned(),
+                    error,
+                )
+            })?;
+        self.vault_opt_in_service_contract = Some(IOptInService::new(
+            vault_opt_in_service_contract_address,
+            self.provider.clone(),
+        ));
+
+        Ok(self)
+    }
+
     pub fn address(&self) -> Address {
         self.provider.default_signer_address()
     }
@@ -98,6 +199,20 @@ impl Publisher {
             contract::Error,
         >,
     ) -> Result<FixedBytes<32>, TransactionError> {
+        let transaction_receipt = self
+            .extract_receipt_from_pending_transaction(pending_transaction)
+            .await?;
+
+        Ok(transaction_receipt.transaction_hash)
+    }
+
+    async fn extract_receipt_from_pending_transaction(
+        &self,
+        pending_transaction: Result<
+            PendingTransactionBuilder<Http<Client>, Ethereum>,
+            contract::Error,
+        >,
+    ) -> Result<TransactionReceipt, TransactionError> {
         let transaction_receipt = pending_transaction
             .map_err(TransactionError::SendTransaction)?
             .get_receipt()
@@ -105,7 +220,7 @@ impl Publisher {
             .map_err(TransactionError::GetReceipt)?;
 
         match transaction_receipt.as_ref().is_success() {
-            true => Ok(transaction_receipt.transaction_hash),
+            true => Ok(transaction_receipt),
             false => Err(TransactionError::FailedTransaction(
                 transaction_receipt.transaction_hash,
             )),
@@ -119,24 +234,21 @@ impl Publisher {
         block_number: u64,
         block_commitment: impl AsRef<[u8]>,
     ) -> Result<FixedBytes<32>, PublisherError> {
-        let cluster_id = cluster_id.as_ref().to_owned();
-        let rollup_id = rollup_id.as_ref().to_owned();
-        let block_number = U256::from(block_number);
-        let block_commitment: FixedBytes<32> = {
-            let length = block_commitment.as_ref().len();
-            if length != 32 {
-                return Err(PublisherError::BlockCommitmentLength(length));
-            }
+        let task = BlockCommitmentTask::new(cluster_id, rollup_id, block_number, block_commitment)
+            .map_err(PublisherError::BlockCommitmentTask)?;
 
-            FixedBytes::from_slice(block_commitment.as_ref())
-        };
+        self.register_block_commitment_task(task).await
+    }
 
-        let transaction = self.validation_contract.createNewTask(
-            cluster_id,
-            rollup_id,
-            block_number,
-            block_commitment,
-        );
+    /// Register a [`BlockCommitmentTask`], which has the same shape for every
+    /// validation provider.
+    pub async fn register_block_commitment_task(
+        &self,
+        task: BlockCommitmentTask,
+    ) -> Result<FixedBytes<32>, PublisherError> {
+        let transaction = self
+            .validation_contract
+            .call_builder(&ValidationServiceManager::createNewTaskCall::from(task));
         let pending_transaction = transaction.send().await;
         let transaction_hash = self
             .extract_transaction_hash_from_pending_transaction(pending_transaction)
@@ -168,6 +280,493 @@ impl Publisher {
 
         Ok(transaction_hash)
     }
+
+    fn operator_registry_contract(&self) -> Result<&OperatorRegistryContract, PublisherError> {
+        self.operator_registry_contract
+            .as_ref()
+            .ok_or(PublisherError::OperatorRegistryNotSet)
+    }
+
+    fn vault_opt_in_service_contract(&self) -> Result<&OptInServiceContract, PublisherError> {
+        self.vault_opt_in_service_contract
+            .as_ref()
+            .ok_or(PublisherError::VaultOptInServiceNotSet)
+    }
+
+    /// Return the network of the validation contract and the network
+    /// `OptInService` it checks operators against.
+    async fn network_opt_in_service_contract(
+        &self,
+    ) -> Result<(Address, OptInServiceContract), PublisherError> {
+        let network = self
+            .validation_contract
+            .NETWORK()
+            .call()
+            .await
+            .map_err(PublisherError::GetNetwork)?
+            ._0;
+        let network_opt_in_service_contract_address = self
+            .validation_contract
+            .OPERATOR_NET_OPT_IN()
+            .call()
+            .await
+            .map_err(PublisherError::GetNetwork)?
+            ._0;
+        let network_opt_in_service_contract = IOptInService::new(
+            network_opt_in_service_contract_address,
+            self.provider.clone(),
+        );
+
+        Ok((network, network_opt_in_service_contract))
+    }
+
+    /// Register the publisher address as a Symbiotic operator, the first
+    /// step of onboarding before opting into the network and vaults.
+    ///
+    /// Requires [`Publisher::with_operator_registry_address()`].
+    pub async fn register_operator(&self) -> Result<FixedBytes<32>, PublisherError> {
+        let transaction = self.operator_registry_contract()?.registerOperator();
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::RegisterOperator)?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Requires [`Publisher::with_operator_registry_address()`].
+    pub async fn is_registered_operator(&self, operator: Address) -> Result<bool, PublisherError> {
+        let is_registered = self
+            .operator_registry_contract()?
+            .isEntity(operator)
+            .call()
+            .await
+            .map_err(PublisherError::GetOperatorStatus)?
+            ._0;
+
+        Ok(is_registered)
+    }
+
+    /// Opt the publisher address into the network of the validation
+    /// contract.
+    pub async fn opt_in_network(&self) -> Result<FixedBytes<32>, PublisherError> {
+        let (network, network_opt_in_service_contract) =
+            self.network_opt_in_service_contract().await?;
+
+        let transaction = network_opt_in_service_contract.optIn(network);
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::OptInNetwork)?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Opt the publisher address out of the network of the validation
+    /// contract.
+    pub async fn opt_out_network(&self) -> Result<FixedBytes<32>, PublisherError> {
+        let (network, network_opt_in_service_contract) =
+            self.network_opt_in_service_contract().await?;
+
+        let transaction = network_opt_in_service_contract.optOut(network);
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::OptOutNetwork)?;
+
+        Ok(transaction_hash)
+    }
+
+    pub async fn is_opted_in_network(&self, operator: Address) -> Result<bool, PublisherError> {
+        let (network, network_opt_in_service_contract) =
+            self.network_opt_in_service_contract().await?;
+
+        let is_opted_in = network_opt_in_service_contract
+            .isOptedIn(operator, network)
+            .call()
+            .await
+            .map_err(PublisherError::GetOperatorStatus)?
+            ._0;
+
+        Ok(is_opted_in)
+    }
+
+    /// Opt the publisher address into `vault`.
+    ///
+    /// Requires [`Publisher::with_vault_opt_in_service_address()`].
+    pub async fn opt_in_vault(&self, vault: Address) -> Result<FixedBytes<32>, PublisherError> {
+        let transaction = self.vault_opt_in_service_contract()?.optIn(vault);
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::OptInVault)?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Opt the publisher address out of `vault`.
+    ///
+    /// Requires [`Publisher::with_vault_opt_in_service_address()`].
+    pub async fn opt_out_vault(&self, vault: Address) -> Result<FixedBytes<32>, PublisherError> {
+        let transaction = self.vault_opt_in_service_contract()?.optOut(vault);
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::OptOutVault)?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Requires [`Publisher::with_vault_opt_in_service_address()`].
+    pub async fn is_opted_in_vault(
+        &self,
+        operator: Address,
+        vault: Address,
+    ) -> Result<bool, PublisherError> {
+        let is_opted_in = self
+            .vault_opt_in_service_contract()?
+            .isOptedIn(operator, vault)
+            .call()
+            .await
+            .map_err(PublisherError::GetOperatorStatus)?
+            ._0;
+
+        Ok(is_opted_in)
+    }
+
+    pub async fn get_current_epoch(&self) -> Result<u64, PublisherError> {
+        let epoch = self
+            .validation_contract
+            .getCurrentEpoch()
+            .call()
+            .await
+            .map_err(PublisherError::GetEpoch)?
+            .epoch;
+
+        Ok(epoch.to())
+    }
+
+    /// Get the epoch that contains `timestamp`, in seconds since the Unix
+    /// epoch.
+    pub async fn get_epoch_at_timestamp(&self, timestamp: u64) -> Result<u64, PublisherError> {
+        let timestamp = timestamp
+            .try_into()
+            .map_err(|_| PublisherError::TimestampOverflow(timestamp))?;
+        let epoch = self
+            .validation_contract
+            .getEpochAtTs(timestamp)
+            .call()
+            .await
+            .map_err(PublisherError::GetEpoch)?
+            .epoch;
+
+        Ok(epoch.to())
+    }
+
+    /// Get the epoch of the block `block_number`, e.g. the `blockNumber` of a
+    /// `NewTaskCreated` event, so that the task is judged by the stake of its
+    /// epoch rather than the current one.
+    pub async fn get_epoch_at_block(&self, block_number: u64) -> Result<u64, PublisherError> {
+        let block = self
+            .provider
+            .get_block_by_number(
+                BlockNumberOrTag::Number(block_number),
+                BlockTransactionsKind::Hashes,
+            )
+            .await
+            .map_err(PublisherError::GetBlock)?
+            .ok_or(PublisherError::BlockNotFound(block_number))?;
+
+        self.get_epoch_at_timestamp(block.header.timestamp).await
+    }
+
+    /// Get the timestamp at which `epoch` starts, in seconds since the Unix
+    /// epoch.
+    pub async fn get_epoch_start_timestamp(&self, epoch: u64) -> Result<u64, PublisherError> {
+        let timestamp = self
+            .validation_contract
+            .getEpochStartTs(epoch_to_uint48(epoch)?)
+            .call()
+            .await
+            .map_err(PublisherError::GetEpoch)?
+            .timestamp;
+
+        Ok(timestamp.to())
+    }
+
+    /// Get the stake of `operator` in each token at `epoch`.
+    pub async fn get_operator_stakes(
+        &self,
+        operator: Address,
+        epoch: u64,
+    ) -> Result<Vec<IValidationServiceManager::StakeInfo>, PublisherError> {
+        let stakes = self
+            .validation_contract
+            .getOperatorAllTokenStakes(operator, epoch_to_uint48(epoch)?)
+            .call()
+            .await
+            .map_err(PublisherError::GetStake)?
+            .tokenStakes;
+
+        Ok(stakes)
+    }
+
+    pub async fn get_operator_token_stake(
+        &self,
+        operator: Address,
+        token: Address,
+        epoch: u64,
+    ) -> Result<U256, PublisherError> {
+        let stake = self
+            .validation_contract
+            .getOperatorTokenStake(operator, token, epoch_to_uint48(epoch)?)
+            .call()
+            .await
+            .map_err(PublisherError::GetStake)?
+            .stakeAmount;
+
+        Ok(stake)
+    }
+
+    pub async fn get_token_total_stake(
+        &self,
+        token: Address,
+        epoch: u64,
+    ) -> Result<U256, PublisherError> {
+        let stake = self
+            .validation_contract
+            .getTokenTotalStake(token, epoch_to_uint48(epoch)?)
+            .call()
+            .await
+            .map_err(PublisherError::GetStake)?
+            .totalStakeAmount;
+
+        Ok(stake)
+    }
+
+    /// Get the operators and their stakes at `epoch`, with the total stake
+    /// in each token.
+    pub async fn get_stake_snapshot(&self, epoch: u64) -> Result<StakeSnapshot, PublisherError> {
+        let epoch_uint48 = epoch_to_uint48(epoch)?;
+        let operator_infos = self.validation_contract.getOperatorInfos(epoch_uint48);
+        let total_stakes = self
+            .validation_contract
+            .getAllTokenTotalStakes(epoch_uint48);
+
+        let (operator_infos, total_stakes) = futures::try_join!(
+            operator_infos.call().into_future(),
+            total_stakes.call().into_future()
+        )
+        .map_err(PublisherError::GetStake)?;
+
+        Ok(StakeSnapshot {
+            epoch,
+            operators: operator_infos.operatorInfos,
+            total_stakes: total_stakes.tokenStakes,
+        })
+    }
+
+    /// Get the slasher of `vault`, the contract that slash requests against
+    /// the stake in the vault are submitted to.
+    pub async fn get_slasher(&self, vault: Address) -> Result<Address, PublisherError> {
+        let slasher = IVault::new(vault, self.provider.clone())
+            .slasher()
+            .call()
+            .await
+            .map_err(PublisherError::GetSlasher)?
+            ._0;
+
+        Ok(slasher)
+    }
+
+    async fn veto_slasher_contract(
+        &self,
+        vault: Address,
+    ) -> Result<VetoSlasherContract, PublisherError> {
+        let slasher = self.get_slasher(vault).await?;
+
+        Ok(IVetoSlasher::new(slasher, self.provider.clone()))
+    }
+
+    /// Get the subnetwork `subnetwork_index` of the network of the
+    /// validation contract.
+    pub async fn get_subnetwork(
+        &self,
+        subnetwork_index: u64,
+    ) -> Result<FixedBytes<32>, PublisherError> {
+        let subnetwork = self
+            .validation_contract
+            .getSubnetwork(Uint::from(subnetwork_index))
+            .call()
+            .await
+            .map_err(PublisherError::GetNetwork)?
+            ._0;
+
+        Ok(subnetwork)
+    }
+
+    /// Request to slash `amount` of the stake of `operator` in `vault` for
+    /// the subnetwork `subnetwork_index`, e.g. for an operator that did not
+    /// respond to a task or responded incorrectly. `capture_timestamp` is
+    /// the time the stake is taken at, usually the
+    /// [`Publisher::get_epoch_start_timestamp()`] of the epoch of the task.
+    ///
+    /// The vault must use a veto slasher. The request can be vetoed by the
+    /// resolver of the vault until [`SlashRequest::veto_deadline`], after
+    /// which it can be executed with [`Publisher::execute_slash()`].
+    ///
+    /// `requestSlash` can only be called by the network middleware of the
+    /// network, and the transaction is sent from [`Publisher::address()`],
+    /// so this only succeeds if the signing key of the publisher is the
+    /// middleware itself. A network whose middleware is a contract must
+    /// request slashes through that contract instead.
+    pub async fn request_slash(
+        &self,
+        vault: Address,
+        subnetwork_index: u64,
+        operator: Address,
+        amount: U256,
+        capture_timestamp: u64,
+    ) -> Result<SlashRequest, PublisherError> {
+        let subnetwork = self.get_subnetwork(subnetwork_index).await?;
+        let capture_timestamp = capture_timestamp
+            .try_into()
+            .map_err(|_| PublisherError::TimestampOverflow(capture_timestamp))?;
+        let veto_slasher_contract = self.veto_slasher_contract(vault).await?;
+
+        let transaction = veto_slasher_contract.requestSlash(
+            subnetwork,
+            operator,
+            amount,
+            capture_timestamp,
+            Bytes::new(),
+        );
+        let pending_transaction = transaction.send().await;
+        let transaction_receipt = self
+            .extract_receipt_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::RequestSlash)?;
+
+        let event = transaction_receipt
+            .inner
+            .logs()
+            .iter()
+            .find(|log| log.topic0() == Some(&IVetoSlasher::RequestSlash::SIGNATURE_HASH))
+            .ok_or(PublisherError::RequestSlash(TransactionError::EmptyLogs))?
+            .log_decode::<IVetoSlasher::RequestSlash>()
+            .map_err(|error| PublisherError::RequestSlash(TransactionError::DecodeLogData(error)))?
+            .inner
+            .data;
+
+        Ok(SlashRequest {
+            transaction_hash: transaction_receipt.transaction_hash,
+            slasher: *veto_slasher_contract.address(),
+            slash_index: event.slashIndex,
+            veto_deadline: event.vetoDeadline.to(),
+        })
+    }
+
+    /// Execute the slash request `slash_index` of the slasher of `vault`
+    /// once its veto period passed without a veto. Like
+    /// [`Publisher::request_slash()`], this requires the signing key of the
+    /// publisher to be the network middleware.
+    pub async fn execute_slash(
+        &self,
+        vault: Address,
+        slash_index: U256,
+    ) -> Result<FixedBytes<32>, PublisherError> {
+        let veto_slasher_contract = self.veto_slasher_contract(vault).await?;
+
+        let transaction = veto_slasher_contract.executeSlash(slash_index, Bytes::new());
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::ExecuteSlash)?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Veto the slash request `slash_index` of the slasher of `vault`, as
+    /// the resolver of the vault, before its veto deadline.
+    pub async fn veto_slash(
+        &self,
+        vault: Address,
+        slash_index: U256,
+    ) -> Result<FixedBytes<32>, PublisherError> {
+        let veto_slasher_contract = self.veto_slasher_contract(vault).await?;
+
+        let transaction = veto_slasher_contract.vetoSlash(slash_index, Bytes::new());
+        let pending_transaction = transaction.send().await;
+        let transaction_hash = self
+            .extract_transaction_hash_from_pending_transaction(pending_transaction)
+            .await
+            .map_err(PublisherError::VetoSlash)?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Get the state of the slash request `slash_index` of the slasher of
+    /// `vault`, e.g. to check whether it can be executed yet.
+    pub async fn get_slash_request(
+        &self,
+        vault: Address,
+        slash_index: U256,
+    ) -> Result<SlashRequestInfo, PublisherError> {
+        let slash_request = self
+            .veto_slasher_contract(vault)
+            .await?
+            .slashRequests(slash_index)
+            .call()
+            .await
+            .map_err(PublisherError::GetSlashRequest)?;
+
+        Ok(SlashRequestInfo {
+            subnetwork: slash_request.subnetwork,
+            operator: slash_request.operator,
+            amount: slash_request.amount,
+            capture_timestamp: slash_request.captureTimestamp.to(),
+            veto_deadline: slash_request.vetoDeadline.to(),
+            completed: slash_request.completed,
+        })
+    }
+
+    /// Get the veto period of the slasher of `vault` in seconds.
+    pub async fn get_veto_duration(&self, vault: Address) -> Result<u64, PublisherError> {
+        let veto_duration = self
+            .veto_slasher_contract(vault)
+            .await?
+            .vetoDuration()
+            .call()
+            .await
+            .map_err(PublisherError::GetVetoDuration)?
+            ._0;
+
+        Ok(veto_duration.to())
+    }
+
+    /// Get the [`StakeSnapshot`] of the epoch of the block `block_number`,
+    /// see [`Publisher::get_epoch_at_block()`].
+    pub async fn get_stake_snapshot_at_block(
+        &self,
+        block_number: u64,
+    ) -> Result<StakeSnapshot, PublisherError> {
+        let epoch = self.get_epoch_at_block(block_number).await?;
+
+        self.get_stake_snapshot(epoch).await
+    }
+}
+
+/// Epochs and timestamps are `uint48` in the contract.
+fn epoch_to_uint48(epoch: u64) -> Result<Uint<48, 1>, PublisherError> {
+    epoch
+        .try_into()
+        .map_err(|_| PublisherError::EpochOverflow(epoch))
 }
 
 #[derive(Debug)]
@@ -192,9 +791,30 @@ pub enum PublisherError {
     ParseEthereumRpcUrl(Box<dyn std::error::Error>),
     ParseSigningKey(alloy::signers::local::LocalSignerError),
     ParseContractAddress(String, alloy::hex::FromHexError),
-    BlockCommitmentLength(usize),
+    BlockCommitmentTask(BlockCommitmentTaskError),
     RegisterBlockCommitment(TransactionError),
     RespondToTask(TransactionError),
+    OperatorRegistryNotSet,
+    VaultOptInServiceNotSet,
+    GetNetwork(alloy::contract::Error),
+    GetOperatorStatus(alloy::contract::Error),
+    RegisterOperator(TransactionError),
+    OptInNetwork(TransactionError),
+    OptOutNetwork(TransactionError),
+    OptInVault(TransactionError),
+    OptOutVault(TransactionError),
+    GetEpoch(alloy::contract::Error),
+    GetStake(alloy::contract::Error),
+    GetBlock(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    BlockNotFound(u64),
+    EpochOverflow(u64),
+    TimestampOverflow(u64),
+    GetSlasher(alloy::contract::Error),
+    GetSlashRequest(alloy::contract::Error),
+    GetVetoDuration(alloy::contract::Error),
+    RequestSlash(TransactionError),
+    ExecuteSlash(TransactionError),
+    VetoSlash(TransactionError),
 }
 
 impl std::fmt::Display for PublisherError {
@@ -220,7 +840,6 @@ mod tests {
         println!("referenceTaskIndex: {:?}", event.referenceTaskIndex);
         println!("blockNumber: {:?}", event.blockNumber);
         println!("commitment: {:?}", event.blockCommitment);
-        println!("taskCreatedBlock: {:?}", event.taskCreatedBlock);
     }
 
     #[tokio::test]
@@ -1,6 +1,10 @@
 use std::{future::Future, str::FromStr};
 
-use alloy::providers::{ProviderBuilder, WsConnect};
+use alloy::{
+    providers::{Provider, ProviderBuilder, WsConnect},
+    rpc::types::Filter,
+    sol_types::{SolEvent, SolEventInterface},
+};
 use futures::StreamExt;
 
 use crate::types::*;
@@ -105,6 +109,99 @@ impl Subscriber {
 
         Err(SubscriberError::EventStreamDisconnected)
     }
+
+    /// Start listening to the slash requests, vetoes and executions of the
+    /// slashers of `vaults`, e.g. to veto a request as the resolver of a
+    /// vault or to execute it once its veto deadline passed. The callback
+    /// receives the log of the event to tell the slashers apart.
+    ///
+    /// # WARNING
+    ///
+    /// This is a blocking operation unless spawned in a separate thread.
+    ///
+    /// # Examples - `tokio`
+    ///
+    /// ```
+    /// let context = Arc::new(String::from("context"));
+    ///
+    /// tokio::spawn(async move {
+    ///     Subscriber::new(
+    ///         "ws://127.0.0.1:8545",
+    ///         "0xc3e53F4d16Ae77Db1c982e75a937B9f60FE63690",
+    ///     )
+    ///     .unwrap()
+    ///     .initialize_slashing_event_handler(vec![vault], callback, context.clone())
+    ///     .await
+    ///     .unwrap();
+    /// });
+    ///
+    /// async fn callback(event: IVetoSlasher::IVetoSlasherEvents, log: Log, _context: Arc<String>) {
+    ///     match event {
+    ///         IVetoSlasher::IVetoSlasherEvents::RequestSlash(request) => {
+    ///             println!(
+    ///                 "Slash request {} of {} on {}, vetoable until {}",
+    ///                 request.slashIndex,
+    ///                 request.operator,
+    ///                 log.address(),
+    ///                 request.vetoDeadline
+    ///             );
+    ///         }
+    ///         IVetoSlasher::IVetoSlasherEvents::VetoSlash(veto) => {
+    ///             println!(
+    ///                 "Slash request {} vetoed by {}",
+    ///                 veto.slashIndex, veto.resolver
+    ///             );
+    ///         }
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub async fn initialize_slashing_event_handler<CB, CTX, F>(
+        &self,
+        vaults: Vec<Address>,
+        callback: CB,
+        context: CTX,
+    ) -> Result<(), SubscriberError>
+    where
+        CB: Fn(IVetoSlasher::IVetoSlasherEvents, Log, CTX) -> F,
+        CTX: Clone + Send + Sync,
+        F: Future<Output = ()>,
+    {
+        let provider = ProviderBuilder::new()
+            .on_ws(self.connection_detail.clone())
+            .await
+            .map_err(SubscriberError::WebsocketProvider)?;
+
+        let mut slashers = Vec::with_capacity(vaults.len());
+        for vault in vaults {
+            let slasher = IVault::new(vault, provider.clone())
+                .slasher()
+                .call()
+                .await
+                .map_err(SubscriberError::GetSlasher)?
+                ._0;
+            slashers.push(slasher);
+        }
+
+        let filter = Filter::new().address(slashers).event_signature(vec![
+            IVetoSlasher::RequestSlash::SIGNATURE_HASH,
+            IVetoSlasher::VetoSlash::SIGNATURE_HASH,
+            IVetoSlasher::ExecuteSlash::SIGNATURE_HASH,
+        ]);
+        let mut slasher_event_stream = provider
+            .subscribe_logs(&filter)
+            .await
+            .map_err(SubscriberError::SubscribeToSlashers)?
+            .into_stream();
+
+        while let Some(log) = slasher_event_stream.next().await {
+            if let Ok(event) = IVetoSlasher::IVetoSlasherEvents::decode_log(&log.inner, true) {
+                callback(event.data, log, context.clone()).await;
+            }
+        }
+
+        Err(SubscriberError::EventStreamDisconnected)
+    }
 }
 
 #[derive(Debug)]
@@ -112,6 +209,8 @@ pub enum SubscriberError {
     ParseContractAddress(String, alloy::hex::FromHexError),
     WebsocketProvider(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
     SubscribeToAvsContract(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
+    GetSlasher(alloy::contract::Error),
+    SubscribeToSlashers(alloy::transports::RpcError<alloy::transports::TransportErrorKind>),
     EventStreamDisconnected,
 }
 
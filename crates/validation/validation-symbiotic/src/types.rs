@@ -1,8 +1,155 @@
 pub use alloy::{primitives::*, rpc::types::Log};
+pub use validation_types::{BlockCommitmentTask, BlockCommitmentTaskError};
 
 alloy::sol!(
     #[allow(missing_docs)]
     #[sol(rpc)]
+    #[derive(Debug)]
     ValidationServiceManager,
     "src/contract/ValidationServiceManager.json"
 );
+
+impl From<BlockCommitmentTask> for ValidationServiceManager::createNewTaskCall {
+    fn from(task: BlockCommitmentTask) -> Self {
+        Self {
+            _clusterId: task.cluster_id,
+            _rollupId: task.rollup_id,
+            _blockNumber: U256::from(task.block_number),
+            _blockCommitment: task.block_commitment,
+        }
+    }
+}
+
+impl TryFrom<ValidationServiceManager::NewTaskCreated> for BlockCommitmentTask {
+    type Error = BlockCommitmentTaskError;
+
+    fn try_from(event: ValidationServiceManager::NewTaskCreated) -> Result<Self, Self::Error> {
+        Ok(Self {
+            cluster_id: event.clusterId,
+            rollup_id: event.rollupId,
+            block_number: event
+                .blockNumber
+                .try_into()
+                .map_err(|_| BlockCommitmentTaskError::BlockNumberOverflow)?,
+            block_commitment: event.blockCommitment,
+        })
+    }
+}
+
+/// The stakes of a validation network at an epoch, see
+/// [`crate::publisher::Publisher::get_stake_snapshot()`].
+#[derive(Clone, Debug)]
+pub struct StakeSnapshot {
+    pub epoch: u64,
+    pub operators: Vec<IValidationServiceManager::OperatorInfo>,
+    pub total_stakes: Vec<IValidationServiceManager::StakeInfo>,
+}
+
+// The Symbiotic core contracts are not bundled, so only the functions used
+// for operator onboarding are declared.
+alloy::sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface IOperatorRegistry {
+        function isEntity(address account) external view returns (bool);
+
+        function registerOperator() external;
+    }
+);
+
+alloy::sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface IOptInService {
+        function isOptedIn(address who, address where) external view returns (bool);
+
+        function optIn(address where) external;
+
+        function optOut(address where) external;
+    }
+);
+
+alloy::sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface IVault {
+        function slasher() external view returns (address);
+    }
+);
+
+// The slasher of a vault with a veto period, through which the network
+// requests a slash that a resolver may veto before it is executed.
+alloy::sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IVetoSlasher {
+        event RequestSlash(
+            uint256 indexed slashIndex,
+            bytes32 indexed subnetwork,
+            address indexed operator,
+            uint256 slashAmount,
+            uint48 captureTimestamp,
+            uint48 vetoDeadline
+        );
+
+        event ExecuteSlash(uint256 indexed slashIndex, uint256 slashedAmount);
+
+        event VetoSlash(uint256 indexed slashIndex, address indexed resolver);
+
+        function vetoDuration() external view returns (uint48);
+
+        function slashRequestsLength() external view returns (uint256);
+
+        function slashRequests(uint256 slashIndex)
+            external
+            view
+            returns (
+                bytes32 subnetwork,
+                address operator,
+                uint256 amount,
+                uint48 captureTimestamp,
+                uint48 vetoDeadline,
+                bool completed
+            );
+
+        function requestSlash(
+            bytes32 subnetwork,
+            address operator,
+            uint256 amount,
+            uint48 captureTimestamp,
+            bytes calldata hints
+        ) external returns (uint256 slashIndex);
+
+        function executeSlash(uint256 slashIndex, bytes calldata hints)
+            external
+            returns (uint256 slashedAmount);
+
+        function vetoSlash(uint256 slashIndex, bytes calldata hints) external;
+    }
+);
+
+/// A slash requested with
+/// [`crate::publisher::Publisher::request_slash()`], which can be executed
+/// with [`crate::publisher::Publisher::execute_slash()`] once
+/// `veto_deadline` passed without a veto.
+#[derive(Clone, Debug)]
+pub struct SlashRequest {
+    pub transaction_hash: FixedBytes<32>,
+    pub slasher: Address,
+    pub slash_index: U256,
+    pub veto_deadline: u64,
+}
+
+/// The state of a slash request, see
+/// [`crate::publisher::Publisher::get_slash_request()`].
+#[derive(Clone, Debug)]
+pub struct SlashRequestInfo {
+    pub subnetwork: FixedBytes<32>,
+    pub operator: Address,
+    pub amount: U256,
+    pub capture_timestamp: u64,
+    pub veto_deadline: u64,
+    /// Set once the request was executed or vetoed.
+    pub completed: bool,
+}
@@ -0,0 +1,49 @@
+use alloy::primitives::FixedBytes;
+
+/// A block commitment to be validated, independent of the validation
+/// provider. Each provider converts it to the arguments of its own
+/// `createNewTask` and back from its task events.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockCommitmentTask {
+    pub cluster_id: String,
+    pub rollup_id: String,
+    pub block_number: u64,
+    pub block_commitment: FixedBytes<32>,
+}
+
+impl BlockCommitmentTask {
+    pub fn new(
+        cluster_id: impl AsRef<str>,
+        rollup_id: impl AsRef<str>,
+        block_number: u64,
+        block_commitment: impl AsRef<[u8]>,
+    ) -> Result<Self, BlockCommitmentTaskError> {
+        let block_commitment = block_commitment.as_ref();
+        if block_commitment.len() != 32 {
+            return Err(BlockCommitmentTaskError::BlockCommitmentLength(
+                block_commitment.len(),
+            ));
+        }
+
+        Ok(Self {
+            cluster_id: cluster_id.as_ref().to_owned(),
+            rollup_id: rollup_id.as_ref().to_owned(),
+            block_number,
+            block_commitment: FixedBytes::from_slice(block_commitment),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum BlockCommitmentTaskError {
+    BlockCommitmentLength(usize),
+    BlockNumberOverflow,
+}
+
+impl std::fmt::Display for BlockCommitmentTaskError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for BlockCommitmentTaskError {}
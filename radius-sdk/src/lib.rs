@@ -20,6 +20,7 @@ pub mod liveness {
 }
 #[cfg(any(feature = "full", feature = "signature"))]
 pub use signature;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod util;
 #[cfg(any(
     feature = "full",